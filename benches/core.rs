@@ -0,0 +1,117 @@
+// Criterion benchmarks for the consensus math on fixed-seed synthetic workloads
+// - Inputs are generated once per benchmark and cycled, so runs are comparable across machines and commits
+// - `cargo bench --bench core -- --save-baseline main` then `-- --baseline main` to A/B a change
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use slimechain_algo::*;
+
+/// Distinct inputs cycled through per benchmark
+const POOL: usize = 1024;
+
+/// Fixed-seed uniform [0, 1) generator (xorshift64*), same as `slimechain-algo bench`
+struct Gen(u64);
+
+impl Gen {
+    fn new() -> Self { Gen(0x9E37_79B9_7F4A_7C15) }
+    fn unit(&mut self) -> f64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        (self.0.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11) as f64 / (1u64 << 53) as f64
+    }
+    fn signals(&mut self) -> RiskSignals {
+        RiskSignals {
+            coordination: Some(self.unit()), clustering: Some(self.unit()), burst: Some(self.unit()), monotonicity: Some(self.unit()),
+            abuse_history: Some(self.unit()), duplication: Some(self.unit()),
+        }
+    }
+    fn qinputs(&mut self) -> QInputs {
+        QInputs { A: self.unit(), R: self.unit(), T: self.unit(), D: self.unit(), H: self.unit(), S: self.unit() }
+    }
+    fn content(&mut self) -> Content {
+        Content { is_claim: Some(self.unit() < 0.3), has_evidence: Some(self.unit() < 0.5), risk_signals: Some(self.signals()), topic: None, kind: None, fact_check: None }
+    }
+    fn actor(&mut self) -> Actor {
+        Actor { rl: self.unit() * 200.0, q: self.unit(), ef: self.unit() * 50.0, posts_1h: Some(self.unit() * 20.0), ..Default::default() }
+    }
+    fn reward(&mut self) -> RewardInput {
+        RewardInput {
+            ticket_budget: self.unit() * 2.0, client_q: self.unit(), size_bytes: (self.unit() * 1e6) as u64,
+            ttfb_ms: (self.unit() * 2000.0) as u32, server_cluster_risk: self.unit(), ..Default::default()
+        }
+    }
+    fn pool<T>(&mut self, f: impl Fn(&mut Self) -> T) -> Vec<T> { (0..POOL).map(|_| f(self)).collect() }
+}
+
+/// Benchmark `f` over a cycled input pool
+fn cycle<T>(c: &mut Criterion, name: &str, pool: &[T], f: impl Fn(&T)) {
+    let mut i = 0;
+    c.bench_function(name, |b| b.iter(|| {
+        f(black_box(&pool[i % pool.len()]));
+        i += 1;
+    }));
+}
+
+fn quality(c: &mut Criterion) {
+    let (p, pool) = (Params::default(), Gen::new().pool(Gen::qinputs));
+    cycle(c, "quality", &pool, |q| { black_box(calculate_quality(q.clone(), &p)); });
+}
+
+fn ef(c: &mut Criterion) {
+    let p = Params::default();
+    let mut g = c.benchmark_group("ef");
+    for followers in [10, 100, 1_000, 10_000] {
+        let mut gen = Gen::new();
+        let qs: Vec<f64> = (0..followers).map(|_| gen.unit()).collect();
+        g.throughput(Throughput::Elements(followers as u64));
+        g.bench_with_input(BenchmarkId::from_parameter(followers), &qs, |b, qs| b.iter(|| calculate_ef(black_box(qs), &p)));
+    }
+    g.finish();
+}
+
+fn risk(c: &mut Criterion) {
+    let pool = Gen::new().pool(|g| Some(g.signals()));
+    cycle(c, "risk", &pool, |s| { black_box(calculate_risk(s, &RiskWeights::DEFAULT)); });
+}
+
+fn cost(c: &mut Criterion) {
+    let (p, pool) = (Params::default(), Gen::new().pool(|g| (g.actor(), g.content())));
+    cycle(c, "cost", &pool, |(a, ct)| { black_box(calculate_post_cost(a, ct, &p, 1.0)); });
+    let prepared = PreparedParams::new(Params::default());
+    cycle(c, "cost/prepared", &pool, |(a, ct)| { black_box(calculate_post_cost(a, ct, &prepared, 1.0)); });
+}
+
+fn reward(c: &mut Criterion) {
+    let (p, pool) = (Params::default(), Gen::new().pool(Gen::reward));
+    cycle(c, "reward", &pool, |r| { black_box(calculate_serve_reward(r, &p)); });
+}
+
+fn congestion(c: &mut Criterion) {
+    let p = Params::default();
+    let pool = Gen::new().pool(|g| (g.unit() * 10.0, g.unit() * 2000.0));
+    cycle(c, "congestion/update", &pool, |&(b, l)| { black_box(update_base_cost(b, l, &p)); });
+    let loads: Vec<f64> = pool.iter().map(|&(_, l)| l).collect();
+    c.bench_function("congestion/simulate_1024", |b| b.iter(|| congestion::simulate(black_box(&loads), 1.0, &p)));
+}
+
+/// Scoring 4096 post events with 1024 distinct: every row (`cost_batch`) against each distinct event once
+/// (`cost_batch_dedup`: `canonicalize`, score, `fan_out`)
+fn dedup(c: &mut Criterion) {
+    #[cfg(feature = "parallel")]
+    {
+        let p = Params::default();
+        let mut gen = Gen::new();
+        let pool: Vec<PostEvent> = gen.pool(|g| PostEvent { actor: g.actor(), content: g.content(), base_fare: 1.0 });
+        let rows: Vec<PostEvent> = pool.iter().cycle().take(4 * POOL).cloned().collect();
+        let mut g = c.benchmark_group("dedup");
+        g.throughput(Throughput::Elements(rows.len() as u64));
+        g.bench_function("every_row", |b| b.iter(|| batch::cost_batch(black_box(&rows), &p)));
+        g.bench_function("canonicalize", |b| b.iter(|| batch::cost_batch_dedup(black_box(&rows), &p)));
+        g.finish();
+    }
+    #[cfg(not(feature = "parallel"))]
+    let _ = c;
+}
+
+criterion_group!(benches, quality, ef, risk, cost, reward, congestion, dedup);
+criterion_main!(benches);
@@ -0,0 +1,45 @@
+// Generates the C header from src/ffi.rs into OUT_DIR when the `capi` feature is enabled; the checked-in
+// include/slimechain_algo.h is compared against it by a test and rewritten only with SLIMECHAIN_UPDATE_HEADER=1
+// Compiles proto/slimechain/v1/slimechain.proto with prost (vendored protoc) when the `proto` feature is enabled,
+// plus the tonic service stubs with `grpc`
+// Generates the UniFFI scaffolding from src/slimechain_algo.udl when the `uniffi` feature is enabled
+// Emits the Node addon link settings when the `napi` feature is enabled
+
+fn main() {
+    #[cfg(feature = "capi")]
+    {
+        let dir = std::env::var("CARGO_MANIFEST_DIR").expect("cargo sets CARGO_MANIFEST_DIR");
+        let out = std::env::var("OUT_DIR").expect("cargo sets OUT_DIR");
+        println!("cargo:rerun-if-changed=src");
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+        println!("cargo:rerun-if-env-changed=SLIMECHAIN_UPDATE_HEADER");
+        let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", dir)).expect("valid cbindgen.toml");
+        // only ffi.rs: the header carries the C ABI, not every public item of the crate
+        let header = cbindgen::Builder::new()
+            .with_src(format!("{}/src/ffi.rs", dir))
+            .with_config(config)
+            .generate()
+            .expect("generate C header");
+        header.write_to_file(format!("{}/slimechain_algo.h", out));
+        if std::env::var_os("SLIMECHAIN_UPDATE_HEADER").is_some() {
+            header.write_to_file(format!("{}/include/slimechain_algo.h", dir));
+        }
+    }
+    #[cfg(feature = "proto")]
+    {
+        println!("cargo:rerun-if-changed=proto/slimechain/v1/slimechain.proto");
+        let mut config = prost_build::Config::new();
+        config.protoc_executable(protoc_bin_vendored::protoc_bin_path().expect("vendored protoc"));
+        // with `grpc`, tonic generates the SlimechainAlgo client/server next to the messages
+        #[cfg(feature = "grpc")]
+        tonic_prost_build::configure()
+            .compile_with_config(config, &["proto/slimechain/v1/slimechain.proto"], &["proto"])
+            .expect("compile slimechain.proto");
+        #[cfg(not(feature = "grpc"))]
+        config.compile_protos(&["proto/slimechain/v1/slimechain.proto"], &["proto"]).expect("compile slimechain.proto");
+    }
+    #[cfg(feature = "uniffi")]
+    uniffi::generate_scaffolding("src/slimechain_algo.udl").expect("generate UniFFI scaffolding");
+    #[cfg(feature = "napi")]
+    napi_build::setup();
+}
@@ -0,0 +1,146 @@
+#ifndef SLIMECHAIN_ALGO_H
+#define SLIMECHAIN_ALGO_H
+
+/* Generated by cbindgen from src/ffi.rs with `cargo build --features capi`; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Opaque parameter bundle (create with `slime_params_default`/`slime_params_from_json`)
+typedef struct SlimeParams SlimeParams;
+
+typedef struct SlimeQInputs {
+  double a;
+  double r;
+  double t;
+  double d;
+  double h;
+  double s;
+} SlimeQInputs;
+
+// Risk signals in [0, 1]; negative = missing
+typedef struct SlimeRiskSignals {
+  double coordination;
+  double clustering;
+  double burst;
+  double monotonicity;
+  double abuse_history;
+  // Near-duplicate share among recent posts
+  double duplication;
+} SlimeRiskSignals;
+
+typedef struct SlimeActor {
+  double rl;
+  double q;
+  double ef;
+  // Posts in the last hour; negative = unknown
+  double posts_1h;
+} SlimeActor;
+
+typedef struct SlimeContent {
+  // 1 = true, 0 = false, -1 = unknown
+  int8_t is_claim;
+  // 1 = true, 0 = false, -1 = unknown
+  int8_t has_evidence;
+  // 0 general, 1 news, 2 finance, 3 health, 4 elections; -1 = none
+  int32_t topic;
+  struct SlimeRiskSignals risk_signals;
+} SlimeContent;
+
+typedef struct SlimePropagation {
+  uint32_t ttl;
+  uint32_t fanout;
+  bool quarantined;
+} SlimePropagation;
+
+typedef struct SlimeRewardInput {
+  double ticket_budget;
+  double client_q;
+  uint64_t size_bytes;
+  uint32_t ttfb_ms;
+  double server_cluster_risk;
+  // Negative = none
+  int64_t prev_serves;
+  // Negative = none
+  double ttfb_percentile;
+} SlimeRewardInput;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Default parameters. Free with `slime_params_free`.
+struct SlimeParams *slime_params_default(void);
+
+// Parse and validate parameters from a JSON string; returns null on error. Free with `slime_params_free`.
+//
+// # Safety
+// `json` must be null or a valid NUL-terminated string.
+struct SlimeParams *slime_params_from_json(const char *json);
+
+// # Safety
+// `params` must be null or a pointer returned by `slime_params_default`/`slime_params_from_json`, freed at most once.
+void slime_params_free(struct SlimeParams *params);
+
+// # Safety
+// Pointers must be null or point to valid values.
+double slime_calculate_quality(const struct SlimeQInputs *inputs, const struct SlimeParams *params);
+
+// # Safety
+// `followers_q` must point to `len` doubles (or be null with `len == 0`); `params` null or valid.
+double slime_calculate_ef(const double *followers_q,
+                          uintptr_t len,
+                          const struct SlimeParams *params);
+
+// Risk with the default weights
+//
+// # Safety
+// `signals` must be null or valid.
+double slime_calculate_risk(const struct SlimeRiskSignals *signals);
+
+// # Safety
+// Pointers must be null or point to valid values.
+double slime_calculate_post_cost(const struct SlimeActor *actor,
+                                 const struct SlimeContent *content,
+                                 double base_fare,
+                                 const struct SlimeParams *params);
+
+// Propagation for content; null `content` means no risk signals and no topic
+//
+// # Safety
+// Pointers must be null or point to valid values.
+struct SlimePropagation slime_adjust_propagation(const struct SlimeContent *content,
+                                                 const struct SlimeParams *params);
+
+// # Safety
+// Pointers must be null or point to valid values.
+double slime_calculate_serve_reward(const struct SlimeRewardInput *input,
+                                    const struct SlimeParams *params);
+
+// # Safety
+// `params` must be null or valid.
+double slime_update_base_cost(double current_base,
+                              double current_load,
+                              const struct SlimeParams *params);
+
+// Evaluate a command (`cost`, `reward`, `prop`, `base`, `quality`, `ef`, `risk`, `pipeline`) on a JSON input
+// shaped like the CLI input (`vectors::eval_command`). `params_json` may be null for the defaults.
+// Returns `{"ok": <result>}` or `{"error": "<message>"}`; free with `slime_string_free`.
+//
+// # Safety
+// String arguments must be null or valid NUL-terminated strings.
+char *slime_eval_json(const char *command,
+                      const char *input_json,
+                      const char *params_json);
+
+// # Safety
+// `s` must be null or a string returned by `slime_eval_json`, freed at most once.
+void slime_string_free(char *s);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SLIMECHAIN_ALGO_H */
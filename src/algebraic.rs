@@ -0,0 +1,208 @@
+// Circuit-friendly integer formulation of risk, posting cost and serve reward
+// - Every value is a fixed-point integer at `SCALE` (1e-6 resolution); the computation uses only integer add, mul and
+//   compare. Rescaling after a product and the few true divisions are floor divisions, which a circuit checks with a
+//   witness `q` and `q * d <= n < (q + 1) * d` (mul + compare), so a ZK fraud proof can re-run them step by step
+// - No ln/exp/powf: `log2` and `exp2` normalize by powers of two (compare + shift) and interpolate linearly between
+//   64 precomputed table points; `pow(x, e) = exp2(e * log2(x))`
+// - Error vs the f64 reference: `log2` is within `LOG2_MAX_ERROR` (absolute), `exp2` within `EXP2_MAX_REL_ERROR`
+//   (relative), so `pow` is within about `EXP2_MAX_REL_ERROR + ln 2 * |e * LOG2_MAX_ERROR|` relative, plus the 1e-6
+//   quantization of inputs and params (`to_fx`, done outside the circuit); for default params cost and reward agree
+//   with `calculate_post_cost` / `calculate_serve_reward` to ~1e-4 relative
+// - Range: `to_fx` clamps inputs to ±`MAX_INPUT`, and `mul`/`div` saturate at the i128 bounds instead of overflowing,
+//   so extreme but finite inputs (e.g. posts_1h = 1e30) give a saturated, deterministic result rather than a panic
+// - The integer results are the reference for proofs: they are exact and identical on every platform, but they are
+//   NOT bit-identical to the f64 functions, so claims checked against them must be produced by them
+
+use crate::{rate_limit_for, Actor, Content, CurvePoint, Params, RewardInput, RiskSignals, RiskWeights};
+
+/// Fixed-point value: `x` stands for `x / SCALE`
+pub type Fx = i128;
+
+pub const SCALE: Fx = 1_000_000;
+
+/// Largest input magnitude `to_fx` represents; beyond it inputs are clamped
+pub const MAX_INPUT: f64 = 1e24;
+
+/// Maximum absolute error of `log2` (table interpolation plus rounding)
+pub const LOG2_MAX_ERROR: f64 = 5e-5;
+/// Maximum relative error of `exp2` (table interpolation plus rounding)
+pub const EXP2_MAX_REL_ERROR: f64 = 2e-5;
+
+/// Table points on [0, 1] per interpolated function
+const SEGMENTS: Fx = 64;
+
+/// round(log2(1 + k / 64) * SCALE), k = 0..=64
+const LOG2_TABLE: [Fx; 65] = [
+    0, 22368, 44394, 66089, 87463, 108524, 129283, 149747, 169925, 189825, 209453, 228819, 247928, 266787, 285402,
+    303781, 321928, 339850, 357552, 375039, 392317, 409391, 426265, 442943, 459432, 475733, 491853, 507795, 523562,
+    539159, 554589, 569856, 584963, 599913, 614710, 629357, 643856, 658211, 672425, 686501, 700440, 714246, 727920,
+    741467, 754888, 768184, 781360, 794416, 807355, 820179, 832890, 845490, 857981, 870365, 882643, 894818, 906891,
+    918863, 930737, 942515, 954196, 965784, 977280, 988685, 1000000,
+];
+
+/// round(2^(k / 64) * SCALE), k = 0..=64
+const EXP2_TABLE: [Fx; 65] = [
+    1000000, 1010889, 1021897, 1033025, 1044274, 1055645, 1067140, 1078761, 1090508, 1102383, 1114387, 1126522,
+    1138789, 1151189, 1163725, 1176397, 1189207, 1202157, 1215247, 1228481, 1241858, 1255381, 1269051, 1282870,
+    1296840, 1310961, 1325237, 1339668, 1354256, 1369002, 1383910, 1398980, 1414214, 1429613, 1445181, 1460918,
+    1476826, 1492908, 1509164, 1525598, 1542211, 1559004, 1575981, 1593142, 1610490, 1628027, 1645755, 1663677,
+    1681793, 1700106, 1718619, 1737334, 1756252, 1775376, 1794709, 1814252, 1834008, 1853979, 1874168, 1894576,
+    1915207, 1936062, 1957144, 1978456, 2000000,
+];
+
+/// Quantize an f64 (nearest 1e-6, clamped to ±`MAX_INPUT`; non-finite -> 0)
+pub fn to_fx(x: f64) -> Fx {
+    if x.is_finite() { (x.clamp(-MAX_INPUT, MAX_INPUT) * SCALE as f64).round() as Fx } else { 0 }
+}
+
+pub fn from_fx(x: Fx) -> f64 { x as f64 / SCALE as f64 }
+
+/// Fixed-point product (floor; saturating)
+pub fn mul(a: Fx, b: Fx) -> Fx { a.saturating_mul(b).div_euclid(SCALE) }
+
+/// Fixed-point quotient (floor; saturating); 0 for `b <= 0`
+pub fn div(a: Fx, b: Fx) -> Fx { if b > 0 { a.saturating_mul(SCALE).div_euclid(b) } else { 0 } }
+
+fn clamp(x: Fx, lo: Fx, hi: Fx) -> Fx { x.max(lo).min(hi) }
+
+/// Linear interpolation in a table over [0, 1) at `frac` (fixed, in 0..SCALE)
+fn lerp(table: &[Fx; 65], frac: Fx) -> Fx {
+    let t = frac * SEGMENTS;
+    let (i, r) = ((t / SCALE) as usize, t % SCALE);
+    table[i] + mul(table[i + 1] - table[i], r)
+}
+
+/// log2(x); `x` is floored at 1 (i.e. 1e-6)
+pub fn log2(x: Fx) -> Fx {
+    let (mut m, mut k) = (x.max(1), 0);
+    while m >= 2 * SCALE { m /= 2; k += 1; }
+    while m < SCALE { m *= 2; k -= 1; }
+    k * SCALE + lerp(&LOG2_TABLE, m - SCALE)
+}
+
+/// 2^y (exponents above 100 saturate)
+pub fn exp2(y: Fx) -> Fx {
+    let (n, f) = (y.div_euclid(SCALE), y.rem_euclid(SCALE));
+    let v = lerp(&EXP2_TABLE, f);
+    if n >= 0 { v << n.min(100) } else { v >> (-n).min(127) }
+}
+
+/// x^e for x >= 0 (`0^e = 0` for e != 0, `x^0 = 1`)
+pub fn pow(x: Fx, e: Fx) -> Fx {
+    if e == 0 { SCALE } else if x <= 0 { 0 } else { exp2(mul(e, log2(x))) }
+}
+
+/// Integer counterpart of `calculate_risk`
+pub fn risk(signals: &Option<RiskSignals>, w: &RiskWeights) -> Fx {
+    let Some(s) = signals else { return 0; };
+    let terms = [
+        (w.w_coord, s.coordination), (w.w_clust, s.clustering), (w.w_burst, s.burst),
+        (w.w_mono, s.monotonicity), (w.w_hist, s.abuse_history), (w.w_dup, s.duplication),
+    ];
+    clamp(terms.iter().map(|&(w, x)| mul(to_fx(w), to_fx(x.unwrap_or(0.0)))).sum(), 0, SCALE)
+}
+
+/// Integer counterpart of `calculate_post_cost`
+pub fn post_cost(actor: &Actor, content: &Content, params: &Params, base_fare: f64) -> Fx {
+    let c = &params.cost;
+    let rl_cost = mul(to_fx(c.a), pow(to_fx(actor.rl.max(0.0)), to_fx(c.alpha)));
+    let ef_cost = mul(to_fx(c.b), pow(to_fx(actor.ef.max(0.0)), to_fx(c.beta)));
+    let mut cost = to_fx(base_fare) + rl_cost + ef_cost;
+
+    let r = risk(&content.risk_signals, &RiskWeights::DEFAULT);
+    cost = mul(cost, SCALE + mul(to_fx(c.lambda_actor), r) + mul(to_fx(c.lambda_content), r));
+
+    if let Some(state) = content.fact_check_state() { cost = mul(cost, to_fx(c.fact_check.get(state))); }
+    if let (Some(posts), rate) = rate_limit_for(actor, content, c, c.rate_limit_per_hour.max(1.0)) {
+        let (posts, rate) = (to_fx(posts), to_fx(rate));
+        if posts > rate { cost = mul(cost, SCALE + mul(SCALE / 2, div(posts, rate) - SCALE)); }
+    }
+    if let Some(age) = actor.account_age_days {
+        let (age, window) = (to_fx(age), to_fx(c.new_account_days));
+        if window > 0 && age < window {
+            cost = mul(cost, SCALE + mul(to_fx(c.new_account_surcharge), SCALE - div(age.max(0), window)));
+        }
+    }
+    let cost = match c.cmin { Some(floor) => cost.max(to_fx(floor)), None => cost };
+    match c.cmax { Some(ceiling) => cost.min(to_fx(ceiling)), None => cost }
+}
+
+fn interpolate(points: &[CurvePoint], x: Fx) -> Fx {
+    let pts: Vec<(Fx, Fx)> = points.iter().map(|p| (to_fx(p.x), to_fx(p.y))).collect();
+    match pts[..] {
+        [] => 0,
+        [first, ..] if x <= first.0 => first.1,
+        [.., last] if x >= last.0 => last.1,
+        _ => {
+            let i = pts.windows(2).position(|w| x <= w[1].0).unwrap_or(0);
+            let (a, b) = (pts[i], pts[i + 1]);
+            if b.0 <= a.0 { return b.1; }
+            a.1 + mul(b.1 - a.1, div(x - a.0, b.0 - a.0))
+        }
+    }
+}
+
+/// Integer counterpart of `calculate_serve_reward`
+pub fn serve_reward(input: &RewardInput, params: &Params) -> Fx {
+    let r = &params.reward;
+    let w_size = div(log2((1 + input.size_bytes as Fx) * SCALE), log2(SCALE + to_fx(r.size_norm_bytes.max(1.0))));
+    let w_latency = match input.ttfb_percentile {
+        Some(pct) if !r.latency_curve.is_empty() => clamp(interpolate(&r.latency_curve, clamp(to_fx(pct), 0, SCALE)), 0, SCALE),
+        _ => {
+            let knee = to_fx(r.latency_knee_ms).max(1);
+            div(knee, knee + input.ttfb_ms as Fx * SCALE)
+        }
+    };
+    let diversity = SCALE - mul(to_fx(r.mu), clamp(to_fx(input.server_cluster_risk), 0, SCALE));
+    let client_q = clamp(to_fx(input.client_q), 0, SCALE);
+    let repetition = match r.repetition_half {
+        0 => SCALE,
+        half => exp2(-(input.prev_serves.unwrap_or(0) as Fx * SCALE / half as Fx)),
+    };
+    let uncapped = [client_q, w_size, w_latency, diversity, repetition].into_iter().fold(to_fx(r.r0), mul);
+    uncapped.min(to_fx(input.ticket_budget).max(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculate_post_cost, calculate_risk, calculate_serve_reward};
+
+    #[test]
+    fn t_matches_f64_within_documented_error() {
+        for x in [0.001, 0.37, 1.0, 1.5, 7.0, 1234.5, 1e9] {
+            assert!((from_fx(log2(to_fx(x))) - x.log2()).abs() <= LOG2_MAX_ERROR + 1e-6, "log2 {x}");
+        }
+        for y in [-5.3, -0.5, 0.0, 0.25, 3.9, 20.0] {
+            assert!((from_fx(exp2(to_fx(y))) / y.exp2() - 1.0).abs() <= EXP2_MAX_REL_ERROR + 1e-5, "exp2 {y}");
+        }
+        assert_eq!(pow(0, to_fx(0.7)), 0);
+        assert_eq!(pow(to_fx(3.0), 0), SCALE);
+
+        let p = Params::default();
+        let rel = |a: Fx, b: f64| (from_fx(a) - b).abs() / b.abs().max(1e-9);
+        let signals = Some(RiskSignals { coordination: Some(0.5), clustering: Some(0.4), burst: Some(0.9), ..Default::default() });
+        assert!((from_fx(risk(&signals, &RiskWeights::DEFAULT)) - calculate_risk(&signals, &RiskWeights::DEFAULT)).abs() <= 1e-5);
+
+        for (rl, ef, posts, age, claim) in [(0.0, 0.0, None, None, None), (120.0, 28.3, Some(25.0), Some(3.0), Some(true)), (5.0, 3.0, Some(2.0), None, Some(false))] {
+            let actor = Actor { rl, q: 0.8, ef, posts_1h: posts, account_age_days: age, ..Default::default() };
+            let content = Content { is_claim: claim, risk_signals: signals.clone(), ..Default::default() };
+            assert!(rel(post_cost(&actor, &content, &p, 1.0), calculate_post_cost(&actor, &content, &p, 1.0)) < 2e-4, "cost rl={rl}");
+        }
+        for (size, ttfb, prev, pct) in [(25_000, 150, None, None), (5_000_000, 0, Some(7), None), (100_000, 900, Some(25), Some(0.95))] {
+            let input = RewardInput {
+                ticket_budget: 10.0, client_q: 0.8, size_bytes: size, ttfb_ms: ttfb, server_cluster_risk: 0.3,
+                prev_serves: prev, ttfb_percentile: pct, ..Default::default()
+            };
+            assert!(rel(serve_reward(&input, &p), calculate_serve_reward(&input, &p)) < 2e-4, "reward size={size}");
+        }
+
+        // extreme finite inputs saturate instead of overflowing
+        let content = Content { risk_signals: signals.clone(), ..Default::default() };
+        let flood = Actor { rl: 1e300, ef: 1e30, posts_1h: Some(1e30), account_age_days: Some(-1e300), ..Default::default() };
+        let busy = Actor { posts_1h: Some(1e3), ..Default::default() };
+        assert!(post_cost(&flood, &content, &p, f64::MAX) >= post_cost(&busy, &content, &p, 1.0));
+        assert_eq!(mul(Fx::MAX, Fx::MAX), Fx::MAX / SCALE);
+        assert!(serve_reward(&RewardInput { ticket_budget: 1e300, client_q: 1.0, size_bytes: u64::MAX, ..Default::default() }, &p) > 0);
+    }
+}
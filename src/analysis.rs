@@ -0,0 +1,152 @@
+// Cohort statistics over evaluated posts (fairness reports)
+// - A `CostRecord` is one post as evaluated: the author's state, their tier, and the resulting cost, reach and reward
+// - `CohortSpec` picks the dimensions to split by; every record lands in one cohort per dimension, keyed
+//   `"<dimension>=<value>"` (e.g. `age=new`, `verification=unverified`, `tier=pro`)
+// - Each cohort gets cost/reach/reward distributions (`stats::StreamStats`) plus its mean cost and reward relative to
+//   the whole population, so a parameter change that shifts burden onto one cohort shows up as a moved ratio
+
+use crate::stats::{StatsReport, StreamStats};
+use crate::{Actor, VerificationLevel};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// One evaluated post
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CostRecord {
+    pub actor: Actor,
+    /// Author's tier name (None = no tier)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tier: Option<String>,
+    pub cost: f64,
+    pub reach: f64,
+    pub reward: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(tag = "by", rename_all = "snake_case"))]
+pub enum CohortDimension {
+    /// `age=new` below `new_account_days`, `age=established` otherwise, `age=unknown` without an account age
+    Age { new_account_days: f64 },
+    /// `verification=verified` for any level above `Unverified`, else `verification=unverified`
+    Verification,
+    /// `tier=<name>`, or `tier=none`
+    Tier,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CohortSpec {
+    pub dimensions: Vec<CohortDimension>,
+}
+
+impl Default for CohortSpec {
+    fn default() -> Self {
+        CohortSpec { dimensions: vec![CohortDimension::Age { new_account_days: 30.0 }, CohortDimension::Verification, CohortDimension::Tier] }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CohortSummary {
+    pub cost: StatsReport,
+    pub reach: StatsReport,
+    pub reward: StatsReport,
+    /// Mean cost and reward over the population's (None when the population's is 0)
+    pub cost_ratio: Option<f64>,
+    pub reward_ratio: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CohortReport {
+    pub overall: CohortSummary,
+    pub cohorts: BTreeMap<String, CohortSummary>,
+}
+
+#[derive(Default)]
+struct Acc { cost: StreamStats, reach: StreamStats, reward: StreamStats }
+
+impl Acc {
+    fn push(&mut self, r: &CostRecord) {
+        self.cost.push(r.cost);
+        self.reach.push(r.reach);
+        self.reward.push(r.reward);
+    }
+
+    fn summary(&self, overall: Option<(f64, f64)>) -> CohortSummary {
+        let (cost, reach, reward) = (self.cost.report(), self.reach.report(), self.reward.report());
+        let ratio = |x: f64, base: f64| (base != 0.0).then(|| x / base);
+        let (cost_ratio, reward_ratio) = match overall {
+            Some((c, r)) => (ratio(cost.mean, c), ratio(reward.mean, r)),
+            None => (Some(1.0), Some(1.0)),
+        };
+        CohortSummary { cost, reach, reward, cost_ratio, reward_ratio }
+    }
+}
+
+fn cohort(record: &CostRecord, dimension: &CohortDimension) -> String {
+    match dimension {
+        CohortDimension::Age { new_account_days } => match record.actor.account_age_days {
+            None => "age=unknown".into(),
+            Some(d) if d < *new_account_days => "age=new".into(),
+            Some(_) => "age=established".into(),
+        },
+        CohortDimension::Verification => match record.actor.verification_level {
+            Some(v) if v != VerificationLevel::Unverified => "verification=verified".into(),
+            _ => "verification=unverified".into(),
+        },
+        CohortDimension::Tier => format!("tier={}", record.tier.as_deref().unwrap_or("none")),
+    }
+}
+
+/// Per-cohort cost, reach and reward distributions
+pub fn cohort_report(records: &[CostRecord], cohorts: &CohortSpec) -> CohortReport {
+    let mut all = Acc::default();
+    let mut by: BTreeMap<String, Acc> = BTreeMap::new();
+    for r in records {
+        all.push(r);
+        for d in &cohorts.dimensions { by.entry(cohort(r, d)).or_default().push(r); }
+    }
+    let overall = all.summary(None);
+    let base = (overall.cost.mean, overall.reward.mean);
+    let cohorts = by.into_iter().map(|(k, acc)| (k, acc.summary(Some(base)))).collect();
+    CohortReport { overall, cohorts }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_cohort_report_ratios() {
+        let rec = |age: f64, verified: bool, tier: Option<&str>, cost: f64| CostRecord {
+            actor: Actor {
+                account_age_days: Some(age),
+                verification_level: Some(if verified { VerificationLevel::Phone } else { VerificationLevel::Unverified }),
+                ..Default::default()
+            },
+            tier: tier.map(Into::into),
+            cost, reach: 100.0, reward: 1.0,
+        };
+        let records = [rec(2.0, false, None, 3.0), rec(5.0, false, None, 3.0), rec(400.0, true, Some("pro"), 1.0), rec(900.0, true, None, 1.0)];
+        let report = cohort_report(&records, &CohortSpec::default());
+        assert_eq!(report.overall.cost.count, 4);
+        assert_eq!(report.overall.cost.mean, 2.0);
+        let c = &report.cohorts;
+        assert_eq!(c.keys().map(String::as_str).collect::<Vec<_>>(), [
+            "age=established", "age=new", "tier=none", "tier=pro", "verification=unverified", "verification=verified",
+        ]);
+        assert_eq!(c["age=new"].cost_ratio, Some(1.5));
+        assert_eq!(c["age=established"].cost_ratio, Some(0.5));
+        assert_eq!(c["tier=none"].cost.count, 3);
+        assert_eq!(c["verification=verified"].reward_ratio, Some(1.0));
+    }
+}
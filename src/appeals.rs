@@ -0,0 +1,111 @@
+// Moderation appeal redress
+// - An upheld appeal reverses (a `restore_share` of) the upheld report it targets: quality regains the report's
+//   multiplicative penalty, abuse history drops by the amount the report added, and the report no longer counts
+// - The cost surcharge the actor paid while flagged is refunded at `refund_share`
+// - A rejected appeal adds a small abuse-history charge so appeals are not free to spam
+// - The reputation ledger applies both as events (`ReputationEvent::AppealUpheld` / `AppealRejected`) through these
+//   functions, with `AppealParams::for_reputation` so the rates undone are the ones the report was applied with
+
+use crate::clamp;
+use crate::reputation::{ActorState, ReputationParams};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Redress rates
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AppealParams {
+    /// Share of the report's penalty undone (1 = full redress)
+    pub restore_share: f64,
+    /// Share of the paid surcharge refunded
+    pub refund_share: f64,
+    /// Abuse history added per rejected appeal
+    pub rejected_abuse: f64,
+    /// `ReputationParams::report_q_penalty` the report was applied with
+    pub report_q_penalty: f64,
+    /// `ReputationParams::report_weight` the report was applied with
+    pub report_weight: f64,
+}
+
+impl Default for AppealParams {
+    /// Full redress; the report rates match `ReputationParams::default`
+    fn default() -> Self {
+        AppealParams { restore_share: 1.0, refund_share: 1.0, rejected_abuse: 0.02, report_q_penalty: 0.1, report_weight: 0.2 }
+    }
+}
+
+impl AppealParams {
+    /// `params.appeals` with the report rates of `params`
+    pub fn for_reputation(params: &ReputationParams) -> Self {
+        AppealParams { report_q_penalty: params.report_q_penalty, report_weight: params.report_weight, ..params.appeals.clone() }
+    }
+}
+
+/// The appealed report
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Appeal {
+    /// Severity the report was upheld with (0..1)
+    pub severity: f64,
+    /// Cost surcharge in SOCIAL the actor paid because of the report
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub surcharge_paid: f64,
+}
+
+fn unit(x: f64) -> f64 { if x.is_finite() { clamp(x, 0.0, 1.0) } else { 0.0 } }
+
+/// Reverse `appeal`'s report on `state`; returns the SOCIAL refund
+pub fn adjust_on_upheld(state: &mut ActorState, params: &AppealParams, appeal: &Appeal) -> f64 {
+    let (sev, share) = (unit(appeal.severity), unit(params.restore_share));
+    let kept = 1.0 - unit(params.report_q_penalty * sev);
+    if kept > 0.0 {
+        state.q = clamp(state.q + share * (state.q / kept - state.q), 0.0, 1.0);
+    }
+    state.abuse_history = clamp(state.abuse_history - share * params.report_weight.max(0.0) * sev, 0.0, 1.0);
+    state.upheld_reports = state.upheld_reports.saturating_sub(1);
+    let paid = if appeal.surcharge_paid.is_finite() { appeal.surcharge_paid.max(0.0) } else { 0.0 };
+    paid * unit(params.refund_share)
+}
+
+/// Charge a rejected appeal to `state`
+pub fn adjust_on_rejected(state: &mut ActorState, params: &AppealParams) {
+    state.abuse_history = clamp(state.abuse_history + unit(params.rejected_abuse), 0.0, 1.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reputation::{Ledger, ReputationEvent};
+
+    #[test]
+    fn t_upheld_appeal_reverses_report() {
+        let mut ledger = Ledger::default();
+        let post = ReputationEvent::PostAccepted { actor: "a".into(), q: 0.8, ef: None };
+        ledger.apply(&post).unwrap();
+        let before = ledger.actor("a").unwrap().clone();
+        let appeal = ReputationEvent::AppealUpheld { actor: "a".into(), severity: 0.5, surcharge_paid: 2.5 };
+        // nothing to reverse yet
+        assert!(ledger.apply(&appeal).is_err());
+        ledger.apply(&ReputationEvent::ReportUpheld { actor: "a".into(), severity: 0.5 }).unwrap();
+
+        assert_eq!(ledger.apply(&appeal).unwrap(), 2.5);
+        let s = ledger.actor("a").unwrap();
+        assert!((s.q - before.q).abs() < 1e-12);
+        assert_eq!((s.abuse_history, s.upheld_reports), (0.0, 0));
+
+        ledger.apply(&ReputationEvent::AppealRejected { actor: "a".into() }).unwrap();
+        assert_eq!((ledger.actor("a").unwrap().abuse_history, ledger.applied), (0.02, 4));
+        // no state is created for an actor the ledger has never seen
+        assert!(ledger.apply(&ReputationEvent::AppealRejected { actor: "b".into() }).is_err());
+        assert!(ledger.actor("b").is_none());
+
+        // the functions the ledger applies, on a bare state: half redress, no refund
+        let mut state = ledger.actor("a").unwrap().clone();
+        let params = AppealParams { restore_share: 0.5, refund_share: 0.0, ..AppealParams::default() };
+        state.abuse_history = 0.2;
+        assert_eq!(adjust_on_upheld(&mut state, &params, &Appeal { severity: 1.0, surcharge_paid: 3.0 }), 0.0);
+        assert!((state.abuse_history - 0.1).abs() < 1e-12);
+    }
+}
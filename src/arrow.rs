@@ -0,0 +1,205 @@
+// Columnar evaluation over Arrow RecordBatches, plus Parquet re-scoring (feature `arrow`)
+// - Input columns use the JSON field names: `A`..`S` for quality; `rl`, `q`, `ef`, `posts_1h`, `account_age_days`,
+//   `is_claim`, `has_evidence`, `topic`, `base_fare` and the risk signal names for cost
+// - Numeric columns of any integer/float type are cast to Float64; nulls in optional columns mean "missing"
+// - A null in a required column yields a null result for that row
+
+use crate::{calculate_post_cost, calculate_quality, calculate_risk, Actor, Content, ContentTopic, Params, QInputs, RiskSignals, RiskWeights};
+use ::arrow::array::{Array, ArrayRef, AsArray, BooleanArray, Float64Array, StringArray};
+use ::arrow::compute::cast;
+use ::arrow::datatypes::{DataType, Field, Schema};
+use ::arrow::error::ArrowError;
+use ::arrow::record_batch::RecordBatch;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Rows per batch when streaming Parquet files
+pub const BATCH_ROWS: usize = 64 * 1024;
+
+const QUALITY_COLUMNS: [&str; 6] = ["A", "R", "T", "D", "H", "S"];
+const SIGNAL_COLUMNS: [&str; 6] = ["coordination", "clustering", "burst", "monotonicity", "abuse_history", "duplication"];
+
+fn f64_column(batch: &RecordBatch, name: &str) -> Result<Option<Float64Array>, ArrowError> {
+    let Some(col) = batch.column_by_name(name) else { return Ok(None) };
+    Ok(Some(cast(col, &DataType::Float64)?.as_primitive().clone()))
+}
+
+fn bool_column(batch: &RecordBatch, name: &str) -> Result<Option<BooleanArray>, ArrowError> {
+    let Some(col) = batch.column_by_name(name) else { return Ok(None) };
+    Ok(Some(cast(col, &DataType::Boolean)?.as_boolean().clone()))
+}
+
+fn required(batch: &RecordBatch, name: &str) -> Result<Float64Array, ArrowError> {
+    f64_column(batch, name)?.ok_or_else(|| ArrowError::SchemaError(format!("missing column {:?}", name)))
+}
+
+fn get(col: &Option<Float64Array>, i: usize) -> Option<f64> { col.as_ref().filter(|c| c.is_valid(i)).map(|c| c.value(i)) }
+
+fn get_bool(col: &Option<BooleanArray>, i: usize) -> Option<bool> { col.as_ref().filter(|c| c.is_valid(i)).map(|c| c.value(i)) }
+
+fn parse_topic(s: &str) -> Option<ContentTopic> {
+    Some(match s {
+        "general" => ContentTopic::General,
+        "news" => ContentTopic::News,
+        "finance" => ContentTopic::Finance,
+        "health" => ContentTopic::Health,
+        "elections" => ContentTopic::Elections,
+        _ => return None,
+    })
+}
+
+/// Per-row risk signals from the optional signal columns
+struct Signals([Option<Float64Array>; 6]);
+
+impl Signals {
+    fn read(batch: &RecordBatch) -> Result<Self, ArrowError> {
+        let mut cols: [Option<Float64Array>; 6] = Default::default();
+        for (c, name) in cols.iter_mut().zip(SIGNAL_COLUMNS) {
+            *c = f64_column(batch, name)?;
+        }
+        Ok(Signals(cols))
+    }
+    fn row(&self, i: usize) -> RiskSignals {
+        let [c, cl, b, m, h, d] = &self.0;
+        RiskSignals {
+            coordination: get(c, i), clustering: get(cl, i), burst: get(b, i), monotonicity: get(m, i), abuse_history: get(h, i),
+            duplication: get(d, i),
+        }
+    }
+}
+
+/// `calculate_quality` per row (requires `A`, `R`, `T`, `D`, `H`, `S`)
+pub fn quality(batch: &RecordBatch, params: &Params) -> Result<Float64Array, ArrowError> {
+    let cols = QUALITY_COLUMNS.iter().map(|n| required(batch, n)).collect::<Result<Vec<_>, _>>()?;
+    Ok((0..batch.num_rows()).map(|i| {
+        if cols.iter().any(|c| c.is_null(i)) { return None; }
+        let v = |k: usize| cols[k].value(i);
+        Some(calculate_quality(QInputs { A: v(0), R: v(1), T: v(2), D: v(3), H: v(4), S: v(5) }, params))
+    }).collect())
+}
+
+/// `calculate_risk` per row with the default weights (absent or null signals count as missing)
+pub fn risk(batch: &RecordBatch) -> Result<Float64Array, ArrowError> {
+    let signals = Signals::read(batch)?;
+    let w = RiskWeights::default();
+    Ok((0..batch.num_rows()).map(|i| Some(calculate_risk(&Some(signals.row(i)), &w))).collect())
+}
+
+/// `calculate_post_cost` per row (requires `rl`, `q`, `ef`; `base_fare` defaults to 1.0)
+pub fn cost(batch: &RecordBatch, params: &Params) -> Result<Float64Array, ArrowError> {
+    let (rl, q, ef) = (required(batch, "rl")?, required(batch, "q")?, required(batch, "ef")?);
+    let posts_1h = f64_column(batch, "posts_1h")?;
+    let account_age_days = f64_column(batch, "account_age_days")?;
+    let base_fare = f64_column(batch, "base_fare")?;
+    let (is_claim, has_evidence) = (bool_column(batch, "is_claim")?, bool_column(batch, "has_evidence")?);
+    let topics: Option<StringArray> = match batch.column_by_name("topic") {
+        Some(col) => Some(cast(col, &DataType::Utf8)?.as_string::<i32>().clone()),
+        None => None,
+    };
+    let signals = Signals::read(batch)?;
+    (0..batch.num_rows()).map(|i| {
+        if rl.is_null(i) || q.is_null(i) || ef.is_null(i) { return Ok(None); }
+        let topic = match topics.as_ref().filter(|t| t.is_valid(i)) {
+            Some(t) => Some(parse_topic(t.value(i))
+                .ok_or_else(|| ArrowError::InvalidArgumentError(format!("row {}: unknown topic {:?}", i, t.value(i))))?),
+            None => None,
+        };
+        let actor = Actor { rl: rl.value(i), q: q.value(i), ef: ef.value(i), posts_1h: get(&posts_1h, i), account_age_days: get(&account_age_days, i), ..Default::default() };
+        let content = Content { is_claim: get_bool(&is_claim, i), has_evidence: get_bool(&has_evidence, i), risk_signals: Some(signals.row(i)), topic, kind: None, fact_check: None };
+        Ok(Some(calculate_post_cost(&actor, &content, params, get(&base_fare, i).unwrap_or(1.0))))
+    }).collect()
+}
+
+/// Append the computable result columns: `quality` (when `A`..`S` exist), `risk`, and `cost` (when `rl`, `q`, `ef` exist)
+pub fn evaluate(batch: &RecordBatch, params: &Params) -> Result<RecordBatch, ArrowError> {
+    let has = |n: &str| batch.column_by_name(n).is_some();
+    let mut outputs: Vec<(&str, ArrayRef)> = Vec::new();
+    if QUALITY_COLUMNS.iter().all(|n| has(n)) {
+        outputs.push(("quality", Arc::new(quality(batch, params)?)));
+    }
+    outputs.push(("risk", Arc::new(risk(batch)?)));
+    if ["rl", "q", "ef"].iter().all(|n| has(n)) {
+        outputs.push(("cost", Arc::new(cost(batch, params)?)));
+    }
+
+    let mut fields: Vec<Field> = batch.schema().fields().iter().map(|f| f.as_ref().clone()).collect();
+    let mut columns = batch.columns().to_vec();
+    for (name, col) in outputs {
+        if has(name) {
+            return Err(ArrowError::SchemaError(format!("input already has a {:?} column", name)));
+        }
+        fields.push(Field::new(name, DataType::Float64, true));
+        columns.push(col);
+    }
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+}
+
+/// Stream a Parquet file through `evaluate` into a new Parquet file (snappy); returns the row count
+pub fn rescore_parquet(input: &Path, output: &Path, params: &Params) -> Result<u64, ParquetError> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(input)?)?.with_batch_size(BATCH_ROWS);
+    // evaluating an empty batch yields the output schema, so empty inputs still produce a valid file
+    let schema = evaluate(&RecordBatch::new_empty(builder.schema().clone()), params)?.schema();
+    let props = WriterProperties::builder().set_compression(Compression::SNAPPY).build();
+    let mut writer = ArrowWriter::try_new(File::create(output)?, schema, Some(props))?;
+    let mut rows = 0;
+    for batch in builder.build()? {
+        let out = evaluate(&batch?, params)?;
+        rows += out.num_rows() as u64;
+        writer.write(&out)?;
+    }
+    writer.close()?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::arrow::array::Int64Array;
+
+    fn batch() -> RecordBatch {
+        let f = |v: Vec<Option<f64>>| Arc::new(Float64Array::from(v)) as ArrayRef;
+        RecordBatch::try_from_iter(vec![
+            ("rl", Arc::new(Int64Array::from(vec![Some(120), Some(5), None])) as ArrayRef),
+            ("q", f(vec![Some(0.8), Some(0.5), Some(0.5)])),
+            ("ef", f(vec![Some(28.3), Some(3.0), Some(1.0)])),
+            ("is_claim", Arc::new(BooleanArray::from(vec![Some(true), None, None])) as ArrayRef),
+            ("topic", Arc::new(StringArray::from(vec![Some("health"), None, None])) as ArrayRef),
+            ("coordination", f(vec![Some(0.5), None, Some(0.9)])),
+        ]).unwrap()
+    }
+
+    #[test]
+    fn t_columns_match_scalar_functions() {
+        let p = Params::default();
+        let out = evaluate(&batch(), &p).unwrap();
+        let cost = out.column_by_name("cost").unwrap().as_primitive::<::arrow::datatypes::Float64Type>();
+        let content = Content { is_claim: Some(true), topic: Some(ContentTopic::Health), risk_signals: Some(RiskSignals { coordination: Some(0.5), ..Default::default() }), ..Default::default() };
+        assert_eq!(cost.value(0), calculate_post_cost(&Actor { rl: 120.0, q: 0.8, ef: 28.3, posts_1h: None, ..Default::default() }, &content, &p, 1.0));
+        assert!(cost.is_null(2));
+        assert!(out.column_by_name("quality").is_none());
+        let risk = out.column_by_name("risk").unwrap().as_primitive::<::arrow::datatypes::Float64Type>();
+        assert_eq!(risk.value(1), 0.0);
+        assert!(evaluate(&out, &p).is_err());
+    }
+
+    #[test]
+    fn t_parquet_rescore_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("slimechain-arrow-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (input, output) = (dir.join("in.parquet"), dir.join("out.parquet"));
+        let b = batch();
+        let mut w = ArrowWriter::try_new(File::create(&input).unwrap(), b.schema(), None).unwrap();
+        w.write(&b).unwrap();
+        w.close().unwrap();
+        assert_eq!(rescore_parquet(&input, &output, &Params::default()).unwrap(), 3);
+        let rows: Vec<RecordBatch> = ParquetRecordBatchReaderBuilder::try_new(File::open(&output).unwrap()).unwrap().build().unwrap().collect::<Result<_, _>>().unwrap();
+        assert!(rows[0].column_by_name("cost").is_some());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,206 @@
+// Audit records of charged costs and paid rewards (feature `serde`)
+// - A `Record` is self-contained: function name, input, the params in force, crate version and output, plus
+//   SHA-256 hashes of the canonical input and params so records can be indexed and compared without the payloads
+// - Canonical JSON: object keys sorted, no whitespace, numbers as serde_json prints them (shortest round-trip), so the
+//   hash does not depend on field order in the producer's struct or map
+// - `verify` re-checks both hashes and the version, recomputes through the `vectors::eval` dispatcher (the same
+//   function names as the conformance vectors) and compares outputs exactly; a record from another crate version
+//   is refused rather than judged, since outputs may legitimately differ across versions
+// - `merkleize` commits to an epoch's records with one root (the PoR tree: SHA-256, domain-separated leaves and
+//   nodes, odd last node paired with itself); a leaf is `record_hash`, which covers everything but the params
+//   themselves (their hash stands in), so a challenged node answers with the record and its `InclusionProof`
+
+use crate::por::{leaf_hash, merkle_depth, merkle_fold, merkle_levels, merkle_path, Hash32};
+use crate::vectors::{eval, VectorError};
+use crate::Params;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Record {
+    pub function: String,
+    pub input: Value,
+    pub input_hash: Hash32,
+    pub params: Params,
+    pub params_hash: Hash32,
+    pub version: String,
+    pub output: Value,
+}
+
+/// Position of a record in a committed batch and its Merkle path (leaf to root sibling hashes)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InclusionProof {
+    pub index: u64,
+    /// Records in the batch
+    pub leaves: u64,
+    pub path: Vec<Hash32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditFailure {
+    /// Recorded under another crate version
+    Version { recorded: String },
+    InputHash,
+    ParamsHash,
+    /// Recomputation failed (unknown function or malformed input)
+    Eval { reason: String },
+    /// Recomputed output differs from the recorded one
+    Output { recomputed: Value },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditError { pub function: String, pub reason: AuditFailure }
+
+impl std::fmt::Display for AuditError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "audit of {} failed: {:?}", self.function, self.reason)
+    }
+}
+
+impl std::error::Error for AuditError {}
+
+/// Canonical JSON encoding of `value`
+pub fn canonical_json(value: &Value) -> String {
+    fn write(v: &Value, out: &mut String) {
+        match v {
+            Value::Array(xs) => {
+                out.push('[');
+                for (i, x) in xs.iter().enumerate() {
+                    if i > 0 { out.push(','); }
+                    write(x, out);
+                }
+                out.push(']');
+            }
+            Value::Object(m) => {
+                let mut keys: Vec<&String> = m.keys().collect();
+                keys.sort();
+                out.push('{');
+                for (i, k) in keys.into_iter().enumerate() {
+                    if i > 0 { out.push(','); }
+                    out.push_str(&Value::String(k.clone()).to_string());
+                    out.push(':');
+                    write(&m[k], out);
+                }
+                out.push('}');
+            }
+            scalar => out.push_str(&scalar.to_string()),
+        }
+    }
+    let mut out = String::new();
+    write(value, &mut out);
+    out
+}
+
+/// SHA-256 of the canonical JSON of `value`, domain-separated by `domain`
+pub fn hash_json(domain: &str, value: &Value) -> Hash32 {
+    let mut h = Sha256::new();
+    h.update(b"slimechain/audit/v1");
+    h.update((domain.len() as u64).to_le_bytes());
+    h.update(domain.as_bytes());
+    h.update(canonical_json(value).as_bytes());
+    h.finalize().into()
+}
+
+/// Hash of the params in force, as recorded and as committed to in claims
+pub fn params_hash(params: &Params) -> Hash32 {
+    hash_json("params", &serde_json::to_value(params).expect("params serialize"))
+}
+
+/// Evaluate `function` on `input` under `params` and record the computation
+pub fn record(function: &str, input: Value, params: &Params) -> Result<Record, VectorError> {
+    let output = eval(function, &input, params)?;
+    Ok(Record {
+        function: function.into(),
+        input_hash: hash_json("input", &input),
+        input,
+        params: params.clone(),
+        params_hash: params_hash(params),
+        version: env!("CARGO_PKG_VERSION").into(),
+        output,
+    })
+}
+
+/// Hash committing to a record (function, input and params hashes, version and canonical output)
+pub fn record_hash(record: &Record) -> Hash32 {
+    hash_json("record", &json!({
+        "function": record.function, "input_hash": record.input_hash, "params_hash": record.params_hash,
+        "version": record.version, "output": record.output,
+    }))
+}
+
+/// Merkle root over `records` (in order) and an inclusion proof per record; an empty batch has no proofs
+pub fn merkleize(records: &[Record]) -> (Hash32, Vec<InclusionProof>) {
+    if records.is_empty() { return (leaf_hash(&[]), Vec::new()); }
+    let levels = merkle_levels(records.iter().map(|r| leaf_hash(&record_hash(r))).collect());
+    let leaves = records.len() as u64;
+    let proofs = (0..records.len()).map(|i| InclusionProof { index: i as u64, leaves, path: merkle_path(&levels, i) }).collect();
+    (levels[levels.len() - 1][0], proofs)
+}
+
+/// Check that `record` is committed to by `root` at the proof's position
+pub fn verify_inclusion(root: &Hash32, record: &Record, proof: &InclusionProof) -> bool {
+    proof.index < proof.leaves && proof.path.len() == merkle_depth(proof.leaves)
+        && merkle_fold(leaf_hash(&record_hash(record)), proof.index, &proof.path) == *root
+}
+
+/// Recompute a record and check it bit for bit
+pub fn verify(record: &Record) -> Result<(), AuditError> {
+    let fail = |reason| Err(AuditError { function: record.function.clone(), reason });
+    if record.version != env!("CARGO_PKG_VERSION") { return fail(AuditFailure::Version { recorded: record.version.clone() }); }
+    if hash_json("input", &record.input) != record.input_hash { return fail(AuditFailure::InputHash); }
+    if params_hash(&record.params) != record.params_hash { return fail(AuditFailure::ParamsHash); }
+    match eval(&record.function, &record.input, &record.params) {
+        Err(e) => fail(AuditFailure::Eval { reason: e.reason }),
+        Ok(out) if out != record.output => fail(AuditFailure::Output { recomputed: out }),
+        Ok(_) => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn t_record_verify_and_tamper() {
+        let params = Params::default();
+        let input = json!({ "actor": { "rl": 5.0, "q": 0.8, "ef": 3.0 }, "content": { "is_claim": true }, "base_fare": 1.0 });
+        let rec = record("calculate_post_cost", input, &params).unwrap();
+        assert_eq!(verify(&rec), Ok(()));
+        // survives a JSON round trip, and key order does not change the hash
+        let back: Record = serde_json::from_str(&serde_json::to_string(&rec).unwrap()).unwrap();
+        assert_eq!(verify(&back), Ok(()));
+        assert_eq!(hash_json("input", &json!({ "a": 1, "b": [2.5] })), hash_json("input", &json!({ "b": [2.5], "a": 1 })));
+
+        let mut overcharged = rec.clone();
+        overcharged.output = json!(rec.output.as_f64().unwrap() * 2.0);
+        assert!(matches!(verify(&overcharged).unwrap_err().reason, AuditFailure::Output { .. }));
+        let mut swapped = rec.clone();
+        swapped.params.cost.alpha *= 2.0;
+        assert_eq!(verify(&swapped).unwrap_err().reason, AuditFailure::ParamsHash);
+        let mut old = rec;
+        old.version = "0.9.0".into();
+        assert!(matches!(verify(&old).unwrap_err().reason, AuditFailure::Version { .. }));
+    }
+
+    #[test]
+    fn t_merkleize_inclusion() {
+        let params = Params::default();
+        let records: Vec<Record> = (0..5).map(|i| {
+            record("calculate_ef", json!({ "followers_q": [0.6 + 0.05 * i as f64, 0.9] }), &params).unwrap()
+        }).collect();
+        let (root, proofs) = merkleize(&records);
+        assert_eq!(proofs.len(), 5);
+        for (r, p) in records.iter().zip(&proofs) { assert!(verify_inclusion(&root, r, p)); }
+        // wrong position, altered output, or the duplicated odd leaf at a phantom index all fail
+        assert!(!verify_inclusion(&root, &records[1], &proofs[2]));
+        let mut forged = records[3].clone();
+        forged.output = json!(0.0);
+        assert!(!verify_inclusion(&root, &forged, &proofs[3]));
+        let phantom = InclusionProof { index: 5, ..proofs[4].clone() };
+        assert!(!verify_inclusion(&root, &records[4], &phantom));
+        assert!(merkleize(&[]).1.is_empty());
+    }
+}
@@ -0,0 +1,146 @@
+// Rayon-parallel batch evaluators (feature `parallel`)
+// - Each function maps one scalar entry point over a slice on the global rayon pool
+// - Output `i` is the result for input `i` (same as a sequential map, bit for bit)
+// - `*_observed` variants also report each result to a `MetricsSink` (from the worker threads, in no particular order)
+// - `canonicalize` dedups identical requests so each is scored once, keyed on the exact bit patterns of their fields
+//   (`BitKey`, the same identity `ScoringContext` caches under); `fan_out` maps the unique results back to every row
+
+use crate::context::CostKey;
+use crate::metrics::{record_cost, record_quality, record_reward, MetricsSink};
+use crate::{
+    calculate_post_cost, calculate_post_cost_detailed, calculate_quality, calculate_quality_detailed, calculate_risk,
+    calculate_serve_reward, calculate_serve_reward_detailed, Params, PostEvent, QInputs, RewardInput, RiskSignals, RiskWeights,
+};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// `calculate_quality` per input
+pub fn quality_batch(inputs: &[QInputs], params: &Params) -> Vec<f64> {
+    inputs.par_iter().map(|q| calculate_quality(q.clone(), params)).collect()
+}
+
+/// `calculate_risk` per signal set
+pub fn risk_batch(signals: &[Option<RiskSignals>], weights: &RiskWeights) -> Vec<f64> {
+    signals.par_iter().map(|s| calculate_risk(s, weights)).collect()
+}
+
+/// `calculate_post_cost` per event (parallel `simulate_actor_costs`)
+pub fn cost_batch(events: &[PostEvent], params: &Params) -> Vec<f64> {
+    events.par_iter().map(|e| calculate_post_cost(&e.actor, &e.content, params, e.base_fare)).collect()
+}
+
+/// `calculate_serve_reward` per input
+pub fn reward_batch(inputs: &[RewardInput], params: &Params) -> Vec<f64> {
+    inputs.par_iter().map(|r| calculate_serve_reward(r, params)).collect()
+}
+
+/// Hashable identity of a request: equal keys iff every field is bit-identical
+pub trait BitKey {
+    type Key: Hash + Eq;
+    fn bit_key(&self) -> Self::Key;
+}
+
+impl BitKey for PostEvent {
+    type Key = CostKey;
+    fn bit_key(&self) -> CostKey { CostKey::new(&self.actor, &self.content, self.base_fare) }
+}
+
+impl BitKey for QInputs {
+    type Key = [u64; 6];
+    fn bit_key(&self) -> [u64; 6] { [self.A, self.R, self.T, self.D, self.H, self.S].map(f64::to_bits) }
+}
+
+impl BitKey for RewardInput {
+    type Key = ([u64; 3], u64, u32, Option<u32>, Option<u64>, Option<String>);
+    fn bit_key(&self) -> Self::Key {
+        let RewardInput { ticket_budget, client_q, size_bytes, ttfb_ms, server_cluster_risk, prev_serves, ttfb_percentile, region } = self;
+        (
+            [ticket_budget.to_bits(), client_q.to_bits(), server_cluster_risk.to_bits()],
+            *size_bytes, *ttfb_ms, *prev_serves, ttfb_percentile.map(f64::to_bits), region.clone(),
+        )
+    }
+}
+
+/// Distinct `items` in first-seen order, and for each input the index of its unique item
+pub fn canonicalize<T: BitKey + Clone>(items: &[T]) -> (Vec<T>, Vec<usize>) {
+    let mut seen: HashMap<T::Key, usize> = HashMap::with_capacity(items.len());
+    let mut unique = Vec::new();
+    let index_map = items.iter().map(|item| {
+        *seen.entry(item.bit_key()).or_insert_with(|| {
+            unique.push(item.clone());
+            unique.len() - 1
+        })
+    }).collect();
+    (unique, index_map)
+}
+
+/// Results for the unique items back in input order
+pub fn fan_out<R: Clone>(unique_results: &[R], index_map: &[usize]) -> Vec<R> {
+    index_map.iter().map(|&i| unique_results[i].clone()).collect()
+}
+
+/// `cost_batch`, scoring each distinct event once
+pub fn cost_batch_dedup(events: &[PostEvent], params: &Params) -> Vec<f64> {
+    let (unique, index_map) = canonicalize(events);
+    fan_out(&cost_batch(&unique, params), &index_map)
+}
+
+/// `quality_batch`, reporting unverified-cap hits
+pub fn quality_batch_observed(inputs: &[QInputs], params: &Params, sink: &dyn MetricsSink) -> Vec<f64> {
+    inputs.par_iter().map(|q| {
+        let b = calculate_quality_detailed(q, params);
+        record_quality(sink, &b);
+        b.q
+    }).collect()
+}
+
+/// `cost_batch`, reporting the cost distribution, C_min floor hits and rate penalties
+pub fn cost_batch_observed(events: &[PostEvent], params: &Params, sink: &dyn MetricsSink) -> Vec<f64> {
+    events.par_iter().map(|e| {
+        let b = calculate_post_cost_detailed(&e.actor, &e.content, params, e.base_fare);
+        record_cost(sink, &b);
+        b.cost
+    }).collect()
+}
+
+/// `reward_batch`, reporting the reward distribution and budget caps
+pub fn reward_batch_observed(inputs: &[RewardInput], params: &Params, sink: &dyn MetricsSink) -> Vec<f64> {
+    inputs.par_iter().map(|r| {
+        let b = calculate_serve_reward_detailed(r, params);
+        record_reward(sink, &b);
+        b.reward
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{simulate_actor_costs, Actor, Content};
+
+    #[test]
+    fn t_matches_sequential_in_order() {
+        let p = Params::default();
+        let events: Vec<PostEvent> = (0..2000).map(|i| PostEvent {
+            actor: Actor { rl: i as f64, q: 0.5, ef: (i % 50) as f64, posts_1h: Some((i % 40) as f64), ..Default::default() },
+            content: Content { is_claim: Some(i % 3 == 0), risk_signals: Some(RiskSignals { burst: Some((i % 10) as f64 / 10.0), ..Default::default() }), ..Default::default() },
+            base_fare: 1.0,
+        }).collect();
+        assert_eq!(cost_batch(&events, &p), simulate_actor_costs(&events, &p));
+        assert_eq!(cost_batch_observed(&events, &p, &()), cost_batch(&events, &p));
+
+        // 300 rows, 30 distinct
+        let repeated: Vec<PostEvent> = events.iter().take(30).cycle().take(300).cloned().collect();
+        let (unique, index_map) = canonicalize(&repeated);
+        assert_eq!((unique.len(), index_map[31]), (30, 1));
+        assert_eq!(cost_batch_dedup(&repeated, &p), cost_batch(&repeated, &p));
+        // -0.0 and 0.0 score the same but are different requests
+        let zero = QInputs { A: 0.0, R: 0.0, T: 0.0, D: 0.0, H: 0.0, S: 0.0 };
+        let signed = [zero.clone(), QInputs { A: -0.0, ..zero.clone() }, zero];
+        assert_eq!(canonicalize(&signed).1, vec![0, 1, 0]);
+
+        let signals: Vec<Option<RiskSignals>> = events.iter().map(|e| e.content.risk_signals.clone()).collect();
+        let w = RiskWeights::default();
+        assert_eq!(risk_batch(&signals, &w), signals.iter().map(|s| calculate_risk(s, &w)).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,149 @@
+// Throughput measurements for the core functions on synthetic data
+// - Inputs come from a fixed-seed generator and are built before timing starts
+// - Memory (allocations and bytes per call) is measured only in builds with the `bench-alloc` feature, which installs a
+//   counting global allocator for the whole binary; other builds report it as null
+
+use crate::{CliError, ErrorKind};
+use serde::Serialize;
+use slimechain_algo::*;
+use std::hint::black_box;
+use std::time::Instant;
+
+#[cfg(feature = "bench-alloc")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// System allocator that counts allocations
+    struct Counting;
+
+    static ALLOCS: AtomicU64 = AtomicU64::new(0);
+    static BYTES: AtomicU64 = AtomicU64::new(0);
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCS.fetch_add(1, Ordering::Relaxed);
+            BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+            System.alloc(layout)
+        }
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) { System.dealloc(ptr, layout) }
+    }
+
+    #[global_allocator]
+    static GLOBAL: Counting = Counting;
+
+    /// (allocations, bytes) so far
+    pub fn allocated() -> Option<(u64, u64)> { Some((ALLOCS.load(Ordering::Relaxed), BYTES.load(Ordering::Relaxed))) }
+}
+
+#[cfg(feature = "bench-alloc")]
+use counting::allocated;
+
+#[cfg(not(feature = "bench-alloc"))]
+fn allocated() -> Option<(u64, u64)> { None }
+
+/// Benchmarked functions
+pub const KINDS: &[&str] = &["quality", "ef", "risk", "cost", "prop", "reward", "base", "pipeline", "batch"];
+
+/// Number of distinct synthetic inputs cycled through
+const POOL: usize = 1024;
+/// Followers per `ef` input
+const FOLLOWERS: usize = 100;
+/// Posts per `batch` call (cost + propagation for each, as a relay scores an incoming batch)
+const BATCH: usize = 256;
+
+#[derive(Debug, Serialize)]
+pub struct BenchReport {
+    pub kind: String,
+    pub n: u64,
+    pub elapsed_secs: f64,
+    pub calls_per_sec: f64,
+    pub ns_per_call: f64,
+    /// Null without the `bench-alloc` feature
+    pub allocs_per_call: Option<f64>,
+    pub bytes_per_call: Option<f64>,
+}
+
+/// Fixed-seed uniform [0, 1) generator (xorshift64*)
+struct Gen(u64);
+
+impl Gen {
+    fn unit(&mut self) -> f64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        (self.0.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11) as f64 / (1u64 << 53) as f64
+    }
+    fn signals(&mut self) -> RiskSignals {
+        RiskSignals {
+            coordination: Some(self.unit()), clustering: Some(self.unit()), burst: Some(self.unit()), monotonicity: Some(self.unit()),
+            abuse_history: Some(self.unit()), duplication: Some(self.unit()),
+        }
+    }
+    fn qinputs(&mut self) -> QInputs {
+        QInputs { A: self.unit(), R: self.unit(), T: self.unit(), D: self.unit(), H: self.unit(), S: self.unit() }
+    }
+    fn content(&mut self) -> Content {
+        Content { is_claim: Some(self.unit() < 0.3), has_evidence: Some(self.unit() < 0.5), risk_signals: Some(self.signals()), topic: None, kind: None, fact_check: None }
+    }
+    fn actor(&mut self) -> Actor {
+        Actor { rl: self.unit() * 200.0, q: self.unit(), ef: self.unit() * 50.0, posts_1h: Some(self.unit() * 20.0), ..Default::default() }
+    }
+    fn reward(&mut self) -> RewardInput {
+        RewardInput {
+            ticket_budget: self.unit() * 2.0, client_q: self.unit(), size_bytes: (self.unit() * 1e6) as u64,
+            ttfb_ms: (self.unit() * 2000.0) as u32, server_cluster_risk: self.unit(), ..Default::default()
+        }
+    }
+}
+
+/// Time `n` calls of `f` over a pool of pre-built inputs
+fn measure<T>(kind: &str, n: u64, pool: Vec<T>, mut f: impl FnMut(&T)) -> BenchReport {
+    let before = allocated();
+    let start = Instant::now();
+    for i in 0..n {
+        f(black_box(&pool[i as usize % pool.len()]));
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    let after = allocated();
+    let per = |x: u64| if n == 0 { 0.0 } else { x as f64 / n as f64 };
+    let used = before.zip(after).map(|((a0, b0), (a1, b1))| (per(a1 - a0), per(b1 - b0)));
+    BenchReport {
+        kind: kind.into(), n, elapsed_secs: elapsed,
+        calls_per_sec: if elapsed > 0.0 { n as f64 / elapsed } else { 0.0 },
+        ns_per_call: per((elapsed * 1e9) as u64),
+        allocs_per_call: used.map(|u| u.0),
+        bytes_per_call: used.map(|u| u.1),
+    }
+}
+
+/// Run one benchmark kind
+pub fn run(kind: &str, n: u64, params: &Params) -> Result<BenchReport, CliError> {
+    let mut g = Gen(0x9E37_79B9_7F4A_7C15);
+    let w = RiskWeights::default();
+    Ok(match kind {
+        "quality" => measure(kind, n, (0..POOL).map(|_| g.qinputs()).collect(), |q| { black_box(calculate_quality(q.clone(), params)); }),
+        "ef" => measure(kind, n, (0..POOL).map(|_| (0..FOLLOWERS).map(|_| g.unit()).collect::<Vec<f64>>()).collect(), |f| { black_box(calculate_ef(f, params)); }),
+        "risk" => measure(kind, n, (0..POOL).map(|_| Some(g.signals())).collect(), |s| { black_box(calculate_risk(s, &w)); }),
+        "cost" => measure(kind, n, (0..POOL).map(|_| (g.actor(), g.content())).collect(), |(a, c)| { black_box(calculate_post_cost(a, c, params, 1.0)); }),
+        "prop" => measure(kind, n, (0..POOL).map(|_| g.content()).collect(), |c| { black_box(adjust_propagation_for_content(c, params)); }),
+        "reward" => measure(kind, n, (0..POOL).map(|_| g.reward()).collect(), |r| { black_box(calculate_serve_reward(r, params)); }),
+        "base" => measure(kind, n, (0..POOL).map(|_| (g.unit() * 10.0, g.unit() * 2000.0)).collect(), |&(b, l)| { black_box(update_base_cost(b, l, params)); }),
+        "pipeline" => measure(kind, n, (0..POOL).map(|_| {
+            let a = g.actor();
+            pipeline::PipelineInput {
+                quality: g.qinputs(), followers_q: (0..FOLLOWERS).map(|_| g.unit()).collect(), rl: a.rl, posts_1h: a.posts_1h,
+                content: g.content(), base_fare: None,
+                load: Some(pipeline::LoadSnapshot { current_base: 1.0, current_load: g.unit() * 2000.0 }),
+                serve: Some(g.reward()),
+            }
+        }).collect(), |i| { black_box(pipeline::run(i, params)); }),
+        "batch" => measure(kind, n, (0..POOL / BATCH).map(|_| (0..BATCH).map(|_| (g.actor(), g.content())).collect::<Vec<_>>()).collect(), |posts| {
+            for (a, c) in posts {
+                black_box(calculate_post_cost(a, c, params, 1.0));
+                black_box(adjust_propagation_for_content(c, params));
+            }
+        }),
+        _ => return Err(CliError::new(ErrorKind::Usage, format!("unknown bench kind {:?} (expected all or one of {})", kind, KINDS.join(", ")))),
+    })
+}
@@ -0,0 +1,675 @@
+
+// Simple CLI: read JSON input and output JSON result
+use std::env;
+use std::fs;
+use std::io::Read;
+use slimechain_algo::*;
+use serde::{Deserialize, Serialize};
+
+mod bench;
+#[cfg(feature = "schema")]
+mod schema;
+#[cfg(feature = "serve")]
+mod serve;
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct CostInput {
+    actor: Actor,
+    content: Content,
+    base_fare: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct BaseInput {
+    current_base: f64,
+    current_load: f64,
+}
+
+const USAGE: &str = "Usage: slimechain-algo <cost|reward|prop|base|quality|ef|risk|pipeline|simulate> <input.json|-> [--params <file.json|file.toml>] [--preset <name>] [--format json|json-compact|csv|yaml|toml] [--encoding json|cbor] [--watch]
+       slimechain-algo explain <cost|reward|risk|quality> <input.json|-> [--params <file>|--preset <name>] [--format ..]
+       slimechain-algo serve [--listen <addr:port>] [--params <file>|--preset <name>]   (feature `serve`)
+       slimechain-algo grpc [--listen <addr:port>] [--params <file>|--preset <name>]    (feature `grpc`)
+       slimechain-algo rescore <posts.parquet> --out <scored.parquet> [--params <file>|--preset <name>]   (feature `arrow`)
+       slimechain-algo diff --a <old params> --b <new params> --inputs <corpus.ndjson|-> [--format ..]
+       slimechain-algo vectors [--out <vectors.json>] [--params <file>|--preset <name>] | vectors --check <vectors.json>
+       slimechain-algo conform <results.json|-> [--tolerances <tolerances.json>]
+       slimechain-algo bench [--kind <all|quality|ef|risk|cost|prop|reward|base|pipeline|batch>] [--n <calls>]
+       slimechain-algo presets list | presets show <name> [--format toml]
+       slimechain-algo schema <command|params|scenario>   (feature `schema`, on by default)
+       slimechain-algo validate [--params <file>|--preset <name>] [--input <input.json|-> --kind <command>]";
+
+/// Poll interval for `--watch`
+const WATCH_POLL_MS: u64 = 300;
+
+/// Commands that take an input file
+const COMMANDS: &[&str] = &["cost", "reward", "prop", "base", "quality", "ef", "risk", "pipeline", "simulate"];
+
+/// Positional arguments plus global flags
+#[derive(Default)]
+struct CliArgs {
+    positional: Vec<String>,
+    params_path: Option<String>,
+    preset: Option<String>,
+    format: Format,
+    /// Wire encoding of command inputs and results (`cbor` overrides `--format`)
+    encoding: Encoding,
+    /// `validate`: input file to check
+    input: Option<String>,
+    /// `validate`: command whose input shape `input` must match; `bench`: function to measure
+    kind: Option<String>,
+    /// Re-run on input/params file changes
+    watch: bool,
+    /// `bench`: calls per function
+    n: Option<u64>,
+    /// `serve`/`grpc`: listen address
+    listen: Option<String>,
+    /// `diff`: parameter files and NDJSON corpus of pipeline inputs
+    params_a: Option<String>,
+    params_b: Option<String>,
+    inputs: Option<String>,
+    /// `vectors`/`rescore`: output file; `vectors`: file to check
+    out: Option<String>,
+    check: Option<String>,
+    /// `conform`: per-function tolerances
+    tolerances: Option<String>,
+}
+
+/// Output encoding for results
+#[derive(Default, Clone, Copy)]
+enum Format { #[default] Json, JsonCompact, Csv, Yaml, Toml }
+
+impl std::str::FromStr for Format {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "json" => Ok(Format::Json),
+            "json-compact" => Ok(Format::JsonCompact),
+            "csv" => Ok(Format::Csv),
+            "yaml" => Ok(Format::Yaml),
+            "toml" => Ok(Format::Toml),
+            _ => Err(format!("unknown format {:?} (expected json, json-compact, csv, yaml or toml)", s)),
+        }
+    }
+}
+
+/// Input/result encoding for the file-based commands and `explain`
+#[derive(Default, Clone, Copy, PartialEq)]
+enum Encoding { #[default] Json, Cbor }
+
+impl std::str::FromStr for Encoding {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "json" => Ok(Encoding::Json),
+            "cbor" => Ok(Encoding::Cbor),
+            _ => Err(format!("unknown encoding {:?} (expected json or cbor)", s)),
+        }
+    }
+}
+
+/// Error categories; each maps to a documented exit code
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ErrorKind { Usage, Io, Parse, Validation, Math }
+
+impl ErrorKind {
+    /// 1 usage/io, 2 parse, 3 validation, 4 math
+    fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::Usage | ErrorKind::Io => 1,
+            ErrorKind::Parse => 2,
+            ErrorKind::Validation => 3,
+            ErrorKind::Math => 4,
+        }
+    }
+}
+
+/// Error printed to stderr as `{"error": {"kind": .., "detail": .., "errors": [..]}}`
+#[derive(Debug, Serialize)]
+struct CliError {
+    kind: ErrorKind,
+    detail: String,
+    /// Individual violations for `validation` errors
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<ParamError>,
+}
+
+impl CliError {
+    fn new(kind: ErrorKind, detail: impl Into<String>) -> Self { Self{ kind, detail: detail.into(), errors: Vec::new() } }
+}
+
+/// Split global flags from positional arguments
+fn parse_args(args: &[String]) -> Result<CliArgs, CliError> {
+    let usage = |msg: &str| CliError::new(ErrorKind::Usage, msg);
+    let mut out = CliArgs::default();
+    let mut it = args.iter();
+    while let Some(a) = it.next() {
+        match a.as_str() {
+            "--params" => out.params_path = Some(it.next().ok_or_else(|| usage("--params requires a file path"))?.clone()),
+            "--preset" => out.preset = Some(it.next().ok_or_else(|| usage("--preset requires a name"))?.clone()),
+            "--format" => out.format = it.next().ok_or_else(|| usage("--format requires a value"))?.parse().map_err(|e: String| usage(&e))?,
+            "--encoding" => out.encoding = it.next().ok_or_else(|| usage("--encoding requires a value"))?.parse().map_err(|e: String| usage(&e))?,
+            "--input" => out.input = Some(it.next().ok_or_else(|| usage("--input requires a file path"))?.clone()),
+            "--listen" => out.listen = Some(it.next().ok_or_else(|| usage("--listen requires an address"))?.clone()),
+            "--a" => out.params_a = Some(it.next().ok_or_else(|| usage("--a requires a params file"))?.clone()),
+            "--b" => out.params_b = Some(it.next().ok_or_else(|| usage("--b requires a params file"))?.clone()),
+            "--inputs" => out.inputs = Some(it.next().ok_or_else(|| usage("--inputs requires a file path"))?.clone()),
+            "--watch" => out.watch = true,
+            "--n" => out.n = Some(it.next().ok_or_else(|| usage("--n requires a count"))?.parse().map_err(|_| usage("--n must be a non-negative integer"))?),
+            "--out" => out.out = Some(it.next().ok_or_else(|| usage("--out requires a file path"))?.clone()),
+            "--check" => out.check = Some(it.next().ok_or_else(|| usage("--check requires a file path"))?.clone()),
+            "--tolerances" => out.tolerances = Some(it.next().ok_or_else(|| usage("--tolerances requires a file path"))?.clone()),
+            "--kind" => out.kind = Some(it.next().ok_or_else(|| usage("--kind requires a command name"))?.clone()),
+            _ => out.positional.push(a.clone()),
+        }
+    }
+    if out.params_path.is_some() && out.preset.is_some() {
+        return Err(usage("--params and --preset are mutually exclusive"));
+    }
+    Ok(out)
+}
+
+/// Resolve parameters from a file (TOML by `.toml` extension, JSON otherwise), a preset, or the defaults
+fn resolve_params(params_path: Option<&str>, preset: Option<&str>) -> Result<Params, CliError> {
+    Ok(match (params_path, preset) {
+        (Some(path), _) => {
+            let text = fs::read_to_string(path)
+                .map_err(|e| CliError::new(ErrorKind::Io, format!("cannot read params file {}: {}", path, e)))?;
+            let parsed = if path.ends_with(".toml") { toml::from_str(&text).map_err(|e| e.to_string()) }
+                else { serde_json::from_str(&text).map_err(|e| e.to_string()) };
+            parsed.map_err(|e| CliError::new(ErrorKind::Parse, format!("invalid params file {}: {}", path, e)))?
+        }
+        (None, Some(name)) => presets::preset(name).ok_or_else(|| CliError::new(ErrorKind::Usage,
+            format!("unknown preset {:?} (available: {})", name, presets::names().join(", "))))?,
+        (None, None) => Params::default(),
+    })
+}
+
+/// Resolve and validate parameters
+fn load_params(params_path: Option<&str>, preset: Option<&str>) -> Result<Params, CliError> {
+    let params = resolve_params(params_path, preset)?;
+    params.validate().map_err(|errors| CliError { kind: ErrorKind::Validation, detail: "invalid params".into(), errors })?;
+    Ok(params)
+}
+
+/// Read the command input from a file, or from stdin when `path` is `-`
+fn read_input(path: &str) -> Result<String, CliError> {
+    let io = |e: String| CliError::new(ErrorKind::Io, e);
+    if path == "-" {
+        let mut buf = String::new();
+        std::io::stdin().read_to_string(&mut buf).map_err(|e| io(format!("cannot read stdin: {}", e)))?;
+        Ok(buf)
+    } else {
+        fs::read_to_string(path).map_err(|e| io(format!("cannot read input file {}: {}", path, e)))
+    }
+}
+
+/// Read a command input as a JSON document; CBOR inputs are decoded first
+fn read_encoded(path: &str, encoding: Encoding) -> Result<String, CliError> {
+    match encoding {
+        Encoding::Json => read_input(path),
+        #[cfg(not(feature = "cbor"))]
+        Encoding::Cbor => Err(cbor_unavailable()),
+        #[cfg(feature = "cbor")]
+        Encoding::Cbor => {
+            let bytes = if path == "-" {
+                let mut buf = Vec::new();
+                std::io::stdin().read_to_end(&mut buf).map(|_| buf)
+            } else {
+                fs::read(path)
+            }.map_err(|e| CliError::new(ErrorKind::Io, format!("cannot read input {}: {}", path, e)))?;
+            let value: serde_json::Value = cbor::from_slice(&bytes).map_err(|e| CliError::new(ErrorKind::Parse, format!("invalid input: {}", e)))?;
+            Ok(value.to_string())
+        }
+    }
+}
+
+/// Write a result to stdout: CBOR bytes, or text in the selected format
+fn emit(value: &serde_json::Value, cli: &CliArgs) -> Result<(), CliError> {
+    match cli.encoding {
+        Encoding::Json => { print!("{}", render(value, cli.format)?); Ok(()) }
+        #[cfg(not(feature = "cbor"))]
+        Encoding::Cbor => Err(cbor_unavailable()),
+        #[cfg(feature = "cbor")]
+        Encoding::Cbor => {
+            use std::io::Write;
+            let bytes = cbor::to_vec(value).map_err(|e| CliError::new(ErrorKind::Usage, format!("cannot encode output: {}", e)))?;
+            std::io::stdout().write_all(&bytes).map_err(|e| CliError::new(ErrorKind::Io, format!("cannot write output: {}", e)))
+        }
+    }
+}
+
+#[cfg(not(feature = "cbor"))]
+fn cbor_unavailable() -> CliError {
+    CliError::new(ErrorKind::Usage, "--encoding cbor is not available: rebuild with `--features cbor`")
+}
+
+/// Deserialize a command input
+fn parse<T: serde::de::DeserializeOwned>(data: &str) -> Result<T, CliError> {
+    serde_json::from_str(data).map_err(|e| CliError::new(ErrorKind::Parse, format!("invalid input: {}", e)))
+}
+
+/// Deserialize one JSON document per non-empty line
+fn parse_ndjson<T: serde::de::DeserializeOwned>(data: &str) -> Result<Vec<T>, CliError> {
+    data.lines().enumerate().filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| serde_json::from_str(l).map_err(|e| CliError::new(ErrorKind::Parse, format!("invalid input on line {}: {}", i + 1, e))))
+        .collect()
+}
+
+/// Reject NaN/infinite results instead of emitting `null`
+fn finite(name: &str, x: f64) -> Result<f64, CliError> {
+    if x.is_finite() { Ok(x) } else { Err(CliError::new(ErrorKind::Math, format!("{} is not finite ({})", name, x))) }
+}
+
+fn to_json<T: Serialize>(x: &T) -> Result<serde_json::Value, CliError> {
+    serde_json::to_value(x).map_err(|e| CliError::new(ErrorKind::Math, e.to_string()))
+}
+
+fn vector_error(e: vectors::VectorError) -> CliError { CliError::new(ErrorKind::Parse, e.to_string()) }
+
+fn main() {
+    // stderr logs filtered by RUST_LOG (off unless set), so stdout stays the result document
+    #[cfg(feature = "tracing")]
+    tracing_subscriber::fmt().with_env_filter(tracing_subscriber::EnvFilter::from_default_env()).with_writer(std::io::stderr).init();
+    let argv: Vec<String> = env::args().skip(1).collect();
+    if let Err(e) = run(&argv) {
+        eprintln!("{}", serde_json::json!({ "error": e }));
+        std::process::exit(e.kind.exit_code());
+    }
+}
+
+fn run(argv: &[String]) -> Result<(), CliError> {
+    let cli = parse_args(argv).map_err(|e| CliError::new(e.kind, format!("{}\n{}", e.detail, USAGE)))?;
+    if cli.positional.first().map(String::as_str) == Some("validate") {
+        let report = run_validate(&cli);
+        print!("{}", render(&to_json(&report)?, cli.format)?);
+        return if report.valid { Ok(()) }
+            else { Err(CliError { kind: ErrorKind::Validation, detail: "validation failed".into(), errors: report.errors }) };
+    }
+    if cli.positional.first().map(String::as_str) == Some("serve") {
+        #[cfg(not(feature = "serve"))]
+        return Err(CliError::new(ErrorKind::Usage, "serve is not available: rebuild with `--features serve`"));
+        #[cfg(feature = "serve")]
+        {
+            let params = load_params(cli.params_path.as_deref(), cli.preset.as_deref())?;
+            return serve::run(cli.listen.as_deref().unwrap_or("127.0.0.1:8080"), &params);
+        }
+    }
+    if cli.positional.first().map(String::as_str) == Some("grpc") {
+        #[cfg(not(feature = "grpc"))]
+        return Err(CliError::new(ErrorKind::Usage, "grpc is not available: rebuild with `--features grpc`"));
+        #[cfg(feature = "grpc")]
+        {
+            let params = load_params(cli.params_path.as_deref(), cli.preset.as_deref())?;
+            let listen = cli.listen.as_deref().unwrap_or("127.0.0.1:50051");
+            let addr = listen.parse().map_err(|e| CliError::new(ErrorKind::Usage, format!("invalid --listen address {:?}: {}", listen, e)))?;
+            let runtime = tokio::runtime::Runtime::new().map_err(|e| CliError::new(ErrorKind::Io, format!("cannot start runtime: {}", e)))?;
+            eprintln!("gRPC SlimechainAlgo listening on {}", listen);
+            return runtime.block_on(grpc::serve(addr, params))
+                .map_err(|e| CliError::new(ErrorKind::Io, format!("gRPC server on {} failed: {}", listen, e)));
+        }
+    }
+    if cli.positional.first().map(String::as_str) == Some("presets") {
+        let out = match (cli.positional.get(1).map(String::as_str), cli.positional.get(2)) {
+            (Some("list") | None, _) => serde_json::Value::Array(presets::names().into_iter()
+                .map(|n| serde_json::json!({ "name": n, "description": presets::description(n) }))
+                .collect()),
+            (Some("show"), Some(name)) => to_json(&resolve_params(None, Some(name))?)?,
+            _ => return Err(CliError::new(ErrorKind::Usage, USAGE)),
+        };
+        print!("{}", render(&out, cli.format)?);
+        return Ok(());
+    }
+    if cli.positional.first().map(String::as_str) == Some("schema") {
+        let Some(target) = cli.positional.get(1) else { return Err(CliError::new(ErrorKind::Usage, USAGE)) };
+        #[cfg(not(feature = "schema"))]
+        return Err(CliError::new(ErrorKind::Usage, format!("schema for {:?} is not available: rebuild with `--features schema`", target)));
+        #[cfg(feature = "schema")]
+        {
+            print!("{}", render(&schema::for_command(target)?, cli.format)?);
+            return Ok(());
+        }
+    }
+    if cli.positional.first().map(String::as_str) == Some("bench") {
+        let params = load_params(cli.params_path.as_deref(), cli.preset.as_deref())?;
+        let n = cli.n.unwrap_or(100_000);
+        let out = match cli.kind.as_deref().unwrap_or("all") {
+            "all" => to_json(&bench::KINDS.iter().map(|k| bench::run(k, n, &params)).collect::<Result<Vec<_>, _>>()?)?,
+            kind => to_json(&bench::run(kind, n, &params)?)?,
+        };
+        print!("{}", render(&out, cli.format)?);
+        return Ok(());
+    }
+    if cli.positional.first().map(String::as_str) == Some("vectors") {
+        if let Some(path) = &cli.check {
+            let report = vectors::check(&parse(&read_input(path)?)?).map_err(vector_error)?;
+            print!("{}", render(&to_json(&report)?, cli.format)?);
+            return if report.mismatches.is_empty() { Ok(()) }
+                else { Err(CliError::new(ErrorKind::Validation, format!("{} of {} vectors differ", report.mismatches.len(), report.checked))) };
+        }
+        let params = load_params(cli.params_path.as_deref(), cli.preset.as_deref())?;
+        let text = render(&to_json(&vectors::generate(&params).map_err(vector_error)?)?, Format::Json)?;
+        return match &cli.out {
+            Some(path) => fs::write(path, text).map_err(|e| CliError::new(ErrorKind::Io, format!("cannot write {}: {}", path, e))),
+            None => { print!("{}", text); Ok(()) }
+        };
+    }
+    if cli.positional.first().map(String::as_str) == Some("conform") {
+        let Some(path) = cli.positional.get(1) else { return Err(CliError::new(ErrorKind::Usage, USAGE)) };
+        let tolerances = match &cli.tolerances {
+            Some(t) => parse(&read_input(t)?)?,
+            None => conformance::Tolerances::default(),
+        };
+        let report = conformance::cross_check(&parse(&read_input(path)?)?, &tolerances);
+        print!("{}", render(&to_json(&report)?, cli.format)?);
+        return if report.conforms() { Ok(()) }
+            else { Err(CliError::new(ErrorKind::Validation, format!("{} of {} results differ", report.checked - report.passed, report.checked))) };
+    }
+    if cli.positional.first().map(String::as_str) == Some("rescore") {
+        let (Some(input), Some(out)) = (cli.positional.get(1), &cli.out) else {
+            return Err(CliError::new(ErrorKind::Usage, format!("rescore requires an input file and --out\n{}", USAGE)));
+        };
+        #[cfg(not(feature = "arrow"))]
+        return Err(CliError::new(ErrorKind::Usage, format!("rescore of {} into {} is not available: rebuild with `--features arrow`", input, out)));
+        #[cfg(feature = "arrow")]
+        {
+            let params = load_params(cli.params_path.as_deref(), cli.preset.as_deref())?;
+            let rows = slimechain_algo::arrow::rescore_parquet(input.as_ref(), out.as_ref(), &params)
+                .map_err(|e| CliError::new(ErrorKind::Io, format!("cannot rescore {}: {}", input, e)))?;
+            print!("{}", render(&serde_json::json!({ "rows": rows, "out": out }), cli.format)?);
+            return Ok(());
+        }
+    }
+    if cli.positional.first().map(String::as_str) == Some("diff") {
+        let (Some(a), Some(b), Some(inputs)) = (&cli.params_a, &cli.params_b, &cli.inputs) else {
+            return Err(CliError::new(ErrorKind::Usage, format!("diff requires --a, --b and --inputs\n{}", USAGE)));
+        };
+        let (a, b) = (load_params(Some(a), None)?, load_params(Some(b), None)?);
+        let corpus = parse_ndjson::<pipeline::PipelineInput>(&read_input(inputs)?)?;
+        let report = pipeline::compare(&corpus, &a, &b);
+        // CSV gets one row per record; other formats get the full report
+        let out = match cli.format { Format::Csv => to_json(&report.records)?, _ => to_json(&report)? };
+        print!("{}", render(&out, cli.format)?);
+        return Ok(());
+    }
+    if cli.positional.first().map(String::as_str) == Some("explain") {
+        let (Some(what), Some(path)) = (cli.positional.get(1), cli.positional.get(2)) else {
+            return Err(CliError::new(ErrorKind::Usage, USAGE));
+        };
+        let params = load_params(cli.params_path.as_deref(), cli.preset.as_deref())?;
+        let out = explain(what, &read_encoded(path, cli.encoding)?, &params)?;
+        return emit(&out, &cli);
+    }
+    if cli.positional.len() < 2 {
+        return Err(CliError::new(ErrorKind::Usage, USAGE));
+    }
+    let cmd = &cli.positional[0];
+    let path = &cli.positional[1];
+    if !COMMANDS.contains(&cmd.as_str()) {
+        return Err(CliError::new(ErrorKind::Usage, format!("unknown command {:?}\n{}", cmd, USAGE)));
+    }
+    if cli.watch {
+        return watch(cmd, path, &cli);
+    }
+    evaluate(cmd, path, &cli)
+}
+
+/// Load params and input, compute, and print the result
+fn evaluate(cmd: &str, path: &str, cli: &CliArgs) -> Result<(), CliError> {
+    let params = load_params(cli.params_path.as_deref(), cli.preset.as_deref())?;
+    let data = read_encoded(path, cli.encoding)?;
+
+    // results go to stdout in the selected format; diagnostics go to stderr
+    let out = compute(cmd, &data, &params)?;
+    emit(&out, cli)
+}
+
+/// `--watch`: re-evaluate whenever the input or params file changes (polls modification times)
+fn watch(cmd: &str, path: &str, cli: &CliArgs) -> Result<(), CliError> {
+    if path == "-" {
+        return Err(CliError::new(ErrorKind::Usage, "--watch needs an input file, not stdin"));
+    }
+    let files: Vec<&str> = std::iter::once(path).chain(cli.params_path.as_deref()).collect();
+    let stamp = || files.iter().map(|f| fs::metadata(f).and_then(|m| m.modified()).ok()).collect::<Vec<_>>();
+    let mut last = None;
+    loop {
+        let now = stamp();
+        if last.as_ref() != Some(&now) {
+            last = Some(now);
+            // errors are reported but do not stop the watch loop
+            if let Err(e) = evaluate(cmd, path, cli) {
+                eprintln!("{}", serde_json::json!({ "error": e }));
+            }
+            eprintln!("watching {} for changes (Ctrl-C to stop)", files.join(", "));
+        }
+        std::thread::sleep(std::time::Duration::from_millis(WATCH_POLL_MS));
+    }
+}
+
+/// Evaluate one command on its JSON input (shared by the file-based commands and `serve`)
+fn compute(cmd: &str, data: &str, params: &Params) -> Result<serde_json::Value, CliError> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("compute", cmd, input = %trace::input_hash(data.as_bytes())).entered();
+    let out = match cmd {
+        "simulate" if sim::scenario::is_versioned(data) => {
+            let file = sim::scenario::load(data)
+                .map_err(|errors| CliError { kind: ErrorKind::Validation, detail: "invalid scenario".into(), errors })?;
+            let report = file.run(params)
+                .map_err(|errors| CliError { kind: ErrorKind::Validation, detail: "scenario params".into(), errors })?;
+            to_json(&report)?
+        },
+        "simulate" => {
+            let scenario: sim::Scenario = parse(data)?;
+            let reports = sim::run(&scenario, params);
+            for r in &reports {
+                finite("base_fare", r.base_fare)?;
+                finite("total_cost", r.total_cost)?;
+                finite("reward_total", r.reward_total)?;
+            }
+            to_json(&reports)?
+        },
+        _ => vectors::eval_command(cmd, &parse(data)?, params).map_err(|e| {
+            let kind = match e.kind {
+                vectors::VectorErrorKind::UnknownFunction => ErrorKind::Usage,
+                vectors::VectorErrorKind::NotFinite => ErrorKind::Math,
+                _ => ErrorKind::Parse,
+            };
+            CliError::new(kind, e.reason)
+        })?,
+    };
+    Ok(out)
+}
+
+/// `explain`: detailed breakdown (per-term contributions, applied caps and floors) for one computation
+fn explain(what: &str, data: &str, params: &Params) -> Result<serde_json::Value, CliError> {
+    match what {
+        "cost" => {
+            let input: CostInput = parse(data)?;
+            let out = calculate_post_cost_detailed(&input.actor, &input.content, params, input.base_fare.unwrap_or(1.0));
+            finite("cost", out.cost)?;
+            to_json(&out)
+        }
+        "reward" => {
+            let input: RewardInput = parse(data)?;
+            let out = calculate_serve_reward_detailed(&input, params);
+            finite("reward", out.reward)?;
+            to_json(&out)
+        }
+        "risk" => {
+            let sig: RiskSignals = parse(data)?;
+            to_json(&calculate_risk_detailed(&Some(sig), &RiskWeights::default()))
+        }
+        "quality" => {
+            let qin: QInputs = parse(data)?;
+            let out = calculate_quality_detailed(&qin, params);
+            finite("q", out.q)?;
+            to_json(&out)
+        }
+        _ => Err(CliError::new(ErrorKind::Usage, format!("cannot explain {:?} (expected cost, reward, risk or quality)", what))),
+    }
+}
+
+/// Result of `validate`
+#[derive(Serialize)]
+struct ValidationReport { valid: bool, errors: Vec<ParamError> }
+
+/// `validate`: check params (and optionally an input file against `--kind`)
+fn run_validate(cli: &CliArgs) -> ValidationReport {
+    let mut errors: Vec<ParamError> = Vec::new();
+    let err = |path: &str, reason: String| ParamError { path: path.into(), reason };
+    match resolve_params(cli.params_path.as_deref(), cli.preset.as_deref()) {
+        Ok(p) => if let Err(errs) = p.validate() {
+            errors.extend(errs.into_iter().map(|e| err(&format!("params.{}", e.path), e.reason)));
+        },
+        Err(e) => errors.push(err("params", e.detail)),
+    }
+    match (&cli.input, &cli.kind) {
+        (Some(path), Some(kind)) => match read_input(path) {
+            Ok(data) => errors.extend(check_input(kind, &data)),
+            Err(e) => errors.push(err("input", e.detail)),
+        },
+        (None, None) => {}
+        _ => errors.push(err("input", "--input and --kind must be given together".into())),
+    }
+    ValidationReport { valid: errors.is_empty(), errors }
+}
+
+/// Shape (deserialization) and range checks for a command input
+fn check_input(kind: &str, data: &str) -> Vec<ParamError> {
+    let mut errs = Vec::new();
+    let mut range = |path: &str, x: f64, lo: f64, hi: f64| {
+        if !(x.is_finite() && x >= lo && x <= hi) {
+            let reason = if hi.is_infinite() { format!("{} must be finite and >= {}", x, lo) } else { format!("{} is outside [{}, {}]", x, lo, hi) };
+            errs.push(ParamError { path: format!("input.{}", path), reason });
+        }
+    };
+    let shape = check_ranges(kind, data, &mut range);
+    if let Err(e) = shape { errs.push(e); }
+    errs
+}
+
+type RangeCheck<'a> = dyn FnMut(&str, f64, f64, f64) + 'a;
+
+fn check_ranges(kind: &str, data: &str, range: &mut RangeCheck) -> Result<(), ParamError> {
+    fn parse<T: serde::de::DeserializeOwned>(data: &str) -> Result<T, ParamError> {
+        serde_json::from_str(data).map_err(|e| ParamError { path: "input".into(), reason: e.to_string() })
+    }
+    fn signals(s: &RiskSignals, prefix: &str, range: &mut RangeCheck) {
+        for (name, x) in [("coordination", s.coordination), ("clustering", s.clustering), ("burst", s.burst),
+                          ("monotonicity", s.monotonicity), ("abuse_history", s.abuse_history), ("duplication", s.duplication)] {
+            if let Some(x) = x { range(&format!("{}{}", prefix, name), x, 0.0, 1.0); }
+        }
+    }
+    match kind {
+        "cost" => {
+            let i: CostInput = parse(data)?;
+            range("actor.rl", i.actor.rl, 0.0, f64::INFINITY);
+            range("actor.q", i.actor.q, 0.0, 1.0);
+            range("actor.ef", i.actor.ef, 0.0, f64::INFINITY);
+            if let Some(x) = i.actor.posts_1h { range("actor.posts_1h", x, 0.0, f64::INFINITY); }
+            if let Some(x) = i.base_fare { range("base_fare", x, 0.0, f64::INFINITY); }
+            if let Some(s) = &i.content.risk_signals { signals(s, "content.risk_signals.", range); }
+        }
+        "reward" => {
+            let i: RewardInput = parse(data)?;
+            range("ticket_budget", i.ticket_budget, 0.0, f64::INFINITY);
+            range("client_q", i.client_q, 0.0, 1.0);
+            range("server_cluster_risk", i.server_cluster_risk, 0.0, 1.0);
+            if let Some(x) = i.ttfb_percentile { range("ttfb_percentile", x, 0.0, 1.0); }
+        }
+        "prop" => {
+            let i: Content = parse(data)?;
+            if let Some(s) = &i.risk_signals { signals(s, "risk_signals.", range); }
+        }
+        "base" => {
+            let i: BaseInput = parse(data)?;
+            range("current_base", i.current_base, 0.0, f64::INFINITY);
+            range("current_load", i.current_load, 0.0, f64::INFINITY);
+        }
+        "quality" => {
+            let q: QInputs = parse(data)?;
+            for (name, x) in [("A", q.A), ("R", q.R), ("T", q.T), ("D", q.D), ("H", q.H), ("S", q.S)] { range(name, x, 0.0, 1.0); }
+        }
+        "ef" => {
+            let arr: Vec<f64> = parse(data)?;
+            for (i, &q) in arr.iter().enumerate() { range(&i.to_string(), q, 0.0, 1.0); }
+        }
+        "risk" => {
+            let s: RiskSignals = parse(data)?;
+            signals(&s, "", range);
+        }
+        "pipeline" => { parse::<pipeline::PipelineInput>(data)?; }
+        "simulate" if sim::scenario::is_versioned(data) => {
+            sim::scenario::load(data).map_err(|mut e| {
+                let e = e.swap_remove(0);
+                ParamError { path: format!("input.{}", e.path).trim_end_matches('.').into(), reason: e.reason }
+            })?;
+        }
+        "simulate" => { parse::<sim::Scenario>(data)?; }
+        _ => return Err(ParamError { path: "kind".into(), reason: format!("unknown command {:?}", kind) }),
+    }
+    Ok(())
+}
+
+/// Encode a result document (TOML needs a table at the top level)
+fn render(value: &serde_json::Value, format: Format) -> Result<String, CliError> {
+    let enc = |e: String| CliError::new(ErrorKind::Usage, format!("cannot encode output: {}", e));
+    Ok(match format {
+        Format::Json => serde_json::to_string_pretty(value).map_err(|e| enc(e.to_string()))? + "\n",
+        Format::JsonCompact => serde_json::to_string(value).map_err(|e| enc(e.to_string()))? + "\n",
+        Format::Yaml => serde_yaml::to_string(value).map_err(|e| enc(e.to_string()))?,
+        Format::Toml => toml::to_string_pretty(&strip_nulls(value)).map_err(|e| enc(e.to_string()))?,
+        Format::Csv => to_csv(value),
+    })
+}
+
+/// TOML has no null: drop null object fields (they deserialize back as `None`)
+fn strip_nulls(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.iter().filter(|(_, v)| !v.is_null()).map(|(k, v)| (k.clone(), strip_nulls(v))).collect()),
+        serde_json::Value::Array(items) => serde_json::Value::Array(items.iter().map(strip_nulls).collect()),
+        v => v.clone(),
+    }
+}
+
+/// CSV with one row per array element (or a single row for an object); nested fields become dotted columns
+fn to_csv(value: &serde_json::Value) -> String {
+    let rows: Vec<Vec<(String, String)>> = match value {
+        serde_json::Value::Array(items) => items.iter().map(|v| flatten(v, "")).collect(),
+        v => vec![flatten(v, "")],
+    };
+    let mut header: Vec<String> = Vec::new();
+    for (k, _) in rows.iter().flatten() {
+        if !header.contains(k) { header.push(k.clone()); }
+    }
+    let mut out = header.iter().map(|h| csv_field(h)).collect::<Vec<_>>().join(",") + "\n";
+    for row in &rows {
+        let cells: Vec<String> = header.iter()
+            .map(|h| row.iter().find(|(k, _)| k == h).map_or(String::new(), |(_, v)| csv_field(v)))
+            .collect();
+        out += &(cells.join(",") + "\n");
+    }
+    out
+}
+
+fn flatten(value: &serde_json::Value, prefix: &str) -> Vec<(String, String)> {
+    let key = |k: &str| if prefix.is_empty() { k.to_string() } else { format!("{}.{}", prefix, k) };
+    match value {
+        serde_json::Value::Object(map) => map.iter().flat_map(|(k, v)| flatten(v, &key(k))).collect(),
+        serde_json::Value::Array(items) => items.iter().enumerate().flat_map(|(i, v)| flatten(v, &key(&i.to_string()))).collect(),
+        serde_json::Value::Null => vec![(prefix_or_value(prefix), String::new())],
+        serde_json::Value::String(s) => vec![(prefix_or_value(prefix), s.clone())],
+        v => vec![(prefix_or_value(prefix), v.to_string())],
+    }
+}
+
+fn prefix_or_value(prefix: &str) -> String {
+    if prefix.is_empty() { "value".into() } else { prefix.into() }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) { format!("\"{}\"", s.replace('"', "\"\"")) } else { s.to_string() }
+}
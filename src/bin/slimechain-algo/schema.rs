@@ -0,0 +1,41 @@
+// JSON Schemas for command inputs and outputs (feature `schema`)
+// - Derived from the same types the commands deserialize (`vectors::eval`) and serialize; single-value results are
+//   wrapped in the one-field objects below
+
+use crate::*;
+use schemars::{schema_for, JsonSchema};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+#[derive(Serialize, JsonSchema)]
+struct CostOutput { cost: f64 }
+#[derive(Serialize, JsonSchema)]
+struct RewardOutput { reward: f64 }
+#[derive(Serialize, JsonSchema)]
+struct BaseOutput { base: f64 }
+#[derive(Serialize, JsonSchema)]
+struct QualityOutput { q: f64 }
+#[derive(Serialize, JsonSchema)]
+struct EfOutput { ef: f64 }
+#[derive(Serialize, JsonSchema)]
+struct RiskOutput { risk: f64 }
+
+/// `{"input": <schema>, "output": <schema>}` for a command (`scenario`: versioned `simulate` files), or the `Params`
+/// schema for `params`
+pub fn for_command(cmd: &str) -> Result<Value, CliError> {
+    let pair = |input: schemars::Schema, output: schemars::Schema| json!({ "input": input, "output": output });
+    Ok(match cmd {
+        "cost" => pair(schema_for!(CostInput), schema_for!(CostOutput)),
+        "reward" => pair(schema_for!(RewardInput), schema_for!(RewardOutput)),
+        "prop" => pair(schema_for!(Content), schema_for!(PropagationResult)),
+        "base" => pair(schema_for!(BaseInput), schema_for!(BaseOutput)),
+        "quality" => pair(schema_for!(QInputs), schema_for!(QualityOutput)),
+        "ef" => pair(schema_for!(Vec<f64>), schema_for!(EfOutput)),
+        "risk" => pair(schema_for!(RiskSignals), schema_for!(RiskOutput)),
+        "pipeline" => pair(schema_for!(pipeline::PipelineInput), schema_for!(pipeline::PipelineOutput)),
+        "simulate" => pair(schema_for!(sim::Scenario), schema_for!(Vec<sim::EpochReport>)),
+        "scenario" => pair(schema_for!(sim::scenario::ScenarioFile), schema_for!(sim::scenario::ScenarioReport)),
+        "params" => to_json(&schema_for!(Params))?,
+        _ => return Err(CliError::new(ErrorKind::Usage, format!("no schema for {:?} (expected a command name or params)", cmd))),
+    })
+}
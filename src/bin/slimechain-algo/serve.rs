@@ -0,0 +1,89 @@
+// HTTP JSON API (feature `serve`)
+// - POST /<command> with the same JSON body as the file-based command; responds with the same JSON document
+// - Only the per-request scoring commands are exposed (`ENDPOINTS`); `simulate` and `base` stay local, a scenario's
+//   size is not bounded by its body size
+// - Errors use the CLI error object; the status code follows the error kind
+// - Bodies over `MAX_BODY_BYTES` (by Content-Length, or while reading a chunked body) get 413 without being read
+// - POST /rpc takes JSON-RPC 2.0 requests (see `slimechain_algo::jsonrpc`)
+
+use crate::{compute, CliError, ErrorKind};
+use slimechain_algo::{jsonrpc, Params};
+use std::io::Read;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Commands exposed over HTTP
+const ENDPOINTS: &[&str] = &["cost", "reward", "prop", "quality", "ef", "risk", "pipeline"];
+
+/// Largest request body accepted
+const MAX_BODY_BYTES: usize = 1 << 20;
+
+/// Request body as UTF-8, or the status and error to answer with
+fn read_body(request: &mut Request) -> Result<String, (u16, CliError)> {
+    let too_large = || (413, CliError::new(ErrorKind::Usage, format!("request body exceeds {} bytes", MAX_BODY_BYTES)));
+    if request.body_length().is_some_and(|n| n > MAX_BODY_BYTES) { return Err(too_large()); }
+    let mut body = String::new();
+    request.as_reader().take(MAX_BODY_BYTES as u64 + 1).read_to_string(&mut body)
+        .map_err(|e| (400, CliError::new(ErrorKind::Io, format!("cannot read body: {}", e))))?;
+    if body.len() > MAX_BODY_BYTES { return Err(too_large()); }
+    Ok(body)
+}
+
+/// Serve requests until the process is stopped
+pub fn run(listen: &str, params: &Params) -> Result<(), CliError> {
+    let server = Server::http(listen).map_err(|e| CliError::new(ErrorKind::Io, format!("cannot listen on {}: {}", listen, e)))?;
+    eprintln!("listening on http://{}", listen);
+    let rpc = jsonrpc::Dispatcher::new(params.clone());
+    for mut request in server.incoming_requests() {
+        let cmd = request.url().trim_start_matches('/').split('?').next().unwrap_or("").to_string();
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("request", method = %request.method(), endpoint = %cmd).entered();
+        if cmd == "rpc" && *request.method() == Method::Post {
+            // JSON-RPC reports its own errors in the body; notifications get 204, oversized requests 413
+            let (response, code) = match read_body(&mut request) {
+                Ok(body) => {
+                    let response = rpc.handle(&body);
+                    let code = if response.is_empty() { 204 } else { 200 };
+                    (response, code)
+                }
+                Err((code, e)) => {
+                    let error = serde_json::json!({ "jsonrpc": "2.0", "error": { "code": jsonrpc::PARSE_ERROR, "message": e.detail }, "id": null });
+                    (error.to_string(), if code == 413 { 413 } else { 200 })
+                }
+            };
+            #[cfg(feature = "tracing")]
+            tracing::info!(status = code, "response");
+            let header = Header::from_bytes("Content-Type", "application/json").expect("static header");
+            if let Err(e) = request.respond(Response::from_string(response).with_status_code(code).with_header(header)) {
+                eprintln!("failed to send response: {}", e);
+            }
+            continue;
+        }
+        let result = if *request.method() != Method::Post {
+            Err((405, CliError::new(ErrorKind::Usage, "only POST is supported")))
+        } else if !ENDPOINTS.contains(&cmd.as_str()) {
+            Err((404, CliError::new(ErrorKind::Usage, format!("unknown endpoint /{}", cmd))))
+        } else {
+            read_body(&mut request).and_then(|body| compute(&cmd, &body, params).map_err(|e| (status(e.kind), e)))
+        };
+        let (code, doc) = match result {
+            Ok(v) => (200, v),
+            Err((code, e)) => (code, serde_json::json!({ "error": e })),
+        };
+        #[cfg(feature = "tracing")]
+        tracing::info!(status = code, "response");
+        let header = Header::from_bytes("Content-Type", "application/json").expect("static header");
+        let response = Response::from_string(doc.to_string()).with_status_code(code).with_header(header);
+        if let Err(e) = request.respond(response) {
+            eprintln!("failed to send response: {}", e);
+        }
+    }
+    Ok(())
+}
+
+fn status(kind: ErrorKind) -> u16 {
+    match kind {
+        ErrorKind::Usage | ErrorKind::Io | ErrorKind::Parse => 400,
+        ErrorKind::Validation => 422,
+        ErrorKind::Math => 500,
+    }
+}
@@ -0,0 +1,6 @@
+// Swift/Kotlin binding generator for the `uniffi` feature:
+//   cargo run --features uniffi --bin uniffi-bindgen generate --library <libslimechain_algo.so> --language kotlin --out-dir out
+
+fn main() {
+    uniffi::uniffi_bindgen_main()
+}
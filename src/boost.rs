@@ -0,0 +1,90 @@
+// Paid amplification (boost) pricing
+// - cost = base_fare * price_per_impression * reach^reach_exp * (1 + risk_lambda * risk)^risk_exp * (1 + q_k * (1 - q))
+//   with reach_exp, risk_exp >= 1: doubling the reach or the risk more than doubles the price
+// - Purchasable reach is capped like organic reach: `max_reach_multiple * reach_cap * (1 - risk_k * risk)` from the
+//   propagation `ReachParams`, so risky content cannot buy its way past the damping organic posts get
+// - Requests above the cap are priced and granted at the cap
+
+use crate::{clamp, Params, Quality, ReachParams, Risk};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BoostParams {
+    /// Multiple of the base fare per impression at reach 1
+    pub price_per_impression: f64,
+    pub reach_exp: f64,
+    pub risk_lambda: f64,
+    pub risk_exp: f64,
+    /// Surcharge per unit of missing actor quality
+    pub q_k: f64,
+    /// Cap on purchased reach as a multiple of the organic `reach_cap`
+    pub max_reach_multiple: f64,
+    /// Organic reach parameters the cap derives from
+    pub reach: ReachParams,
+}
+
+impl Default for BoostParams {
+    fn default() -> Self {
+        BoostParams {
+            price_per_impression: 0.001, reach_exp: 1.2, risk_lambda: 1.0, risk_exp: 2.0, q_k: 1.0,
+            max_reach_multiple: 1.0, reach: ReachParams::default(),
+        }
+    }
+}
+
+impl BoostParams {
+    /// Defaults with the reach cap taken from `params`
+    pub fn for_params(params: &Params) -> Self { BoostParams { reach: params.propagation.reach.clone(), ..Default::default() } }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BoostQuote {
+    /// Impressions sold (`target_reach` or the cap)
+    pub reach: u64,
+    /// Price in SOCIAL
+    pub cost: f64,
+    /// True when the cap cut the request
+    pub capped: bool,
+}
+
+/// Largest reach a boost can buy for content at `content_risk`
+pub fn max_boost_reach(content_risk: impl Into<Risk>, params: &BoostParams) -> u64 {
+    let damping = 1.0 - clamp(params.reach.risk_k, 0.0, 1.0) * content_risk.into().get();
+    (params.max_reach_multiple.max(0.0) * params.reach.reach_cap.max(0.0) * damping).round() as u64
+}
+
+/// Price a boost of `target_reach` impressions
+pub fn calculate_boost_cost(target_reach: u64, actor_q: impl Into<Quality>, content_risk: impl Into<Risk>, base_fare: f64, params: &BoostParams) -> BoostQuote {
+    let (q, risk) = (actor_q.into().get(), content_risk.into().get());
+    let cap = max_boost_reach(risk, params);
+    let reach = target_reach.min(cap);
+    let reach_term = (reach as f64).powf(params.reach_exp.max(1.0));
+    let risk_term = (1.0 + params.risk_lambda.max(0.0) * risk).powf(params.risk_exp.max(1.0));
+    let q_term = 1.0 + params.q_k.max(0.0) * (1.0 - q);
+    let cost = base_fare.max(0.0) * params.price_per_impression.max(0.0) * reach_term * risk_term * q_term;
+    BoostQuote { reach, cost, capped: reach < target_reach }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_superlinear_and_capped() {
+        let p = BoostParams::default();
+        let small = calculate_boost_cost(1_000, 1.0, 0.0, 1.0, &p);
+        let large = calculate_boost_cost(2_000, 1.0, 0.0, 1.0, &p);
+        assert!(large.cost > 2.0 * small.cost);
+        assert!(calculate_boost_cost(1_000, 1.0, 0.5, 1.0, &p).cost > 2.0 * small.cost);
+        assert_eq!(calculate_boost_cost(1_000, 0.0, 0.0, 1.0, &p).cost, 2.0 * small.cost);
+
+        let risky = calculate_boost_cost(1_000_000, 1.0, 1.0, 1.0, &p);
+        assert_eq!((risky.reach, risky.capped), (20_000, true));
+        assert_eq!(max_boost_reach(0.0, &BoostParams::for_params(&Params::default())), 100_000);
+    }
+}
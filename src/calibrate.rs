@@ -0,0 +1,197 @@
+// Parameter auto-calibration against target metrics of the agent-based simulation
+// - Free parameters are dotted `Params` paths with a search box (`sim::sweep::ParamRange`; `steps` is ignored)
+// - Nelder–Mead runs in the unit cube of those boxes, starting from the base params (clamped into the box)
+// - Loss is the sum of squared relative target violations (0 once every target holds, which stops the search);
+//   params failing `Params::validate` are infeasible
+// - Deterministic: same scenario, base params and targets give the same calibration
+
+use crate::sim::agents::{run_detailed, AgentScenario};
+use crate::sim::sweep::{metrics, with_values, ParamRange, SweepMetrics};
+use crate::{ParamError, Params};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Simulated quantity a target constrains
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Metric {
+    /// Median over honest agents of their mean cost per post
+    HonestMedianCost,
+    /// Mean cost per honest post
+    HonestCost,
+    /// Mean spam post cost over mean honest post cost
+    SpamCostRatio,
+    RewardGini,
+}
+
+impl Metric {
+    fn of(self, m: &SweepMetrics) -> f64 {
+        match self {
+            Metric::HonestMedianCost => m.honest_median_cost,
+            Metric::HonestCost => m.honest_cost,
+            Metric::SpamCostRatio => m.spam_cost_ratio,
+            Metric::RewardGini => m.reward_gini,
+        }
+    }
+}
+
+/// `metric <= value` (`at_most`) or `metric >= value` (`at_least`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Target {
+    AtMost { metric: Metric, value: f64 },
+    AtLeast { metric: Metric, value: f64 },
+}
+
+impl Target {
+    /// Squared violation relative to the target value
+    fn violation(&self, m: &SweepMetrics) -> f64 {
+        let rel = |x: f64, v: f64| x / v.abs().max(1e-12);
+        let v = match *self {
+            Target::AtMost { metric, value } => (rel(metric.of(m) - value, value)).max(0.0),
+            Target::AtLeast { metric, value } => (rel(value - metric.of(m), value)).max(0.0),
+        };
+        v * v
+    }
+}
+
+fn default_max_evals() -> u32 { 200 }
+fn default_spam_risk() -> f64 { 0.5 }
+
+/// Targets plus the parameters allowed to move
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Targets {
+    pub targets: Vec<Target>,
+    pub free: Vec<ParamRange>,
+    /// Risk at or above which an agent counts as a spammer
+    #[serde(default = "default_spam_risk")]
+    pub spam_risk: f64,
+    /// Simulation budget
+    #[serde(default = "default_max_evals")]
+    pub max_evals: u32,
+}
+
+/// Calibration result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Calibration {
+    pub params: Params,
+    /// Fitted free values by path
+    pub values: BTreeMap<String, f64>,
+    /// Metrics at the fitted params (None when no feasible point was found)
+    pub metrics: Option<SweepMetrics>,
+    pub loss: f64,
+    /// Every target holds
+    pub met: bool,
+    pub evals: u32,
+}
+
+/// Fit the free parameters of `base` so `scenario` meets `targets`; errors only for unknown or non-numeric paths
+pub fn fit(scenario: &AgentScenario, base: &Params, targets: &Targets) -> Result<Calibration, ParamError> {
+    let base_json = serde_json::to_value(base).map_err(|e| ParamError { path: String::new(), reason: e.to_string() })?;
+    let free = &targets.free;
+    let to_value = |u: &[f64]| -> Vec<f64> { free.iter().zip(u).map(|(r, &x)| r.lo + (r.hi - r.lo) * x.clamp(0.0, 1.0)).collect() };
+    let assign = |values: &[f64]| -> Vec<(&str, f64)> { free.iter().map(|r| r.path.as_str()).zip(values.iter().copied()).collect() };
+
+    let evals = std::cell::Cell::new(0u32);
+    let eval = |u: &[f64]| -> Result<(f64, Option<SweepMetrics>), ParamError> {
+        evals.set(evals.get() + 1);
+        let params = with_values(&base_json, &assign(&to_value(u)))?;
+        if params.validate().is_err() { return Ok((f64::INFINITY, None)); }
+        let m = metrics(&run_detailed(scenario, &params), targets.spam_risk);
+        Ok((targets.targets.iter().map(|t| t.violation(&m)).sum(), Some(m)))
+    };
+
+    // starting point: the base values, normalized into the boxes
+    let start: Vec<f64> = free.iter().map(|r| {
+        let v = base_json.pointer(&format!("/{}", r.path.replace('.', "/"))).and_then(|v| v.as_f64()).unwrap_or(r.lo);
+        if r.hi > r.lo { ((v - r.lo) / (r.hi - r.lo)).clamp(0.0, 1.0) } else { 0.0 }
+    }).collect();
+    let mut simplex = vec![start.clone()];
+    for i in 0..free.len() {
+        let mut p = start.clone();
+        p[i] = if p[i] + 0.25 <= 1.0 { p[i] + 0.25 } else { p[i] - 0.25 };
+        simplex.push(p);
+    }
+    let mut scored = Vec::with_capacity(simplex.len());
+    for p in simplex { let (loss, m) = eval(&p)?; scored.push((p, loss, m)); }
+
+    while evals.get() < targets.max_evals && !free.is_empty() {
+        scored.sort_by(|a, b| a.1.total_cmp(&b.1));
+        if scored[0].1 == 0.0 { break; }
+        let n = free.len();
+        let centroid: Vec<f64> = (0..n).map(|i| scored[..n].iter().map(|s| s.0[i]).sum::<f64>() / n as f64).collect();
+        let along = |t: f64| -> Vec<f64> { centroid.iter().zip(&scored[n].0).map(|(c, w)| (c + t * (c - w)).clamp(0.0, 1.0)).collect() };
+
+        let reflected = along(1.0);
+        let (lr, mr) = eval(&reflected)?;
+        if lr < scored[0].1 {
+            let expanded = along(2.0);
+            let (le, me) = eval(&expanded)?;
+            scored[n] = if le < lr { (expanded, le, me) } else { (reflected, lr, mr) };
+        } else if lr < scored[n - 1].1 {
+            scored[n] = (reflected, lr, mr);
+        } else {
+            let contracted = along(if lr < scored[n].1 { 0.5 } else { -0.5 });
+            let (lc, mc) = eval(&contracted)?;
+            if lc < scored[n].1.min(lr) {
+                scored[n] = (contracted, lc, mc);
+            } else {
+                // shrink toward the best vertex
+                let best = scored[0].0.clone();
+                for s in scored.iter_mut().skip(1) {
+                    let p: Vec<f64> = best.iter().zip(&s.0).map(|(b, x)| b + 0.5 * (x - b)).collect();
+                    let (l, m) = eval(&p)?;
+                    *s = (p, l, m);
+                }
+            }
+        }
+    }
+    scored.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let (u, loss, metrics) = scored.swap_remove(0);
+    let values = to_value(&u);
+    let params = with_values(&base_json, &assign(&values))?;
+    Ok(Calibration {
+        values: free.iter().map(|r| r.path.clone()).zip(values).collect(),
+        params, metrics, met: loss == 0.0, loss, evals: evals.get(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::agents::{Dist, Population};
+    use crate::Content;
+
+    #[test]
+    fn t_fit_reaches_cost_target() {
+        let scenario = AgentScenario {
+            population: Population {
+                size: 100,
+                quality: Dist::Uniform { lo: 0.2, hi: 1.0 },
+                ef: Dist::Const { value: 5.0 },
+                posts_per_epoch: Dist::Const { value: 2.0 },
+                rl: Dist::Uniform { lo: 1.0, hi: 30.0 },
+                risk: Dist::Uniform { lo: 0.0, hi: 1.0 },
+                willingness_to_pay: None,
+            },
+            epochs: 2, seed: 5, initial_base: 1.0, load_per_post: 1.0, background_load: vec![],
+            content: Content::default(), serve: None, serves_per_impression: 0.0,
+        };
+        let base = Params::default();
+        let before = metrics(&run_detailed(&scenario, &base), 0.5).honest_median_cost;
+        let targets = Targets {
+            targets: vec![Target::AtMost { metric: Metric::HonestMedianCost, value: before * 0.5 }],
+            free: vec![ParamRange { path: "cost.a".into(), lo: 0.1, hi: 2.0, steps: 0 }],
+            spam_risk: 0.5,
+            max_evals: 60,
+        };
+        let cal = fit(&scenario, &base, &targets).unwrap();
+        assert!(cal.met, "{:?}", cal);
+        assert!(cal.metrics.unwrap().honest_median_cost <= before * 0.5);
+        assert_eq!(cal.params.cost.a, cal.values["cost.a"]);
+    }
+}
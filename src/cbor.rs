@@ -0,0 +1,47 @@
+// CBOR encoding for the public serde types (feature `cbor`)
+// - Same data model as the JSON shapes: field names, enum tags and `None` handling are shared
+// - Floats are written in the shortest IEEE width that preserves the value, so decoding is exact
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Encode or decode failure
+#[derive(Debug, Clone, PartialEq)]
+pub struct CborError(pub String);
+
+impl std::fmt::Display for CborError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "cbor: {}", self.0) }
+}
+
+impl std::error::Error for CborError {}
+
+/// Encode any public type (params, inputs, results) as CBOR
+pub fn to_vec<T: Serialize>(value: &T) -> Result<Vec<u8>, CborError> {
+    let mut out = Vec::new();
+    ciborium::into_writer(value, &mut out).map_err(|e| CborError(e.to_string()))?;
+    Ok(out)
+}
+
+/// Decode a CBOR document
+pub fn from_slice<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CborError> {
+    ciborium::from_reader(bytes).map_err(|e| CborError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Content, ContentTopic, Params, RiskSignals};
+
+    #[test]
+    fn t_roundtrip_matches_json() {
+        let p = Params::default();
+        let back: Params = from_slice(&to_vec(&p).unwrap()).unwrap();
+        assert_eq!(serde_json::to_value(&back).unwrap(), serde_json::to_value(&p).unwrap());
+
+        let c = Content { is_claim: Some(true), risk_signals: Some(RiskSignals { burst: Some(0.1), ..Default::default() }), topic: Some(ContentTopic::Health), ..Default::default() };
+        let back: Content = from_slice(&to_vec(&c).unwrap()).unwrap();
+        assert_eq!(back.risk_signals.unwrap().burst, Some(0.1));
+        assert_eq!(back.topic, Some(ContentTopic::Health));
+        assert!(from_slice::<Params>(&[0xff]).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+// Replay verification of published results (feature `serde`)
+// - A claim is what a node publishes for a charged cost, paid reward or propagation decision: the inputs, the hash
+//   of the params it ran under (`audit::params_hash`) and the output it asserts
+// - Validators replay the claim under their own params: a params hash that differs means the claimant ran other
+//   params (settled by governance, not by recomputation); otherwise the output is recomputed and compared bit for bit
+// - Outcomes are plain values so fraud-proof code can match on them; nothing here panics on adversarial input
+
+use crate::audit::params_hash;
+use crate::por::Hash32;
+use crate::{adjust_propagation_for_content, calculate_post_cost, calculate_serve_reward, Actor, Content, Params, RewardInput};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CostClaim {
+    pub actor: Actor,
+    pub content: Content,
+    pub base_fare: f64,
+    pub params_hash: Hash32,
+    pub cost: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RewardClaim {
+    pub input: RewardInput,
+    pub params_hash: Hash32,
+    pub reward: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PropagationClaim {
+    pub content: Content,
+    pub params_hash: Hash32,
+    pub ttl: u32,
+    pub fanout: u32,
+    #[serde(default)]
+    pub quarantined: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum VerifyResult {
+    Valid,
+    /// Claimant ran params other than the validator's
+    ParamsMismatch { expected: Hash32 },
+    /// Recomputed output differs from the claimed one (JSON of the recomputed output)
+    OutputMismatch { recomputed: String },
+}
+
+impl VerifyResult {
+    pub fn is_valid(&self) -> bool { matches!(self, VerifyResult::Valid) }
+}
+
+fn replay<T: Serialize>(claimed_hash: &Hash32, params: &Params, matches: impl FnOnce() -> (bool, T)) -> VerifyResult {
+    let expected = params_hash(params);
+    if *claimed_hash != expected { return VerifyResult::ParamsMismatch { expected }; }
+    match matches() {
+        (true, _) => VerifyResult::Valid,
+        (false, out) => VerifyResult::OutputMismatch { recomputed: serde_json::to_string(&out).unwrap_or_default() },
+    }
+}
+
+/// Check a published post cost against `params`
+pub fn verify_cost_claim(claim: &CostClaim, params: &Params) -> VerifyResult {
+    replay(&claim.params_hash, params, || {
+        let cost = calculate_post_cost(&claim.actor, &claim.content, params, claim.base_fare);
+        (cost.to_bits() == claim.cost.to_bits(), cost)
+    })
+}
+
+/// Check a published serve reward against `params`
+pub fn verify_reward_claim(claim: &RewardClaim, params: &Params) -> VerifyResult {
+    replay(&claim.params_hash, params, || {
+        let reward = calculate_serve_reward(&claim.input, params);
+        (reward.to_bits() == claim.reward.to_bits(), reward)
+    })
+}
+
+/// Check a published propagation decision against `params`
+pub fn verify_propagation_claim(claim: &PropagationClaim, params: &Params) -> VerifyResult {
+    replay(&claim.params_hash, params, || {
+        let p = adjust_propagation_for_content(&claim.content, params);
+        ((p.ttl, p.fanout, p.quarantined) == (claim.ttl, claim.fanout, claim.quarantined), p)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_verify_claims() {
+        let params = Params::default();
+        let hash = params_hash(&params);
+        let actor = Actor { rl: 12.0, q: 0.7, ef: 8.0, ..Default::default() };
+        let content = Content { is_claim: Some(true), ..Default::default() };
+        let cost = calculate_post_cost(&actor, &content, &params, 1.0);
+        let mut claim = CostClaim { actor, content: content.clone(), base_fare: 1.0, params_hash: hash, cost };
+        assert!(verify_cost_claim(&claim, &params).is_valid());
+        claim.cost = cost * 1.01;
+        assert!(matches!(verify_cost_claim(&claim, &params), VerifyResult::OutputMismatch { .. }));
+        claim.params_hash = [0; 32];
+        assert_eq!(verify_cost_claim(&claim, &params), VerifyResult::ParamsMismatch { expected: hash });
+
+        let input = RewardInput { ticket_budget: 1.0, client_q: 0.8, size_bytes: 20_000, ttfb_ms: 120, ..Default::default() };
+        let reward = calculate_serve_reward(&input, &params);
+        assert!(verify_reward_claim(&RewardClaim { input, params_hash: hash, reward }, &params).is_valid());
+
+        let p = adjust_propagation_for_content(&content, &params);
+        let prop = PropagationClaim { content, params_hash: hash, ttl: p.ttl, fanout: p.fanout + 1, quarantined: p.quarantined };
+        assert!(!verify_propagation_claim(&prop, &params).is_valid());
+    }
+}
@@ -0,0 +1,78 @@
+// Per-community parameter overrides (feature `serde`)
+// - Each community stores only the fields it changes, as a `ParamsDelta` of dotted paths (as in `sim::sweep`)
+// - The operator allowlists which paths communities may override and within which bounds; a path without bounds or a
+//   value outside them is rejected rather than clamped, so a community never runs params the operator did not allow
+// - `Params::for_community` resolves a community's params on top of the operator's; unknown communities get them as-is
+
+use crate::governance::ParamsDelta;
+use crate::{ParamError, Params};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Inclusive range a community may set a field to
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Bounds {
+    pub lo: f64,
+    pub hi: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CommunityOverrides {
+    /// Overridable paths and their bounds (operator-set)
+    pub bounds: BTreeMap<String, Bounds>,
+    /// Overrides by community id
+    pub communities: BTreeMap<String, ParamsDelta>,
+}
+
+impl CommunityOverrides {
+    /// Out-of-bounds or non-overridable entries of one community's delta
+    pub fn check(&self, delta: &ParamsDelta) -> Vec<ParamError> {
+        delta.iter().filter_map(|(path, &x)| {
+            let reason = match self.bounds.get(path) {
+                None => "not overridable by communities".to_string(),
+                Some(b) if !(b.lo..=b.hi).contains(&x) => format!("{} outside [{}, {}]", x, b.lo, b.hi),
+                Some(_) => return None,
+            };
+            Some(ParamError { path: path.clone(), reason })
+        }).collect()
+    }
+}
+
+impl Params {
+    /// Params for community `id`: these params with the community's overrides applied (bounds-checked and validated)
+    pub fn for_community(&self, id: &str, overrides: &CommunityOverrides) -> Result<Params, Vec<ParamError>> {
+        let Some(delta) = overrides.communities.get(id) else { return Ok(self.clone()); };
+        let errors = overrides.check(delta);
+        if !errors.is_empty() { return Err(errors); }
+        self.with_delta(delta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_for_community_applies_bounded_overrides() {
+        let base = Params::default();
+        let overrides = CommunityOverrides {
+            bounds: BTreeMap::from([
+                ("propagation.ttl_base".to_string(), Bounds { lo: 1.0, hi: base.propagation.ttl_base }),
+                ("cost.alpha".to_string(), Bounds { lo: 0.5, hi: 1.5 }),
+            ]),
+            communities: BTreeMap::from([
+                ("news".to_string(), ParamsDelta::from([("propagation.ttl_base".to_string(), 2.0)])),
+                ("rogue".to_string(), ParamsDelta::from([("propagation.ttl_base".to_string(), 50.0), ("cost.b".to_string(), 0.0)])),
+            ]),
+        };
+        let news = base.for_community("news", &overrides).unwrap();
+        assert_eq!(news.propagation.ttl_base, 2.0);
+        assert_eq!(news.cost.alpha, base.cost.alpha);
+        assert_eq!(base.for_community("unknown", &overrides).unwrap().propagation.ttl_base, base.propagation.ttl_base);
+
+        let errs = base.for_community("rogue", &overrides).unwrap_err();
+        assert_eq!(errs.iter().map(|e| e.path.as_str()).collect::<Vec<_>>(), ["cost.b", "propagation.ttl_base"]);
+    }
+}
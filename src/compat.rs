@@ -0,0 +1,118 @@
+// Backward-compatible stored records (feature `serde`)
+// - `Versioned<T>` reads both the enveloped form this release writes (`{"schema_version": 1, "data": {...}}`) and bare
+//   records as written by v0.x (no `schema_version` key)
+// - An envelope with a version this build does not know (0 or newer than `SCHEMA_VERSION`) is an error, never re-read
+//   as a bare record; decode errors of the record itself are reported as they are, under `data` for enveloped ones
+// - Fields added since v0.x are `Option` or `#[serde(default)]`, so older records fill them with the defaults that
+//   reproduce v0.x behavior; `fixtures/v0/` holds records from that era and the tests pin that they keep decoding
+// - Rule for new fields on stored types: `Option<_>` or a serde default equal to the previous behavior, never a rename
+
+use crate::{Actor, Content, Params, PostEvent, QInputs, RewardInput};
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+/// Envelope version written by `Versioned::new`
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// A stored record with or without the version envelope
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum Versioned<T> {
+    Tagged { schema_version: u32, data: T },
+    /// Bare record (v0.x)
+    Bare(T),
+}
+
+impl<T> Versioned<T> {
+    /// Wrap a record for storage under the current schema version
+    pub fn new(data: T) -> Self { Versioned::Tagged { schema_version: SCHEMA_VERSION, data } }
+
+    /// Envelope version (0 for bare records)
+    pub fn schema_version(&self) -> u32 {
+        match self {
+            Versioned::Tagged { schema_version, .. } => *schema_version,
+            Versioned::Bare(_) => 0,
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        match self {
+            Versioned::Tagged { data, .. } | Versioned::Bare(data) => data,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Envelope {
+    schema_version: u32,
+    data: Value,
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Versioned<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        if value.get("schema_version").is_none() {
+            return T::deserialize(value).map(Versioned::Bare).map_err(D::Error::custom);
+        }
+        let Envelope { schema_version, data } = Envelope::deserialize(value).map_err(D::Error::custom)?;
+        if !(1..=SCHEMA_VERSION).contains(&schema_version) {
+            return Err(D::Error::custom(format!("unsupported schema_version {} (this build reads 1..={})", schema_version, SCHEMA_VERSION)));
+        }
+        let data = T::deserialize(data).map_err(|e| D::Error::custom(format!("data: {}", e)))?;
+        Ok(Versioned::Tagged { schema_version, data })
+    }
+}
+
+impl<T> From<T> for Versioned<T> {
+    fn from(data: T) -> Self { Versioned::new(data) }
+}
+
+pub type VersionedActor = Versioned<Actor>;
+pub type VersionedContent = Versioned<Content>;
+pub type VersionedPostEvent = Versioned<PostEvent>;
+pub type VersionedQInputs = Versioned<QInputs>;
+pub type VersionedRewardInput = Versioned<RewardInput>;
+pub type VersionedParams = Versioned<Params>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v0<T: serde::de::DeserializeOwned>(fixture: &str) -> Versioned<T> { serde_json::from_str(fixture).unwrap() }
+
+    #[test]
+    fn t_v0_fixtures_keep_decoding() {
+        let params: VersionedParams = v0(include_str!("../fixtures/v0/params.json"));
+        assert_eq!(params.schema_version(), 0);
+        // every field added since v0.x defaults to the value that reproduces v0.x behavior
+        assert_eq!(serde_json::to_value(params.into_inner()).unwrap(), serde_json::to_value(Params::default()).unwrap());
+
+        let event: VersionedPostEvent = v0(include_str!("../fixtures/v0/post_event.json"));
+        let event = event.into_inner();
+        assert_eq!((event.actor.ef, event.content.topic), (28.3, None));
+        let reward: VersionedRewardInput = v0(include_str!("../fixtures/v0/reward_input.json"));
+        let reward = reward.into_inner();
+        assert_eq!((reward.size_bytes, reward.prev_serves, reward.ttfb_percentile), (25_000, None, None));
+        let q: VersionedQInputs = v0(include_str!("../fixtures/v0/q_inputs.json"));
+        assert_eq!(q.into_inner().H, 0.2);
+    }
+
+    #[test]
+    fn t_envelope_round_trips() {
+        let actor = Actor { rl: 1.0, q: 0.5, ef: 2.0, posts_1h: None, ..Default::default() };
+        let json = serde_json::to_string(&VersionedActor::new(actor)).unwrap();
+        assert!(json.starts_with(r#"{"schema_version":1,"data":{"rl":1.0"#));
+        let back: VersionedActor = serde_json::from_str(&json).unwrap();
+        assert_eq!((back.schema_version(), back.into_inner().ef), (1, 2.0));
+        let bare: VersionedContent = serde_json::from_str(r#"{"is_claim": true}"#).unwrap();
+        assert_eq!(bare.schema_version(), 0);
+
+        // unknown versions and bad records fail with the real reason instead of falling back to a bare read
+        let newer = serde_json::from_str::<VersionedActor>(r#"{"schema_version":2,"data":{"rl":1.0,"q":0.5,"ef":2.0}}"#);
+        assert!(newer.unwrap_err().to_string().starts_with("unsupported schema_version 2"));
+        let bad = serde_json::from_str::<VersionedActor>(r#"{"schema_version":1,"data":{"rl":"x","q":0.5,"ef":2.0}}"#);
+        assert!(bad.unwrap_err().to_string().starts_with("data: invalid type"));
+    }
+}
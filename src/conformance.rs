@@ -0,0 +1,187 @@
+// Cross-checks against another implementation's results (feature `serde`)
+// - A `ResultFile` has the layout `slimechain-algo vectors --out` writes, produced by another implementation (e.g. the
+//   TypeScript port) from its own evaluation of the inputs, plus the name of that implementation
+// - `cross_check` re-evaluates every input here under the file's params and compares leaf by leaf: numbers within the
+//   function's `Tolerance`, everything else exactly; the default tolerance is 0 (bit-exact, like `vectors::check`)
+// - The report keeps per-function counts and the largest differences seen (also within tolerance, to tune them), each
+//   divergence with its JSON path, and inputs this build cannot evaluate as errors rather than aborting
+
+use crate::vectors::{eval, Vector, VectorError};
+use crate::Params;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Results from another implementation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultFile {
+    /// e.g. "slimechain-algo-ts 0.4.1"
+    #[serde(default)]
+    pub implementation: Option<String>,
+    #[serde(default)]
+    pub version: String,
+    pub params: Params,
+    pub vectors: Vec<Vector>,
+}
+
+/// Allowed difference |theirs - ours| <= abs + rel * max(|theirs|, |ours|)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Tolerance {
+    #[serde(default)]
+    pub abs: f64,
+    #[serde(default)]
+    pub rel: f64,
+}
+
+impl Tolerance {
+    pub fn allows(&self, theirs: f64, ours: f64) -> bool {
+        (theirs - ours).abs() <= self.abs.max(0.0) + self.rel.max(0.0) * theirs.abs().max(ours.abs())
+    }
+}
+
+/// Tolerance per function name (as in `vectors::eval`), else `default`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Tolerances {
+    #[serde(default)]
+    pub default: Tolerance,
+    #[serde(default)]
+    pub functions: BTreeMap<String, Tolerance>,
+}
+
+impl Tolerances {
+    pub fn for_function(&self, function: &str) -> Tolerance { self.functions.get(function).copied().unwrap_or(self.default) }
+}
+
+/// First differing leaf of one result
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Divergence {
+    pub index: usize,
+    pub function: String,
+    /// Dotted path into the output ("" = the output itself)
+    pub path: String,
+    pub theirs: Value,
+    pub ours: Value,
+    /// For numbers
+    pub abs_diff: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FunctionSummary {
+    pub checked: usize,
+    pub failed: usize,
+    /// Largest numeric differences seen, within tolerance or not
+    pub max_abs_diff: f64,
+    pub max_rel_diff: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConformanceReport {
+    pub implementation: Option<String>,
+    pub version: String,
+    pub checked: usize,
+    pub passed: usize,
+    pub functions: BTreeMap<String, FunctionSummary>,
+    pub divergences: Vec<Divergence>,
+    /// Inputs this build could not evaluate (unknown function, malformed input)
+    pub errors: Vec<VectorError>,
+}
+
+impl ConformanceReport {
+    pub fn conforms(&self) -> bool { self.divergences.is_empty() && self.errors.is_empty() }
+}
+
+/// Leaf-by-leaf comparison; records the largest numeric differences in `summary` and returns the first divergence
+fn compare(theirs: &Value, ours: &Value, tol: Tolerance, path: &str, summary: &mut FunctionSummary) -> Option<(String, Value, Value, Option<f64>)> {
+    let join = |key: &str| if path.is_empty() { key.to_string() } else { format!("{}.{}", path, key) };
+    match (theirs, ours) {
+        (Value::Number(a), Value::Number(b)) => {
+            let (a, b) = (a.as_f64().unwrap_or(f64::NAN), b.as_f64().unwrap_or(f64::NAN));
+            let diff = (a - b).abs();
+            summary.max_abs_diff = summary.max_abs_diff.max(diff);
+            let scale = a.abs().max(b.abs());
+            if scale > 0.0 { summary.max_rel_diff = summary.max_rel_diff.max(diff / scale); }
+            (!tol.allows(a, b)).then(|| (path.to_string(), theirs.clone(), ours.clone(), Some(diff)))
+        }
+        (Value::Array(a), Value::Array(b)) if a.len() == b.len() => {
+            let mut first = None;
+            for (i, (x, y)) in a.iter().zip(b).enumerate() {
+                let d = compare(x, y, tol, &join(&i.to_string()), summary);
+                first = first.or(d);
+            }
+            first
+        }
+        (Value::Object(a), Value::Object(b)) if a.len() == b.len() && a.keys().all(|k| b.contains_key(k)) => {
+            let mut first = None;
+            for (k, x) in a {
+                let d = compare(x, &b[k], tol, &join(k), summary);
+                first = first.or(d);
+            }
+            first
+        }
+        _ => (theirs != ours).then(|| (path.to_string(), theirs.clone(), ours.clone(), None)),
+    }
+}
+
+/// Re-evaluate every input of `file` under its params and compare with its outputs
+pub fn cross_check(file: &ResultFile, tolerances: &Tolerances) -> ConformanceReport {
+    let mut functions: BTreeMap<String, FunctionSummary> = BTreeMap::new();
+    let (mut divergences, mut errors) = (Vec::new(), Vec::new());
+    for (index, v) in file.vectors.iter().enumerate() {
+        let summary = functions.entry(v.function.clone()).or_default();
+        summary.checked += 1;
+        let ours = match eval(&v.function, &v.input, &file.params) {
+            Ok(out) => out,
+            Err(e) => {
+                summary.failed += 1;
+                errors.push(VectorError { index: Some(index), ..e });
+                continue;
+            }
+        };
+        if let Some((path, theirs, ours, abs_diff)) = compare(&v.output, &ours, tolerances.for_function(&v.function), "", summary) {
+            summary.failed += 1;
+            divergences.push(Divergence { index, function: v.function.clone(), path, theirs, ours, abs_diff });
+        }
+    }
+    let checked = file.vectors.len();
+    let failed: usize = functions.values().map(|s| s.failed).sum();
+    ConformanceReport {
+        implementation: file.implementation.clone(),
+        version: file.version.clone(),
+        checked,
+        passed: checked - failed,
+        functions,
+        divergences,
+        errors,
+    }
+}
+
+// fast-math builds are not expected to conform
+#[cfg(all(test, not(feature = "fast-math")))]
+mod tests {
+    use super::*;
+    use crate::vectors::canonical;
+    use serde_json::json;
+
+    #[test]
+    fn t_cross_check_with_tolerances() {
+        let canon = canonical();
+        let mut file = ResultFile { implementation: Some("port".into()), version: canon.version, params: canon.params, vectors: canon.vectors };
+        assert!(cross_check(&file, &Tolerances::default()).conforms());
+
+        // a port that is off in the last digits of one quality score
+        let i = file.vectors.iter().position(|v| v.function == "calculate_quality").unwrap();
+        let q = file.vectors[i].output.as_f64().unwrap();
+        file.vectors[i].output = json!(q + 1e-12);
+        let j = file.vectors.iter().position(|v| v.function == "adjust_propagation_for_content").unwrap();
+        file.vectors[j].output["ttl"] = json!(99);
+        file.vectors.push(Vector { function: "ts_only".into(), input: json!({}), output: json!(0) });
+
+        let report = cross_check(&file, &Tolerances::default());
+        assert_eq!((report.passed, report.divergences.len(), report.errors.len()), (report.checked - 3, 2, 1));
+        assert_eq!((report.divergences[0].index, report.divergences[1].path.as_str()), (i, "ttl"));
+        assert!(report.functions["calculate_quality"].max_abs_diff > 0.0);
+
+        let loose = Tolerances { functions: [("calculate_quality".to_string(), Tolerance { abs: 1e-9, rel: 0.0 })].into(), ..Default::default() };
+        assert_eq!(cross_check(&file, &loose).divergences.len(), 1);
+    }
+}
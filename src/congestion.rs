@@ -0,0 +1,468 @@
+// Congestion control extensions (stateful controllers on top of `update_base_cost`)
+// - Callers persist the small state structs between updates; functions stay deterministic
+
+use crate::{cap_increase, clamp, step_base_cost, update_base_cost, CongestionController, CongestionParams, Params};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// PID controller state (persist between updates; start from `Default`)
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PidState {
+    pub integral: f64,
+    pub prev_error: Option<f64>,
+}
+
+/// Update the base fare with the controller selected in `params.congestion.controller`
+/// - `Exponential` ignores `state` and matches `update_base_cost`
+/// - `Pid` steps `ln(B)` by `kp*e + ki*∫e + kd*Δe` with `e = load/target - 1`
+pub fn update_base_cost_controlled(current_base: f64, current_load: f64, state: &mut PidState, params: &Params) -> f64 {
+    let c = &params.congestion;
+    match c.controller {
+        CongestionController::Exponential => update_base_cost(current_base, current_load, params),
+        CongestionController::Pid { kp, ki, kd, i_limit } => {
+            let target = c.target_load.max(1e-9);
+            let e = current_load / target - 1.0;
+            let limit = i_limit.abs();
+            state.integral = clamp(state.integral + e, -limit, limit);
+            let de = state.prev_error.map_or(0.0, |p| e - p);
+            state.prev_error = Some(e);
+            let mut step = kp * e + ki * state.integral + kd * de;
+            if step < 0.0 { step *= c.surge.cooldown_factor.max(0.0); }
+            clamp(cap_increase(current_base, current_base * crate::fastmath::exp_sel(step), c), c.base_min, c.base_max)
+        }
+    }
+}
+
+/// Base fare with exponentially smoothed load (persist between updates)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CongestionState {
+    pub base: f64,
+    /// EMA of observed load; None until the first sample
+    pub smoothed_load: Option<f64>,
+    /// Surge window bookkeeping
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub window: SurgeWindow,
+}
+
+/// Increase counter for the current surge window
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SurgeWindow {
+    /// Updates seen in the current window
+    pub updates: u32,
+    /// Fare increases applied in the current window
+    pub increases: u32,
+}
+
+impl CongestionState {
+    pub fn new(base: f64) -> Self { Self{ base, smoothed_load: None, window: SurgeWindow::default() } }
+}
+
+/// Count one update in `window` and hold `next` at `current_base` if it is an increase past
+/// `surge.max_increases_per_window`; a new window starts every `surge.window_updates` updates
+pub(crate) fn hold_in_window(current_base: f64, next: f64, window: &mut SurgeWindow, c: &CongestionParams) -> f64 {
+    let surge = &c.surge;
+    if window.updates >= surge.window_updates.max(1) {
+        *window = SurgeWindow::default();
+    }
+    window.updates += 1;
+    if next > current_base {
+        if surge.max_increases_per_window.is_some_and(|m| window.increases >= m) {
+            return current_base;
+        }
+        window.increases += 1;
+    }
+    next
+}
+
+/// `update_base_cost` with every surge cap enforced, including `surge.max_increases_per_window` (counted in `window`,
+/// persist between updates)
+pub fn update_base_cost_windowed(current_base: f64, current_load: f64, window: &mut SurgeWindow, params: &Params) -> f64 {
+    hold_in_window(current_base, update_base_cost(current_base, current_load, params), window, &params.congestion)
+}
+
+/// Fold an instantaneous load sample into the EMA and update the base fare from the smoothed load
+/// `L_ema = alpha * load + (1 - alpha) * L_ema`, then `update_base_cost_windowed(B, L_ema)`
+pub fn update_base_cost_smoothed(state: &mut CongestionState, instantaneous_load: f64, params: &Params) -> f64 {
+    let alpha = clamp(params.congestion.load_ema_alpha, 0.0, 1.0);
+    let load = instantaneous_load.max(0.0);
+    let smoothed = match state.smoothed_load {
+        Some(prev) => alpha * load + (1.0 - alpha) * prev,
+        None => load,
+    };
+    state.smoothed_load = Some(smoothed);
+    state.base = update_base_cost_windowed(state.base, smoothed, &mut state.window, params);
+    state.base
+}
+
+/// Per-resource quantities (loads, fares or weights)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ResourceLoads { pub bandwidth: f64, pub storage_writes: f64, pub compute: f64 }
+
+/// Per-resource base fares
+pub type ResourceFares = ResourceLoads;
+
+/// Independent congestion controllers per resource plus weights for the combined fare
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MultiCongestionParams {
+    pub bandwidth: CongestionParams,
+    pub storage_writes: CongestionParams,
+    pub compute: CongestionParams,
+    /// combined = Σ weight_i * fare_i
+    pub weights: ResourceLoads,
+}
+
+impl Default for MultiCongestionParams {
+    fn default() -> Self {
+        let base = Params::default().congestion;
+        Self {
+            bandwidth: CongestionParams { target_load: 1_000_000_000.0, ..base.clone() },
+            storage_writes: CongestionParams { target_load: 10_000.0, ..base.clone() },
+            compute: CongestionParams { target_load: 1_000_000.0, ..base },
+            weights: ResourceLoads { bandwidth: 1.0 / 3.0, storage_writes: 1.0 / 3.0, compute: 1.0 / 3.0 },
+        }
+    }
+}
+
+/// Multi-resource update result
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MultiFare {
+    pub fares: ResourceFares,
+    pub combined: f64,
+}
+
+/// Update each resource's base fare against its own target and combine them
+/// (each dimension uses the exponential step of `update_base_cost`)
+pub fn update_base_cost_multi(current: &ResourceFares, loads: &ResourceLoads, params: &MultiCongestionParams) -> MultiFare {
+    let fares = ResourceLoads {
+        bandwidth: step_base_cost(current.bandwidth, loads.bandwidth, &params.bandwidth),
+        storage_writes: step_base_cost(current.storage_writes, loads.storage_writes, &params.storage_writes),
+        compute: step_base_cost(current.compute, loads.compute, &params.compute),
+    };
+    let w = &params.weights;
+    let combined = w.bandwidth.max(0.0) * fares.bandwidth
+        + w.storage_writes.max(0.0) * fares.storage_writes
+        + w.compute.max(0.0) * fares.compute;
+    MultiFare { fares, combined }
+}
+
+/// Independent base fares per partition (region, shard, topic) under one shared `Params`
+/// - Each partition runs `update_base_cost_smoothed` on its own `CongestionState`
+/// - The global fare blends partition fares weighted by their latest smoothed load
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CongestionMap {
+    pub params: Params,
+    /// Base fare for partitions seen for the first time
+    pub initial_base: f64,
+    pub partitions: BTreeMap<String, CongestionState>,
+}
+
+impl CongestionMap {
+    pub fn new(params: Params, initial_base: f64) -> Self {
+        Self { params, initial_base, partitions: BTreeMap::new() }
+    }
+
+    /// Feed a load sample for one partition and return its new base fare
+    pub fn update_partition(&mut self, key: &str, load: f64) -> f64 {
+        let st = self.partitions.entry(key.to_string()).or_insert_with(|| CongestionState::new(self.initial_base));
+        update_base_cost_smoothed(st, load, &self.params)
+    }
+
+    /// Current base fare of a partition (`initial_base` if unseen)
+    pub fn fare(&self, key: &str) -> f64 {
+        self.partitions.get(key).map_or(self.initial_base, |st| st.base)
+    }
+
+    /// Load-weighted blend of partition fares (plain mean when no load was observed)
+    pub fn global_fare(&self) -> f64 {
+        if self.partitions.is_empty() { return self.initial_base; }
+        let (mut wsum, mut acc) = (0.0, 0.0);
+        for st in self.partitions.values() {
+            let w = st.smoothed_load.unwrap_or(0.0).max(0.0);
+            wsum += w;
+            acc += w * st.base;
+        }
+        if wsum > 0.0 {
+            acc / wsum
+        } else {
+            self.partitions.values().map(|st| st.base).sum::<f64>() / self.partitions.len() as f64
+        }
+    }
+}
+
+/// Replay a load series through the configured controller (surge window cap included) and return the base fare after
+/// each step
+pub fn simulate(load_series: &[f64], initial_base: f64, params: &Params) -> Vec<f64> {
+    let (mut state, mut window) = (PidState::default(), SurgeWindow::default());
+    let mut base = initial_base;
+    load_series.iter().map(|&load| {
+        base = hold_in_window(base, update_base_cost_controlled(base, load, &mut state, params), &mut window, &params.congestion);
+        base
+    }).collect()
+}
+
+/// Summary of a base fare trajectory (all zeros, `converged_at: None`, for an empty one)
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TrajectoryStats {
+    pub max_fare: f64,
+    pub min_fare: f64,
+    pub final_fare: f64,
+    /// Steps with fare strictly above `threshold`
+    pub steps_above: usize,
+    pub fraction_above: f64,
+    /// First step from which every later relative change stays within `tolerance`
+    pub converged_at: Option<usize>,
+}
+
+/// Summarize a trajectory from `simulate`
+pub fn summarize(trajectory: &[f64], threshold: f64, tolerance: f64) -> TrajectoryStats {
+    let Some(&final_fare) = trajectory.last() else { return TrajectoryStats::default() };
+    let steps_above = trajectory.iter().filter(|&&b| b > threshold).count();
+    let mut converged_at = None;
+    for i in (0..trajectory.len()).rev() {
+        let stable = i + 1 >= trajectory.len()
+            || ((trajectory[i + 1] - trajectory[i]) / trajectory[i].abs().max(1e-12)).abs() <= tolerance;
+        if !stable { break; }
+        converged_at = Some(i);
+    }
+    TrajectoryStats {
+        max_fare: trajectory.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        min_fare: trajectory.iter().copied().fold(f64::INFINITY, f64::min),
+        final_fare,
+        steps_above,
+        fraction_above: steps_above as f64 / trajectory.len() as f64,
+        converged_at,
+    }
+}
+
+/// Price-elastic demand: load offered at a given base fare (non-increasing in the fare)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum DemandCurve {
+    /// `L(B) = load_at_ref * (B / ref_fare)^(-elasticity)`
+    ConstantElasticity { load_at_ref: f64, ref_fare: f64, elasticity: f64 },
+    /// `L(B) = max(0, intercept - slope * B)`
+    Linear { intercept: f64, slope: f64 },
+}
+
+impl DemandCurve {
+    pub fn load(&self, fare: f64) -> f64 {
+        match *self {
+            DemandCurve::ConstantElasticity { load_at_ref, ref_fare, elasticity } =>
+                load_at_ref.max(0.0) * (fare.max(1e-12) / ref_fare.max(1e-12)).powf(-elasticity.max(0.0)),
+            DemandCurve::Linear { intercept, slope } => (intercept - slope.max(0.0) * fare).max(0.0),
+        }
+    }
+}
+
+/// Which fare bound (if any) holds the equilibrium
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FareBound { BaseMin, BaseMax }
+
+/// Steady state of the exponential controller under a demand curve
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Equilibrium {
+    pub base_fare: f64,
+    pub load: f64,
+    /// Set when demand cannot reach `target_load` inside `[base_min, base_max]`
+    pub bound: Option<FareBound>,
+    /// Point elasticity `-dL/dB * B/L` at the equilibrium
+    pub elasticity: f64,
+    /// Per-step error multiplier of `ln B` near the equilibrium (`max |1 - eta*k*elasticity|` over up/down steps,
+    /// `k` = 1 or `cooldown_factor`); < 1 converges, < 1 without overshoot when `eta*k*elasticity <= 1`
+    pub contraction: f64,
+    pub stable: bool,
+}
+
+/// Solve `L(B*) = target_load` for the `update_base_cost` fixed point (bisection in `ln B` over `[base_min, base_max]`)
+/// - Interior solutions have `step = eta * (L/T - 1) = 0`; otherwise the fare rests on the binding bound
+/// - Stability linearizes `ln B' = ln B + eta * (L(B)/T - 1)`; surge caps only slow the approach and are ignored
+pub fn solve_equilibrium(demand: &DemandCurve, params: &Params) -> Equilibrium {
+    let c = &params.congestion;
+    let target = c.target_load.max(1e-9);
+    let (lo, hi) = (c.base_min.max(1e-12), c.base_max.max(c.base_min.max(1e-12)));
+    let excess = |b: f64| demand.load(b) - target;
+    let (base_fare, bound) = if excess(lo) <= 0.0 {
+        (lo, Some(FareBound::BaseMin))
+    } else if excess(hi) >= 0.0 {
+        (hi, Some(FareBound::BaseMax))
+    } else {
+        let (mut a, mut b) = (lo.ln(), hi.ln());
+        for _ in 0..200 {
+            let m = 0.5 * (a + b);
+            if excess(m.exp()) > 0.0 { a = m } else { b = m }
+            if b - a <= 1e-15 * a.abs().max(1.0) { break; }
+        }
+        ((0.5 * (a + b)).exp(), None)
+    };
+    let load = demand.load(base_fare);
+    let h = 1e-6;
+    let (up, down) = (demand.load(base_fare * (1.0 + h)), demand.load(base_fare * (1.0 - h)));
+    let elasticity = if load > 0.0 { -(up - down) / (2.0 * h * load) } else { 0.0 };
+    // slope of the step in ln B is -eta * elasticity * L/T (L = T in the interior)
+    let g = c.eta * elasticity * load / target;
+    let contraction = (1.0 - g).abs().max((1.0 - g * c.surge.cooldown_factor.max(0.0)).abs());
+    Equilibrium { base_fare, load, bound, elasticity, contraction, stable: bound.is_some() || contraction < 1.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exponential_matches_update_base_cost() {
+        let params = Params::default();
+        let mut st = PidState::default();
+        assert_eq!(update_base_cost_controlled(1.0, 1000.0, &mut st, &params), update_base_cost(1.0, 1000.0, &params));
+        assert_eq!(st, PidState::default());
+    }
+
+    #[test]
+    fn test_pid_damps_bursts() {
+        let mut params = Params::default();
+        let loads = [500.0, 2000.0, 100.0, 2000.0, 100.0, 500.0, 500.0, 500.0];
+        let run = |params: &Params| {
+            let mut st = PidState::default();
+            let mut b = 1.0;
+            let mut path = Vec::new();
+            for &l in &loads {
+                b = update_base_cost_controlled(b, l, &mut st, params);
+                path.push(b);
+            }
+            path
+        };
+        let exp_path = run(&params);
+        params.congestion.controller = CongestionController::Pid { kp: 0.05, ki: 0.01, kd: 0.0, i_limit: 5.0 };
+        let pid_path = run(&params);
+        let swing = |p: &[f64]| p.windows(2).map(|w| (w[1] / w[0]).ln().abs()).fold(0.0, f64::max);
+        assert!(swing(&pid_path) < swing(&exp_path));
+        assert!(pid_path.iter().all(|b| (params.congestion.base_min..=params.congestion.base_max).contains(b)));
+    }
+
+    #[test]
+    fn test_smoothed_spike() {
+        let params = Params::default();
+        let mut st = CongestionState::new(1.0);
+        update_base_cost_smoothed(&mut st, 500.0, &params);
+        let smoothed = update_base_cost_smoothed(&mut st, 5000.0, &params);
+        let raw = update_base_cost(1.0, 5000.0, &params);
+        assert_eq!(st.smoothed_load, Some(0.3 * 5000.0 + 0.7 * 500.0));
+        assert!(smoothed < raw);
+    }
+
+    #[test]
+    fn test_multi_resource_independent() {
+        let mp = MultiCongestionParams::default();
+        let current = ResourceLoads { bandwidth: 1.0, storage_writes: 1.0, compute: 1.0 };
+        let loads = ResourceLoads { bandwidth: 4_000_000_000.0, storage_writes: 10_000.0, compute: 1_000_000.0 };
+        let out = update_base_cost_multi(&current, &loads, &mp);
+        assert!(out.fares.bandwidth > 1.0);
+        assert_eq!(out.fares.storage_writes, 1.0);
+        assert_eq!(out.fares.compute, 1.0);
+        assert!(out.combined > 1.0 && out.combined < out.fares.bandwidth);
+    }
+
+    #[test]
+    fn test_simulate_trajectory() {
+        let params = Params::default();
+        let mut loads = vec![1500.0; 10];
+        loads.extend(vec![500.0; 10]);
+        let path = simulate(&loads, 1.0, &params);
+        assert_eq!(path.len(), 20);
+        assert_eq!(path[0], update_base_cost(1.0, 1500.0, &params));
+
+        let stats = summarize(&path, 2.0, 1e-9);
+        assert_eq!(stats.max_fare, path[9]);
+        assert_eq!(stats.final_fare, path[19]);
+        assert_eq!(stats.converged_at, Some(9));
+        assert!(stats.steps_above > 0);
+    }
+
+    #[test]
+    fn test_summarize_empty_trajectory() {
+        let stats = summarize(&simulate(&[], 1.0, &Params::default()), 2.0, 1e-9);
+        assert_eq!(stats, TrajectoryStats::default());
+        assert_eq!((stats.max_fare, stats.min_fare, stats.final_fare, stats.fraction_above), (0.0, 0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_surge_window_holds_increases() {
+        let mut params = Params::default();
+        params.congestion.load_ema_alpha = 1.0;
+        params.congestion.surge.max_increases_per_window = Some(2);
+        params.congestion.surge.window_updates = 4;
+        let mut st = CongestionState::new(1.0);
+        let path: Vec<f64> = (0..5).map(|_| update_base_cost_smoothed(&mut st, 1000.0, &params)).collect();
+        assert!(path[1] > path[0]);
+        assert_eq!(path[2], path[1]);
+        assert_eq!(path[3], path[1]);
+        // new window
+        assert!(path[4] > path[3]);
+
+        // the stateless step keeps rising; the windowed one and `simulate` hold after two increases
+        let mut window = SurgeWindow::default();
+        let held: Vec<f64> = (0..3).fold(vec![1.0], |mut p, _| {
+            let b = *p.last().unwrap();
+            p.push(update_base_cost_windowed(b, 1000.0, &mut window, &params));
+            p
+        });
+        assert_eq!(held[3], held[2]);
+        assert!(update_base_cost(held[2], 1000.0, &params) > held[2]);
+        assert_eq!(simulate(&[1000.0; 3], 1.0, &params), held[1..].to_vec());
+    }
+
+    #[test]
+    fn test_congestion_map_partitions() {
+        let mut map = CongestionMap::new(Params::default(), 1.0);
+        for _ in 0..5 {
+            map.update_partition("eu", 2000.0);
+            map.update_partition("us", 500.0);
+        }
+        assert!(map.fare("eu") > 1.0);
+        assert_eq!(map.fare("us"), 1.0);
+        assert_eq!(map.fare("apac"), 1.0);
+        let g = map.global_fare();
+        assert!(g > 1.0 && g < map.fare("eu"));
+    }
+
+    #[test]
+    fn test_equilibrium_matches_closed_form_and_dynamics() {
+        let mut params = Params::default();
+        let demand = DemandCurve::ConstantElasticity { load_at_ref: 2000.0, ref_fare: 1.0, elasticity: 1.0 };
+        let eq = solve_equilibrium(&demand, &params);
+        // 2000 / B = 500
+        assert!((eq.base_fare - 4.0).abs() < 1e-9 && eq.bound.is_none());
+        assert!((eq.elasticity - 1.0).abs() < 1e-6 && (eq.contraction - 0.9).abs() < 1e-6 && eq.stable);
+        let mut b = 1.0;
+        for _ in 0..300 { b = update_base_cost(b, demand.load(b), &params); }
+        assert!((b - eq.base_fare).abs() < 1e-6);
+
+        params.congestion.eta = 2.5;
+        assert!(!solve_equilibrium(&demand, &params).stable);
+        let weak = solve_equilibrium(&DemandCurve::Linear { intercept: 100.0, slope: 1.0 }, &params);
+        assert_eq!((weak.base_fare, weak.bound), (0.1, Some(FareBound::BaseMin)));
+    }
+}
@@ -0,0 +1,252 @@
+// Memoized scoring for repeated actors and content
+// - `ScoringContext` caches EF, risk and cost results in bounded LRU maps under one `PreparedParams`
+// - Keys are the exact bit patterns of every field of the inputs, so a hit returns what the scalar call would return;
+//   the key functions destructure `Actor`, `Content` and `RiskSignals` exhaustively, so a new field fails to compile
+//   until it is part of the key
+// - Replacing the params clears every cache; `invalidate_*` drop entries for one actor or piece of content
+// - `with_sink` reports every hit and miss to a `MetricsSink`
+
+use crate::metrics::{Metric, MetricsSink};
+use crate::{
+    calculate_ef, calculate_post_cost, calculate_risk, Actor, Content, ContentKind, ContentTopic, FactCheckState, Params, PreparedParams,
+    RiskSignals, RiskWeights, VerificationLevel,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Arc;
+
+type SignalsKey = Option<[Option<u64>; 6]>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct ActorKey { rl: u64, q: u64, ef: u64, posts_1h: Option<u64>, account_age_days: Option<u64>, verification_level: Option<VerificationLevel>, kind_posts_1h: Option<u64> }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct ContentKey { is_claim: Option<bool>, has_evidence: Option<bool>, signals: SignalsKey, topic: Option<ContentTopic>, kind: Option<ContentKind>, fact_check: Option<FactCheckState> }
+
+/// Exact identity of one cost computation: the bit patterns of every field of the actor, the content and the base fare
+/// (also the dedup key of `batch::canonicalize` for post events)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CostKey(ActorKey, ContentKey, u64);
+
+impl CostKey {
+    pub fn new(actor: &Actor, content: &Content, base_fare: f64) -> Self { CostKey(actor_key(actor), content_key(content), base_fare.to_bits()) }
+}
+
+fn bits(x: Option<f64>) -> Option<u64> { x.map(f64::to_bits) }
+
+fn signals_key(s: &Option<RiskSignals>) -> SignalsKey {
+    let RiskSignals { coordination, clustering, burst, monotonicity, abuse_history, duplication } = s.as_ref()?;
+    Some([bits(*coordination), bits(*clustering), bits(*burst), bits(*monotonicity), bits(*abuse_history), bits(*duplication)])
+}
+
+fn actor_key(a: &Actor) -> ActorKey {
+    let Actor { rl, q, ef, posts_1h, account_age_days, verification_level, kind_posts_1h } = a;
+    ActorKey {
+        rl: rl.to_bits(), q: q.to_bits(), ef: ef.to_bits(), posts_1h: bits(*posts_1h), account_age_days: bits(*account_age_days),
+        verification_level: *verification_level, kind_posts_1h: bits(*kind_posts_1h),
+    }
+}
+
+fn content_key(c: &Content) -> ContentKey {
+    let Content { is_claim, has_evidence, risk_signals, topic, kind, fact_check } = c;
+    ContentKey { is_claim: *is_claim, has_evidence: *has_evidence, signals: signals_key(risk_signals), topic: *topic, kind: *kind, fact_check: *fact_check }
+}
+
+/// Least-recently-used map (ticks order the entries; the oldest tick is evicted first)
+#[derive(Debug)]
+struct Lru<K, V> {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<K, (V, u64)>,
+    order: BTreeMap<u64, K>,
+}
+
+impl<K: Hash + Eq + Clone, V: Copy> Lru<K, V> {
+    fn new(capacity: usize) -> Self { Lru { capacity, tick: 0, entries: HashMap::new(), order: BTreeMap::new() } }
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        let (value, tick) = self.entries.get_mut(key)?;
+        self.order.remove(tick);
+        self.tick += 1;
+        *tick = self.tick;
+        self.order.insert(self.tick, key.clone());
+        Some(*value)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 { return; }
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            if let Some((_, oldest)) = self.order.pop_first() { self.entries.remove(&oldest); }
+        }
+        self.tick += 1;
+        if let Some((_, old)) = self.entries.insert(key.clone(), (value, self.tick)) { self.order.remove(&old); }
+        self.order.insert(self.tick, key);
+    }
+
+    fn retain(&mut self, mut keep: impl FnMut(&K) -> bool) {
+        self.entries.retain(|k, _| keep(k));
+        self.order.retain(|_, k| keep(k));
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
+
+/// Hit/miss counters and current entry count (all caches combined)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+/// Scoring front-end that memoizes EF, risk (default weights) and cost
+pub struct ScoringContext {
+    params: PreparedParams,
+    ef: Lru<Vec<u64>, f64>,
+    risk: Lru<SignalsKey, f64>,
+    cost: Lru<CostKey, f64>,
+    hits: u64,
+    misses: u64,
+    sink: Arc<dyn MetricsSink>,
+}
+
+impl fmt::Debug for ScoringContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScoringContext").field("params", &self.params).field("stats", &self.stats()).finish_non_exhaustive()
+    }
+}
+
+impl ScoringContext {
+    /// Context holding at most `capacity` entries per cache
+    pub fn new(params: Params, capacity: usize) -> Self {
+        ScoringContext {
+            params: PreparedParams::new(params),
+            ef: Lru::new(capacity),
+            risk: Lru::new(capacity),
+            cost: Lru::new(capacity),
+            hits: 0,
+            misses: 0,
+            sink: Arc::new(()),
+        }
+    }
+
+    /// Report cache hits and misses to `sink`
+    pub fn with_sink(mut self, sink: Arc<dyn MetricsSink>) -> Self {
+        self.sink = sink;
+        self
+    }
+
+    pub fn params(&self) -> &Params { &self.params }
+
+    /// Replace the params; every cached result is dropped
+    pub fn set_params(&mut self, params: Params) {
+        self.params = PreparedParams::new(params);
+        self.clear();
+    }
+
+    fn lookup<K: Hash + Eq + Clone>(cache: &mut Lru<K, f64>, counters: (&mut u64, &mut u64, &dyn MetricsSink), key: K, compute: impl FnOnce() -> f64) -> f64 {
+        let (hits, misses, sink) = counters;
+        if let Some(v) = cache.get(&key) {
+            *hits += 1;
+            sink.observe(Metric::CacheHit);
+            return v;
+        }
+        *misses += 1;
+        sink.observe(Metric::CacheMiss);
+        let v = compute();
+        cache.insert(key, v);
+        v
+    }
+
+    /// Memoized `calculate_ef`
+    pub fn ef(&mut self, followers_q: &[f64]) -> f64 {
+        let key = followers_q.iter().map(|q| q.to_bits()).collect();
+        let params = &self.params;
+        Self::lookup(&mut self.ef, (&mut self.hits, &mut self.misses, &*self.sink), key, || calculate_ef(followers_q, params))
+    }
+
+    /// Memoized `calculate_risk` with the default weights
+    pub fn risk(&mut self, signals: &Option<RiskSignals>) -> f64 {
+        Self::lookup(&mut self.risk, (&mut self.hits, &mut self.misses, &*self.sink), signals_key(signals), || calculate_risk(signals, &RiskWeights::DEFAULT))
+    }
+
+    /// Memoized `calculate_post_cost`
+    pub fn cost(&mut self, actor: &Actor, content: &Content, base_fare: f64) -> f64 {
+        let key = CostKey::new(actor, content, base_fare);
+        let params = &self.params;
+        Self::lookup(&mut self.cost, (&mut self.hits, &mut self.misses, &*self.sink), key, || calculate_post_cost(actor, content, params, base_fare))
+    }
+
+    /// Drop the cost entries of one actor (e.g., after its posting rate or EF was refreshed)
+    pub fn invalidate_actor(&mut self, actor: &Actor) {
+        let key = actor_key(actor);
+        self.cost.retain(|k| k.0 != key);
+    }
+
+    /// Drop the cost and risk entries of one piece of content (e.g., after its risk signals were re-scored)
+    pub fn invalidate_content(&mut self, content: &Content) {
+        let key = content_key(content);
+        self.cost.retain(|k| k.1 != key);
+        self.risk.retain(|s| *s != key.signals);
+    }
+
+    /// Drop every cached result (the counters are kept)
+    pub fn clear(&mut self) {
+        self.ef.clear();
+        self.risk.clear();
+        self.cost.clear();
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats { hits: self.hits, misses: self.misses, entries: self.ef.entries.len() + self.risk.entries.len() + self.cost.entries.len() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_hits_match_scalar_and_invalidate() {
+        let p = Params::default();
+        let mut ctx = ScoringContext::new(p.clone(), 2);
+        let actor = Actor { rl: 120.0, q: 0.8, ef: 28.3, posts_1h: Some(12.0), ..Default::default() };
+        let content = Content { is_claim: Some(true), risk_signals: Some(RiskSignals { burst: Some(0.4), ..Default::default() }), ..Default::default() };
+        let want = calculate_post_cost(&actor, &content, &p, 1.0);
+        assert_eq!(ctx.cost(&actor, &content, 1.0), want);
+        assert_eq!(ctx.cost(&actor, &content, 1.0), want);
+        assert_eq!(ctx.stats(), CacheStats { hits: 1, misses: 1, entries: 1 });
+
+        ctx.invalidate_actor(&actor);
+        assert_eq!(ctx.stats().entries, 0);
+
+        // capacity 2: the least recently used entry goes first
+        for base in [1.0, 2.0, 3.0] { ctx.cost(&actor, &content, base); }
+        ctx.cost(&actor, &content, 1.0);
+        assert_eq!(ctx.stats().misses, 5);
+        let newcomer = Actor { account_age_days: Some(1.0), ..actor.clone() };
+        assert_eq!(ctx.cost(&newcomer, &content, 1.0), calculate_post_cost(&newcomer, &content, &p, 1.0));
+        assert_eq!(ctx.stats().misses, 6);
+        let verified = Actor { verification_level: Some(VerificationLevel::Identity), ..newcomer.clone() };
+        assert_eq!(ctx.cost(&verified, &content, 1.0), calculate_post_cost(&verified, &content, &p, 1.0));
+        assert_eq!(ctx.stats().misses, 7);
+        // per-kind rate limits price the same actor differently by content kind
+        let mut kinds = p.clone();
+        kinds.cost.kind_rate_limits.insert(ContentKind::Media, 2.0);
+        ctx.set_params(kinds.clone());
+        let media = Content { kind: Some(ContentKind::Media), ..content.clone() };
+        assert_eq!(ctx.cost(&actor, &content, 1.0), calculate_post_cost(&actor, &content, &kinds, 1.0));
+        assert_eq!(ctx.cost(&actor, &media, 1.0), calculate_post_cost(&actor, &media, &kinds, 1.0));
+
+        ctx.set_params(Params { q_min: 0.5, ..Params::default() });
+        assert_eq!(ctx.stats().entries, 0);
+    }
+}
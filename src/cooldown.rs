@@ -0,0 +1,83 @@
+// Cooldown after a risk spike
+// - A risk event at or above `min_severity` puts the actor on cooldown: posting cost is multiplied by up to
+//   `max_cost` and propagation (ttl/fanout) by down to `min_propagation`, scaled by how far the severity exceeds
+//   `min_severity` (0 at the threshold, 1 at severity 1)
+// - The effect decays linearly to none over `duration_secs`, so the multipliers return exactly to 1.0
+// - Multiply `calculate_post_cost` by `Cooldown::cost`; scale a propagation decision with `Cooldown::apply`
+
+use crate::{clamp, PropagationResult, Risk};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CooldownParams {
+    /// Spike severity below which no cooldown applies
+    pub min_severity: f64,
+    /// Cost multiplier right after a severity-1 spike
+    pub max_cost: f64,
+    /// Propagation multiplier right after a severity-1 spike
+    pub min_propagation: f64,
+    pub duration_secs: f64,
+}
+
+impl Default for CooldownParams {
+    fn default() -> Self {
+        CooldownParams { min_severity: 0.5, max_cost: 3.0, min_propagation: 0.25, duration_secs: 24.0 * 3_600.0 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Cooldown {
+    /// >= 1
+    pub cost: f64,
+    /// In (0, 1]
+    pub propagation: f64,
+}
+
+impl Cooldown {
+    pub const NONE: Cooldown = Cooldown { cost: 1.0, propagation: 1.0 };
+
+    /// Scale ttl/fanout (rounded, never below 1 unless already 0)
+    pub fn apply(&self, p: &PropagationResult) -> PropagationResult {
+        let scale = |x: u32| if x == 0 { 0 } else { ((x as f64 * self.propagation).round() as u32).max(1) };
+        PropagationResult { ttl: scale(p.ttl), fanout: scale(p.fanout), quarantined: p.quarantined }
+    }
+}
+
+/// Cooldown multipliers `last_spike_elapsed_secs` after a spike of `spike_severity`
+pub fn multiplier(last_spike_elapsed_secs: f64, spike_severity: impl Into<Risk>, params: &CooldownParams) -> Cooldown {
+    let severity = spike_severity.into().get();
+    let min = clamp(params.min_severity, 0.0, 1.0);
+    let active = last_spike_elapsed_secs.is_finite() && last_spike_elapsed_secs < params.duration_secs;
+    if severity < min || !active { return Cooldown::NONE; }
+    let excess = if min < 1.0 { (severity - min) / (1.0 - min) } else { 1.0 };
+    let remaining = 1.0 - last_spike_elapsed_secs.max(0.0) / params.duration_secs;
+    let strength = excess * remaining;
+    Cooldown {
+        cost: 1.0 + (params.max_cost.max(1.0) - 1.0) * strength,
+        propagation: 1.0 - (1.0 - clamp(params.min_propagation, 0.0, 1.0)) * strength,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_multiplier_decays_to_one() {
+        let p = CooldownParams::default();
+        assert_eq!(multiplier(0.0, 1.0, &p), Cooldown { cost: 3.0, propagation: 0.25 });
+        assert_eq!(multiplier(12.0 * 3_600.0, 1.0, &p), Cooldown { cost: 2.0, propagation: 0.625 });
+        assert_eq!(multiplier(0.0, 0.75, &p), Cooldown { cost: 2.0, propagation: 0.625 });
+        assert_eq!(multiplier(24.0 * 3_600.0, 1.0, &p), Cooldown::NONE);
+        assert_eq!(multiplier(0.0, 0.4, &p), Cooldown::NONE);
+        assert_eq!(multiplier(f64::NAN, 1.0, &p), Cooldown::NONE);
+
+        let c = multiplier(0.0, 1.0, &p).apply(&PropagationResult { ttl: 4, fanout: 5, quarantined: false });
+        assert_eq!((c.ttl, c.fanout), (1, 1));
+    }
+}
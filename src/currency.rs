@@ -0,0 +1,93 @@
+// Pricing currencies and their conversion to SOCIAL (feature `tiers`)
+// - A price in any `Currency` is converted through a chain of oracle quotes: currency -> USD
+//   (`PriceOracle::usd_per_unit`) -> SOCIAL (`checked_usd_per_social`, with the policy's freshness, deviation and
+//   fallback rules); a missing currency quote is a hard `Rejection::Unquoted`, the fallback peg only covers SOCIAL
+// - `WithFx` adds a table of fiat quotes to any oracle
+// - Charged amounts are rounded to the currency's minimum unit (`Currency::round`); conversions of per-item anchors
+//   such as C_min are exact, so sub-cent anchors survive
+
+use crate::money::round_units;
+use crate::oracles::{checked_usd_per_social, Conversion, OracleError, PriceContext, PriceOracle, Rejection};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "UPPERCASE"))]
+pub enum Currency { #[default] Usd, Usdc, Eur, Gbp, Jpy, Inr, Brl }
+
+pub use crate::money::Rounding;
+
+impl Currency {
+    /// Decimal places of the smallest payable amount
+    pub fn decimals(self) -> u32 {
+        match self {
+            Currency::Jpy => 0,
+            Currency::Usdc => 6,
+            Currency::Usd | Currency::Eur | Currency::Gbp | Currency::Inr | Currency::Brl => 2,
+        }
+    }
+
+    pub fn min_unit(self) -> f64 { 10f64.powi(-(self.decimals() as i32)) }
+
+    /// `amount` as a whole number of minimum units (values within 1e-9 units of a whole unit snap to it, so float
+    /// noise such as `3.6000000000000005` never rounds up a cent)
+    pub fn round(self, amount: f64, rounding: Rounding) -> f64 {
+        if !amount.is_finite() { return 0.0; }
+        let scale = 10f64.powi(self.decimals() as i32);
+        round_units(amount * scale, rounding) / scale
+    }
+}
+
+/// An oracle plus fiat quotes (USD per unit of each currency); SOCIAL and USDC quotes come from `oracle`
+pub struct WithFx<'a> {
+    pub oracle: &'a dyn PriceOracle,
+    pub usd_per_unit: BTreeMap<Currency, f64>,
+}
+
+impl PriceOracle for WithFx<'_> {
+    fn usd_per_social(&self) -> Option<f64> { self.oracle.usd_per_social() }
+    fn usd_per_usdc(&self) -> Option<f64> { self.oracle.usd_per_usdc() }
+    fn updated_at(&self) -> Option<u64> { self.oracle.updated_at() }
+    fn usd_per_unit(&self, currency: Currency) -> Option<f64> {
+        self.usd_per_unit.get(&currency).copied().or_else(|| self.oracle.usd_per_unit(currency))
+    }
+}
+
+/// Convert `amount` of `currency` to SOCIAL through the currency's USD quote and the checked SOCIAL price
+pub fn convert(amount: f64, currency: Currency, px: &PriceContext) -> Result<Conversion, OracleError> {
+    let usd_per_unit = px.oracle.usd_per_unit(currency).filter(|p| p.is_finite() && *p > 0.0)
+        .ok_or(OracleError { reason: Rejection::Unquoted { currency } })?;
+    let (usd_per_social, source) = checked_usd_per_social(px.oracle, px.policy, px.now)?;
+    Ok(Conversion { social: amount * usd_per_unit / usd_per_social, usd_per_social, source })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracles::{OraclePolicy, PriceSource, StubOracle};
+
+    #[test]
+    fn t_convert_chained_and_round() {
+        let social = StubOracle { usd_per_social_px: Some(0.2) };
+        let policy = OraclePolicy::default();
+        let fx = WithFx { oracle: &social, usd_per_unit: BTreeMap::from([(Currency::Eur, 1.1), (Currency::Jpy, 0.0066)]) };
+        let px = PriceContext { oracle: &fx, policy: &policy, now: 0 };
+
+        let usd = convert(1.0, Currency::Usd, &px).unwrap();
+        assert_eq!((usd.social, usd.source), (5.0, PriceSource::Oracle));
+        assert!((convert(1.0, Currency::Eur, &px).unwrap().social - 5.5).abs() < 1e-12);
+        assert!((convert(1_000.0, Currency::Jpy, &px).unwrap().social - 33.0).abs() < 1e-9);
+        assert_eq!(convert(1.0, Currency::Usdc, &px).unwrap().social, 5.0);
+        assert_eq!(convert(1.0, Currency::Gbp, &px).unwrap_err().reason, Rejection::Unquoted { currency: Currency::Gbp });
+
+        assert_eq!(Currency::Usd.round(3.6000000000000005, Rounding::Up), 3.6);
+        assert_eq!(Currency::Usd.round(0.005, Rounding::Up), 0.01);
+        assert_eq!(Currency::Usd.round(0.019, Rounding::Down), 0.01);
+        assert_eq!(Currency::Jpy.round(2.5, Rounding::HalfEven), 2.0);
+        assert_eq!(Currency::Jpy.round(3.5, Rounding::HalfEven), 4.0);
+        assert_eq!(Currency::Usdc.min_unit(), 1e-6);
+    }
+}
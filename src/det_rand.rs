@@ -0,0 +1,125 @@
+// Deterministic randomness keyed by (epoch, content hash)
+// - `DetRng` is xoshiro256** seeded from SHA-256("slimechain/det_rand/v1" || domain || epoch || content_hash)
+// - Any node (or auditor) re-deriving a decision from the same key draws the same numbers
+// - `domain` separates independent uses (e.g. "propagation/jitter" vs "reward/round") of the same content in an epoch
+// - Not a CSPRNG for secrets: the key is public by design
+
+use crate::por::Hash32;
+use crate::propagation::FanoutWave;
+use sha2::{Digest, Sha256};
+
+/// xoshiro256** generator with a keyed seed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetRng {
+    s: [u64; 4],
+}
+
+impl DetRng {
+    /// Generator for `domain` at (`epoch`, `content_hash`)
+    pub fn new(domain: &str, epoch: u64, content_hash: &Hash32) -> Self {
+        let mut h = Sha256::new();
+        h.update(b"slimechain/det_rand/v1");
+        h.update((domain.len() as u64).to_le_bytes());
+        h.update(domain.as_bytes());
+        h.update(epoch.to_le_bytes());
+        h.update(content_hash);
+        let d: [u8; 32] = h.finalize().into();
+        let mut s = [0u64; 4];
+        for (w, c) in s.iter_mut().zip(d.chunks_exact(8)) { *w = u64::from_le_bytes(c.try_into().expect("8-byte chunk")); }
+        // the all-zero state is a fixed point; SHA-256 output reaching it is not a practical concern, but stay total
+        if s == [0; 4] { s[0] = 1; }
+        DetRng { s }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.s;
+        let out = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        out
+    }
+
+    /// Uniform in [0, 1) with 53 bits of precision
+    pub fn next_f64(&mut self) -> f64 { (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 }
+
+    /// Uniform in `0..=max` without modulo bias
+    pub fn up_to(&mut self, max: u64) -> u64 {
+        if max == u64::MAX { return self.next_u64(); }
+        let n = max + 1;
+        let zone = u64::MAX - (u64::MAX - n + 1) % n;
+        loop {
+            let x = self.next_u64();
+            if x <= zone { return x % n; }
+        }
+    }
+
+    /// Round `x` down or up with probability equal to its fractional part (expected value `x`)
+    pub fn stochastic_round(&mut self, x: f64) -> u64 {
+        if x.is_nan() || x <= 0.0 { return 0; }
+        let floor = x.floor();
+        floor as u64 + u64::from(self.next_f64() < x - floor)
+    }
+}
+
+/// Domain used by `jitter_secs` and `jitter_waves`
+pub const JITTER_DOMAIN: &str = "propagation/jitter";
+
+/// `delay_secs` plus a uniform jitter in `0..=max_jitter_secs` for (epoch, content)
+pub fn jitter_secs(delay_secs: u64, max_jitter_secs: u64, epoch: u64, content_hash: &Hash32) -> u64 {
+    delay_secs.saturating_add(DetRng::new(JITTER_DOMAIN, epoch, content_hash).up_to(max_jitter_secs))
+}
+
+/// Jitter every wave of a `propagation::schedule` result; waves stay in non-decreasing delay order
+pub fn jitter_waves(waves: &[FanoutWave], max_jitter_secs: u64, epoch: u64, content_hash: &Hash32) -> Vec<FanoutWave> {
+    let mut rng = DetRng::new(JITTER_DOMAIN, epoch, content_hash);
+    let mut prev = 0;
+    waves
+        .iter()
+        .map(|w| {
+            let delay = w.delay_secs.saturating_add(rng.up_to(max_jitter_secs)).max(prev);
+            prev = delay;
+            FanoutWave { delay_secs: delay, peers: w.peers }
+        })
+        .collect()
+}
+
+/// Stochastically round `x` for (domain, epoch, content)
+pub fn stochastic_round(x: f64, domain: &str, epoch: u64, content_hash: &Hash32) -> u64 {
+    DetRng::new(domain, epoch, content_hash).stochastic_round(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_keyed_and_reproducible() {
+        let (h1, h2) = ([1u8; 32], [2u8; 32]);
+        let draw = |d: &str, e: u64, h: &Hash32| { let mut r = DetRng::new(d, e, h); [r.next_u64(), r.next_u64()] };
+        assert_eq!(draw("a", 7, &h1), draw("a", 7, &h1));
+        assert_ne!(draw("a", 7, &h1), draw("a", 8, &h1));
+        assert_ne!(draw("a", 7, &h1), draw("a", 7, &h2));
+        assert_ne!(draw("a", 7, &h1), draw("b", 7, &h1));
+
+        let waves = [FanoutWave { delay_secs: 0, peers: 4 }, FanoutWave { delay_secs: 2, peers: 2 }, FanoutWave { delay_secs: 4, peers: 2 }];
+        let j = jitter_waves(&waves, 10, 7, &h1);
+        assert_eq!(j, jitter_waves(&waves, 10, 7, &h1));
+        assert!(j.windows(2).all(|w| w[0].delay_secs <= w[1].delay_secs));
+        assert!(j.iter().zip(&waves).all(|(a, b)| a.peers == b.peers && a.delay_secs >= b.delay_secs));
+        assert!(jitter_secs(5, 0, 7, &h1) == 5 && jitter_secs(5, 3, 7, &h1) <= 8);
+    }
+
+    #[test]
+    fn t_stochastic_round_is_unbiased() {
+        let mut rng = DetRng::new("test", 0, &[0u8; 32]);
+        let n = 100_000;
+        let sum: u64 = (0..n).map(|_| rng.stochastic_round(2.3)).sum();
+        assert!((sum as f64 / n as f64 - 2.3).abs() < 0.01);
+        assert_eq!((rng.stochastic_round(4.0), rng.stochastic_round(-1.0), rng.stochastic_round(f64::NAN)), (4, 0, 0));
+    }
+}
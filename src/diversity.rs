@@ -0,0 +1,97 @@
+// Server diversity risk from serve history (derives `RewardInput::server_cluster_risk`)
+// - Concentration: a server whose serves go to few clients is likely wash-serving; with `H` the entropy of its
+//   per-client serve shares, `exp(H)` effective clients map linearly to 0 risk at `target_clients`
+// - Overlap: servers run by one operator serve the same client set; the largest Jaccard similarity between this
+//   server's clients and any other server's clients measures that co-serving
+// - Collusion: `reward::collusion_score` over the same serves (this server's most mutually dependent client pair),
+//   weighted by `w_collusion` (0 by default)
+// - risk = clamp(w_concentration * concentration + w_overlap * overlap + w_collusion * collusion, 0, 1); a server
+//   with no serves scores 1
+
+use crate::clamp;
+use crate::reward::{collusion_score, ClientId, ServerId};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// One serve from a server to a client
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ServeEdge {
+    pub server_id: ServerId,
+    pub client_id: ClientId,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DiversityParams {
+    /// Effective clients at which concentration risk reaches 0
+    pub target_clients: f64,
+    pub w_concentration: f64,
+    pub w_overlap: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub w_collusion: f64,
+}
+
+impl Default for DiversityParams {
+    fn default() -> Self { DiversityParams { target_clients: 20.0, w_concentration: 0.5, w_overlap: 0.5, w_collusion: 0.0 } }
+}
+
+/// `server_cluster_risk` of `server_id` given the recent serves of all servers
+pub fn cluster_risk(server_id: &str, recent_serves: &[ServeEdge], params: &DiversityParams) -> f64 {
+    let mut clients: BTreeMap<&str, BTreeMap<&str, u64>> = BTreeMap::new();
+    for e in recent_serves {
+        *clients.entry(e.server_id.as_str()).or_default().entry(e.client_id.as_str()).or_insert(0) += 1;
+    }
+    let Some(own) = clients.get(server_id) else { return 1.0; };
+
+    let total = own.values().sum::<u64>() as f64;
+    let entropy: f64 = own.values().map(|&n| { let p = n as f64 / total; -p * p.ln() }).sum();
+    let concentration = if params.target_clients > 1.0 { 1.0 - clamp((entropy.exp() - 1.0) / (params.target_clients - 1.0), 0.0, 1.0) } else { 0.0 };
+
+    let own_set: BTreeSet<&str> = own.keys().copied().collect();
+    let overlap = clients.iter().filter(|(s, _)| **s != server_id).map(|(_, other)| {
+        let shared = other.keys().filter(|c| own_set.contains(*c)).count();
+        shared as f64 / (own_set.len() + other.len() - shared) as f64
+    }).fold(0.0, f64::max);
+
+    let collusion = if params.w_collusion > 0.0 {
+        let pairs: Vec<(ServerId, ClientId, u64)> = clients.iter()
+            .flat_map(|(s, cs)| cs.iter().map(move |(c, &n)| (s.to_string(), c.to_string(), n)))
+            .collect();
+        collusion_score(&pairs).into_iter().find(|(s, _)| s == server_id).map_or(0.0, |(_, x)| x)
+    } else { 0.0 };
+
+    clamp(params.w_concentration.max(0.0) * concentration + params.w_overlap.max(0.0) * overlap
+        + params.w_collusion.max(0.0) * collusion, 0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(server: &str, client: String) -> ServeEdge { ServeEdge { server_id: server.into(), client_id: client } }
+
+    #[test]
+    fn t_cluster_risk_from_co_serving() {
+        let p = DiversityParams::default();
+        // an honest server with a broad audience, and two sock-puppet servers washing the same three clients
+        let mut serves: Vec<ServeEdge> = (0..40).map(|i| edge("honest", format!("c{}", i))).collect();
+        for i in 0..30 {
+            serves.push(edge("wash1", format!("w{}", i % 3)));
+            serves.push(edge("wash2", format!("w{}", i % 3)));
+        }
+        serves.push(edge("honest", "w0".into()));
+
+        let honest = cluster_risk("honest", &serves, &p);
+        let wash = cluster_risk("wash1", &serves, &p);
+        assert!(honest < 0.05);
+        assert!((wash - (0.5 * (1.0 - 2.0 / 19.0) + 0.5)).abs() < 1e-9);
+        assert_eq!(cluster_risk("unknown", &serves, &p), 1.0);
+
+        let with = DiversityParams { w_concentration: 0.0, w_overlap: 0.0, w_collusion: 1.0, ..p };
+        assert!(cluster_risk("wash1", &serves, &with) > cluster_risk("honest", &serves, &with));
+    }
+}
@@ -0,0 +1,105 @@
+// DM spam scoring
+// - DM abuse shows up differently from public-post risk: unsolicited first contacts, link-heavy text, bursts of
+//   sends and a poor acceptance record, on top of the sender's quality
+// - score = clamp(w_first * first_contact + w_links * link_density + w_burst * burst + w_reject * rejection
+//   + w_q * (1 - q), 0, 1) with every term in 0..1
+// - Link density saturates at `link_density_sat` links per word, burst at `burst_per_hour` sends; the acceptance rate
+//   is shrunk toward `accept_prior` (weight `accept_prior_n`) so new senders are neither cleared nor condemned
+// - Used to size the DM escrow and for receiver-side filtering
+
+use crate::{clamp, Actor};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The DM being scored
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DmMeta {
+    /// No prior conversation between sender and recipient
+    pub first_contact: bool,
+    pub words: u32,
+    pub links: u32,
+}
+
+/// Sender's DM record
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DmHistory {
+    /// DMs sent in the last hour
+    pub sent_1h: u32,
+    /// First-contact DMs sent, and how many of them were accepted
+    pub first_contacts: u64,
+    pub accepted: u64,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DmSpamWeights {
+    pub w_first: f64,
+    pub w_links: f64,
+    pub w_burst: f64,
+    pub w_reject: f64,
+    pub w_q: f64,
+    pub link_density_sat: f64,
+    pub burst_per_hour: f64,
+    pub accept_prior: f64,
+    pub accept_prior_n: f64,
+}
+
+impl DmSpamWeights {
+    pub const DEFAULT: Self = Self {
+        w_first: 0.15, w_links: 0.25, w_burst: 0.25, w_reject: 0.25, w_q: 0.1,
+        link_density_sat: 0.2, burst_per_hour: 20.0, accept_prior: 0.5, accept_prior_n: 5.0,
+    };
+}
+
+impl Default for DmSpamWeights {
+    fn default() -> Self { Self::DEFAULT }
+}
+
+/// Spam score (0..1) of a DM under the default weights
+pub fn spam_score(sender: &Actor, dm_meta: &DmMeta, history: &DmHistory) -> f64 {
+    spam_score_with(sender, dm_meta, history, &DmSpamWeights::DEFAULT)
+}
+
+/// Same as `spam_score` with explicit weights
+pub fn spam_score_with(sender: &Actor, dm_meta: &DmMeta, history: &DmHistory, w: &DmSpamWeights) -> f64 {
+    let first = if dm_meta.first_contact { 1.0 } else { 0.0 };
+    let density = dm_meta.links as f64 / dm_meta.words.max(1) as f64;
+    let links = if w.link_density_sat > 0.0 { (density / w.link_density_sat).min(1.0) } else { 0.0 };
+    let burst = if w.burst_per_hour > 0.0 { (history.sent_1h as f64 / w.burst_per_hour).min(1.0) } else { 0.0 };
+    let prior_n = w.accept_prior_n.max(0.0);
+    let sent = history.first_contacts as f64;
+    let accepted = (history.accepted as f64).min(sent);
+    let accept_rate = if sent + prior_n > 0.0 { (accepted + clamp(w.accept_prior, 0.0, 1.0) * prior_n) / (sent + prior_n) } else { 1.0 };
+    let low_q = 1.0 - if sender.q.is_finite() { clamp(sender.q, 0.0, 1.0) } else { 0.0 };
+    clamp(w.w_first * first + w.w_links * links + w.w_burst * burst + w.w_reject * (1.0 - accept_rate) + w.w_q * low_q, 0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_spam_score_signals() {
+        let friend = Actor { q: 0.9, ..Default::default() };
+        let reply = DmMeta { first_contact: false, words: 40, links: 0 };
+        let regular = DmHistory { sent_1h: 2, first_contacts: 20, accepted: 18 };
+        let low = spam_score(&friend, &reply, &regular);
+        assert!(low < 0.1);
+
+        let bot = Actor { q: 0.2, ..Default::default() };
+        let pitch = DmMeta { first_contact: true, words: 10, links: 3 };
+        let blast = DmHistory { sent_1h: 60, first_contacts: 500, accepted: 5 };
+        let high = spam_score(&bot, &pitch, &blast);
+        assert!(high > 0.85);
+
+        // a brand-new sender sits at the prior acceptance rate
+        let fresh = spam_score(&friend, &reply, &DmHistory::default());
+        assert!((fresh - (0.25 * 0.5 + 0.1 * (1.0 - 0.9))).abs() < 1e-12);
+        assert!(low < fresh && fresh < high);
+    }
+}
@@ -0,0 +1,138 @@
+// DM escrow settlement (the hold priced by `tiers::dm_escrow_social`)
+// - Each outcome has its own split of the escrow: `refund` back to the sender, `receiver` to the recipient for their
+//   attention, the rest burned
+// - An ignored DM settles only once `expiry_secs` have elapsed; before that the whole escrow stays held
+// - Shares are clamped so refund + receiver never exceed the escrow, and the burn takes the remainder, so the three
+//   parts always add up to the escrow on both nodes
+// - With `unit` set, refund and receiver are paid in whole smallest units (`money::Amount`, floored); the burn takes
+//   everything else, the rounding dust and the escrow's own sub-unit remainder included
+
+use crate::clamp;
+use crate::money::{apportion, Amount, DustPolicy, Rounding};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DmOutcome {
+    /// Recipient replied or accepted the request
+    Accepted,
+    /// No response
+    Ignored,
+    ReportedSpam,
+    /// Recipient blocked the sender without reporting
+    Blocked,
+}
+
+/// Shares of the escrow for one outcome (the rest is burned)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct OutcomeSplit {
+    pub refund: f64,
+    pub receiver: f64,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EscrowPolicy {
+    /// Seconds after which an ignored DM settles
+    pub expiry_secs: u64,
+    pub accepted: OutcomeSplit,
+    pub expired: OutcomeSplit,
+    pub spam: OutcomeSplit,
+    pub blocked: OutcomeSplit,
+    /// Smallest payable amount (None = settle in unrounded SOCIAL, as before unit accounting)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unit: Option<f64>,
+}
+
+impl Default for EscrowPolicy {
+    fn default() -> Self {
+        EscrowPolicy {
+            expiry_secs: 7 * 86_400,
+            accepted: OutcomeSplit { refund: 1.0, receiver: 0.0 },
+            expired: OutcomeSplit { refund: 0.5, receiver: 0.5 },
+            spam: OutcomeSplit { refund: 0.0, receiver: 0.5 },
+            blocked: OutcomeSplit { refund: 0.25, receiver: 0.5 },
+            unit: Some(1e-6),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EscrowSettlement {
+    /// Back to the sender
+    pub refund: f64,
+    pub to_receiver: f64,
+    pub burned: f64,
+    /// Still held (ignored and not yet expired)
+    pub held: f64,
+}
+
+/// Split `escrow` for `outcome` after `elapsed_secs` since sending
+pub fn settle(escrow: f64, outcome: DmOutcome, elapsed_secs: u64, policy: &EscrowPolicy) -> EscrowSettlement {
+    let escrow = if escrow.is_finite() { escrow.max(0.0) } else { 0.0 };
+    let split = match outcome {
+        DmOutcome::Accepted => policy.accepted,
+        DmOutcome::Ignored if elapsed_secs < policy.expiry_secs => {
+            return EscrowSettlement { refund: 0.0, to_receiver: 0.0, burned: 0.0, held: escrow };
+        }
+        DmOutcome::Ignored => policy.expired,
+        DmOutcome::ReportedSpam => policy.spam,
+        DmOutcome::Blocked => policy.blocked,
+    };
+    let refund_share = clamp(split.refund, 0.0, 1.0);
+    let receiver_share = clamp(split.receiver, 0.0, 1.0 - refund_share);
+    if let Some(unit) = policy.unit {
+        let units = Amount::from_social(escrow, unit, Rounding::Down);
+        let n = units.units() as f64;
+        let shares = [n * refund_share, n * receiver_share, n * (1.0 - refund_share - receiver_share).max(0.0)];
+        let s = apportion(units, &shares, None, DustPolicy::ToPart { index: 2 });
+        let [refund, to_receiver] = [s.parts[0], s.parts[1]].map(|a| a.to_social(unit));
+        // the burned units plus what `from_social` floored away
+        return EscrowSettlement { refund, to_receiver, burned: (escrow - refund - to_receiver).max(0.0), held: 0.0 };
+    }
+    let refund = escrow * refund_share;
+    let to_receiver = escrow * receiver_share;
+    EscrowSettlement { refund, to_receiver, burned: (escrow - refund - to_receiver).max(0.0), held: 0.0 }
+}
+
+/// Amount returned to the sender (see `settle`)
+pub fn refund_amount(escrow: f64, outcome: DmOutcome, elapsed_secs: u64, policy: &EscrowPolicy) -> f64 {
+    settle(escrow, outcome, elapsed_secs, policy).refund
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_outcomes_settle_distinctly() {
+        let p = EscrowPolicy::default();
+        let day = 86_400;
+        assert_eq!(refund_amount(2.0, DmOutcome::Accepted, day, &p), 2.0);
+        assert_eq!(settle(2.0, DmOutcome::Ignored, day, &p).held, 2.0);
+        assert_eq!(settle(2.0, DmOutcome::Ignored, 7 * day, &p), EscrowSettlement { refund: 1.0, to_receiver: 1.0, burned: 0.0, held: 0.0 });
+        assert_eq!(settle(2.0, DmOutcome::ReportedSpam, 60, &p), EscrowSettlement { refund: 0.0, to_receiver: 1.0, burned: 1.0, held: 0.0 });
+        assert_eq!(settle(2.0, DmOutcome::Blocked, 60, &p), EscrowSettlement { refund: 0.5, to_receiver: 1.0, burned: 0.5, held: 0.0 });
+
+        // over-allocated shares are trimmed so the parts never exceed the escrow
+        let greedy = EscrowPolicy { spam: OutcomeSplit { refund: 0.7, receiver: 0.7 }, ..p };
+        let s = settle(2.0, DmOutcome::ReportedSpam, 0, &greedy);
+        assert!((s.refund + s.to_receiver + s.burned - 2.0).abs() < 1e-12 && s.burned < 1e-12);
+
+        // in whole cents: 5 units split 1.25 / 2.5 / 1.25, the floored dust is burned
+        let cents = EscrowPolicy { unit: Some(0.01), ..p };
+        assert_eq!(settle(0.05, DmOutcome::Blocked, 60, &cents), EscrowSettlement { refund: 0.01, to_receiver: 0.02, burned: 0.02, held: 0.0 });
+        // half a cent below one unit is burned too, so nothing is lost
+        let s = settle(0.055, DmOutcome::Blocked, 60, &cents);
+        assert_eq!((s.refund, s.to_receiver), (0.01, 0.02));
+        assert!((s.refund + s.to_receiver + s.burned - 0.055).abs() < 1e-15 && (s.burned - 0.025).abs() < 1e-15);
+    }
+}
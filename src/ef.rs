@@ -0,0 +1,79 @@
+// Adaptive EF inclusion threshold
+// - `adaptive_q_min` returns the `q_min` that keeps a target share of the population above the bar (e.g. the top 60%),
+//   so inclusion tracks the quality distribution as scoring drifts instead of a fixed 0.5
+// - The population is summarized by a `QHistogram` of equal-width bins over 0..=1; within the bin where the target
+//   is crossed, qualities are taken as evenly spread and the threshold is interpolated
+// - Recompute per epoch (or on a schedule) and write the result into `Params::q_min`
+
+use crate::{clamp, Params};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Population quality counts in equal-width bins over 0..=1 (q = 1 falls in the last bin)
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct QHistogram {
+    pub counts: Vec<u64>,
+}
+
+impl QHistogram {
+    pub fn new(bins: usize) -> Self { QHistogram { counts: vec![0; bins.max(1)] } }
+
+    pub fn from_qs(qs: &[f64], bins: usize) -> Self {
+        let mut h = QHistogram::new(bins);
+        for &q in qs { h.push(q); }
+        h
+    }
+
+    /// Count one quality (non-finite values are ignored)
+    pub fn push(&mut self, q: f64) {
+        if !q.is_finite() || self.counts.is_empty() { return; }
+        let n = self.counts.len();
+        self.counts[((clamp(q, 0.0, 1.0) * n as f64) as usize).min(n - 1)] += 1;
+    }
+
+    pub fn total(&self) -> u64 { self.counts.iter().sum() }
+}
+
+/// `q_min` including `target_inclusion_rate` (0..1) of the population; an empty histogram keeps the default `q_min`
+pub fn adaptive_q_min(population_q_histogram: &QHistogram, target_inclusion_rate: f64) -> f64 {
+    let h = population_q_histogram;
+    let total = h.total();
+    if total == 0 { return Params::default().q_min; }
+    let rate = if target_inclusion_rate.is_finite() { clamp(target_inclusion_rate, 0.0, 1.0) } else { 0.0 };
+    let wanted = rate * total as f64;
+    let width = 1.0 / h.counts.len() as f64;
+    let mut above = 0.0;
+    for (i, &n) in h.counts.iter().enumerate().rev() {
+        let n = n as f64;
+        if n > 0.0 && above + n >= wanted {
+            let hi = (i + 1) as f64 * width;
+            return clamp(hi - (wanted - above) / n * width, 0.0, 1.0);
+        }
+        above += n;
+    }
+    0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_adaptive_q_min_tracks_distribution() {
+        // uniform population: including the top 60% puts the bar at 0.4
+        let uniform: Vec<f64> = (0..1_000).map(|i| (i as f64 + 0.5) / 1_000.0).collect();
+        let h = QHistogram::from_qs(&uniform, 20);
+        assert!((adaptive_q_min(&h, 0.6) - 0.4).abs() < 1e-9);
+        assert_eq!(adaptive_q_min(&h, 1.0), 0.0);
+
+        // scoring drifts up: the same target raises the bar
+        let drifted: Vec<f64> = uniform.iter().map(|q| 0.5 + q * 0.5).collect();
+        let q_min = adaptive_q_min(&QHistogram::from_qs(&drifted, 20), 0.6);
+        assert!((q_min - 0.7).abs() < 1e-9);
+        assert_eq!(drifted.iter().filter(|&&q| q >= q_min).count(), 600);
+
+        assert_eq!(adaptive_q_min(&QHistogram::new(10), 0.6), Params::default().q_min);
+    }
+}
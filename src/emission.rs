@@ -0,0 +1,134 @@
+// Long-horizon serve-reward emission schedule
+// - `epoch_budget` is the total serve-reward pool of one epoch, the budget `reward::epoch::distribute` splits
+//   (`reward::epoch::distribute_scheduled` does both)
+// - Schedules: constant, exponential decay per epoch, or halving every `interval_epochs`; `tail` is a floor on the
+//   per-epoch emission so serving stays paid after the schedule has decayed
+// - `emitted` is the cumulative emission of the first n epochs in closed form (per halving era), for supply projections
+// - `epoch_budget` raises the decay to the epoch index by explicit square-and-multiply (`pow_u64`): a fixed sequence of
+//   IEEE multiplications, unlike `powi`, whose evaluation is left to the compiler and platform; halving powers of two are
+//   exact. `emitted` is a projection and uses `ln` for the exponential tail, so it may differ in the last digits
+
+use crate::clamp;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum EmissionSchedule {
+    Constant,
+    /// Budget shrinks by `decay_per_epoch` (0..1) each epoch
+    Exponential { decay_per_epoch: f64 },
+    /// Budget halves every `interval_epochs` epochs
+    Halving { interval_epochs: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EmissionParams {
+    /// Budget of epoch 0 (SOCIAL)
+    pub initial_budget: f64,
+    pub schedule: EmissionSchedule,
+    /// Minimum budget per epoch (0 = none)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tail: f64,
+}
+
+impl Default for EmissionParams {
+    fn default() -> Self { EmissionParams { initial_budget: 10_000.0, schedule: EmissionSchedule::Halving { interval_epochs: 8_760 }, tail: 0.0 } }
+}
+
+fn nonneg(x: f64) -> f64 { if x.is_finite() { x.max(0.0) } else { 0.0 } }
+
+/// `base^exp` by square-and-multiply over the bits of `exp`, least significant first
+fn pow_u64(base: f64, mut exp: u64) -> f64 {
+    let (mut acc, mut sq) = (1.0, base);
+    while exp > 0 {
+        if exp & 1 == 1 { acc *= sq; }
+        exp >>= 1;
+        if exp > 0 { sq *= sq; }
+    }
+    acc
+}
+
+/// Budget before the tail floor
+fn raw_budget(epoch_index: u64, p: &EmissionParams) -> f64 {
+    let b0 = nonneg(p.initial_budget);
+    match p.schedule {
+        EmissionSchedule::Constant => b0,
+        EmissionSchedule::Exponential { decay_per_epoch } => {
+            let r = 1.0 - clamp(nonneg(decay_per_epoch), 0.0, 1.0);
+            b0 * pow_u64(r, epoch_index)
+        }
+        EmissionSchedule::Halving { interval_epochs } => {
+            b0 * pow_u64(0.5, epoch_index / interval_epochs.max(1))
+        }
+    }
+}
+
+/// Serve-reward pool of epoch `epoch_index` (0-based)
+pub fn epoch_budget(epoch_index: u64, params: &EmissionParams) -> f64 {
+    raw_budget(epoch_index, params).max(nonneg(params.tail))
+}
+
+/// Total emitted over epochs `0..epochs`
+pub fn emitted(epochs: u64, params: &EmissionParams) -> f64 {
+    let tail = nonneg(params.tail);
+    let b0 = nonneg(params.initial_budget);
+    if b0 <= tail { return tail * epochs as f64; }
+    match params.schedule {
+        EmissionSchedule::Constant => b0 * epochs as f64,
+        EmissionSchedule::Exponential { decay_per_epoch } => {
+            let r = 1.0 - clamp(nonneg(decay_per_epoch), 0.0, 1.0);
+            // epochs before the tail takes over
+            let mut m = epochs;
+            if tail > 0.0 && r < 1.0 {
+                let k = if r == 0.0 { 1 } else { ((tail / b0).ln() / r.ln()).ceil().max(0.0) as u64 };
+                m = m.min(k);
+            }
+            let head = if r == 1.0 { b0 * m as f64 } else { b0 * (1.0 - pow_u64(r, m)) / (1.0 - r) };
+            head + tail * (epochs - m) as f64
+        }
+        EmissionSchedule::Halving { interval_epochs } => {
+            let interval = interval_epochs.max(1);
+            let mut total = 0.0;
+            let mut start = 0;
+            while start < epochs {
+                let b = epoch_budget(start, params);
+                if b <= tail || b == 0.0 { return total + tail * (epochs - start) as f64; }
+                let len = interval.min(epochs - start);
+                total += b * len as f64;
+                start += len;
+            }
+            total
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_schedules_and_cumulative() {
+        let halving = EmissionParams { initial_budget: 100.0, schedule: EmissionSchedule::Halving { interval_epochs: 10 }, tail: 0.0 };
+        assert_eq!((epoch_budget(0, &halving), epoch_budget(9, &halving), epoch_budget(10, &halving)), (100.0, 100.0, 50.0));
+        assert_eq!(epoch_budget(u64::MAX, &halving), 0.0);
+        assert_eq!((pow_u64(0.5, 1_074), pow_u64(0.9, 0), pow_u64(0.9, 3)), (f64::from_bits(1), 1.0, 0.9 * (0.9 * 0.9)));
+        assert_eq!(emitted(25, &halving), 1_000.0 + 500.0 + 5.0 * 25.0);
+        // the supply converges to 2 * interval * initial
+        assert!((emitted(10_000, &halving) - 2_000.0).abs() < 1e-9);
+
+        let decay = EmissionParams { schedule: EmissionSchedule::Exponential { decay_per_epoch: 0.1 }, tail: 20.0, ..halving.clone() };
+        assert!((epoch_budget(2, &decay) - 81.0).abs() < 1e-9);
+        assert_eq!(epoch_budget(100, &decay), 20.0);
+        let summed: f64 = (0..40).map(|i| epoch_budget(i, &decay)).sum();
+        assert!((emitted(40, &decay) - summed).abs() < 1e-9);
+
+        let tailed = EmissionParams { tail: 30.0, ..halving };
+        let summed: f64 = (0..45).map(|i| epoch_budget(i, &tailed)).sum();
+        assert_eq!(emitted(45, &tailed), summed);
+    }
+}
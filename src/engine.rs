@@ -0,0 +1,110 @@
+// Pluggable pricing engine
+// - `PricingEngine` covers the per-post and per-serve decisions a node makes (cost, propagation, rewards, base fare)
+// - `ReferenceEngine` is the crate's own functions under a fixed `PreparedParams`
+// - Node software holds a `PricingEngine` (generic or `dyn`) so it can swap in an experimental engine, or wrap the
+//   reference with caching/telemetry, without touching its call sites
+// - Methods take `&self`; wrappers needing state use interior mutability
+
+use crate::{
+    adjust_propagation_for_content, calculate_availability_reward, calculate_ef, calculate_post_cost, calculate_quality,
+    calculate_serve_reward, update_base_cost, Actor, Content, Params, PreparedParams, PropagationResult, QInputs, RewardInput,
+};
+
+/// Cost, propagation and reward decisions behind one interface
+pub trait PricingEngine {
+    /// Parameters the engine currently prices with
+    fn params(&self) -> &Params;
+    fn quality(&self, inp: QInputs) -> f64;
+    fn ef(&self, followers_q: &[f64]) -> f64;
+    fn post_cost(&self, actor: &Actor, content: &Content, base_fare: f64) -> f64;
+    fn propagation(&self, content: &Content) -> PropagationResult;
+    fn serve_reward(&self, input: &RewardInput) -> f64;
+    fn availability_reward(&self, uptime_ratio: f64, probes_passed: u32, stake: f64) -> f64;
+    fn base_fare(&self, current_base: f64, current_load: f64) -> f64;
+}
+
+/// The crate's free functions as a `PricingEngine`
+#[derive(Debug, Clone)]
+pub struct ReferenceEngine {
+    params: PreparedParams,
+}
+
+impl ReferenceEngine {
+    pub fn new(params: Params) -> Self { ReferenceEngine { params: params.into() } }
+
+    pub fn into_params(self) -> Params { self.params.into_inner() }
+}
+
+impl Default for ReferenceEngine {
+    fn default() -> Self { ReferenceEngine::new(Params::default()) }
+}
+
+impl From<Params> for ReferenceEngine {
+    fn from(params: Params) -> Self { ReferenceEngine::new(params) }
+}
+
+impl PricingEngine for ReferenceEngine {
+    fn params(&self) -> &Params { &self.params }
+    fn quality(&self, inp: QInputs) -> f64 { calculate_quality(inp, &self.params) }
+    fn ef(&self, followers_q: &[f64]) -> f64 { calculate_ef(followers_q, &self.params) }
+    fn post_cost(&self, actor: &Actor, content: &Content, base_fare: f64) -> f64 { calculate_post_cost(actor, content, &self.params, base_fare) }
+    fn propagation(&self, content: &Content) -> PropagationResult { adjust_propagation_for_content(content, &self.params) }
+    fn serve_reward(&self, input: &RewardInput) -> f64 { calculate_serve_reward(input, &self.params) }
+    fn availability_reward(&self, uptime_ratio: f64, probes_passed: u32, stake: f64) -> f64 {
+        calculate_availability_reward(uptime_ratio, probes_passed, stake, &self.params)
+    }
+    fn base_fare(&self, current_base: f64, current_load: f64) -> f64 { update_base_cost(current_base, current_load, &self.params) }
+}
+
+impl<E: PricingEngine + ?Sized> PricingEngine for Box<E> {
+    fn params(&self) -> &Params { (**self).params() }
+    fn quality(&self, inp: QInputs) -> f64 { (**self).quality(inp) }
+    fn ef(&self, followers_q: &[f64]) -> f64 { (**self).ef(followers_q) }
+    fn post_cost(&self, actor: &Actor, content: &Content, base_fare: f64) -> f64 { (**self).post_cost(actor, content, base_fare) }
+    fn propagation(&self, content: &Content) -> PropagationResult { (**self).propagation(content) }
+    fn serve_reward(&self, input: &RewardInput) -> f64 { (**self).serve_reward(input) }
+    fn availability_reward(&self, uptime_ratio: f64, probes_passed: u32, stake: f64) -> f64 {
+        (**self).availability_reward(uptime_ratio, probes_passed, stake)
+    }
+    fn base_fare(&self, current_base: f64, current_load: f64) -> f64 { (**self).base_fare(current_base, current_load) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    /// Telemetry-style wrapper: counts cost calls, delegates everything
+    struct Counting<E> { inner: E, costs: Cell<u32> }
+
+    impl<E: PricingEngine> PricingEngine for Counting<E> {
+        fn params(&self) -> &Params { self.inner.params() }
+        fn quality(&self, inp: QInputs) -> f64 { self.inner.quality(inp) }
+        fn ef(&self, followers_q: &[f64]) -> f64 { self.inner.ef(followers_q) }
+        fn post_cost(&self, actor: &Actor, content: &Content, base_fare: f64) -> f64 {
+            self.costs.set(self.costs.get() + 1);
+            self.inner.post_cost(actor, content, base_fare)
+        }
+        fn propagation(&self, content: &Content) -> PropagationResult { self.inner.propagation(content) }
+        fn serve_reward(&self, input: &RewardInput) -> f64 { self.inner.serve_reward(input) }
+        fn availability_reward(&self, uptime_ratio: f64, probes_passed: u32, stake: f64) -> f64 {
+            self.inner.availability_reward(uptime_ratio, probes_passed, stake)
+        }
+        fn base_fare(&self, current_base: f64, current_load: f64) -> f64 { self.inner.base_fare(current_base, current_load) }
+    }
+
+    #[test]
+    fn t_reference_matches_free_functions_behind_dyn() {
+        let p = Params::default();
+        let engine: Box<dyn PricingEngine> = Box::new(Counting { inner: ReferenceEngine::new(p.clone()), costs: Cell::new(0) });
+        let actor = Actor { rl: 12.0, q: 0.7, ef: 20.0, posts_1h: Some(3.0), ..Default::default() };
+        let content = Content { is_claim: Some(true), ..Default::default() };
+        assert_eq!(engine.post_cost(&actor, &content, 1.5), calculate_post_cost(&actor, &content, &p, 1.5));
+        let (a, b) = (engine.propagation(&content), adjust_propagation_for_content(&content, &p));
+        assert_eq!((a.ttl, a.fanout), (b.ttl, b.fanout));
+        let r = RewardInput { ticket_budget: 5.0, client_q: 0.9, size_bytes: 250_000, ttfb_ms: 120, ..Default::default() };
+        assert_eq!(engine.serve_reward(&r), calculate_serve_reward(&r, &p));
+        assert_eq!(engine.base_fare(1.0, 2.0), update_base_cost(1.0, 2.0, &p));
+        assert_eq!(engine.params().q_min, p.q_min);
+    }
+}
@@ -0,0 +1,145 @@
+// Epoch rollover: how params, base fare and running statistics carry from one epoch to the next
+// - `Epoch::advance` folds the closing epoch's load samples into the base fare under the *closing* params, then
+//   activates `next_params` (if any) and clamps the carried fare into the new `[base_min, base_max]`
+// - The controller follows `params.congestion.controller`: EMA-smoothed exponential steps or the PID controller
+// - Pure function of (epoch, inputs): every node advancing from the same state with the same inputs gets the same epoch
+// - `next_params` is taken as-is; check it with `Params::validate` before scheduling it
+
+use crate::congestion::{hold_in_window, update_base_cost_controlled, update_base_cost_smoothed, CongestionState, PidState};
+use crate::metrics::{Metric, MetricsSink};
+use crate::{clamp, CongestionController, Params};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Load and reward totals over one or more epochs
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EpochTotals {
+    pub epochs: u64,
+    pub load_samples: u64,
+    pub load_sum: f64,
+    pub load_max: f64,
+    pub posts: u64,
+    pub rewards_paid: f64,
+}
+
+impl EpochTotals {
+    pub fn mean_load(&self) -> f64 { if self.load_samples == 0 { 0.0 } else { self.load_sum / self.load_samples as f64 } }
+
+    fn add(&mut self, other: &EpochTotals) {
+        self.epochs += other.epochs;
+        self.load_samples += other.load_samples;
+        self.load_sum += other.load_sum;
+        self.load_max = self.load_max.max(other.load_max);
+        self.posts += other.posts;
+        self.rewards_paid += other.rewards_paid;
+    }
+}
+
+/// What happened during the closing epoch
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EpochInputs {
+    /// Load samples in observation order (non-finite samples are skipped)
+    pub load_samples: Vec<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub posts: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rewards_paid: f64,
+    /// Params active from the next epoch on (`None` keeps the current ones)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub next_params: Option<Params>,
+}
+
+/// Active params, base fare controller state and statistics for one epoch
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Epoch {
+    pub number: u64,
+    pub params: Params,
+    /// Base fare (`congestion.base`) plus load EMA and surge window
+    pub congestion: CongestionState,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pid: PidState,
+    /// Statistics of the epoch that closed into this one (empty for epoch 0)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub last: EpochTotals,
+    /// Statistics of every closed epoch
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub totals: EpochTotals,
+}
+
+impl Epoch {
+    /// Epoch 0 with the base fare at `initial_base` (clamped to the params' bounds)
+    pub fn genesis(params: Params, initial_base: f64) -> Self {
+        let base = clamp(initial_base, params.congestion.base_min, params.congestion.base_max);
+        Epoch { number: 0, params, congestion: CongestionState::new(base), pid: PidState::default(), last: EpochTotals::default(), totals: EpochTotals::default() }
+    }
+
+    /// Current base fare
+    pub fn base_fare(&self) -> f64 { self.congestion.base }
+
+    /// Close this epoch with `inputs` and return the next one
+    pub fn advance(&self, inputs: EpochInputs) -> Epoch { self.advance_observed(inputs, &()) }
+
+    /// `advance`, reporting the new base fare (and whether it sits at a bound) to `sink`
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", target = "slimechain::epoch", skip_all, fields(epoch = self.number)))]
+    pub fn advance_observed(&self, inputs: EpochInputs, sink: &dyn MetricsSink) -> Epoch {
+        let mut congestion = self.congestion.clone();
+        let mut pid = self.pid.clone();
+        let mut last = EpochTotals { epochs: 1, posts: inputs.posts, rewards_paid: inputs.rewards_paid.max(0.0), ..Default::default() };
+        for &load in inputs.load_samples.iter().filter(|l| l.is_finite()) {
+            let load = load.max(0.0);
+            match self.params.congestion.controller {
+                CongestionController::Exponential => { update_base_cost_smoothed(&mut congestion, load, &self.params); }
+                CongestionController::Pid { .. } => {
+                    let next = update_base_cost_controlled(congestion.base, load, &mut pid, &self.params);
+                    congestion.base = hold_in_window(congestion.base, next, &mut congestion.window, &self.params.congestion);
+                }
+            }
+            last.load_samples += 1;
+            last.load_sum += load;
+            last.load_max = last.load_max.max(load);
+        }
+
+        let params = inputs.next_params.unwrap_or_else(|| self.params.clone());
+        congestion.base = clamp(congestion.base, params.congestion.base_min, params.congestion.base_max);
+        sink.observe(Metric::BaseFare(congestion.base));
+        if congestion.base <= params.congestion.base_min || congestion.base >= params.congestion.base_max {
+            sink.observe(Metric::BaseFareClamped);
+        }
+        let mut totals = self.totals.clone();
+        totals.add(&last);
+        Epoch { number: self.number + 1, params, congestion, pid, last, totals }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::update_base_cost;
+
+    #[test]
+    fn t_advance_carries_fare_and_params() {
+        let mut params = Params::default();
+        params.congestion.load_ema_alpha = 1.0;
+        let e0 = Epoch::genesis(params.clone(), 1.0);
+        let inputs = EpochInputs { load_samples: vec![2000.0, f64::NAN, 2000.0], posts: 40, rewards_paid: 12.5, next_params: None };
+        let e1 = e0.advance(inputs.clone());
+        let want = update_base_cost(update_base_cost(1.0, 2000.0, &params), 2000.0, &params);
+        assert_eq!((e1.number, e1.base_fare()), (1, want));
+        assert_eq!(e1.last.load_samples, 2);
+        assert_eq!(e1.advance(inputs.clone()).totals.posts, 80);
+        #[cfg(feature = "serde")]
+        assert_eq!(serde_json::to_string(&e0.advance(inputs)).unwrap(), serde_json::to_string(&e1).unwrap());
+
+        let mut next = params.clone();
+        next.congestion.base_max = 1.0;
+        let e2 = e1.advance(EpochInputs { next_params: Some(next), ..Default::default() });
+        assert_eq!((e2.base_fare(), e2.params.congestion.base_max), (1.0, 1.0));
+        assert_eq!(e2.last.mean_load(), 0.0);
+    }
+}
@@ -0,0 +1,136 @@
+// A/B experiment bucketing (feature `serde`)
+// - An actor's bucket is a point in [0, 1) from SHA-256("slimechain/experiments/v1" || experiment id || actor id hash),
+//   so every node assigns the same actor to the same variant without coordination, and distinct experiments bucket
+//   independently
+// - Variants own consecutive slices of [0, 1) in proportion to their weights (in declaration order); reordering or
+//   reweighting variants reshuffles actors, so change the experiment id when doing so
+// - Each variant carries a `ParamsDelta`; `Experiment::params_for` applies it to the operator's params, and `expose`
+//   records which variant an actor was served under which epoch, for auditing
+// - An experiment without variants or with no positive weight is invalid (`Experiment::validate`); bucketing it is an
+//   error rather than a panic or an arbitrary pick
+
+use crate::governance::ParamsDelta;
+use crate::por::Hash32;
+use crate::{ParamError, Params};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Variant {
+    pub name: String,
+    /// Relative share of actors (>= 0)
+    pub weight: f64,
+    /// Overrides applied for actors in this variant (empty for control)
+    #[serde(default)]
+    pub delta: ParamsDelta,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Experiment {
+    pub id: String,
+    pub variants: Vec<Variant>,
+}
+
+/// Record of an actor being served a variant
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Exposure {
+    pub experiment_id: String,
+    pub variant: String,
+    pub actor_id_hash: Hash32,
+    pub epoch: u64,
+    /// The actor's point in [0, 1), so auditors can re-check the assignment
+    pub point: f64,
+}
+
+/// The actor's point in [0, 1) for `experiment_id`
+pub fn bucket_point(actor_id_hash: &Hash32, experiment_id: &str) -> f64 {
+    let mut h = Sha256::new();
+    h.update(b"slimechain/experiments/v1");
+    h.update((experiment_id.len() as u64).to_le_bytes());
+    h.update(experiment_id.as_bytes());
+    h.update(actor_id_hash);
+    let d: [u8; 32] = h.finalize().into();
+    let x = u64::from_le_bytes(d[..8].try_into().expect("8-byte prefix"));
+    (x >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// The variant an actor is assigned to (errors on an invalid experiment)
+pub fn bucket(actor_id_hash: &Hash32, experiment: &Experiment) -> Result<Variant, ParamError> {
+    Ok(experiment.variants[index(bucket_point(actor_id_hash, &experiment.id), experiment)?].clone())
+}
+
+fn index(point: f64, experiment: &Experiment) -> Result<usize, ParamError> {
+    experiment.validate()?;
+    let total: f64 = experiment.variants.iter().map(|v| v.weight).sum();
+    let mut acc = 0.0;
+    for (i, v) in experiment.variants.iter().enumerate() {
+        acc += v.weight;
+        if point * total < acc { return Ok(i); }
+    }
+    // rounding at the top of [0, 1): the last variant with a positive weight
+    Ok(experiment.variants.iter().rposition(|v| v.weight > 0.0).unwrap_or(0))
+}
+
+impl Experiment {
+    /// At least one variant, weights finite and >= 0, and not all zero
+    pub fn validate(&self) -> Result<(), ParamError> {
+        let err = |path: String, reason: &str| Err(ParamError { path, reason: reason.into() });
+        if self.variants.is_empty() { return err("variants".into(), "must not be empty"); }
+        if let Some(i) = self.variants.iter().position(|v| !(v.weight.is_finite() && v.weight >= 0.0)) {
+            return err(format!("variants[{}].weight", i), "must be finite and >= 0");
+        }
+        if self.variants.iter().all(|v| v.weight == 0.0) { return err("variants".into(), "weights must not all be zero"); }
+        Ok(())
+    }
+
+    /// Exposure record for an actor served in `epoch`
+    pub fn expose(&self, actor_id_hash: &Hash32, epoch: u64) -> Result<Exposure, ParamError> {
+        let point = bucket_point(actor_id_hash, &self.id);
+        let variant = self.variants[index(point, self)?].name.clone();
+        Ok(Exposure { experiment_id: self.id.clone(), variant, actor_id_hash: *actor_id_hash, epoch, point })
+    }
+
+    /// Params an actor runs under: `base` with the actor's variant delta applied (validated)
+    pub fn params_for(&self, actor_id_hash: &Hash32, base: &Params) -> Result<Params, Vec<ParamError>> {
+        base.with_delta(&bucket(actor_id_hash, self).map_err(|e| vec![e])?.delta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_bucket_deterministic_and_weighted() {
+        let exp = Experiment {
+            id: "cheaper-posting-2026q4".into(),
+            variants: vec![
+                Variant { name: "control".into(), weight: 3.0, delta: ParamsDelta::new() },
+                Variant { name: "cheap".into(), weight: 1.0, delta: ParamsDelta::from([("cost.alpha".to_string(), 0.5)]) },
+            ],
+        };
+        let actors: Vec<Hash32> = (0..4000u32).map(|i| Sha256::digest(i.to_le_bytes()).into()).collect();
+        let cheap = actors.iter().filter(|a| bucket(a, &exp).unwrap().name == "cheap").count();
+        assert!((900..1100).contains(&cheap), "{cheap}");
+        assert_eq!(bucket(&actors[7], &exp), bucket(&actors[7], &exp.clone()));
+        assert!(exp.validate().is_ok());
+
+        let a = actors.iter().find(|a| bucket(a, &exp).unwrap().name == "cheap").unwrap();
+        let e = exp.expose(a, 12).unwrap();
+        assert_eq!((e.variant.as_str(), e.epoch), ("cheap", 12));
+        assert!(e.point >= 0.75);
+        assert_eq!(exp.params_for(a, &Params::default()).unwrap().cost.alpha, 0.5);
+
+        // no variants or no positive weight is an error, not a panic
+        let empty = Experiment { id: "empty".into(), variants: vec![] };
+        assert_eq!(bucket(a, &empty).unwrap_err().path, "variants");
+        let mut zero = exp.clone();
+        zero.variants.iter_mut().for_each(|v| v.weight = 0.0);
+        assert_eq!(zero.expose(a, 1).unwrap_err().reason, "weights must not all be zero");
+        zero.variants[1].weight = f64::NAN;
+        assert_eq!(zero.params_for(a, &Params::default()).unwrap_err()[0].path, "variants[1].weight");
+    }
+}
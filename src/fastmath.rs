@@ -0,0 +1,143 @@
+// Polynomial approximations of `exp`, `ln_1p` and `powf` with a documented error bound
+// - With the `fast-math` feature the core functions call these instead of the libm versions
+// - Bound: relative error <= `MAX_REL_ERROR` for finite results, given `|y * ln(x)| <= 700` in `powf`
+//   (exp/ln themselves stay within ~1e-11; `powf` amplifies the ln error by `|y * ln(x)|`)
+// - Results are NOT bit-identical to libm: golden vectors and cross-node consensus need the default build
+// - NaN/infinity/zero/negative inputs follow the libm conventions; subnormal ranges fall back to libm
+
+/// Documented maximum relative error of every approximation in this module
+pub const MAX_REL_ERROR: f64 = 1e-6;
+
+/// ln(2) split so that `k * LN2_HI` is exact for |k| < 2^11 (fdlibm constants)
+const LN2_HI: f64 = f64::from_bits(0x3fe6_2e42_fee0_0000);
+const LN2_LO: f64 = f64::from_bits(0x3dea_39ef_3579_3c76);
+
+/// Adding and subtracting 1.5 * 2^52 rounds to the nearest integer without a libm call
+const ROUND: f64 = 6_755_399_441_055_744.0;
+
+/// Table resolution: 2^(j/N) for exp and 1/c_j, ln(c_j) at mantissa bucket centers for ln
+const N: usize = 64;
+
+struct Tables {
+    exp2: [f64; N],
+    inv: [f64; N],
+    ln: [f64; N],
+}
+
+/// Built once from libm on first use
+fn tables() -> &'static Tables {
+    static TABLES: std::sync::OnceLock<Tables> = std::sync::OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut t = Tables { exp2: [0.0; N], inv: [0.0; N], ln: [0.0; N] };
+        for j in 0..N {
+            t.exp2[j] = (j as f64 / N as f64).exp2();
+            let c = 1.0 + (j as f64 + 0.5) / N as f64;
+            t.inv[j] = 1.0 / c;
+            t.ln[j] = (1.0 / t.inv[j]).ln();
+        }
+        t
+    })
+}
+
+/// `e^x` via `x = (k + j/N)*ln2 + r` (|r| <= ln2/2N), a table lookup for `2^(j/N)` and a quartic in `r`
+/// (truncation < 1e-13)
+pub fn exp(x: f64) -> f64 {
+    if x.is_nan() { return x; }
+    if !(-708.0..=709.0).contains(&x) { return x.exp(); }
+    let kn = (x * (N as f64 * std::f64::consts::LOG2_E) + ROUND) - ROUND;
+    let r = (x - kn * (LN2_HI / N as f64)) - kn * (LN2_LO / N as f64);
+    let kn = kn as i64;
+    let (k, j) = (kn.div_euclid(N as i64), kn.rem_euclid(N as i64) as usize);
+    let p = (1.0 + r) + r * r * (0.5 + r * (1.0 / 6.0 + r * (1.0 / 24.0)));
+    p * tables().exp2[j] * f64::from_bits(((k + 1023) as u64) << 52)
+}
+
+/// `ln(x)` via `x = m * 2^e`, `m = c_j * (1 + r)` with a table for `ln(c_j)` (|r| < 1/2N) and a quartic in `r`
+/// (absolute truncation < 6e-12, relative once |ln x| > 1/2N)
+fn ln(x: f64) -> f64 {
+    if x.is_nan() || x.is_infinite() || x < f64::MIN_POSITIVE { return x.ln(); }
+    let d = x - 1.0;
+    if d.abs() < 1.0 / N as f64 {
+        // near 1 the table's absolute error would dominate; the plain series keeps the result relative (|d|^8/8 < 1e-15)
+        let d2 = d * d;
+        let odd = d * (1.0 + d2 * (1.0 / 3.0 + d2 * (1.0 / 5.0 + d2 * (1.0 / 7.0))));
+        let even = d2 * (0.5 + d2 * (0.25 + d2 * (1.0 / 6.0)));
+        return odd - even;
+    }
+    let bits = x.to_bits();
+    let e = ((bits >> 52) & 0x7ff) as i64 - 1023;
+    let m = f64::from_bits((bits & 0x000f_ffff_ffff_ffff) | 0x3ff0_0000_0000_0000);
+    let j = ((bits >> (52 - N.trailing_zeros())) as usize) & (N - 1);
+    let t = tables();
+    let r = m * t.inv[j] - 1.0;
+    let r2 = r * r;
+    let p = r - r2 * (0.5 - r * (1.0 / 3.0)) - r2 * r2 * 0.25;
+    let e = e as f64;
+    (e * LN2_HI + t.ln[j] + p) + e * LN2_LO
+}
+
+/// `ln(1 + x)`, keeping full relative accuracy for tiny `x`
+pub fn ln_1p(x: f64) -> f64 {
+    let u = 1.0 + x;
+    if u == 1.0 { return x; }
+    if u.is_infinite() || u <= 0.0 || x.is_nan() { return x.ln_1p(); }
+    // `u - 1` is exact, so the ratio cancels the rounding of `1 + x`
+    ln(u) * (x / (u - 1.0))
+}
+
+/// `x^y` as `exp(y * ln x)` for `x > 0`; small integer exponents use `powi`; other cases defer to libm
+pub fn powf(x: f64, y: f64) -> f64 {
+    if y == y.trunc() && y.abs() <= 64.0 {
+        x.powi(y as i32)
+    } else if x > 0.0 && x.is_finite() && y.is_finite() && x >= f64::MIN_POSITIVE {
+        exp(y * ln(x))
+    } else {
+        x.powf(y)
+    }
+}
+
+// -------- Dispatch used by the core functions --------
+
+#[cfg(feature = "fast-math")]
+pub(crate) use self::{exp as exp_sel, ln_1p as ln_1p_sel, powf as powf_sel};
+
+#[cfg(not(feature = "fast-math"))]
+pub(crate) fn exp_sel(x: f64) -> f64 { x.exp() }
+#[cfg(not(feature = "fast-math"))]
+pub(crate) fn ln_1p_sel(x: f64) -> f64 { x.ln_1p() }
+#[cfg(not(feature = "fast-math"))]
+pub(crate) fn powf_sel(x: f64, y: f64) -> f64 { x.powf(y) }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rel(a: f64, b: f64) -> f64 { if a == b { 0.0 } else { ((a - b) / b).abs() } }
+
+    /// Deterministic log-spaced sample of (0, 1e12]
+    fn samples() -> impl Iterator<Item = f64> { (-2000..=1200).map(|i| 10f64.powf(i as f64 / 100.0) * 1.000_123) }
+
+    #[test]
+    fn t_error_within_bound() {
+        let mut worst = 0.0f64;
+        for x in samples() {
+            worst = worst.max(rel(ln_1p(x), x.ln_1p()));
+            worst = worst.max(rel(exp(x.ln().clamp(-700.0, 700.0)), x.ln().clamp(-700.0, 700.0).exp()));
+            for y in [0.1, 0.5, 0.9, 1.0, 1.3, 2.0, 3.7] {
+                if (y * x.ln()).abs() <= 700.0 { worst = worst.max(rel(powf(x, y), x.powf(y))); }
+            }
+        }
+        for x in [-0.999_999, -0.5, -1e-9, -1e-300, 0.0, 1e-300, 1e-17] {
+            worst = worst.max(rel(ln_1p(x), x.ln_1p()));
+        }
+        assert!(worst <= MAX_REL_ERROR, "worst relative error {}", worst);
+    }
+
+    #[test]
+    fn t_special_values() {
+        assert!(exp(f64::NAN).is_nan() && ln_1p(f64::NAN).is_nan() && powf(f64::NAN, 2.0).is_nan());
+        assert_eq!((exp(800.0), exp(-800.0)), (f64::INFINITY, 0.0));
+        assert_eq!((ln_1p(-1.0), powf(0.0, 0.5), powf(4.0, 0.0)), (f64::NEG_INFINITY, 0.0, 1.0));
+        assert!(ln_1p(-2.0).is_nan());
+    }
+}
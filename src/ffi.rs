@@ -0,0 +1,272 @@
+// C ABI (feature `capi`); header: include/slimechain_algo.h (generated from this file by build.rs via cbindgen;
+// `t_header_up_to_date` fails when it is stale, `SLIMECHAIN_UPDATE_HEADER=1 cargo build --features capi` rewrites it)
+// - Flat structs for the hot paths; `slime_eval_json` for everything else
+// - Optional values in flat structs use a negative sentinel (tri-state flags use -1 for "unknown")
+// - Null pointers yield NaN (or an error document for the JSON entry point); nothing panics across the boundary
+
+use crate::{
+    Actor, Content, ContentTopic, Params, PropagationResult, QInputs, RewardInput, RiskSignals, RiskWeights,
+};
+use std::ffi::{c_char, CStr, CString};
+
+/// Opaque parameter bundle (create with `slime_params_default`/`slime_params_from_json`)
+pub struct SlimeParams(Params);
+
+#[repr(C)]
+pub struct SlimeActor {
+    pub rl: f64,
+    pub q: f64,
+    pub ef: f64,
+    /// Posts in the last hour; negative = unknown
+    pub posts_1h: f64,
+}
+
+/// Risk signals in [0, 1]; negative = missing
+#[repr(C)]
+pub struct SlimeRiskSignals {
+    pub coordination: f64,
+    pub clustering: f64,
+    pub burst: f64,
+    pub monotonicity: f64,
+    pub abuse_history: f64,
+    /// Near-duplicate share among recent posts
+    pub duplication: f64,
+}
+
+#[repr(C)]
+pub struct SlimeContent {
+    /// 1 = true, 0 = false, -1 = unknown
+    pub is_claim: i8,
+    /// 1 = true, 0 = false, -1 = unknown
+    pub has_evidence: i8,
+    /// 0 general, 1 news, 2 finance, 3 health, 4 elections; -1 = none
+    pub topic: i32,
+    pub risk_signals: SlimeRiskSignals,
+}
+
+#[repr(C)]
+pub struct SlimeQInputs { pub a: f64, pub r: f64, pub t: f64, pub d: f64, pub h: f64, pub s: f64 }
+
+#[repr(C)]
+pub struct SlimeRewardInput {
+    pub ticket_budget: f64,
+    pub client_q: f64,
+    pub size_bytes: u64,
+    pub ttfb_ms: u32,
+    pub server_cluster_risk: f64,
+    /// Negative = none
+    pub prev_serves: i64,
+    /// Negative = none
+    pub ttfb_percentile: f64,
+}
+
+#[repr(C)]
+pub struct SlimePropagation { pub ttl: u32, pub fanout: u32, pub quarantined: bool }
+
+fn opt(x: f64) -> Option<f64> { if x < 0.0 || x.is_nan() { None } else { Some(x) } }
+
+fn flag(x: i8) -> Option<bool> { if x < 0 { None } else { Some(x != 0) } }
+
+impl From<&SlimeRiskSignals> for RiskSignals {
+    fn from(s: &SlimeRiskSignals) -> Self {
+        RiskSignals {
+            coordination: opt(s.coordination), clustering: opt(s.clustering), burst: opt(s.burst),
+            monotonicity: opt(s.monotonicity), abuse_history: opt(s.abuse_history), duplication: opt(s.duplication),
+        }
+    }
+}
+
+impl From<&SlimeContent> for Content {
+    fn from(c: &SlimeContent) -> Self {
+        let topic = match c.topic {
+            0 => Some(ContentTopic::General),
+            1 => Some(ContentTopic::News),
+            2 => Some(ContentTopic::Finance),
+            3 => Some(ContentTopic::Health),
+            4 => Some(ContentTopic::Elections),
+            _ => None,
+        };
+        Content { is_claim: flag(c.is_claim), has_evidence: flag(c.has_evidence), risk_signals: Some((&c.risk_signals).into()), topic, kind: None, fact_check: None }
+    }
+}
+
+impl From<PropagationResult> for SlimePropagation {
+    fn from(r: PropagationResult) -> Self { SlimePropagation { ttl: r.ttl, fanout: r.fanout, quarantined: r.quarantined } }
+}
+
+/// Borrow params, falling back to the defaults for null
+unsafe fn params_or_default(params: *const SlimeParams, default: &Params) -> &Params {
+    match params.as_ref() { Some(p) => &p.0, None => default }
+}
+
+// -------- Params handles --------
+
+/// Default parameters. Free with `slime_params_free`.
+#[no_mangle]
+pub extern "C" fn slime_params_default() -> *mut SlimeParams {
+    Box::into_raw(Box::new(SlimeParams(Params::default())))
+}
+
+/// Parse and validate parameters from a JSON string; returns null on error. Free with `slime_params_free`.
+///
+/// # Safety
+/// `json` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn slime_params_from_json(json: *const c_char) -> *mut SlimeParams {
+    if json.is_null() { return std::ptr::null_mut(); }
+    let parsed = CStr::from_ptr(json).to_str().ok().and_then(|s| serde_json::from_str::<Params>(s).ok());
+    match parsed {
+        Some(p) if p.validate().is_ok() => Box::into_raw(Box::new(SlimeParams(p))),
+        _ => std::ptr::null_mut(),
+    }
+}
+
+/// # Safety
+/// `params` must be null or a pointer returned by `slime_params_default`/`slime_params_from_json`, freed at most once.
+#[no_mangle]
+pub unsafe extern "C" fn slime_params_free(params: *mut SlimeParams) {
+    if !params.is_null() { drop(Box::from_raw(params)); }
+}
+
+// -------- Flat struct API (null params = defaults) --------
+
+/// # Safety
+/// Pointers must be null or point to valid values.
+#[no_mangle]
+pub unsafe extern "C" fn slime_calculate_quality(inputs: *const SlimeQInputs, params: *const SlimeParams) -> f64 {
+    let Some(i) = inputs.as_ref() else { return f64::NAN };
+    let d = Params::default();
+    crate::calculate_quality(QInputs { A: i.a, R: i.r, T: i.t, D: i.d, H: i.h, S: i.s }, params_or_default(params, &d))
+}
+
+/// # Safety
+/// `followers_q` must point to `len` doubles (or be null with `len == 0`); `params` null or valid.
+#[no_mangle]
+pub unsafe extern "C" fn slime_calculate_ef(followers_q: *const f64, len: usize, params: *const SlimeParams) -> f64 {
+    let qs = if len == 0 { &[][..] } else if followers_q.is_null() { return f64::NAN } else { std::slice::from_raw_parts(followers_q, len) };
+    let d = Params::default();
+    crate::calculate_ef(qs, params_or_default(params, &d))
+}
+
+/// Risk with the default weights
+///
+/// # Safety
+/// `signals` must be null or valid.
+#[no_mangle]
+pub unsafe extern "C" fn slime_calculate_risk(signals: *const SlimeRiskSignals) -> f64 {
+    let Some(s) = signals.as_ref() else { return f64::NAN };
+    crate::calculate_risk(&Some(s.into()), &RiskWeights::default())
+}
+
+/// # Safety
+/// Pointers must be null or point to valid values.
+#[no_mangle]
+pub unsafe extern "C" fn slime_calculate_post_cost(actor: *const SlimeActor, content: *const SlimeContent, base_fare: f64, params: *const SlimeParams) -> f64 {
+    let (Some(a), Some(c)) = (actor.as_ref(), content.as_ref()) else { return f64::NAN };
+    let actor = Actor { rl: a.rl, q: a.q, ef: a.ef, posts_1h: opt(a.posts_1h), ..Default::default() };
+    let d = Params::default();
+    crate::calculate_post_cost(&actor, &c.into(), params_or_default(params, &d), base_fare)
+}
+
+/// Propagation for content; null `content` means no risk signals and no topic
+///
+/// # Safety
+/// Pointers must be null or point to valid values.
+#[no_mangle]
+pub unsafe extern "C" fn slime_adjust_propagation(content: *const SlimeContent, params: *const SlimeParams) -> SlimePropagation {
+    let content = content.as_ref().map(Content::from).unwrap_or_default();
+    let d = Params::default();
+    crate::adjust_propagation_for_content(&content, params_or_default(params, &d)).into()
+}
+
+/// # Safety
+/// Pointers must be null or point to valid values.
+#[no_mangle]
+pub unsafe extern "C" fn slime_calculate_serve_reward(input: *const SlimeRewardInput, params: *const SlimeParams) -> f64 {
+    let Some(i) = input.as_ref() else { return f64::NAN };
+    let input = RewardInput {
+        ticket_budget: i.ticket_budget, client_q: i.client_q, size_bytes: i.size_bytes, ttfb_ms: i.ttfb_ms,
+        server_cluster_risk: i.server_cluster_risk,
+        prev_serves: u32::try_from(i.prev_serves).ok(),
+        ttfb_percentile: opt(i.ttfb_percentile),
+        region: None,
+    };
+    let d = Params::default();
+    crate::calculate_serve_reward(&input, params_or_default(params, &d))
+}
+
+/// # Safety
+/// `params` must be null or valid.
+#[no_mangle]
+pub unsafe extern "C" fn slime_update_base_cost(current_base: f64, current_load: f64, params: *const SlimeParams) -> f64 {
+    let d = Params::default();
+    crate::update_base_cost(current_base, current_load, params_or_default(params, &d))
+}
+
+// -------- JSON API --------
+
+fn eval_json(command: &str, input: &str, params: &Params) -> Result<serde_json::Value, String> {
+    let input: serde_json::Value = serde_json::from_str(input).map_err(|e| e.to_string())?;
+    crate::vectors::eval_command(command, &input, params).map_err(|e| e.reason)
+}
+
+/// Evaluate a command (`cost`, `reward`, `prop`, `base`, `quality`, `ef`, `risk`, `pipeline`) on a JSON input
+/// shaped like the CLI input (`vectors::eval_command`). `params_json` may be null for the defaults.
+/// Returns `{"ok": <result>}` or `{"error": "<message>"}`; free with `slime_string_free`.
+///
+/// # Safety
+/// String arguments must be null or valid NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn slime_eval_json(command: *const c_char, input_json: *const c_char, params_json: *const c_char) -> *mut c_char {
+    let text = |p: *const c_char| -> Result<&str, String> {
+        if p.is_null() { return Err("null string argument".into()); }
+        CStr::from_ptr(p).to_str().map_err(|e| e.to_string())
+    };
+    let result = (|| {
+        let params: Params = if params_json.is_null() { Params::default() } else { serde_json::from_str(text(params_json)?).map_err(|e| e.to_string())? };
+        params.validate().map_err(|errs| errs.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; "))?;
+        eval_json(text(command)?, text(input_json)?, &params)
+    })();
+    let doc = match result {
+        Ok(v) => serde_json::json!({ "ok": v }),
+        Err(e) => serde_json::json!({ "error": e }),
+    };
+    // serde_json never emits interior NUL bytes
+    CString::new(doc.to_string()).map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+/// # Safety
+/// `s` must be null or a string returned by `slime_eval_json`, freed at most once.
+#[no_mangle]
+pub unsafe extern "C" fn slime_string_free(s: *mut c_char) {
+    if !s.is_null() { drop(CString::from_raw(s)); }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_flat_and_json_agree() {
+        let actor = SlimeActor { rl: 120.0, q: 0.8, ef: 28.3, posts_1h: 12.0 };
+        let sig = SlimeRiskSignals { coordination: 0.5, clustering: 0.4, burst: -1.0, monotonicity: -1.0, abuse_history: -1.0, duplication: 0.3 };
+        let content = SlimeContent { is_claim: 1, has_evidence: 0, topic: -1, risk_signals: sig };
+        let flat = unsafe { slime_calculate_post_cost(&actor, &content, 1.0, std::ptr::null()) };
+
+        let cmd = CString::new("cost").unwrap();
+        let input = CString::new(r#"{"actor":{"rl":120.0,"q":0.8,"ef":28.3,"posts_1h":12.0},"content":{"is_claim":true,"has_evidence":false,"risk_signals":{"coordination":0.5,"clustering":0.4,"duplication":0.3}}}"#).unwrap();
+        unsafe {
+            let out = slime_eval_json(cmd.as_ptr(), input.as_ptr(), std::ptr::null());
+            let doc: serde_json::Value = serde_json::from_str(CStr::from_ptr(out).to_str().unwrap()).unwrap();
+            slime_string_free(out);
+            assert_eq!(doc["ok"]["cost"].as_f64().unwrap(), flat);
+            assert!(slime_calculate_post_cost(std::ptr::null(), &content, 1.0, std::ptr::null()).is_nan());
+        }
+    }
+    #[test]
+    fn t_header_up_to_date() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/slimechain_algo.h"));
+        let checked_in = include_str!("../include/slimechain_algo.h");
+        assert!(generated == checked_in, "include/slimechain_algo.h is stale: run `SLIMECHAIN_UPDATE_HEADER=1 cargo build --features capi`");
+    }
+}
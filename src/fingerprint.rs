@@ -0,0 +1,67 @@
+// Content fingerprints for near-duplicate detection
+// - Text is normalized (lowercased, split on non-alphanumeric characters) and shingled into overlapping word triples
+//   (the whole text when it has fewer than three words)
+// - 64-bit SimHash over FNV-1a shingle hashes: near-identical texts differ in few bits, so Hamming distance measures
+//   similarity; everything is integer arithmetic, so every node computes the same fingerprint
+// - Empty text fingerprints to 0
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Words per shingle
+pub const SHINGLE_WORDS: usize = 3;
+
+/// SimHash of a text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Fingerprint(pub u64);
+
+impl Fingerprint {
+    /// Differing bits (0..=64)
+    pub fn distance(self, other: Fingerprint) -> u32 { (self.0 ^ other.0).count_ones() }
+
+    /// `1 - distance / 64`
+    pub fn similarity(self, other: Fingerprint) -> f64 { 1.0 - self.distance(other) as f64 / 64.0 }
+}
+
+/// Lowercased words, joined by single spaces
+pub fn normalize(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).map(str::to_lowercase).collect::<Vec<_>>().join(" ")
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |h, &b| (h ^ b as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// Fingerprint a text
+pub fn fingerprint(text: &str) -> Fingerprint {
+    let normalized = normalize(text);
+    let words: Vec<&str> = normalized.split(' ').filter(|w| !w.is_empty()).collect();
+    if words.is_empty() { return Fingerprint(0); }
+    let mut counts = [0i64; 64];
+    for shingle in words.windows(SHINGLE_WORDS.min(words.len())) {
+        let h = fnv1a(shingle.join(" ").as_bytes());
+        for (bit, c) in counts.iter_mut().enumerate() {
+            *c += if (h >> bit) & 1 == 1 { 1 } else { -1 };
+        }
+    }
+    Fingerprint(counts.iter().enumerate().fold(0, |fp, (bit, &c)| if c > 0 { fp | 1 << bit } else { fp }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_near_duplicates_are_close() {
+        let base = "Breaking: the central bank will cut rates by two points tomorrow, insiders confirm. Share before it's deleted!";
+        let tweak = "BREAKING - the central bank will cut rates by two points tomorrow, insiders confirm. Share before it's deleted!!";
+        let edit = "Breaking: the central bank will cut rates by two points tomorrow, insiders say. Share before it's deleted!";
+        let other = "Photos from this morning's hike up the ridge; the wildflowers are out early this year.";
+        assert_eq!(fingerprint(base), fingerprint(tweak));
+        assert!(fingerprint(base).distance(fingerprint(edit)) < fingerprint(base).distance(fingerprint(other)));
+        assert_eq!(fingerprint(" \n"), Fingerprint(0));
+        assert_eq!(normalize("Hello,  WORLD!"), "hello world");
+    }
+}
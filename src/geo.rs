@@ -0,0 +1,64 @@
+// Geographic diversity bonus for serve rewards
+// - `RegionDistribution` is serving capacity per region (any scale: nodes, bytes pinned, serves)
+// - A region's coverage is its capacity share over the even share `1 / regions`; serving from a region below the
+//   even share earns `1 + max_bonus * (1 - coverage)`, so the least-covered (highest-latency) regions pay most
+// - Regions missing from the distribution count as uncovered; covered regions and serves without a region earn 1
+// - The bonus is bounded by `1 + max_bonus` and multiplies `calculate_serve_reward`; it never reduces a reward
+
+use crate::{calculate_serve_reward, clamp, Params, RewardInput};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Serving capacity by region
+pub type RegionDistribution = BTreeMap<String, f64>;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GeoParams {
+    /// Bonus for a region with no capacity (0.25 => up to +25%)
+    pub max_bonus: f64,
+}
+
+impl Default for GeoParams {
+    fn default() -> Self { GeoParams { max_bonus: 0.25 } }
+}
+
+/// Reward multiplier (1..=1 + max_bonus) for serving from `serving_region`
+pub fn diversity_bonus(region_distribution: &RegionDistribution, serving_region: &str, params: &GeoParams) -> f64 {
+    let capacity = |x: f64| if x.is_finite() { x.max(0.0) } else { 0.0 };
+    let total: f64 = region_distribution.values().map(|&x| capacity(x)).sum();
+    let regions = region_distribution.len() + usize::from(!region_distribution.contains_key(serving_region));
+    let share = if total > 0.0 { region_distribution.get(serving_region).map_or(0.0, |&x| capacity(x)) / total } else { 0.0 };
+    let coverage = share * regions as f64;
+    1.0 + params.max_bonus.max(0.0) * clamp(1.0 - coverage, 0.0, 1.0)
+}
+
+/// `calculate_serve_reward` times the diversity bonus of `input.region`
+pub fn calculate_serve_reward_geo(input: &RewardInput, region_distribution: &RegionDistribution, params: &Params, geo: &GeoParams) -> f64 {
+    let bonus = input.region.as_deref().map_or(1.0, |r| diversity_bonus(region_distribution, r, geo));
+    calculate_serve_reward(input, params) * bonus
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_bonus_for_under_covered_regions() {
+        let p = GeoParams::default();
+        let dist = RegionDistribution::from([("eu".to_string(), 60.0), ("us".to_string(), 30.0), ("af".to_string(), 10.0)]);
+        assert_eq!(diversity_bonus(&dist, "eu", &p), 1.0);
+        assert!((diversity_bonus(&dist, "us", &p) - (1.0 + 0.25 * 0.1)).abs() < 1e-12);
+        assert!((diversity_bonus(&dist, "af", &p) - (1.0 + 0.25 * 0.7)).abs() < 1e-12);
+        assert_eq!(diversity_bonus(&dist, "sa", &p), 1.25);
+
+        let params = Params::default();
+        let input = RewardInput { ticket_budget: 1.0, client_q: 0.8, size_bytes: 50_000, ttfb_ms: 100, ..Default::default() };
+        let plain = calculate_serve_reward(&input, &params);
+        assert_eq!(calculate_serve_reward_geo(&input, &dist, &params, &p), plain);
+        let remote = RewardInput { region: Some("sa".into()), ..input };
+        assert_eq!(calculate_serve_reward_geo(&remote, &dist, &params, &p), plain * 1.25);
+    }
+}
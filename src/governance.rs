@@ -0,0 +1,117 @@
+// Governance voting weight and quorum
+// - weight = q^q_exp * (w_stake * min(max_term, (stake/stake_norm)^stake_exp) + w_ef * min(max_term, (ef/ef_norm)^ef_exp))
+//   for q >= min_q, else 0
+// - Both terms are sublinear and capped: a whale's stake or a farmed follower count stops adding weight at
+//   `max_term`, and quality multiplies the whole weight, so neither capital nor low-quality followers buy control
+// - Uses the same Q/EF/stake inputs as pricing and rewards; nothing governance-specific feeds the weight
+// - Quorum counts every cast weight (abstentions included); approval is yes / (yes + no)
+// - `proposal` scores a parameter change's impact on a simulated population (feature `serde`)
+
+use crate::{clamp, Ef, Quality, Social};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "serde")]
+pub mod proposal;
+
+#[cfg(feature = "serde")]
+pub use proposal::{score_proposal, ImpactReport, ParamsDelta};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct VoteParams {
+    /// Voters below this quality have no weight
+    pub min_q: f64,
+    pub q_exp: f64,
+    pub w_stake: f64,
+    pub stake_norm: f64,
+    pub stake_exp: f64,
+    pub w_ef: f64,
+    pub ef_norm: f64,
+    pub ef_exp: f64,
+    /// Cap on each of the stake and EF terms
+    pub max_term: f64,
+    /// Share of the eligible weight that must be cast
+    pub quorum_share: f64,
+    /// Approval share of yes + no needed to pass (strictly above)
+    pub pass_share: f64,
+}
+
+impl Default for VoteParams {
+    fn default() -> Self {
+        VoteParams {
+            min_q: 0.3, q_exp: 1.0,
+            w_stake: 0.5, stake_norm: 1_000.0, stake_exp: 0.5,
+            w_ef: 0.5, ef_norm: 100.0, ef_exp: 0.5,
+            max_term: 3.0, quorum_share: 0.2, pass_share: 0.5,
+        }
+    }
+}
+
+impl VoteParams {
+    /// Largest weight any single voter can hold
+    pub fn max_weight(&self) -> f64 { (self.w_stake.max(0.0) + self.w_ef.max(0.0)) * self.max_term.max(0.0) }
+}
+
+fn term(x: f64, norm: f64, exp: f64, max: f64) -> f64 {
+    if norm > 0.0 { (x / norm).powf(exp).min(max.max(0.0)) } else { 0.0 }
+}
+
+/// Voting weight of one account
+pub fn voting_weight(q: impl Into<Quality>, ef: impl Into<Ef>, stake: impl Into<Social>, params: &VoteParams) -> f64 {
+    let q = q.into().get();
+    if q < params.min_q { return 0.0; }
+    let stake = term(stake.into().get(), params.stake_norm, params.stake_exp, params.max_term);
+    let ef = term(ef.into().get(), params.ef_norm, params.ef_exp, params.max_term);
+    q.powf(params.q_exp) * (params.w_stake.max(0.0) * stake + params.w_ef.max(0.0) * ef)
+}
+
+/// Cast voting weight per choice
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Tally {
+    pub yes: f64,
+    pub no: f64,
+    pub abstain: f64,
+}
+
+impl Tally {
+    pub fn cast(&self) -> f64 { self.yes + self.no + self.abstain }
+}
+
+/// Weight that must be cast for the vote to count
+pub fn quorum(eligible_weight: f64, params: &VoteParams) -> f64 { eligible_weight.max(0.0) * clamp(params.quorum_share, 0.0, 1.0) }
+
+pub fn quorum_reached(tally: &Tally, eligible_weight: f64, params: &VoteParams) -> bool {
+    tally.cast() > 0.0 && tally.cast() >= quorum(eligible_weight, params)
+}
+
+/// Quorum reached and approval above `pass_share`
+pub fn passes(tally: &Tally, eligible_weight: f64, params: &VoteParams) -> bool {
+    let decided = tally.yes + tally.no;
+    quorum_reached(tally, eligible_weight, params) && decided > 0.0 && tally.yes / decided > params.pass_share
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_weight_is_bounded_and_sublinear() {
+        let p = VoteParams::default();
+        let base = voting_weight(0.8, 100.0, 1_000.0, &p);
+        assert!((base - 0.8).abs() < 1e-12);
+        // 4x the stake doubles the stake term; a whale and a follower farm both hit the cap
+        assert!((voting_weight(0.8, 100.0, 4_000.0, &p) - 0.8 * 1.5).abs() < 1e-12);
+        assert_eq!(voting_weight(1.0, 1e9, 1e12, &p), p.max_weight());
+        assert_eq!(voting_weight(0.2, 1e9, 1e12, &p), 0.0);
+
+        let t = Tally { yes: 30.0, no: 20.0, abstain: 10.0 };
+        assert!(passes(&t, 300.0, &p));
+        assert!(!passes(&t, 301.0, &p));
+        assert!(!passes(&Tally { yes: 25.0, no: 25.0, abstain: 10.0 }, 100.0, &p));
+        assert!(!quorum_reached(&Tally::default(), 0.0, &p));
+    }
+}
@@ -0,0 +1,131 @@
+// Proposal impact scoring (feature `serde`)
+// - A `ParamsDelta` sets numeric `Params` fields by dotted path, as in `sim::sweep` (e.g. `cost.alpha`)
+// - `score_proposal` runs the `sim` scenario under the current and the proposed params and reports, per cohort, the
+//   mean cost per post, reach budget per post and serve reward per post before and after, plus scenario totals
+// - The report serializes as-is for attaching to a proposal; the proposed params must pass `Params::validate`
+
+use crate::propagation::reach_budget;
+use crate::sim::sweep::with_values;
+use crate::sim::{loads_and_fares, run, Scenario};
+use crate::{calculate_post_cost, calculate_risk, calculate_serve_reward, ParamError, Params, RiskWeights};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Proposed values by dotted `Params` path
+pub type ParamsDelta = BTreeMap<String, f64>;
+
+impl Params {
+    /// These params with `delta` applied, validated
+    pub fn with_delta(&self, delta: &ParamsDelta) -> Result<Params, Vec<ParamError>> {
+        let base = serde_json::to_value(self).map_err(|e| vec![ParamError { path: String::new(), reason: e.to_string() }])?;
+        let assignments: Vec<(&str, f64)> = delta.iter().map(|(p, &x)| (p.as_str(), x)).collect();
+        let params = with_values(&base, &assignments).map_err(|e| vec![e])?;
+        params.validate()?;
+        Ok(params)
+    }
+}
+
+/// A metric under the current and the proposed params
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Change {
+    pub before: f64,
+    pub after: f64,
+    /// `after - before`
+    pub delta: f64,
+}
+
+impl Change {
+    pub fn new(before: f64, after: f64) -> Self { Change { before, after, delta: after - before } }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CohortImpact {
+    pub cohort: String,
+    /// Posts by the cohort over the scenario (unchanged by the proposal)
+    pub posts: u64,
+    pub cost_per_post: Change,
+    pub reach_per_post: Change,
+    pub reward_per_post: Change,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ImpactReport {
+    pub delta: ParamsDelta,
+    pub epochs: u32,
+    /// In scenario cohort order
+    pub cohorts: Vec<CohortImpact>,
+    pub total_cost: Change,
+    pub total_reward: Change,
+}
+
+/// (mean cost per post, reach per post, reward per post) per cohort
+fn per_cohort(scenario: &Scenario, params: &Params) -> Vec<(f64, f64, f64)> {
+    let (_, fares) = loads_and_fares(scenario, params);
+    scenario.cohorts.iter().map(|c| {
+        let cost = if fares.is_empty() { 0.0 }
+            else { fares.iter().map(|&b| calculate_post_cost(&c.actor, &c.content, params, b)).sum::<f64>() / fares.len() as f64 };
+        let risk = calculate_risk(&c.content.risk_signals, &RiskWeights::DEFAULT);
+        let reach = reach_budget(c.actor.ef, c.actor.q, risk, params) as f64;
+        let reward = c.serve.as_ref().map_or(0.0, |s| calculate_serve_reward(s, params)) * c.serves_per_post as f64;
+        (cost, reach, reward)
+    }).collect()
+}
+
+/// Score `delta` against `current` params on `scenario`
+pub fn score_proposal(delta: &ParamsDelta, current: &Params, scenario: &Scenario) -> Result<ImpactReport, Vec<ParamError>> {
+    let proposed = current.with_delta(delta)?;
+
+    let (before, after) = (per_cohort(scenario, current), per_cohort(scenario, &proposed));
+    let cohorts = scenario.cohorts.iter().zip(before.iter().zip(&after)).map(|(c, (b, a))| CohortImpact {
+        cohort: c.name.clone(),
+        posts: ((c.count as f64 * c.posts_per_epoch.max(0.0)).round() as u64).saturating_mul(scenario.epochs as u64),
+        cost_per_post: Change::new(b.0, a.0),
+        reach_per_post: Change::new(b.1, a.1),
+        reward_per_post: Change::new(b.2, a.2),
+    }).collect();
+    let totals = |params: &Params| run(scenario, params).iter().fold((0.0, 0.0), |(c, r), e| (c + e.total_cost, r + e.reward_total));
+    let ((cost_b, reward_b), (cost_a, reward_a)) = (totals(current), totals(&proposed));
+    Ok(ImpactReport {
+        delta: delta.clone(),
+        epochs: scenario.epochs,
+        cohorts,
+        total_cost: Change::new(cost_b, cost_a),
+        total_reward: Change::new(reward_b, reward_a),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::Cohort;
+    use crate::{Actor, Content, RewardInput, RiskSignals};
+
+    #[test]
+    fn t_score_proposal_per_cohort() {
+        let cohort = |name: &str, risk: f64| Cohort {
+            name: name.into(), count: 10, posts_per_epoch: 2.0,
+            actor: Actor { rl: 5.0, q: 0.8, ef: 20.0, posts_1h: None, ..Default::default() },
+            content: Content { risk_signals: Some(RiskSignals { coordination: Some(risk), clustering: Some(risk), ..Default::default() }), ..Default::default() },
+            serves_per_post: 3,
+            serve: Some(RewardInput { ticket_budget: 1.0, client_q: 0.8, size_bytes: 10_000, ttfb_ms: 100, ..Default::default() }),
+        };
+        let scenario = Scenario { cohorts: vec![cohort("honest", 0.0), cohort("spam", 0.9)], load_curve: vec![500.0], epochs: 3, initial_base: 1.0, demand: None };
+        let current = Params::default();
+        let delta = ParamsDelta::from([("cost.lambda_content".to_string(), current.cost.lambda_content + 1.0)]);
+
+        let r = score_proposal(&delta, &current, &scenario).unwrap();
+        let (honest, spam) = (&r.cohorts[0], &r.cohorts[1]);
+        assert_eq!((honest.posts, r.epochs), (60, 3));
+        assert_eq!(honest.cost_per_post.delta, 0.0);
+        assert!(spam.cost_per_post.delta > 0.0);
+        assert_eq!((spam.reach_per_post.delta, spam.reward_per_post.delta), (0.0, 0.0));
+        let spent: f64 = r.cohorts.iter().map(|c| c.posts as f64 * c.cost_per_post.after).sum();
+        assert!((spent - r.total_cost.after).abs() < 1e-9 * spent);
+
+        let bad = ParamsDelta::from([("cost.nope".to_string(), 1.0)]);
+        assert_eq!(score_proposal(&bad, &current, &scenario).unwrap_err()[0].path, "cost.nope");
+    }
+}
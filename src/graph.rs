@@ -0,0 +1,130 @@
+// Follower graph in compressed sparse row form, with per-node quality
+// - Nodes are dense `u32` ids `0..node_count`; edge `(a, b)` means b follows a, so b is in `followers(a)` and receives
+//   what a forwards
+// - Each node's followers are one sorted, deduplicated slice of a shared array (self-follows dropped): 4 bytes per
+//   edge plus 4 per node, versus a `Vec` header per node for `Vec<Vec<usize>>`
+// - One representation for EF (`ef`, `quality_degree`), coordination detection (`mutual_clusters`) and the reach
+//   simulator (`propagation::simulate::estimate_reach_on`)
+
+use crate::{calculate_ef, clamp, AsParams};
+
+/// Edge naming a node outside `0..node_count`
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphError { pub edge: usize, pub node: u32 }
+
+impl std::fmt::Display for GraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "edge {}: node {} out of range", self.edge, self.node) }
+}
+
+impl std::error::Error for GraphError {}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FollowerGraph {
+    /// `targets[offsets[n]..offsets[n + 1]]` are n's followers
+    offsets: Vec<u32>,
+    targets: Vec<u32>,
+    quality: Vec<f64>,
+}
+
+impl FollowerGraph {
+    /// Build from per-node quality (its length is the node count) and `(followed, follower)` edges
+    pub fn from_edges(quality: Vec<f64>, edges: impl IntoIterator<Item = (u32, u32)>) -> Result<Self, GraphError> {
+        let n = quality.len();
+        let mut edges: Vec<(u32, u32)> = edges.into_iter().collect();
+        if let Some((edge, &(a, b))) = edges.iter().enumerate().find(|(_, &(a, b))| a as usize >= n || b as usize >= n) {
+            return Err(GraphError { edge, node: if a as usize >= n { a } else { b } });
+        }
+        edges.retain(|(a, b)| a != b);
+        edges.sort_unstable();
+        edges.dedup();
+
+        let mut offsets = vec![0u32; n + 1];
+        for &(a, _) in &edges { offsets[a as usize + 1] += 1; }
+        for i in 0..n { offsets[i + 1] += offsets[i]; }
+        let quality = quality.into_iter().map(|q| if q.is_finite() { clamp(q, 0.0, 1.0) } else { 0.0 }).collect();
+        Ok(FollowerGraph { offsets, targets: edges.into_iter().map(|(_, b)| b).collect(), quality })
+    }
+
+    pub fn node_count(&self) -> usize { self.quality.len() }
+
+    pub fn edge_count(&self) -> usize { self.targets.len() }
+
+    /// Followers of `node`, sorted (empty for unknown nodes)
+    pub fn followers(&self, node: u32) -> &[u32] {
+        let i = node as usize;
+        if i >= self.node_count() { return &[]; }
+        &self.targets[self.offsets[i] as usize..self.offsets[i + 1] as usize]
+    }
+
+    /// True when `follower` follows `node`
+    pub fn follows(&self, follower: u32, node: u32) -> bool { self.followers(node).binary_search(&follower).is_ok() }
+
+    /// Quality of `node` in 0..1 (0 for unknown nodes)
+    pub fn quality(&self, node: u32) -> f64 { self.quality.get(node as usize).copied().unwrap_or(0.0) }
+
+    /// Replace a node's quality (clamped to 0..1; ignored for unknown nodes)
+    pub fn set_quality(&mut self, node: u32, q: f64) {
+        if let Some(x) = self.quality.get_mut(node as usize) { *x = if q.is_finite() { clamp(q, 0.0, 1.0) } else { 0.0 }; }
+    }
+
+    /// Followers with quality >= `q_min`
+    pub fn quality_degree(&self, node: u32, q_min: f64) -> usize {
+        self.followers(node).iter().filter(|&&f| self.quality(f) >= q_min).count()
+    }
+
+    /// Qualities of `node`'s followers, as taken by `calculate_ef`
+    pub fn follower_q(&self, node: u32) -> Vec<f64> { self.followers(node).iter().map(|&f| self.quality(f)).collect() }
+
+    /// Effective followers of `node`
+    pub fn ef(&self, node: u32, params: &impl AsParams) -> f64 { calculate_ef(&self.follower_q(node), params) }
+
+    /// Connected components of the mutual-follow subgraph with at least `min_size` nodes (ids sorted, components
+    /// ordered by smallest id); tight mutual-follow rings are the coordination signal
+    pub fn mutual_clusters(&self, min_size: usize) -> Vec<Vec<u32>> {
+        let n = self.node_count();
+        let mut parent: Vec<u32> = (0..n as u32).collect();
+        fn root(parent: &mut [u32], mut x: u32) -> u32 {
+            while parent[x as usize] != x {
+                parent[x as usize] = parent[parent[x as usize] as usize];
+                x = parent[x as usize];
+            }
+            x
+        }
+        for a in 0..n as u32 {
+            for &b in self.followers(a) {
+                if a < b && self.follows(a, b) {
+                    let (ra, rb) = (root(&mut parent, a), root(&mut parent, b));
+                    if ra != rb { parent[ra.max(rb) as usize] = ra.min(rb); }
+                }
+            }
+        }
+        let mut clusters: Vec<Vec<u32>> = vec![Vec::new(); n];
+        for x in 0..n as u32 {
+            let r = root(&mut parent, x);
+            clusters[r as usize].push(x);
+        }
+        clusters.retain(|c| c.len() >= min_size.max(2));
+        clusters
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Params;
+
+    #[test]
+    fn t_csr_queries() {
+        // 0 <-> 1 <-> 2 mutual ring, 3 follows 0 one-way, 4 <-> 5 mutual pair
+        let edges = [(0, 1), (1, 0), (1, 2), (2, 1), (0, 3), (0, 1), (4, 5), (5, 4), (2, 2)];
+        let g = FollowerGraph::from_edges(vec![0.9, 0.4, 0.8, 0.7, 0.5, 0.5], edges).unwrap();
+        assert_eq!((g.node_count(), g.edge_count()), (6, 7));
+        assert_eq!(g.followers(0), [1, 3]);
+        assert!(g.follows(3, 0) && !g.follows(0, 3));
+        assert_eq!(g.quality_degree(0, 0.5), 1);
+        assert_eq!(g.ef(0, &Params::default()), calculate_ef(&[0.4, 0.7], &Params::default()));
+        assert_eq!(g.mutual_clusters(2), [vec![0, 1, 2], vec![4, 5]]);
+        assert_eq!(g.mutual_clusters(3), [vec![0, 1, 2]]);
+        assert_eq!(FollowerGraph::from_edges(vec![0.5; 2], [(0, 1), (1, 7)]).unwrap_err(), GraphError { edge: 1, node: 7 });
+    }
+}
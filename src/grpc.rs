@@ -0,0 +1,97 @@
+// tonic server for the SlimechainAlgo service (feature `grpc`)
+// - Every RPC evaluates under the params the server was started with (validate them before `serve`)
+// - Malformed messages are INVALID_ARGUMENT; non-finite results are INTERNAL (the CLI's `math` errors)
+
+use crate::proto::{self, slimechain_algo_server::SlimechainAlgo};
+use crate::{pipeline, Params, RiskWeights};
+use tonic::{Request, Response, Status};
+
+pub use crate::proto::slimechain_algo_client::SlimechainAlgoClient;
+pub use crate::proto::slimechain_algo_server::SlimechainAlgoServer;
+
+/// Service implementation holding the evaluation params
+#[derive(Debug, Clone)]
+pub struct AlgoService {
+    params: Params,
+}
+
+impl AlgoService {
+    pub fn new(params: Params) -> Self { AlgoService { params } }
+}
+
+fn finite(name: &str, x: f64) -> Result<f64, Status> {
+    if x.is_finite() { Ok(x) } else { Err(Status::internal(format!("{} is not finite ({})", name, x))) }
+}
+
+fn invalid(e: proto::ProtoError) -> Status { Status::invalid_argument(e.to_string()) }
+
+#[tonic::async_trait]
+impl SlimechainAlgo for AlgoService {
+    async fn cost(&self, request: Request<proto::CostRequest>) -> Result<Response<proto::CostResponse>, Status> {
+        let r = request.into_inner();
+        let actor = crate::Actor::try_from(r.actor.ok_or_else(|| Status::invalid_argument("actor: missing"))?).map_err(invalid)?;
+        let content = r.content.map(crate::Content::try_from).transpose().map_err(invalid)?.unwrap_or_default();
+        let cost = crate::calculate_post_cost(&actor, &content, &self.params, r.base_fare.unwrap_or(1.0));
+        Ok(Response::new(proto::CostResponse { cost: finite("cost", cost)? }))
+    }
+
+    async fn reward(&self, request: Request<proto::RewardInput>) -> Result<Response<proto::RewardResponse>, Status> {
+        let reward = crate::calculate_serve_reward(&request.into_inner().into(), &self.params);
+        Ok(Response::new(proto::RewardResponse { reward: finite("reward", reward)? }))
+    }
+
+    async fn propagation(&self, request: Request<proto::PropagationRequest>) -> Result<Response<proto::PropagationResult>, Status> {
+        let r = request.into_inner();
+        let content = crate::Content::try_from(proto::Content { risk_signals: r.risk_signals, topic: r.topic, ..Default::default() }).map_err(invalid)?;
+        Ok(Response::new(crate::adjust_propagation_for_content(&content, &self.params).into()))
+    }
+
+    async fn quality(&self, request: Request<proto::QInputs>) -> Result<Response<proto::QualityResponse>, Status> {
+        let q = crate::calculate_quality(request.into_inner().into(), &self.params);
+        Ok(Response::new(proto::QualityResponse { q: finite("q", q)? }))
+    }
+
+    async fn risk(&self, request: Request<proto::RiskSignals>) -> Result<Response<proto::RiskResponse>, Status> {
+        let risk = crate::calculate_risk(&Some(request.into_inner().into()), &RiskWeights::default());
+        Ok(Response::new(proto::RiskResponse { risk: finite("risk", risk)? }))
+    }
+
+    async fn pipeline(&self, request: Request<proto::PipelineInput>) -> Result<Response<proto::PipelineOutput>, Status> {
+        let input = pipeline::PipelineInput::try_from(request.into_inner()).map_err(invalid)?;
+        let out = pipeline::run(&input, &self.params);
+        for (name, x) in [("q", out.q), ("ef", out.ef), ("risk", out.risk), ("base_fare", out.base_fare), ("cost", out.cost)] {
+            finite(name, x)?;
+        }
+        if let Some(r) = out.reward { finite("reward", r)?; }
+        Ok(Response::new((&out).into()))
+    }
+}
+
+/// Serve the SlimechainAlgo service on `addr` until the future is dropped
+pub async fn serve(addr: std::net::SocketAddr, params: Params) -> Result<(), tonic::transport::Error> {
+    tonic::transport::Server::builder()
+        .add_service(SlimechainAlgoServer::new(AlgoService::new(params)))
+        .serve(addr)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn t_rpcs_match_library() {
+        let svc = AlgoService::new(Params::default());
+        let actor = crate::Actor { rl: 120.0, q: 0.8, ef: 28.3, posts_1h: Some(12.0), ..Default::default() };
+        let content = crate::Content { is_claim: Some(true), topic: Some(crate::ContentTopic::Health), ..Default::default() };
+        let req = proto::CostRequest { actor: Some((&actor).into()), content: Some((&content).into()), base_fare: None };
+        let got = svc.cost(Request::new(req)).await.unwrap().into_inner().cost;
+        assert_eq!(got, crate::calculate_post_cost(&actor, &content, &Params::default(), 1.0));
+
+        let prop = svc.propagation(Request::new(proto::PropagationRequest { risk_signals: None, topic: proto::Topic::Health as i32 })).await.unwrap();
+        assert_eq!(crate::PropagationResult::from(prop.into_inner()).ttl, crate::adjust_propagation_for_content(&content, &Params::default()).ttl);
+
+        let err = svc.cost(Request::new(proto::CostRequest::default())).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+}
@@ -0,0 +1,123 @@
+// Transport-agnostic JSON-RPC 2.0 dispatcher
+// - `Dispatcher::handle(&str) -> String` maps `slimechain_*` methods onto the library (single and batch requests)
+// - `params` is a by-name object shaped like the CLI input of the same function; results match the CLI output (both
+//   go through `vectors::eval_command`)
+// - Notifications (no `id`) are evaluated but produce no response; an all-notification batch yields ""
+
+use crate::vectors::{eval_command, VectorErrorKind};
+use crate::Params;
+use serde_json::{json, Value};
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+
+/// Supported methods
+pub const METHODS: &[&str] = &[
+    "slimechain_calculateCost", "slimechain_calculateReward", "slimechain_adjustPropagation", "slimechain_updateBaseCost",
+    "slimechain_calculateQuality", "slimechain_calculateEf", "slimechain_calculateRisk", "slimechain_runPipeline",
+];
+
+/// `vectors::COMMANDS` entry of each method, in `METHODS` order
+const METHOD_COMMANDS: &[&str] = &["cost", "reward", "prop", "base", "quality", "ef", "risk", "pipeline"];
+
+/// (code, message)
+type RpcError = (i64, String);
+
+/// Dispatches requests under fixed params
+#[derive(Debug, Clone, Default)]
+pub struct Dispatcher {
+    pub params: Params,
+}
+
+impl Dispatcher {
+    pub fn new(params: Params) -> Self { Dispatcher { params } }
+
+    /// Handle one request or a batch; returns the response document ("" when nothing is to be sent)
+    pub fn handle(&self, request: &str) -> String {
+        let doc: Value = match serde_json::from_str(request) {
+            Ok(v) => v,
+            Err(e) => return error(Value::Null, (PARSE_ERROR, format!("parse error: {}", e))).to_string(),
+        };
+        match doc {
+            Value::Array(items) if items.is_empty() => error(Value::Null, (INVALID_REQUEST, "empty batch".into())).to_string(),
+            Value::Array(items) => {
+                let out: Vec<Value> = items.iter().filter_map(|r| self.handle_one(r)).collect();
+                if out.is_empty() { String::new() } else { Value::Array(out).to_string() }
+            }
+            one => self.handle_one(&one).map_or_else(String::new, |v| v.to_string()),
+        }
+    }
+
+    fn handle_one(&self, request: &Value) -> Option<Value> {
+        let Some(obj) = request.as_object() else {
+            return Some(error(Value::Null, (INVALID_REQUEST, "request must be an object".into())));
+        };
+        let id = obj.get("id").cloned();
+        let valid_id = matches!(id, None | Some(Value::Null | Value::Number(_) | Value::String(_)));
+        let (Some("2.0"), Some(method), true) = (obj.get("jsonrpc").and_then(Value::as_str), obj.get("method").and_then(Value::as_str), valid_id) else {
+            return Some(error(id.unwrap_or(Value::Null), (INVALID_REQUEST, "invalid request".into())));
+        };
+        let result = self.call(method, obj.get("params").cloned().unwrap_or(Value::Null));
+        // notifications get no response
+        let id = id?;
+        Some(match result {
+            Ok(v) => json!({ "jsonrpc": "2.0", "result": v, "id": id }),
+            Err(e) => error(id, e),
+        })
+    }
+
+    /// Evaluate one method on its params
+    pub fn call(&self, method: &str, params: Value) -> Result<Value, (i64, String)> {
+        let Some(i) = METHODS.iter().position(|m| *m == method) else {
+            return Err((METHOD_NOT_FOUND, format!("method not found: {}", method)));
+        };
+        if !params.is_object() {
+            return Err((INVALID_PARAMS, "params must be an object".into()));
+        }
+        eval_command(METHOD_COMMANDS[i], &params, &self.params).map_err(|e| {
+            let code = match e.kind {
+                VectorErrorKind::InvalidInput => INVALID_PARAMS,
+                _ => INTERNAL_ERROR,
+            };
+            (code, e.reason)
+        })
+    }
+}
+
+/// Handle a request under `Params::default()`
+pub fn handle_request(request: &str) -> String { Dispatcher::default().handle(request) }
+
+fn error(id: Value, (code, message): RpcError) -> Value {
+    json!({ "jsonrpc": "2.0", "error": { "code": code, "message": message }, "id": id })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_single_batch_and_errors() {
+        let r: Value = serde_json::from_str(&handle_request(r#"{"jsonrpc":"2.0","id":1,"method":"slimechain_calculateEf","params":{"followers_q":[0.9,0.8]}}"#)).unwrap();
+        assert_eq!(r["result"]["ef"], json!(crate::calculate_ef(&[0.9, 0.8], &Params::default())));
+        assert_eq!(r["id"], 1);
+
+        let batch = r#"[
+            {"jsonrpc":"2.0","id":"a","method":"slimechain_calculateRisk","params":{"coordination":0.5}},
+            {"jsonrpc":"2.0","method":"slimechain_calculateRisk","params":{}},
+            {"jsonrpc":"2.0","id":2,"method":"nope"},
+            {"jsonrpc":"2.0","id":3,"method":"slimechain_calculateQuality","params":[1]}
+        ]"#;
+        let r: Value = serde_json::from_str(&handle_request(batch)).unwrap();
+        let r = r.as_array().unwrap();
+        assert_eq!(r.len(), 3);
+        assert_eq!(r[1]["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(r[2]["error"]["code"], INVALID_PARAMS);
+
+        assert_eq!(handle_request(r#"{"jsonrpc":"2.0","method":"slimechain_calculateRisk","params":{}}"#), "");
+        let r: Value = serde_json::from_str(&handle_request("{")).unwrap();
+        assert_eq!(r["error"]["code"], PARSE_ERROR);
+    }
+}
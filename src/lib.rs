@@ -2,11 +2,129 @@
 // Slimechain algorithm Rust implementation
 // - Comments are written in English
 // - Composed of pure functions with no external state
+// - `serde` (default) derives Serialize/Deserialize on the public types; without it only the math is built
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub mod algebraic;
+pub mod analysis;
+pub mod appeals;
+#[cfg(feature = "serde")]
+pub mod audit;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "parallel")]
+pub mod batch;
+pub mod boost;
+#[cfg(feature = "serde")]
+pub mod calibrate;
+#[cfg(feature = "cbor")]
+pub mod cbor;
+#[cfg(feature = "serde")]
+pub mod claims;
+#[cfg(feature = "serde")]
+pub mod community;
+#[cfg(feature = "serde")]
+pub mod compat;
+#[cfg(feature = "serde")]
+pub mod conformance;
+pub mod congestion;
+pub mod context;
+pub mod cooldown;
+#[cfg(feature = "tiers")]
+pub mod currency;
+pub mod det_rand;
+pub mod diversity;
+pub mod dm;
+pub mod dm_escrow;
+pub mod ef;
+pub mod emission;
+pub mod engine;
+pub mod epoch;
+#[cfg(feature = "serde")]
+pub mod experiments;
+pub mod fastmath;
+#[cfg(feature = "capi")]
+pub mod ffi;
+pub mod fingerprint;
+pub mod geo;
+pub mod governance;
+pub mod graph;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "serde")]
+pub mod jsonrpc;
+pub mod metrics;
+#[cfg(feature = "uniffi")]
+pub mod mobile;
+pub mod moderation;
+pub mod money;
+#[cfg(feature = "napi")]
+pub mod node;
+pub mod notify;
+#[cfg(feature = "tiers")]
+pub mod oracles;
+pub mod pipeline;
+pub mod policy;
+pub mod por;
+pub mod prepared;
+pub mod presets;
+pub mod priors;
+pub mod propagation;
+#[cfg(feature = "proto")]
+pub mod proto;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod quality;
+pub mod ranking;
+pub mod ratelimit;
+pub mod rebates;
+pub mod referrals;
+pub mod reputation;
+pub mod reward;
+pub mod risk;
+pub mod shadow;
+pub mod sim;
+pub mod slashing;
+pub mod stats;
+pub mod storage;
+#[cfg(feature = "tiers")]
+pub mod subscriptions;
+pub mod tickets;
+#[cfg(feature = "tiers")]
+pub mod tiers;
+pub mod tips;
+#[cfg(any(feature = "tracing", feature = "trace"))]
+pub mod trace;
+pub mod trending;
+pub mod units;
+#[cfg(feature = "serde")]
+pub mod vectors;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod wire;
+
+// UniFFI looks the scaffolding tag up at the crate root
+#[cfg(feature = "uniffi")]
+#[doc(hidden)]
+pub use mobile::UniFfiTag;
+
+pub use engine::{PricingEngine, ReferenceEngine};
+pub use prepared::{AsParams, DerivedParams, PreparedParams};
+pub use units::{Ef, Quality, Risk, Social};
+
+/// Version of the borsh layout of the public types, bumped whenever a field is added, removed or reordered
+/// - 2: `RiskSignals::duplication` and `RiskWeights::w_dup` appended; layout-1 bytes no longer decode
+#[cfg(feature = "borsh")]
+pub const BORSH_LAYOUT_VERSION: u32 = 2;
 
 /// Parameter bundle
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
 pub struct Params {
     pub q_weights: QWeights,
     pub q_min: f64,
@@ -27,45 +145,497 @@ impl Default for Params {
                 alpha: 0.7, beta: 0.5, a: 1.2, b: 0.6,
                 lambda_actor: 0.6, lambda_content: 0.4,
                 rate_limit_per_hour: 10.0,
+                cmin: None, cmax: None,
+                new_account_surcharge: 0.5, new_account_days: 30.0,
+                kind_rate_limits: BTreeMap::new(),
+                fact_check: FactCheckMultipliers::DEFAULT,
+            },
+            propagation: PropagationParams {
+                ttl_base: 4.0, fanout_base: 5.0, k1: 2.0, k2: 2.0,
+                topic_multipliers: default_topic_multipliers(),
+                reach: ReachParams::default(),
+                quarantine_threshold: None,
+                schedule: ScheduleParams::default(),
+            },
+            reward: RewardParams {
+                r0: 1.0, mu: 0.3, size_norm_bytes: 1_000_000.0, latency_knee_ms: 1000.0,
+                epoch: EpochParams::default(),
+                repetition_half: 10,
+                availability: AvailabilityParams::default(),
+                latency_curve: default_latency_curve(),
+            },
+            congestion: CongestionParams {
+                eta: 0.1, target_load: 500.0, base_min: 0.1, base_max: 100.0,
+                controller: CongestionController::Exponential,
+                load_ema_alpha: 0.3,
+                surge: SurgeParams::default(),
             },
-            propagation: PropagationParams { ttl_base: 4.0, fanout_base: 5.0, k1: 2.0, k2: 2.0 },
-            reward: RewardParams { r0: 1.0, mu: 0.3 },
-            congestion: CongestionParams { eta: 0.1, target_load: 500.0, base_min: 0.1, base_max: 100.0 },
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct QWeights { pub w_a: f64, pub w_r: f64, pub w_t: f64, pub w_d: f64, pub w_h: f64, pub w_s: f64 }
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+pub struct QWeights {
+    pub w_a: f64, pub w_r: f64, pub w_t: f64, pub w_d: f64, pub w_h: f64, pub w_s: f64,
+    /// Quality cap for `H == 0` by the author's verification level
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unverified_caps: UnverifiedCaps,
+}
 impl Default for QWeights {
-    fn default() -> Self { Self{ w_a:0.2, w_r:0.2, w_t:0.2, w_d:0.15, w_h:0.2, w_s:0.25 } }
+    fn default() -> Self { Self{ w_a:0.2, w_r:0.2, w_t:0.2, w_d:0.15, w_h:0.2, w_s:0.25, unverified_caps: UnverifiedCaps::default() } }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Cap on q when `H == 0`, relaxed as the author's verification level rises (unknown level = `unverified`)
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+pub struct UnverifiedCaps { pub unverified: f64, pub email: f64, pub phone: f64, pub identity: f64 }
+impl Default for UnverifiedCaps {
+    fn default() -> Self { Self { unverified: 0.4, email: 0.5, phone: 0.6, identity: 0.8 } }
+}
+
+impl UnverifiedCaps {
+    pub fn cap(&self, level: Option<VerificationLevel>) -> f64 {
+        match level.unwrap_or_default() {
+            VerificationLevel::Unverified => self.unverified,
+            VerificationLevel::Email => self.email,
+            VerificationLevel::Phone => self.phone,
+            VerificationLevel::Identity => self.identity,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
 pub struct EfParams { pub gamma: f64, pub cap: f64 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
 pub struct CostParams {
     pub alpha: f64, pub beta: f64, pub a: f64, pub b: f64,
     pub lambda_actor: f64, pub lambda_content: f64,
     pub rate_limit_per_hour: f64,
+    /// Minimum posting cost in SOCIAL (C_min), enforced after all multipliers and discounts
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cmin: Option<f64>,
+    /// Maximum posting cost in SOCIAL (C_max), enforced last (after C_min and tier floors)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cmax: Option<f64>,
+    /// Extra cost share for a brand-new account, fading linearly to 0 at `new_account_days` (accounts of unknown age pay none)
+    #[cfg_attr(feature = "serde", serde(default = "default_new_account_surcharge"))]
+    pub new_account_surcharge: f64,
+    #[cfg_attr(feature = "serde", serde(default = "default_new_account_days"))]
+    pub new_account_days: f64,
+    /// Per-kind limits used instead of `rate_limit_per_hour` for content of that kind (missing kind => global limit)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub kind_rate_limits: BTreeMap<ContentKind, f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fact_check: FactCheckMultipliers,
+}
+
+#[cfg(feature = "serde")]
+fn default_new_account_surcharge() -> f64 { 0.5 }
+#[cfg(feature = "serde")]
+fn default_new_account_days() -> f64 { 30.0 }
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+pub struct PropagationParams {
+    pub ttl_base: f64, pub fanout_base: f64, pub k1: f64, pub k2: f64,
+    /// Structural ttl/fanout multipliers for sensitive topics (missing topic => 1.0)
+    #[cfg_attr(feature = "serde", serde(default = "default_topic_multipliers"))]
+    pub topic_multipliers: BTreeMap<ContentTopic, TopicMultiplier>,
+    /// Total impression budget per post
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reach: ReachParams,
+    /// Risk above this threshold yields ttl=0/fanout=0 (author-only visibility); None disables quarantine
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub quarantine_threshold: Option<f64>,
+    /// Time-staggered fanout waves (see `propagation::schedule`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub schedule: ScheduleParams,
+}
+
+/// Fanout wave schedule parameters
+/// - Risk at or below `immediate_below` sends the full fanout at once
+/// - Otherwise the first wave carries `fanout * (1 - risk)` peers and the rest follow every `wave_delay_secs`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+pub struct ScheduleParams { pub immediate_below: f64, pub wave_delay_secs: u64, pub max_waves: u32 }
+impl Default for ScheduleParams {
+    fn default() -> Self { Self{ immediate_below:0.2, wave_delay_secs:600, max_waves:3 } }
+}
+
+/// Total-reach budget parameters (see `propagation::reach_budget`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+pub struct ReachParams { pub reach_base: f64, pub reach_per_ef: f64, pub risk_k: f64, pub reach_cap: f64 }
+impl Default for ReachParams {
+    fn default() -> Self { Self{ reach_base:100.0, reach_per_ef:20.0, risk_k:0.8, reach_cap:100_000.0 } }
+}
+
+/// Content topic/category (drives structural propagation limits)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ContentTopic { General, News, Finance, Health, Elections }
+
+/// Content format (selects a per-kind posting rate limit)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ContentKind { Text, Link, Media }
+
+/// Fact-check status of a claim (selects the claim multiplier; see `FactCheckMultipliers`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FactCheckState { Unreviewed, EvidenceProvided, VerifiedTrue, VerifiedFalse, Disputed }
+
+/// Posting cost multiplier per fact-check state (defaults keep the legacy 1.2 / 0.7 claim multipliers)
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+pub struct FactCheckMultipliers {
+    pub unreviewed: f64, pub evidence_provided: f64, pub verified_true: f64, pub verified_false: f64, pub disputed: f64,
+}
+impl FactCheckMultipliers {
+    pub const DEFAULT: Self = Self{ unreviewed:1.2, evidence_provided:0.7, verified_true:0.5, verified_false:3.0, disputed:1.5 };
+
+    pub fn get(&self, state: FactCheckState) -> f64 {
+        match state {
+            FactCheckState::Unreviewed => self.unreviewed,
+            FactCheckState::EvidenceProvided => self.evidence_provided,
+            FactCheckState::VerifiedTrue => self.verified_true,
+            FactCheckState::VerifiedFalse => self.verified_false,
+            FactCheckState::Disputed => self.disputed,
+        }
+    }
 }
+impl Default for FactCheckMultipliers {
+    fn default() -> Self { Self::DEFAULT }
+}
+
+/// Per-topic multipliers applied to ttl_base/fanout_base (0..1 tightens spread)
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+pub struct TopicMultiplier { pub ttl: f64, pub fanout: f64 }
+
+fn default_topic_multipliers() -> BTreeMap<ContentTopic, TopicMultiplier> {
+    let mut m = BTreeMap::new();
+    m.insert(ContentTopic::Health, TopicMultiplier { ttl: 0.75, fanout: 0.6 });
+    m.insert(ContentTopic::Elections, TopicMultiplier { ttl: 0.75, fanout: 0.6 });
+    m
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+pub struct RewardParams {
+    pub r0: f64, pub mu: f64,
+    /// Payload size that maps to w_size = 1 (log-normalized)
+    #[cfg_attr(feature = "serde", serde(default = "default_size_norm_bytes"))]
+    pub size_norm_bytes: f64,
+    /// TTFB at which w_latency = 0.5
+    #[cfg_attr(feature = "serde", serde(default = "default_latency_knee_ms"))]
+    pub latency_knee_ms: f64,
+    /// Epoch pool distribution (see `reward::epoch`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub epoch: EpochParams,
+    /// Repeat serves after which the reward halves (0 disables the discount)
+    #[cfg_attr(feature = "serde", serde(default = "default_repetition_half"))]
+    pub repetition_half: u32,
+    /// Uptime/availability component (see `calculate_availability_reward`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub availability: AvailabilityParams,
+    /// Piecewise-linear latency weight by TTFB percentile (points sorted by percentile)
+    #[cfg_attr(feature = "serde", serde(default = "default_latency_curve"))]
+    pub latency_curve: Vec<CurvePoint>,
+}
+
+/// Point on a piecewise-linear curve
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+pub struct CurvePoint { pub x: f64, pub y: f64 }
+
+fn default_latency_curve() -> Vec<CurvePoint> {
+    vec![
+        CurvePoint { x: 0.0, y: 1.0 },
+        CurvePoint { x: 0.5, y: 0.9 },
+        CurvePoint { x: 0.9, y: 0.6 },
+        CurvePoint { x: 1.0, y: 0.4 },
+    ]
+}
+
+/// Availability reward parameters
+/// - `a0`: reward per epoch for a fully available node at `stake_norm`
+/// - `min_uptime`: uptime below this earns nothing
+/// - `probes_required`: passed probes needed for full credit
+/// - `stake_norm`/`stake_exp`: stake factor `min(1, (stake/stake_norm)^stake_exp)`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+pub struct AvailabilityParams { pub a0: f64, pub min_uptime: f64, pub probes_required: u32, pub stake_norm: f64, pub stake_exp: f64 }
+impl Default for AvailabilityParams {
+    fn default() -> Self { Self{ a0:0.5, min_uptime:0.9, probes_required:10, stake_norm:1000.0, stake_exp:0.5 } }
+}
+
+/// Epoch reward pool parameters
+/// - `max_server_share`: cap on any single server's payout as a fraction of the epoch budget
+/// - `unit`: smallest payable amount; payouts are whole multiples of it
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+pub struct EpochParams { pub max_server_share: f64, pub unit: f64 }
+impl Default for EpochParams {
+    fn default() -> Self { Self{ max_server_share:0.1, unit:1e-6 } }
+}
+
+#[cfg(feature = "serde")]
+fn default_size_norm_bytes() -> f64 { 1_000_000.0 }
+#[cfg(feature = "serde")]
+fn default_latency_knee_ms() -> f64 { 1000.0 }
+#[cfg(feature = "serde")]
+fn default_repetition_half() -> u32 { 10 }
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+pub struct CongestionParams {
+    pub eta: f64, pub target_load: f64, pub base_min: f64, pub base_max: f64,
+    /// Base fare controller used by `congestion::update_base_cost_controlled`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub controller: CongestionController,
+    /// EMA weight of the newest load sample in `congestion::update_base_cost_smoothed` (1.0 = no smoothing)
+    #[cfg_attr(feature = "serde", serde(default = "default_load_ema_alpha"))]
+    pub load_ema_alpha: f64,
+    /// Surge caps and cool-down
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub surge: SurgeParams,
+}
+
+#[cfg(feature = "serde")]
+fn default_load_ema_alpha() -> f64 { 0.3 }
+
+/// Surge constraints on base fare updates
+/// - `max_increase_pct`: cap on a single update's increase (e.g. 12.5 => at most +12.5%)
+/// - `max_increases_per_window`/`window_updates`: at most N increases per window of updates (stateful: enforced by
+///   `congestion::update_base_cost_windowed` and the smoothed, simulated and epoch updates built on it)
+/// - `cooldown_factor`: multiplier on the downward step when load is below target (>1 recovers faster)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+pub struct SurgeParams {
+    pub max_increase_pct: Option<f64>,
+    pub max_increases_per_window: Option<u32>,
+    pub window_updates: u32,
+    pub cooldown_factor: f64,
+}
+impl Default for SurgeParams {
+    fn default() -> Self { Self{ max_increase_pct:None, max_increases_per_window:None, window_updates:10, cooldown_factor:1.0 } }
+}
+
+/// Base fare controller selection
+/// - `Exponential`: proportional controller of `update_base_cost` (uses `eta`)
+/// - `Pid`: log-space PID on the load error `load/target - 1`; the integral is clamped to `±i_limit`
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum CongestionController {
+    #[default]
+    Exponential,
+    Pid { kp: f64, ki: f64, kd: f64, i_limit: f64 },
+}
+
+// -------- Validation --------
+
+/// A parameter that failed validation (`path` is the dotted field path, e.g. `cost.alpha`)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ParamError { pub path: String, pub reason: String }
+
+impl std::fmt::Display for ParamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "{}: {}", self.path, self.reason) }
+}
+
+impl std::error::Error for ParamError {}
+
+impl Params {
+    /// Check ranges and internal consistency; returns every violation, not just the first
+    pub fn validate(&self) -> Result<(), Vec<ParamError>> {
+        let mut errs = Vec::new();
+        let mut check = |path: &str, ok: bool, reason: &str| {
+            if !ok { errs.push(ParamError { path: path.into(), reason: reason.into() }); }
+        };
+        let nonneg = |x: f64| x.is_finite() && x >= 0.0;
+        let pos = |x: f64| x.is_finite() && x > 0.0;
+        let unit = |x: f64| (0.0..=1.0).contains(&x);
+
+        let w = &self.q_weights;
+        for (name, x) in [("w_a", w.w_a), ("w_r", w.w_r), ("w_t", w.w_t), ("w_d", w.w_d), ("w_h", w.w_h), ("w_s", w.w_s)] {
+            check(&format!("q_weights.{}", name), nonneg(x), "must be finite and >= 0");
+        }
+        let caps = &w.unverified_caps;
+        for (name, x) in [("unverified", caps.unverified), ("email", caps.email), ("phone", caps.phone), ("identity", caps.identity)] {
+            check(&format!("q_weights.unverified_caps.{}", name), unit(x), "must be in [0, 1]");
+        }
+        check("q_min", unit(self.q_min), "must be in [0, 1]");
+        check("ef.gamma", pos(self.ef.gamma), "must be > 0");
+        check("ef.cap", pos(self.ef.cap), "must be > 0");
+
+        let c = &self.cost;
+        for (name, x) in [("alpha", c.alpha), ("beta", c.beta), ("a", c.a), ("b", c.b), ("lambda_actor", c.lambda_actor), ("lambda_content", c.lambda_content)] {
+            check(&format!("cost.{}", name), nonneg(x), "must be finite and >= 0");
+        }
+        check("cost.rate_limit_per_hour", pos(c.rate_limit_per_hour), "must be > 0");
+        check("cost.cmin", c.cmin.is_none_or(nonneg), "must be finite and >= 0");
+        check("cost.cmax", c.cmax.is_none_or(nonneg), "must be finite and >= 0");
+        if let (Some(lo), Some(hi)) = (c.cmin, c.cmax) { check("cost.cmax", hi >= lo, "must be >= cost.cmin"); }
+        check("cost.new_account_surcharge", nonneg(c.new_account_surcharge), "must be finite and >= 0");
+        check("cost.new_account_days", nonneg(c.new_account_days), "must be finite and >= 0");
+        let f = &c.fact_check;
+        for (name, x) in [("unreviewed", f.unreviewed), ("evidence_provided", f.evidence_provided), ("verified_true", f.verified_true), ("verified_false", f.verified_false), ("disputed", f.disputed)] {
+            check(&format!("cost.fact_check.{}", name), nonneg(x), "must be finite and >= 0");
+        }
+        for (kind, &x) in &c.kind_rate_limits {
+            check(&format!("cost.kind_rate_limits.{:?}", kind).to_lowercase(), pos(x), "must be > 0");
+        }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PropagationParams { pub ttl_base: f64, pub fanout_base: f64, pub k1: f64, pub k2: f64 }
+        let pr = &self.propagation;
+        for (name, x) in [("ttl_base", pr.ttl_base), ("fanout_base", pr.fanout_base), ("k1", pr.k1), ("k2", pr.k2)] {
+            check(&format!("propagation.{}", name), nonneg(x), "must be finite and >= 0");
+        }
+        for (topic, m) in &pr.topic_multipliers {
+            check(&format!("propagation.topic_multipliers.{:?}", topic), nonneg(m.ttl) && nonneg(m.fanout), "multipliers must be finite and >= 0");
+        }
+        check("propagation.quarantine_threshold", pr.quarantine_threshold.is_none_or(unit), "must be in [0, 1]");
+        check("propagation.reach.reach_cap", nonneg(pr.reach.reach_cap), "must be finite and >= 0");
+        check("propagation.schedule.immediate_below", unit(pr.schedule.immediate_below), "must be in [0, 1]");
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RewardParams { pub r0: f64, pub mu: f64 }
+        let r = &self.reward;
+        check("reward.r0", nonneg(r.r0), "must be finite and >= 0");
+        check("reward.mu", unit(r.mu), "must be in [0, 1]");
+        check("reward.size_norm_bytes", pos(r.size_norm_bytes), "must be > 0");
+        check("reward.latency_knee_ms", pos(r.latency_knee_ms), "must be > 0");
+        check("reward.epoch.max_server_share", r.epoch.max_server_share > 0.0 && r.epoch.max_server_share <= 1.0, "must be in (0, 1]");
+        check("reward.epoch.unit", pos(r.epoch.unit), "must be > 0");
+        check("reward.availability.min_uptime", unit(r.availability.min_uptime), "must be in [0, 1]");
+        check("reward.availability.stake_norm", pos(r.availability.stake_norm), "must be > 0");
+        check("reward.latency_curve", r.latency_curve.windows(2).all(|p| p[0].x <= p[1].x), "points must be sorted by x");
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CongestionParams { pub eta: f64, pub target_load: f64, pub base_min: f64, pub base_max: f64 }
+        let g = &self.congestion;
+        check("congestion.eta", nonneg(g.eta), "must be finite and >= 0");
+        check("congestion.target_load", pos(g.target_load), "must be > 0");
+        check("congestion.base_min", pos(g.base_min), "must be > 0");
+        check("congestion.base_max", g.base_max.is_finite() && g.base_max >= g.base_min, "must be finite and >= base_min");
+        check("congestion.load_ema_alpha", g.load_ema_alpha > 0.0 && g.load_ema_alpha <= 1.0, "must be in (0, 1]");
+        check("congestion.surge.max_increase_pct", g.surge.max_increase_pct.is_none_or(nonneg), "must be finite and >= 0");
+        check("congestion.surge.window_updates", g.surge.window_updates >= 1, "must be >= 1");
+        check("congestion.surge.cooldown_factor", pos(g.surge.cooldown_factor), "must be > 0");
+
+        if errs.is_empty() { Ok(()) } else { Err(errs) }
+    }
+}
 
 /// Quality score inputs
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct QInputs { pub A: f64, pub R: f64, pub T: f64, pub D: f64, pub H: f64, pub S: f64 }
+/// - Serialized as `A,R,T,D,H,S`; the snake_case names are accepted on input (output them via `QInputsSnakeCase`)
+#[allow(non_snake_case)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+pub struct QInputs {
+    #[cfg_attr(feature = "serde", serde(alias = "authenticity"))]
+    pub A: f64,
+    #[cfg_attr(feature = "serde", serde(alias = "reliability"))]
+    pub R: f64,
+    #[cfg_attr(feature = "serde", serde(alias = "timeliness"))]
+    pub T: f64,
+    #[cfg_attr(feature = "serde", serde(alias = "depth"))]
+    pub D: f64,
+    #[cfg_attr(feature = "serde", serde(alias = "human_verified"))]
+    pub H: f64,
+    #[cfg_attr(feature = "serde", serde(alias = "spam_signal"))]
+    pub S: f64,
+}
+
+/// `QInputs` under snake_case field names (serialize this to emit them; `A,R,T,D,H,S` are accepted on input)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct QInputsSnakeCase {
+    #[cfg_attr(feature = "serde", serde(alias = "A"))]
+    pub authenticity: f64,
+    #[cfg_attr(feature = "serde", serde(alias = "R"))]
+    pub reliability: f64,
+    #[cfg_attr(feature = "serde", serde(alias = "T"))]
+    pub timeliness: f64,
+    #[cfg_attr(feature = "serde", serde(alias = "D"))]
+    pub depth: f64,
+    #[cfg_attr(feature = "serde", serde(alias = "H"))]
+    pub human_verified: f64,
+    #[cfg_attr(feature = "serde", serde(alias = "S"))]
+    pub spam_signal: f64,
+}
+
+impl From<&QInputs> for QInputsSnakeCase {
+    fn from(q: &QInputs) -> Self {
+        QInputsSnakeCase { authenticity: q.A, reliability: q.R, timeliness: q.T, depth: q.D, human_verified: q.H, spam_signal: q.S }
+    }
+}
+
+impl From<QInputsSnakeCase> for QInputs {
+    fn from(q: QInputsSnakeCase) -> Self {
+        QInputs { A: q.authenticity, R: q.reliability, T: q.timeliness, D: q.depth, H: q.human_verified, S: q.spam_signal }
+    }
+}
+
+/// How far an account's identity has been verified (ordered weakest to strongest)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum VerificationLevel { #[default] Unverified, Email, Phone, Identity }
 
 /// Actor (author) input
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
 pub struct Actor {
     /// Recent average request load (keep unit definition consistent, e.g., per minute)
     pub rl: f64,
@@ -75,92 +645,368 @@ pub struct Actor {
     pub ef: f64,
     /// Posts in the last hour (used for rate-limit penalty)
     pub posts_1h: Option<f64>,
+    /// Account age in days (drives the new-account surcharge; None = unknown)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub account_age_days: Option<f64>,
+    /// None = unknown, treated as unverified
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub verification_level: Option<VerificationLevel>,
+    /// Posts of the current content's kind in the last hour (per-kind rate limit; None = use `posts_1h`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub kind_posts_1h: Option<f64>,
 }
 
-/// Content input (factual claim/evidence and risk signals)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Content input (factual claim/evidence, risk signals and topic)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
 pub struct Content {
     pub is_claim: Option<bool>,
     pub has_evidence: Option<bool>,
     pub risk_signals: Option<RiskSignals>,
+    pub topic: Option<ContentTopic>,
+    /// None = uncategorized (global rate limit)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub kind: Option<ContentKind>,
+    /// Takes precedence over `is_claim`/`has_evidence` when set (see `Content::fact_check_state`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fact_check: Option<FactCheckState>,
+}
+
+impl Content {
+    /// `fact_check`, else the legacy flags: a claim with evidence is `EvidenceProvided`, without `Unreviewed`;
+    /// None = not a claim (no multiplier)
+    pub fn fact_check_state(&self) -> Option<FactCheckState> {
+        self.fact_check.or(match (self.is_claim, self.has_evidence) {
+            (Some(true), Some(true)) => Some(FactCheckState::EvidenceProvided),
+            (Some(true), _) => Some(FactCheckState::Unreviewed),
+            _ => None,
+        })
+    }
+}
+
+/// Historical post event (actor/content state and base fare at posting time)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PostEvent {
+    pub actor: Actor,
+    pub content: Content,
+    pub base_fare: f64,
 }
 
 /// Risk signals (0..1)
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
 pub struct RiskSignals {
     pub coordination: Option<f64>,
     pub clustering: Option<f64>,
     pub burst: Option<f64>,
     pub monotonicity: Option<f64>,
     pub abuse_history: Option<f64>,
+    /// Near-duplicate share among recent posts (see `risk::duplication_score`)
+    pub duplication: Option<f64>,
 }
 
 /// Risk weights
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RiskWeights { pub w_coord: f64, pub w_clust: f64, pub w_burst: f64, pub w_mono: f64, pub w_hist: f64 }
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RiskWeights {
+    pub w_coord: f64, pub w_clust: f64, pub w_burst: f64, pub w_mono: f64, pub w_hist: f64,
+    #[cfg_attr(feature = "serde", serde(default = "default_w_dup"))]
+    pub w_dup: f64,
+}
+impl RiskWeights {
+    /// Default weights as a constant, so hot paths borrow instead of rebuilding them
+    pub const DEFAULT: Self = Self{ w_coord:0.25, w_clust:0.25, w_burst:0.20, w_mono:0.15, w_hist:0.15, w_dup:0.2 };
+}
+#[cfg(feature = "serde")]
+fn default_w_dup() -> f64 { RiskWeights::DEFAULT.w_dup }
 impl Default for RiskWeights {
-    fn default() -> Self { Self{ w_coord:0.25, w_clust:0.25, w_burst:0.20, w_mono:0.15, w_hist:0.15 } }
+    fn default() -> Self { Self::DEFAULT }
 }
 
 /// Propagation result
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PropagationResult { pub ttl: u32, pub fanout: u32 }
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+pub struct PropagationResult {
+    pub ttl: u32,
+    pub fanout: u32,
+    /// True when risk exceeded `quarantine_threshold` (ttl and fanout are 0)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub quarantined: bool,
+}
+
+impl PropagationResult {
+    /// Author-only visibility
+    pub fn quarantine() -> Self { Self{ ttl:0, fanout:0, quarantined:true } }
+}
 
 /// Reward calculation input
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
 pub struct RewardInput {
     pub ticket_budget: f64,
     pub client_q: f64,
     pub size_bytes: u64,
     pub ttfb_ms: u32,
     pub server_cluster_risk: f64,
+    /// Prior serves of the same content to the same client (drives the repetition discount)
+    pub prev_serves: Option<u32>,
+    /// Server's recent TTFB percentile vs. network targets (0 = fastest, 1 = slowest);
+    /// when set, `w_latency` comes from `RewardParams::latency_curve` instead of the ttfb knee
+    pub ttfb_percentile: Option<f64>,
+    /// Serving node's region; not used by `calculate_serve_reward` (see `geo::calculate_serve_reward_geo`)
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub region: Option<String>,
 }
 
+/// Weighted quality terms (see `calculate_quality_detailed`); `s` is the (negative) spam penalty
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct QualityBreakdown {
+    pub a: f64, pub r: f64, pub t: f64, pub d: f64, pub h: f64, pub s: f64,
+    /// Sum before clamping to 0..1
+    pub raw: f64,
+    /// True when `H == 0` capped q at the unverified cap
+    pub unverified_cap_applied: bool,
+    pub q: f64,
+}
+
+/// Weighted risk signal contributions (see `calculate_risk_detailed`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RiskBreakdown {
+    pub coordination: f64,
+    pub clustering: f64,
+    pub burst: f64,
+    pub monotonicity: f64,
+    pub abuse_history: f64,
+    pub duplication: f64,
+    /// Sum before clamping to 0..1
+    pub raw: f64,
+    pub risk: f64,
+}
+
+/// Posting cost terms (see `calculate_post_cost_detailed`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CostBreakdown {
+    pub base_fare: f64,
+    /// `a * max(RL,0)^alpha`
+    pub rl_cost: f64,
+    /// `b * max(EF,0)^beta`
+    pub ef_cost: f64,
+    pub risk_actor: f64,
+    pub risk_content: f64,
+    pub risk_multiplier: f64,
+    pub claim_multiplier: f64,
+    pub rate_multiplier: f64,
+    /// New-account surcharge multiplier (1.0 for established or unknown-age accounts)
+    pub age_multiplier: f64,
+    /// Tier discount (1.0 outside the tier wrapper)
+    pub discount: f64,
+    /// Cost before the C_min floor
+    pub pre_floor: f64,
+    /// True when the C_min floor bound the result
+    pub cmin_applied: bool,
+    /// True when the C_max ceiling bound the result
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cmax_applied: bool,
+    pub cost: f64,
+}
+
+/// Serve reward factors (see `calculate_serve_reward_detailed`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RewardBreakdown {
+    pub w_size: f64,
+    pub w_latency: f64,
+    /// Server diversity factor `1 - mu * cluster_risk`
+    pub diversity: f64,
+    /// Client quality factor (clamped to 0..1)
+    pub client_q: f64,
+    /// Repeated client/content discount (1.0 for a first serve)
+    pub repetition: f64,
+    /// Reward before the ticket budget cap
+    pub uncapped: f64,
+    pub reward: f64,
+    /// True when the ticket budget bound the result
+    pub budget_capped: bool,
+}
+
+/// Follower identifier in `calculate_ef_detailed`
+pub type FollowerId = String;
+
+/// One follower's share of EF (see `calculate_ef_detailed`)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EfContribution {
+    pub follower: FollowerId,
+    pub q: f64,
+    /// `q^gamma`, this follower's term of `sum`
+    pub weight: f64,
+    /// `weight / sum`
+    pub share: f64,
+}
+
+/// EF terms (see `calculate_ef_detailed`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EfBreakdown {
+    /// Largest contributors, by weight (ties by id), at most `EF_TOP_CONTRIBUTORS`
+    pub top: Vec<EfContribution>,
+    pub contributing: usize,
+    /// Followers below `q_min` (or with a non-numeric q)
+    pub excluded: usize,
+    /// `Σ q^gamma` over contributing followers, before `ln(1 + sum) * cap`
+    pub sum: f64,
+    pub ef: f64,
+}
+
+/// Contributors listed in `EfBreakdown::top`
+pub const EF_TOP_CONTRIBUTORS: usize = 10;
+
 // -------- Utilities --------
 
-fn clamp(x: f64, lo: f64, hi: f64) -> f64 { x.max(lo).min(hi) }
+pub(crate) fn clamp(x: f64, lo: f64, hi: f64) -> f64 { x.max(lo).min(hi) }
 
 fn v(opt: Option<f64>) -> f64 { opt.unwrap_or(0.0) }
 
+/// Piecewise-linear interpolation over points sorted by x (flat beyond the ends)
+pub(crate) fn interpolate(points: &[CurvePoint], x: f64) -> f64 {
+    match points {
+        [] => 0.0,
+        [first, ..] if x <= first.x => first.y,
+        [.., last] if x >= last.x => last.y,
+        _ => {
+            let i = points.windows(2).position(|w| x <= w[1].x).unwrap_or(0);
+            let (a, b) = (points[i], points[i + 1]);
+            if b.x <= a.x { return b.y; }
+            a.y + (b.y - a.y) * (x - a.x) / (b.x - a.x)
+        }
+    }
+}
+
 // -------- Quality/EF --------
 
 /// Compute quality score q
-pub fn calculate_quality(inp: QInputs, params: &Params) -> f64 {
-    let w = &params.q_weights;
-    let mut q = w.w_a*inp.A + w.w_r*inp.R + w.w_t*inp.T + w.w_d*inp.D + w.w_h*inp.H - w.w_s*inp.S;
-    q = clamp(q, 0.0, 1.0);
-    if inp.H == 0.0 { q = q.min(0.4); } // TG unverified cap
-    q
+pub fn calculate_quality(inp: QInputs, params: &impl AsParams) -> f64 {
+    calculate_quality_detailed(&inp, params).q
+}
+
+/// Compute quality score q for an author at `level`: higher verification relaxes the `H == 0` cap
+pub fn calculate_quality_with_verification(inp: QInputs, level: Option<VerificationLevel>, params: &impl AsParams) -> f64 {
+    quality_breakdown(&inp, level, params).q
+}
+
+/// Compute quality score q with per-input contributions
+pub fn calculate_quality_detailed(inp: &QInputs, params: &impl AsParams) -> QualityBreakdown {
+    quality_breakdown(inp, None, params)
+}
+
+fn quality_breakdown(inp: &QInputs, level: Option<VerificationLevel>, params: &impl AsParams) -> QualityBreakdown {
+    let w = &params.params().q_weights;
+    let (a, r, t, d, h, s) = (w.w_a*inp.A, w.w_r*inp.R, w.w_t*inp.T, w.w_d*inp.D, w.w_h*inp.H, -w.w_s*inp.S);
+    let raw = a + r + t + d + h + s;
+    let mut q = clamp(raw, 0.0, 1.0);
+    let cap = w.unverified_caps.cap(level);
+    let unverified_cap_applied = inp.H == 0.0 && q > cap;
+    if inp.H == 0.0 { q = q.min(cap); } // TG unverified cap
+    #[cfg(feature = "tracing")]
+    tracing::trace!(target: "slimechain::quality", input = %trace::debug_hash(inp), raw, q, unverified_cap_applied, "quality");
+    QualityBreakdown { a, r, t, d, h, s, raw, unverified_cap_applied, q }
 }
 
 /// Compute effective followers EF
-pub fn calculate_ef(followers_q: &[f64], params: &Params) -> f64 {
+pub fn calculate_ef(followers_q: &[f64], params: &impl AsParams) -> f64 {
+    let params = params.params();
     let gamma = params.ef.gamma;
     let cap = params.ef.cap;
     let mut sum = 0.0;
     for &q in followers_q {
-        if q >= params.q_min { sum += q.powf(gamma); }
+        if q >= params.q_min { sum += fastmath::powf_sel(q, gamma); }
     }
-    sum.ln_1p() * cap
+    fastmath::ln_1p_sel(sum) * cap
+}
+
+/// Compute EF with its largest contributors and the excluded count (for influence-score disputes)
+pub fn calculate_ef_detailed(followers: &[(FollowerId, f64)], params: &impl AsParams) -> EfBreakdown {
+    let params = params.params();
+    let gamma = params.ef.gamma;
+    let mut sum = 0.0;
+    let mut contributors = Vec::new();
+    for (id, q) in followers {
+        if *q >= params.q_min {
+            let weight = fastmath::powf_sel(*q, gamma);
+            sum += weight;
+            contributors.push((id, *q, weight));
+        }
+    }
+    let (contributing, excluded) = (contributors.len(), followers.len() - contributors.len());
+    contributors.sort_by(|a, b| b.2.total_cmp(&a.2).then_with(|| a.0.cmp(b.0)));
+    let top = contributors.into_iter().take(EF_TOP_CONTRIBUTORS).map(|(id, q, weight)| EfContribution {
+        follower: id.clone(), q, weight, share: if sum > 0.0 { weight / sum } else { 0.0 },
+    }).collect();
+    EfBreakdown { top, contributing, excluded, sum, ef: fastmath::ln_1p_sel(sum) * params.ef.cap }
 }
 
 // -------- Risk --------
 
 /// Compute risk score (0..1)
 pub fn calculate_risk(signals: &Option<RiskSignals>, weights: &RiskWeights) -> f64 {
-    let s = signals.as_ref().cloned().unwrap_or_default();
-    let r = weights.w_coord*v(s.coordination)
-          + weights.w_clust*v(s.clustering)
-          + weights.w_burst*v(s.burst)
-          + weights.w_mono*v(s.monotonicity)
-          + weights.w_hist*v(s.abuse_history);
-    clamp(r, 0.0, 1.0)
+    calculate_risk_detailed(signals, weights).risk
+}
+
+/// Compute risk score with per-signal contributions
+pub fn calculate_risk_detailed(signals: &Option<RiskSignals>, weights: &RiskWeights) -> RiskBreakdown {
+    // borrow the signals; absent ones read as 0
+    let sig = |f: fn(&RiskSignals) -> Option<f64>| v(signals.as_ref().and_then(f));
+    let coordination = weights.w_coord*sig(|s| s.coordination);
+    let clustering = weights.w_clust*sig(|s| s.clustering);
+    let burst = weights.w_burst*sig(|s| s.burst);
+    let monotonicity = weights.w_mono*sig(|s| s.monotonicity);
+    let abuse_history = weights.w_hist*sig(|s| s.abuse_history);
+    let duplication = weights.w_dup*sig(|s| s.duplication);
+    let raw = coordination + clustering + burst + monotonicity + abuse_history + duplication;
+    RiskBreakdown { coordination, clustering, burst, monotonicity, abuse_history, duplication, raw, risk: clamp(raw, 0.0, 1.0) }
 }
 
 // -------- Posting cost (DPP) --------
 
 /// Compute posting cost
-pub fn calculate_post_cost(actor: &Actor, content: &Content, params: &Params, base_fare: f64) -> f64 {
+pub fn calculate_post_cost(actor: &Actor, content: &Content, params: &impl AsParams, base_fare: f64) -> f64 {
+    calculate_post_cost_with_risk_factor(actor, content, params, base_fare, 1.0)
+}
+
+/// Compute posting cost with actor/content risk scaled by `risk_factor` (e.g., tier attenuation; clamped to 0..1 after scaling)
+pub fn calculate_post_cost_with_risk_factor(actor: &Actor, content: &Content, params: &impl AsParams, base_fare: f64, risk_factor: f64) -> f64 {
+    post_cost_breakdown(actor, content, params, base_fare, risk_factor, 1.0, None).cost
+}
+
+/// Compute posting cost with every term exposed
+pub fn calculate_post_cost_detailed(actor: &Actor, content: &Content, params: &impl AsParams, base_fare: f64) -> CostBreakdown {
+    post_cost_breakdown(actor, content, params, base_fare, 1.0, 1.0, None)
+}
+
+/// Shared cost path: terms -> risk/claim/rate multipliers -> `discount` -> floor `max(cost.cmin, extra_floor)` -> ceiling `cost.cmax`
+pub(crate) fn post_cost_breakdown(actor: &Actor, content: &Content, params: &impl AsParams, base_fare: f64, risk_factor: f64, discount: f64, extra_floor: Option<f64>) -> CostBreakdown {
+    let derived = params.derived();
+    let params = params.params();
     let a = params.cost.a;
     let b = params.cost.b;
     let alpha = params.cost.alpha;
@@ -168,65 +1014,203 @@ pub fn calculate_post_cost(actor: &Actor, content: &Content, params: &Params, ba
     let lambda_a = params.cost.lambda_actor;
     let lambda_c = params.cost.lambda_content;
 
-    let rl_cost = a * actor.rl.max(0.0).powf(alpha);
-    let ef_cost = b * actor.ef.max(0.0).powf(beta);
+    let rl_cost = a * fastmath::powf_sel(actor.rl.max(0.0), alpha);
+    let ef_cost = b * fastmath::powf_sel(actor.ef.max(0.0), beta);
     let mut cost = base_fare + rl_cost + ef_cost;
 
-    let weights = RiskWeights::default();
-    let risk_actor = calculate_risk(&content.risk_signals, &weights);
-    let risk_content = calculate_risk(&content.risk_signals, &weights);
-    cost *= 1.0 + lambda_a*risk_actor + lambda_c*risk_content;
+    // actor and content risk both come from the content's signals, so score them once
+    let risk = clamp(calculate_risk(&content.risk_signals, &RiskWeights::DEFAULT) * risk_factor.max(0.0), 0.0, 1.0);
+    let (risk_actor, risk_content) = (risk, risk);
+    let risk_multiplier = 1.0 + lambda_a*risk_actor + lambda_c*risk_content;
+    cost *= risk_multiplier;
 
-    if content.is_claim.unwrap_or(false) {
-        if content.has_evidence.unwrap_or(false) { cost *= 0.7; }
-        else { cost *= 1.2; }
-    }
+    let claim_multiplier = content.fact_check_state().map_or(1.0, |s| params.cost.fact_check.get(s));
+    cost *= claim_multiplier;
 
-    if let Some(posts) = actor.posts_1h {
-        let rate = params.cost.rate_limit_per_hour.max(1.0);
+    let mut rate_multiplier = 1.0;
+    if let (Some(posts), rate) = rate_limit_for(actor, content, &params.cost, derived.rate_limit) {
         if posts > rate {
             let over = posts / rate - 1.0;
-            cost *= 1.0 + 0.5 * over;
+            rate_multiplier = 1.0 + 0.5 * over;
+        }
+    }
+    cost *= rate_multiplier;
+
+    let mut age_multiplier = 1.0;
+    if let Some(age) = actor.account_age_days {
+        let window = params.cost.new_account_days;
+        if window > 0.0 && age < window {
+            age_multiplier = 1.0 + params.cost.new_account_surcharge * (1.0 - age.max(0.0) / window);
         }
     }
-    cost
+    cost *= age_multiplier;
+    cost *= discount;
+
+    let pre_floor = cost;
+    let floor = match (params.cost.cmin, extra_floor) {
+        (Some(x), Some(y)) => Some(x.max(y)),
+        (x, y) => x.or(y),
+    };
+    let cmin_applied = floor.is_some_and(|f| cost < f);
+    if let Some(f) = floor.filter(|_| cmin_applied) { cost = f; }
+    let cmax_applied = params.cost.cmax.is_some_and(|c| cost > c);
+    if let Some(c) = params.cost.cmax.filter(|_| cmax_applied) { cost = c; }
+    #[cfg(feature = "tracing")]
+    tracing::trace!(
+        target: "slimechain::cost", input = %trace::debug_hash(&(actor, content, base_fare, risk_factor, discount)),
+        cost, pre_floor, risk_multiplier, claim_multiplier, rate_multiplier, age_multiplier, cmin_applied, cmax_applied, "post cost",
+    );
+
+    CostBreakdown {
+        base_fare, rl_cost, ef_cost, risk_actor, risk_content, risk_multiplier,
+        claim_multiplier, rate_multiplier, age_multiplier, discount, pre_floor, cmin_applied, cmax_applied, cost,
+    }
+}
+
+/// Cost change when a post's fact-check verdict becomes `verdict` after posting: > 0 is owed, < 0 is refunded
+/// (both costs at the original `base_fare`, floors and ceiling included)
+pub fn fact_check_adjustment(actor: &Actor, content: &Content, verdict: FactCheckState, params: &impl AsParams, base_fare: f64) -> f64 {
+    let revised = Content { fact_check: Some(verdict), ..content.clone() };
+    calculate_post_cost(actor, &revised, params, base_fare) - calculate_post_cost(actor, content, params, base_fare)
+}
+
+/// Posts counted against the limit and the limit itself: the content kind's own limit when one is configured, else
+/// `posts_1h` against the global `rate_limit` (already floored at 1)
+pub(crate) fn rate_limit_for(actor: &Actor, content: &Content, cost: &CostParams, rate_limit: f64) -> (Option<f64>, f64) {
+    match content.kind.and_then(|k| cost.kind_rate_limits.get(&k)) {
+        Some(&limit) => (actor.kind_posts_1h.or(actor.posts_1h), limit.max(1.0)),
+        None => (actor.posts_1h, rate_limit),
+    }
+}
+
+/// Replay an actor's recent posts under a candidate parameter set and return the cost trajectory
+/// (one entry per event, same order as `history`)
+pub fn simulate_actor_costs(history: &[PostEvent], params: &impl AsParams) -> Vec<f64> {
+    history.iter()
+        .map(|e| calculate_post_cost(&e.actor, &e.content, params, e.base_fare))
+        .collect()
 }
 
 // -------- Propagation control (RWP/TFR) --------
 
 /// Adjust TTL/Fanout
-pub fn adjust_propagation(risk_signals: &Option<RiskSignals>, params: &Params) -> PropagationResult {
-    let weights = RiskWeights::default();
-    let risk = calculate_risk(risk_signals, &weights);
-    let ttl = clamp(params.propagation.ttl_base - params.propagation.k1 * risk, 1.0, params.propagation.ttl_base);
-    let fanout = clamp(params.propagation.fanout_base - params.propagation.k2 * risk, 1.0, params.propagation.fanout_base);
-    PropagationResult { ttl: ttl.round() as u32, fanout: fanout.round() as u32 }
+pub fn adjust_propagation(risk_signals: &Option<RiskSignals>, params: &impl AsParams) -> PropagationResult {
+    propagation_decision(risk_signals, None, 1.0, params.params())
+}
+
+/// Adjust TTL/Fanout for a piece of content (risk signals plus topic multipliers)
+pub fn adjust_propagation_for_content(content: &Content, params: &impl AsParams) -> PropagationResult {
+    propagation_decision(&content.risk_signals, content.topic, 1.0, params.params())
+}
+
+/// Adjust TTL/Fanout for a piece of content with its risk scaled by `risk_factor` (e.g., tier attenuation)
+pub fn adjust_propagation_with_risk_factor(content: &Content, params: &impl AsParams, risk_factor: f64) -> PropagationResult {
+    propagation_decision(&content.risk_signals, content.topic, risk_factor, params.params())
+}
+
+fn propagation_decision(risk_signals: &Option<RiskSignals>, topic: Option<ContentTopic>, risk_factor: f64, params: &Params) -> PropagationResult {
+    let risk = clamp(calculate_risk(risk_signals, &RiskWeights::DEFAULT) * risk_factor.max(0.0), 0.0, 1.0);
+    let out = propagation_at_risk(risk, topic, params);
+    #[cfg(feature = "tracing")]
+    if out.quarantined {
+        tracing::trace!(target: "slimechain::propagation", input = %trace::debug_hash(&(risk_signals, topic)), risk, "quarantined");
+    } else {
+        tracing::trace!(target: "slimechain::propagation", input = %trace::debug_hash(&(risk_signals, topic)), risk, ttl = out.ttl, fanout = out.fanout, "propagation");
+    }
+    out
+}
+
+/// TTL/fanout (or quarantine) for an already computed risk (0..1)
+pub(crate) fn propagation_at_risk(risk: f64, topic: Option<ContentTopic>, params: &Params) -> PropagationResult {
+    let p = &params.propagation;
+    if p.quarantine_threshold.is_some_and(|t| risk > t) { return PropagationResult::quarantine(); }
+    let m = topic.and_then(|t| p.topic_multipliers.get(&t).copied())
+        .unwrap_or(TopicMultiplier { ttl: 1.0, fanout: 1.0 });
+    let ttl_base = (p.ttl_base * clamp(m.ttl, 0.0, 1.0)).max(1.0);
+    let fanout_base = (p.fanout_base * clamp(m.fanout, 0.0, 1.0)).max(1.0);
+    let ttl = clamp(ttl_base - p.k1 * risk, 1.0, ttl_base);
+    let fanout = clamp(fanout_base - p.k2 * risk, 1.0, fanout_base);
+    PropagationResult { ttl: ttl.round() as u32, fanout: fanout.round() as u32, quarantined: false }
 }
 
 // -------- PoR/S reward --------
 
 /// Compute serving reward
-pub fn calculate_serve_reward(input: &RewardInput, params: &Params) -> f64 {
+pub fn calculate_serve_reward(input: &RewardInput, params: &impl AsParams) -> f64 {
+    calculate_serve_reward_detailed(input, params).reward
+}
+
+/// Compute serving reward with every factor exposed (for payout explanations)
+pub fn calculate_serve_reward_detailed(input: &RewardInput, params: &impl AsParams) -> RewardBreakdown {
+    let derived = params.derived();
+    let params = params.params();
     let r0 = params.reward.r0;
     let mu = params.reward.mu;
-    let w_size = (1.0 + (input.size_bytes as f64)).ln() / (1.0 + 1_000_000.0_f64).ln();
-    let w_latency = 1.0 / (1.0 + (input.ttfb_ms as f64) / 1000.0);
+    let knee = derived.latency_knee;
+    let w_size = (1.0 + (input.size_bytes as f64)).ln() / derived.ln_size_norm;
+    let w_latency = match input.ttfb_percentile {
+        Some(pct) if !params.reward.latency_curve.is_empty() => clamp(interpolate(&params.reward.latency_curve, clamp(pct, 0.0, 1.0)), 0.0, 1.0),
+        _ => 1.0 / (1.0 + (input.ttfb_ms as f64) / knee),
+    };
     let diversity = 1.0 - mu * clamp(input.server_cluster_risk, 0.0, 1.0);
-    let reward = r0 * clamp(input.client_q, 0.0, 1.0) * w_size * w_latency * diversity;
-    reward.min(input.ticket_budget.max(0.0))
+    let client_q = clamp(input.client_q, 0.0, 1.0);
+    let repetition = reward::repetition_factor(input.prev_serves.unwrap_or(0), params.reward.repetition_half);
+    let uncapped = r0 * client_q * w_size * w_latency * diversity * repetition;
+    let budget = input.ticket_budget.max(0.0);
+    #[cfg(feature = "tracing")]
+    tracing::trace!(target: "slimechain::reward", input = %trace::debug_hash(input), uncapped, budget, budget_capped = uncapped > budget, "serve reward");
+    RewardBreakdown {
+        w_size, w_latency, diversity, client_q, repetition, uncapped,
+        reward: uncapped.min(budget),
+        budget_capped: uncapped > budget,
+    }
+}
+
+/// Compute the availability reward for a storage node over one epoch (complements serve-triggered rewards)
+pub fn calculate_availability_reward(uptime_ratio: f64, probes_passed: u32, stake: impl Into<Social>, params: &impl AsParams) -> f64 {
+    let ap = &params.params().reward.availability;
+    let stake = stake.into().get();
+    let min_up = clamp(ap.min_uptime, 0.0, 1.0);
+    let uptime = clamp(uptime_ratio, 0.0, 1.0);
+    if uptime < min_up { return 0.0; }
+    let uptime_factor = if min_up >= 1.0 { 1.0 } else { (uptime - min_up) / (1.0 - min_up) };
+    let probe_factor = if ap.probes_required == 0 { 1.0 } else { (probes_passed as f64 / ap.probes_required as f64).min(1.0) };
+    let stake_factor = fastmath::powf_sel(stake / ap.stake_norm.max(1e-9), ap.stake_exp.max(0.0)).min(1.0);
+    ap.a0.max(0.0) * uptime_factor * probe_factor * stake_factor
 }
 
 // -------- Congestion control base fare --------
 
-/// Update base fare
-pub fn update_base_cost(current_base: f64, current_load: f64, params: &Params) -> f64 {
-    let eta = params.congestion.eta;
-    let target = params.congestion.target_load.max(1e-9);
-    let mut b = current_base * ((eta * (current_load / target - 1.0))).exp();
-    b = clamp(b, params.congestion.base_min, params.congestion.base_max);
+/// Update base fare (one stateless step: per-update surge cap, cool-down, base_min/base_max; the per-window increase
+/// cap needs the window counter, see `congestion::update_base_cost_windowed`)
+pub fn update_base_cost(current_base: f64, current_load: f64, params: &impl AsParams) -> f64 {
+    step_base_cost(current_base, current_load, &params.params().congestion)
+}
+
+/// Exponential base fare step for one congestion dimension
+pub(crate) fn step_base_cost(current_base: f64, current_load: f64, c: &CongestionParams) -> f64 {
+    let target = c.target_load.max(1e-9);
+    let mut step = c.eta * (current_load / target - 1.0);
+    if step < 0.0 { step *= c.surge.cooldown_factor.max(0.0); }
+    let proposed = current_base * fastmath::exp_sel(step);
+    let capped = cap_increase(current_base, proposed, c);
+    let b = clamp(capped, c.base_min, c.base_max);
+    #[cfg(feature = "tracing")]
+    tracing::trace!(
+        target: "slimechain::base_fare", current_base, current_load, proposed, base = b,
+        surge_capped = capped < proposed, clamped = b != capped, "base fare step",
+    );
     b
 }
 
+/// Enforce `surge.max_increase_pct` on a proposed base fare
+pub(crate) fn cap_increase(current_base: f64, proposed: f64, c: &CongestionParams) -> f64 {
+    match c.surge.max_increase_pct {
+        Some(pct) if current_base > 0.0 => proposed.min(current_base * (1.0 + pct.max(0.0) / 100.0)),
+        _ => proposed,
+    }
+}
+
 // -------- Tests (basic) --------
 
 #[cfg(test)]
@@ -234,6 +1218,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::manual_range_contains)]
     fn test_quality_ef() {
         let params = Params::default();
         let q = calculate_quality(QInputs{ A:0.8, R:0.7, T:0.6, D:0.5, H:1.0, S:0.2 }, &params);
@@ -242,26 +1227,290 @@ mod tests {
         assert!(ef > 0.0);
     }
 
+    #[test]
+    fn test_quality_bounds_at_extremes() {
+        let params = Params::default();
+        for (a, h) in [(0.0, 1.0), (1.0, 0.0)] {
+            let q = calculate_quality(QInputs{ A:a, R:a, T:a, D:a, H:h, S:h }, &params);
+            assert!((0.0..=1.0).contains(&q));
+        }
+    }
+
     #[test]
     fn test_cost_prop_reward() {
         let params = Params::default();
-        let actor = Actor { rl:120.0, q:0.8, ef:30.0, posts_1h:Some(12.0) };
-        let content = Content { is_claim:Some(true), has_evidence:Some(false), risk_signals:Some(RiskSignals{ coordination:Some(0.5), clustering:Some(0.4), burst:None, monotonicity:None, abuse_history:None }) };
+        let actor = Actor { rl:120.0, q:0.8, ef:30.0, posts_1h:Some(12.0), ..Default::default() };
+        let content = Content { is_claim:Some(true), has_evidence:Some(false), risk_signals:Some(RiskSignals{ coordination:Some(0.5), clustering:Some(0.4), burst:None, monotonicity:None, abuse_history:None, duplication:None }), topic:None, kind:None, fact_check:None };
         let cost = calculate_post_cost(&actor, &content, &params, 1.0);
         assert!(cost > 0.0);
 
         let pr = adjust_propagation(&content.risk_signals, &params);
         assert!(pr.ttl >= 1 && pr.ttl <= params.propagation.ttl_base as u32);
 
-        let ri = RewardInput{ ticket_budget:1.5, client_q:0.8, size_bytes:24000, ttfb_ms:120, server_cluster_risk:0.2 };
+        let ri = RewardInput{ ticket_budget:1.5, client_q:0.8, size_bytes:24000, ttfb_ms:120, server_cluster_risk:0.2, ..Default::default() };
         let rew = calculate_serve_reward(&ri, &params);
         assert!(rew >= 0.0);
     }
 
+    #[test]
+    fn test_simulate_actor_costs() {
+        let params = Params::default();
+        let ev = PostEvent {
+            actor: Actor { rl:120.0, q:0.8, ef:30.0, posts_1h:Some(12.0), ..Default::default() },
+            content: Content { is_claim:None, has_evidence:None, risk_signals:None, topic:None, kind:None, fact_check:None },
+            base_fare: 1.0,
+        };
+        let history = vec![ev.clone(), ev];
+        let base = simulate_actor_costs(&history, &params);
+        assert_eq!(base.len(), 2);
+        assert_eq!(base[0], calculate_post_cost(&history[0].actor, &history[0].content, &params, 1.0));
+
+        let mut steeper = params.clone();
+        steeper.cost.alpha = 0.9;
+        let what_if = simulate_actor_costs(&history, &steeper);
+        assert!(what_if[0] > base[0]);
+    }
+
+    #[test]
+    fn test_topic_multipliers() {
+        let params = Params::default();
+        let general = Content { topic:Some(ContentTopic::General), ..Default::default() };
+        let health = Content { topic:Some(ContentTopic::Health), ..Default::default() };
+        let pg = adjust_propagation_for_content(&general, &params);
+        let ph = adjust_propagation_for_content(&health, &params);
+        assert_eq!((pg.ttl, pg.fanout), (4, 5));
+        assert!(ph.ttl < pg.ttl && ph.fanout < pg.fanout);
+    }
+
+    #[test]
+    fn test_quarantine_threshold() {
+        let mut params = Params::default();
+        let signals = Some(RiskSignals{ coordination:Some(1.0), clustering:Some(1.0), burst:Some(1.0), ..Default::default() });
+        let pr = adjust_propagation(&signals, &params);
+        assert!(!pr.quarantined && pr.ttl >= 1 && pr.fanout >= 1);
+
+        params.propagation.quarantine_threshold = Some(0.6);
+        let pr = adjust_propagation(&signals, &params);
+        assert!(pr.quarantined);
+        assert_eq!((pr.ttl, pr.fanout), (0, 0));
+    }
+
+    #[test]
+    fn test_reward_breakdown() {
+        let params = Params::default();
+        let ri = RewardInput{ ticket_budget:1.5, client_q:0.8, size_bytes:24000, ttfb_ms:120, server_cluster_risk:0.2, ..Default::default() };
+        let d = calculate_serve_reward_detailed(&ri, &params);
+        assert!(!d.budget_capped);
+        assert_eq!(d.reward, calculate_serve_reward(&ri, &params));
+        assert!((d.reward - d.client_q * d.w_size * d.w_latency * d.diversity).abs() < 1e-12);
+
+        let tight = RewardInput{ ticket_budget:0.01, ..ri };
+        let d = calculate_serve_reward_detailed(&tight, &params);
+        assert!(d.budget_capped);
+        assert_eq!(d.reward, 0.01);
+    }
+
+    #[test]
+    fn test_reward_normalization_params() {
+        let params = Params::default();
+        let ri = RewardInput{ ticket_budget:10.0, client_q:1.0, size_bytes:50_000_000, ttfb_ms:1500, server_cluster_risk:0.0, ..Default::default() };
+        let d = calculate_serve_reward_detailed(&ri, &params);
+        assert!(d.w_size > 1.0);
+
+        let mut cdn = params.clone();
+        cdn.reward.size_norm_bytes = 100_000_000.0;
+        cdn.reward.latency_knee_ms = 3000.0;
+        let dc = calculate_serve_reward_detailed(&ri, &cdn);
+        assert!(dc.w_size < 1.0);
+        assert!(dc.w_latency > d.w_latency);
+    }
+
+    #[test]
+    fn test_repeated_serves_discounted() {
+        let params = Params::default();
+        let first = RewardInput{ ticket_budget:10.0, client_q:0.8, size_bytes:24000, ttfb_ms:120, server_cluster_risk:0.0, ..Default::default() };
+        let tenth = RewardInput{ prev_serves:Some(10), ..first.clone() };
+        let wash = RewardInput{ prev_serves:Some(500), ..first.clone() };
+        let r1 = calculate_serve_reward(&first, &params);
+        assert!((calculate_serve_reward(&tenth, &params) - r1 / 2.0).abs() < 1e-12);
+        assert!(calculate_serve_reward(&wash, &params) < r1 * 1e-12);
+    }
+
+    #[test]
+    fn test_availability_reward() {
+        let params = Params::default();
+        assert_eq!(calculate_availability_reward(1.0, 10, 1000.0, &params), 0.5);
+        assert_eq!(calculate_availability_reward(0.85, 10, 1000.0, &params), 0.0);
+        let partial = calculate_availability_reward(0.95, 5, 250.0, &params);
+        assert!((partial - 0.5 * 0.5 * 0.5 * 0.5).abs() < 1e-12);
+        assert_eq!(calculate_availability_reward(1.0, 50, 1e9, &params), 0.5);
+    }
+
+    #[test]
+    fn test_latency_percentile_curve() {
+        let params = Params::default();
+        let far = RewardInput{ ticket_budget:10.0, client_q:1.0, size_bytes:24000, ttfb_ms:1800, ..Default::default() };
+        let knee = calculate_serve_reward_detailed(&far, &params);
+        let ranked = calculate_serve_reward_detailed(&RewardInput{ ttfb_percentile:Some(0.7), ..far.clone() }, &params);
+        assert!((ranked.w_latency - 0.75).abs() < 1e-12);
+        assert!(ranked.w_latency > knee.w_latency);
+        assert_eq!(interpolate(&params.reward.latency_curve, -1.0), 1.0);
+        assert_eq!(interpolate(&params.reward.latency_curve, 2.0), 0.4);
+    }
+
+    #[test]
+    fn test_surge_cap_and_cooldown() {
+        let mut params = Params::default();
+        params.congestion.surge.max_increase_pct = Some(5.0);
+        params.congestion.surge.cooldown_factor = 2.0;
+        assert!((update_base_cost(1.0, 5000.0, &params) - 1.05).abs() < 1e-12);
+        let down = update_base_cost(1.0, 250.0, &params);
+        assert!((down - (-0.1_f64).exp()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fact_check_states() {
+        let p = Params::default();
+        let actor = Actor { rl: 10.0, q: 0.8, ef: 2.0, ..Default::default() };
+        let legacy = Content { is_claim: Some(true), has_evidence: Some(false), ..Default::default() };
+        assert_eq!(legacy.fact_check_state(), Some(FactCheckState::Unreviewed));
+        let explicit = Content { fact_check: Some(FactCheckState::Unreviewed), ..Default::default() };
+        assert_eq!(calculate_post_cost(&actor, &legacy, &p, 1.0), calculate_post_cost(&actor, &explicit, &p, 1.0));
+        assert_eq!(calculate_post_cost_detailed(&actor, &Content { has_evidence: Some(true), ..legacy.clone() }, &p, 1.0).claim_multiplier, 0.7);
+
+        // the verdict lands after posting: false claims owe more, verified ones get a refund
+        let owed = fact_check_adjustment(&actor, &legacy, FactCheckState::VerifiedFalse, &p, 1.0);
+        let plain = calculate_post_cost(&actor, &Content::default(), &p, 1.0);
+        assert!((owed - plain * (3.0 - 1.2)).abs() < 1e-9);
+        assert!(fact_check_adjustment(&actor, &legacy, FactCheckState::VerifiedTrue, &p, 1.0) < 0.0);
+        assert_eq!(fact_check_adjustment(&actor, &explicit, FactCheckState::Unreviewed, &p, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_cost_breakdown_and_cmin() {
+        let mut params = Params::default();
+        let actor = Actor { rl:0.0, q:0.8, ef:0.0, posts_1h:None, ..Default::default() };
+        let content = Content::default();
+        let d = calculate_post_cost_detailed(&actor, &content, &params, 0.1);
+        assert_eq!(d.cost, calculate_post_cost(&actor, &content, &params, 0.1));
+        assert!(!d.cmin_applied);
+
+        params.cost.cmin = Some(0.5);
+        let d = calculate_post_cost_detailed(&actor, &content, &params, 0.1);
+        assert!(d.cmin_applied);
+        assert_eq!((d.pre_floor, d.cost), (0.1, 0.5));
+
+        // runaway multipliers stop at C_max
+        params.cost.cmax = Some(2.0);
+        let risky = Content { risk_signals: Some(RiskSignals { coordination: Some(1.0), burst: Some(1.0), ..Default::default() }), is_claim: Some(true), ..Default::default() };
+        let spammer = Actor { rl: 50.0, posts_1h: Some(100.0), ..actor };
+        let d = calculate_post_cost_detailed(&spammer, &risky, &params, 1.0);
+        assert!(d.cmax_applied && !d.cmin_applied);
+        assert!(d.pre_floor > 2.0);
+        assert_eq!(d.cost, 2.0);
+        params.cost.cmin = Some(3.0);
+        assert!(params.validate().unwrap_err().iter().any(|e| e.path == "cost.cmax"));
+    }
+
+    #[test]
+    fn test_quality_and_risk_breakdowns() {
+        let p = Params::default();
+        let qin = QInputs { A:1.0, R:1.0, T:1.0, D:1.0, H:0.0, S:0.0 };
+        let qb = calculate_quality_detailed(&qin, &p);
+        assert!(qb.unverified_cap_applied);
+        assert_eq!(qb.q, calculate_quality(qin, &p));
+        let sig = Some(RiskSignals { coordination: Some(1.0), burst: Some(1.0), abuse_history: Some(1.0), clustering: Some(1.0), monotonicity: Some(1.0), duplication: Some(1.0) });
+        let rb = calculate_risk_detailed(&sig, &RiskWeights::default());
+        assert!(rb.raw >= rb.risk);
+        assert_eq!(rb.risk, calculate_risk(&sig, &RiskWeights::default()));
+    }
+
+    #[test]
+    fn test_ef_breakdown() {
+        let p = Params::default();
+        let followers: Vec<(FollowerId, f64)> = (0..30).map(|i| (format!("f{:02}", i), i as f64 / 30.0)).collect();
+        let d = calculate_ef_detailed(&followers, &p);
+        let qs: Vec<f64> = followers.iter().map(|f| f.1).collect();
+        assert_eq!(d.ef, calculate_ef(&qs, &p));
+        assert_eq!(d.contributing + d.excluded, 30);
+        assert_eq!(d.excluded, qs.iter().filter(|&&q| q < p.q_min).count());
+        assert_eq!(d.top.len(), EF_TOP_CONTRIBUTORS);
+        assert_eq!(d.top[0].follower, "f29");
+        assert!(d.top.windows(2).all(|w| w[0].weight >= w[1].weight));
+        assert!((d.top[0].share - d.top[0].weight / d.sum).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_params_validate() {
+        assert!(Params::default().validate().is_ok());
+        let mut p = Params { q_min: 1.5, ..Default::default() };
+        p.congestion.base_max = 0.01;
+        let errs = p.validate().unwrap_err();
+        let paths: Vec<&str> = errs.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec!["q_min", "congestion.base_max"]);
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_borsh_roundtrip() {
+        let p = Params::default();
+        let bytes = borsh::to_vec(&p).unwrap();
+        let back: Params = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(borsh::to_vec(&back).unwrap(), bytes);
+        #[cfg(feature = "serde")]
+        assert_eq!(serde_json::to_value(&back).unwrap(), serde_json::to_value(&p).unwrap());
+        // Fixed layout: 6 x Option<f64> in declaration order
+        let sig = RiskSignals { coordination: Some(0.5), ..Default::default() };
+        let b = borsh::to_vec(&sig).unwrap();
+        assert_eq!(b.len(), 1 + 8 + 5);
+        assert_eq!(&b[1..9], &0.5f64.to_le_bytes());
+    }
+
     #[test]
     fn test_base() {
         let params = Params::default();
         let b2 = update_base_cost(1.0, 1000.0, &params);
         assert!(b2 > 1.0);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_q_inputs_snake_case_aliases() {
+        let snake = r#"{"authenticity":0.8,"reliability":0.7,"timeliness":0.9,"depth":0.6,"human_verified":0.2,"spam_signal":0.1}"#;
+        let q: QInputs = serde_json::from_str(snake).unwrap();
+        let legacy: QInputs = serde_json::from_str(r#"{"A":0.8,"R":0.7,"T":0.9,"D":0.6,"H":0.2,"S":0.1}"#).unwrap();
+        assert_eq!(serde_json::to_value(&q).unwrap(), serde_json::to_value(&legacy).unwrap());
+        assert_eq!(serde_json::to_string(&QInputsSnakeCase::from(&q)).unwrap(), snake);
+        assert_eq!(QInputs::from(QInputsSnakeCase::from(&q)).S, 0.1);
+    }
+
+    #[test]
+    fn test_account_age_and_verification_level() {
+        let p = Params::default();
+        let content = Content::default();
+        let known = Actor { rl: 10.0, q: 0.6, ef: 5.0, ..Default::default() };
+        let cost = |age: Option<f64>| calculate_post_cost_detailed(&Actor { account_age_days: age, ..known.clone() }, &content, &p, 1.0);
+        assert_eq!(cost(None).cost, cost(Some(30.0)).cost);
+        assert_eq!(cost(Some(0.0)).age_multiplier, 1.5);
+        assert_eq!(cost(Some(15.0)).cost, cost(None).cost * 1.25);
+
+        let inp = QInputs { A: 1.0, R: 1.0, T: 1.0, D: 1.0, H: 0.0, S: 0.0 };
+        assert_eq!(calculate_quality_with_verification(inp.clone(), None, &p), calculate_quality(inp.clone(), &p));
+        assert_eq!(calculate_quality_with_verification(inp.clone(), Some(VerificationLevel::Unverified), &p), 0.4);
+        assert_eq!(calculate_quality_with_verification(inp, Some(VerificationLevel::Phone), &p), 0.6);
+    }
+
+    #[test]
+    fn test_kind_rate_limits() {
+        let mut p = Params::default();
+        p.cost.kind_rate_limits.insert(ContentKind::Link, 2.0);
+        // 8 posts in the hour, 4 of them links: under the global limit of 10, over the link limit of 2
+        let actor = Actor { rl: 10.0, q: 0.6, ef: 5.0, posts_1h: Some(8.0), kind_posts_1h: Some(4.0), ..Default::default() };
+        let rate = |kind| calculate_post_cost_detailed(&actor, &Content { kind, ..Default::default() }, &p, 1.0).rate_multiplier;
+        assert_eq!(rate(None), 1.0);
+        assert_eq!(rate(Some(ContentKind::Text)), 1.0);
+        assert_eq!(rate(Some(ContentKind::Link)), 1.5);
+        p.cost.kind_rate_limits.insert(ContentKind::Media, 0.0);
+        assert_eq!(p.validate().unwrap_err()[0].path, "cost.kind_rate_limits.media");
+    }
 }
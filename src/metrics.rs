@@ -0,0 +1,137 @@
+// Operator metrics hook
+// - `MetricsSink::observe` receives counters and histogram samples from the batch and stateful APIs that take a sink
+//   (`batch::*_observed`, `ScoringContext::with_sink`, `Epoch::advance_observed`); the plain variants emit nothing
+// - `Metric::name` gives a stable Prometheus-style name and `Metric::kind` how to register it, so a sink is a thin
+//   adapter over whatever client the operator runs
+// - Observing never changes a result: the `_observed` variants return exactly what the plain calls return
+
+use crate::{CostBreakdown, PropagationResult, QualityBreakdown, RewardBreakdown};
+
+/// How a metric aggregates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    /// Monotonic count; each observation adds `value()`
+    Counter,
+    /// Distribution of `value()` samples
+    Histogram,
+}
+
+/// One observation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Metric {
+    /// Final posting cost
+    PostCost(f64),
+    /// Cost raised to the C_min floor
+    CminFloorHit,
+    /// Cost cut to the C_max ceiling
+    CmaxCeilingHit,
+    /// Cost multiplied by the rate-limit penalty
+    RatePenalty,
+    /// Quality held at the unverified cap
+    QualityCapped,
+    /// Content quarantined by propagation control
+    Quarantine,
+    /// Final serve reward
+    ServeReward(f64),
+    /// Serve reward capped at the ticket budget
+    BudgetCap,
+    /// Base fare after an epoch rollover
+    BaseFare(f64),
+    /// Base fare ended an epoch at `base_min` or `base_max`
+    BaseFareClamped,
+    CacheHit,
+    CacheMiss,
+}
+
+impl Metric {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Metric::PostCost(_) => "slimechain_post_cost",
+            Metric::CminFloorHit => "slimechain_cmin_floor_hits_total",
+            Metric::CmaxCeilingHit => "slimechain_cmax_ceiling_hits_total",
+            Metric::RatePenalty => "slimechain_rate_penalties_total",
+            Metric::QualityCapped => "slimechain_quality_capped_total",
+            Metric::Quarantine => "slimechain_quarantines_total",
+            Metric::ServeReward(_) => "slimechain_serve_reward",
+            Metric::BudgetCap => "slimechain_budget_caps_total",
+            Metric::BaseFare(_) => "slimechain_base_fare",
+            Metric::BaseFareClamped => "slimechain_base_fare_clamped_total",
+            Metric::CacheHit => "slimechain_cache_hits_total",
+            Metric::CacheMiss => "slimechain_cache_misses_total",
+        }
+    }
+
+    pub fn kind(&self) -> MetricKind {
+        match self {
+            Metric::PostCost(_) | Metric::ServeReward(_) | Metric::BaseFare(_) => MetricKind::Histogram,
+            _ => MetricKind::Counter,
+        }
+    }
+
+    /// Sample for histograms, 1 for counters
+    pub fn value(&self) -> f64 {
+        match *self {
+            Metric::PostCost(x) | Metric::ServeReward(x) | Metric::BaseFare(x) => x,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Receiver of metrics (shared across the rayon pool by the batch evaluators)
+pub trait MetricsSink: Send + Sync {
+    fn observe(&self, metric: Metric);
+}
+
+/// Discards everything
+impl MetricsSink for () {
+    fn observe(&self, _metric: Metric) {}
+}
+
+// -------- Emitters for the detailed results --------
+
+pub fn record_cost(sink: &dyn MetricsSink, b: &CostBreakdown) {
+    sink.observe(Metric::PostCost(b.cost));
+    if b.cmin_applied { sink.observe(Metric::CminFloorHit); }
+    if b.cmax_applied { sink.observe(Metric::CmaxCeilingHit); }
+    if b.rate_multiplier > 1.0 { sink.observe(Metric::RatePenalty); }
+}
+
+pub fn record_reward(sink: &dyn MetricsSink, b: &RewardBreakdown) {
+    sink.observe(Metric::ServeReward(b.reward));
+    if b.budget_capped { sink.observe(Metric::BudgetCap); }
+}
+
+pub fn record_quality(sink: &dyn MetricsSink, b: &QualityBreakdown) {
+    if b.unverified_cap_applied { sink.observe(Metric::QualityCapped); }
+}
+
+pub fn record_propagation(sink: &dyn MetricsSink, r: &PropagationResult) {
+    if r.quarantined { sink.observe(Metric::Quarantine); }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::epoch::{Epoch, EpochInputs};
+    use crate::Params;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Collect(Mutex<Vec<Metric>>);
+
+    impl MetricsSink for Collect {
+        fn observe(&self, metric: Metric) { self.0.lock().unwrap().push(metric); }
+    }
+
+    #[test]
+    fn t_epoch_rollover_reports_fare_and_clamp() {
+        let sink = Collect::default();
+        let e0 = Epoch::genesis(Params::default(), 1.0);
+        let inputs = EpochInputs { load_samples: vec![0.0; 50], ..Default::default() };
+        let e1 = e0.advance_observed(inputs.clone(), &sink);
+        assert_eq!(e1.base_fare(), e0.advance(inputs).base_fare());
+        let seen = sink.0.into_inner().unwrap();
+        assert_eq!(seen, [Metric::BaseFare(e1.base_fare()), Metric::BaseFareClamped]);
+        assert_eq!((seen[0].name(), seen[0].kind(), seen[1].value()), ("slimechain_base_fare", MetricKind::Histogram, 1.0));
+    }
+}
@@ -0,0 +1,115 @@
+// UniFFI scaffolding for Swift/Kotlin (feature `uniffi`); interface: src/slimechain_algo.udl
+// - Dictionaries are the crate's own input/output types; `AlgoParams` wraps a validated `Params`
+// - Omitted params mean `Params::default()`
+
+// the generated scaffolding trips this lint
+#![allow(clippy::empty_line_after_doc_comments)]
+
+use crate::{
+    Actor, Content, ContentKind, ContentTopic, CostBreakdown, FactCheckState, Params, PropagationResult, QInputs, RewardInput,
+    RiskSignals, RiskWeights, VerificationLevel,
+};
+use std::sync::Arc;
+
+uniffi::include_scaffolding!("slimechain_algo");
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlgoError {
+    InvalidJson(String),
+    InvalidParams(String),
+    UnknownPreset(String),
+}
+
+impl std::fmt::Display for AlgoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlgoError::InvalidJson(e) => write!(f, "invalid JSON: {}", e),
+            AlgoError::InvalidParams(e) => write!(f, "invalid params: {}", e),
+            AlgoError::UnknownPreset(name) => write!(f, "unknown preset {:?}", name),
+        }
+    }
+}
+
+impl std::error::Error for AlgoError {}
+
+/// Validated parameter bundle
+#[derive(Debug, Clone, Default)]
+pub struct AlgoParams(Params);
+
+impl AlgoParams {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn from_json(json: String) -> Result<Self, AlgoError> {
+        let p: Params = serde_json::from_str(&json).map_err(|e| AlgoError::InvalidJson(e.to_string()))?;
+        Self::checked(p)
+    }
+
+    pub fn preset(name: String) -> Result<Self, AlgoError> {
+        Self::checked(crate::presets::preset(&name).ok_or(AlgoError::UnknownPreset(name))?)
+    }
+
+    pub fn to_json(&self) -> String { serde_json::to_string(&self.0).expect("params serialize") }
+
+    fn checked(p: Params) -> Result<Self, AlgoError> {
+        p.validate().map_err(|errs| {
+            AlgoError::InvalidParams(errs.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; "))
+        })?;
+        Ok(AlgoParams(p))
+    }
+}
+
+fn with_params<T>(params: Option<Arc<AlgoParams>>, f: impl FnOnce(&Params) -> T) -> T {
+    match params {
+        Some(p) => f(&p.0),
+        None => f(&Params::default()),
+    }
+}
+
+pub fn calculate_post_cost(actor: Actor, content: Content, base_fare: f64, params: Option<Arc<AlgoParams>>) -> f64 {
+    with_params(params, |p| crate::calculate_post_cost(&actor, &content, p, base_fare))
+}
+
+pub fn explain_post_cost(actor: Actor, content: Content, base_fare: f64, params: Option<Arc<AlgoParams>>) -> CostBreakdown {
+    with_params(params, |p| crate::calculate_post_cost_detailed(&actor, &content, p, base_fare))
+}
+
+pub fn calculate_quality(inputs: QInputs, params: Option<Arc<AlgoParams>>) -> f64 {
+    with_params(params, |p| crate::calculate_quality(inputs, p))
+}
+
+pub fn calculate_ef(followers_q: Vec<f64>, params: Option<Arc<AlgoParams>>) -> f64 {
+    with_params(params, |p| crate::calculate_ef(&followers_q, p))
+}
+
+/// Risk with the default weights
+pub fn calculate_risk(signals: RiskSignals) -> f64 {
+    crate::calculate_risk(&Some(signals), &RiskWeights::default())
+}
+
+pub fn adjust_propagation(content: Content, params: Option<Arc<AlgoParams>>) -> PropagationResult {
+    with_params(params, |p| crate::adjust_propagation_for_content(&content, p))
+}
+
+pub fn calculate_serve_reward(input: RewardInput, params: Option<Arc<AlgoParams>>) -> f64 {
+    with_params(params, |p| crate::calculate_serve_reward(&input, p))
+}
+
+pub fn update_base_cost(current_base: f64, current_load: f64, params: Option<Arc<AlgoParams>>) -> f64 {
+    with_params(params, |p| crate::update_base_cost(current_base, current_load, p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_params_and_cost_preview() {
+        let actor = Actor { rl: 120.0, q: 0.8, ef: 28.3, posts_1h: Some(12.0), ..Default::default() };
+        let content = Content { is_claim: Some(true), topic: Some(ContentTopic::Health), ..Default::default() };
+        let p = Arc::new(AlgoParams::preset("default".into()).unwrap());
+        let b = explain_post_cost(actor.clone(), content.clone(), 1.0, Some(p.clone()));
+        assert_eq!(b.cost, calculate_post_cost(actor, content, 1.0, None));
+        assert!(AlgoParams::from_json(p.to_json()).is_ok());
+        assert!(matches!(AlgoParams::preset("nope".into()), Err(AlgoError::UnknownPreset(_))));
+    }
+}
@@ -0,0 +1,170 @@
+// Moderation action pricing: reporter bonds and reviewer rewards
+// - Reporting posts a bond that shrinks as the content's risk rises and as the reporter's quality rises, so flagging
+//   obviously risky content is cheap while mass-reporting clean content from throwaway accounts is expensive
+// - Reviewers earn a base reward scaled up with content risk (harder calls)
+// - `settle_report` returns an upheld report's bond plus a bonus; a rejected report forfeits it, part to the reviewer
+//   and the rest burned
+// - All amounts in SOCIAL
+// - `aggregate_reports` turns many reports on one item into a 0..1 score: each counts reporter_q * precision, and
+//   within one mutual-follow cluster the k-th strongest report counts 1/k, so a brigade adds up logarithmically
+
+use crate::{clamp, Quality, Risk};
+use std::collections::BTreeMap;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ModParams {
+    /// Bond for a top-quality reporter flagging maximum-risk content
+    pub bond_base: f64,
+    /// Bond multiplier per unit of missing risk: `1 + bond_risk_k * (1 - risk)`
+    pub bond_risk_k: f64,
+    /// Bond multiplier per unit of missing reporter quality: `1 + bond_q_k * (1 - q)`
+    pub bond_q_k: f64,
+    pub bond_max: f64,
+    /// Review reward at risk 0
+    pub review_base: f64,
+    /// Review reward multiplier per unit of risk: `1 + review_risk_k * risk`
+    pub review_risk_k: f64,
+    /// Share of the bond added as a bonus when a report is upheld
+    pub upheld_bonus_share: f64,
+    /// Share of a forfeited bond paid to the reviewer (the rest is burned)
+    pub forfeit_review_share: f64,
+}
+
+impl Default for ModParams {
+    fn default() -> Self {
+        ModParams {
+            bond_base: 1.0, bond_risk_k: 4.0, bond_q_k: 4.0, bond_max: 25.0,
+            review_base: 0.5, review_risk_k: 1.0,
+            upheld_bonus_share: 0.2, forfeit_review_share: 0.5,
+        }
+    }
+}
+
+/// Where a settled bond goes
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ReportSettlement {
+    /// Returned bond plus bonus
+    pub to_reporter: f64,
+    pub to_reviewer: f64,
+    pub burned: f64,
+}
+
+/// Bond a reporter posts to flag content
+pub fn report_bond(content_risk: impl Into<Risk>, reporter_q: impl Into<Quality>, params: &ModParams) -> f64 {
+    let (risk, q) = (content_risk.into().get(), reporter_q.into().get());
+    let bond = params.bond_base.max(0.0) * (1.0 + params.bond_risk_k.max(0.0) * (1.0 - risk)) * (1.0 + params.bond_q_k.max(0.0) * (1.0 - q));
+    bond.min(params.bond_max.max(0.0))
+}
+
+/// Reward for reviewing a report, paid from the protocol (not from the bond)
+pub fn review_reward(content_risk: impl Into<Risk>, params: &ModParams) -> f64 {
+    params.review_base.max(0.0) * (1.0 + params.review_risk_k.max(0.0) * content_risk.into().get())
+}
+
+/// Split a bond once the report is decided
+pub fn settle_report(bond: f64, upheld: bool, params: &ModParams) -> ReportSettlement {
+    let bond = if bond.is_finite() { bond.max(0.0) } else { 0.0 };
+    if upheld {
+        ReportSettlement { to_reporter: bond * (1.0 + params.upheld_bonus_share.max(0.0)), to_reviewer: 0.0, burned: 0.0 }
+    } else {
+        let to_reviewer = bond * clamp(params.forfeit_review_share, 0.0, 1.0);
+        ReportSettlement { to_reporter: 0.0, to_reviewer, burned: bond - to_reviewer }
+    }
+}
+
+/// Precision assumed for a reporter with no decided reports
+const PRIOR_PRECISION: f64 = 0.5;
+/// Effective weight at which the aggregate score reaches 0.5
+const HALF_SCORE_WEIGHT: f64 = 3.0;
+
+/// One report against an item
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Report {
+    pub reporter_q: f64,
+    /// Share of the reporter's decided reports that were upheld (None = no history)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub precision: Option<f64>,
+    /// The reporter's `graph::mutual_clusters` component (None = not in one)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cluster: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AggregateVerdictScore {
+    /// `weight / (weight + 3)`, in 0..1
+    pub score: f64,
+    /// Effective report weight after cluster discounting
+    pub weight: f64,
+    pub reports: usize,
+    /// Distinct clusters plus unclustered reporters
+    pub independent: usize,
+}
+
+/// Combine the reports on one item
+pub fn aggregate_reports(reports: &[Report]) -> AggregateVerdictScore {
+    let weight_of = |r: &Report| {
+        let q = if r.reporter_q.is_finite() { clamp(r.reporter_q, 0.0, 1.0) } else { 0.0 };
+        let precision = r.precision.filter(|p| p.is_finite()).map_or(PRIOR_PRECISION, |p| clamp(p, 0.0, 1.0));
+        q * precision
+    };
+    let mut clusters: BTreeMap<u32, Vec<f64>> = BTreeMap::new();
+    let (mut weight, mut independent) = (0.0, 0);
+    for r in reports {
+        match r.cluster {
+            Some(c) => clusters.entry(c).or_default().push(weight_of(r)),
+            None => { weight += weight_of(r); independent += 1; }
+        }
+    }
+    independent += clusters.len();
+    for mut ws in clusters.into_values() {
+        ws.sort_by(|a, b| b.total_cmp(a));
+        weight += ws.iter().enumerate().map(|(k, w)| w / (k + 1) as f64).sum::<f64>();
+    }
+    AggregateVerdictScore { score: weight / (weight + HALF_SCORE_WEIGHT), weight, reports: reports.len(), independent }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_false_reports_cost_more() {
+        let p = ModParams::default();
+        assert_eq!(report_bond(1.0, 1.0, &p), 1.0);
+        assert_eq!(report_bond(0.0, 1.0, &p), 5.0);
+        assert_eq!(report_bond(0.0, 0.0, &p), p.bond_max);
+        assert!(report_bond(0.2, 0.9, &p) < report_bond(0.2, 0.3, &p));
+        assert_eq!(review_reward(1.0, &p), 1.0);
+
+        assert_eq!(settle_report(5.0, true, &p), ReportSettlement { to_reporter: 6.0, to_reviewer: 0.0, burned: 0.0 });
+        assert_eq!(settle_report(5.0, false, &p), ReportSettlement { to_reporter: 0.0, to_reviewer: 2.5, burned: 2.5 });
+    }
+
+    #[test]
+    fn t_aggregate_reports_resists_brigading() {
+        let honest = |i: u32| Report { reporter_q: 0.9, precision: Some(0.9), cluster: i.is_multiple_of(2).then_some(i) };
+        let independent = aggregate_reports(&(0..10).map(honest).collect::<Vec<_>>());
+        assert_eq!(independent.independent, 10);
+        assert!((independent.weight - 8.1).abs() < 1e-12);
+        assert!(independent.score > 0.7);
+
+        // a hundred throwaways from one ring
+        let brigade: Vec<Report> = (0..100).map(|_| Report { reporter_q: 0.3, precision: None, cluster: Some(7) }).collect();
+        let b = aggregate_reports(&brigade);
+        assert_eq!((b.reports, b.independent), (100, 1));
+        assert!(b.score < 0.25);
+        assert!(b.score < independent.score);
+
+        assert_eq!(aggregate_reports(&[]).score, 0.0);
+    }
+}
@@ -0,0 +1,185 @@
+// Integer smallest-unit amounts and deterministic splitting
+// - `Amount` counts smallest units (u64); the unit size in SOCIAL is a parameter (`params.reward.epoch.unit`,
+//   `TipParams::unit`, ...), and every conversion from f64 names its `Rounding`
+// - Conversions snap values within 1e-9 units of a whole unit to it, so `0.3 / 0.1` is 3 units on every platform
+// - `apportion` / `split` floor every part, then place the leftover units ("dust") per `DustPolicy`, so the parts plus
+//   the dust always sum exactly to the total
+// - `DustLedger` carries undistributed dust into the next pool instead of losing it
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+
+/// Rounding direction for f64 -> smallest-unit conversions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Rounding {
+    /// Payouts: never pay more than owed
+    Down,
+    /// Charges: never charge less than the price
+    Up,
+    HalfEven,
+}
+
+/// `units` rounded to a whole number (values within 1e-9 of one snap to it)
+pub fn round_units(units: f64, rounding: Rounding) -> f64 {
+    let nearest = units.round();
+    if (units - nearest).abs() < 1e-9 { return nearest; }
+    match rounding {
+        Rounding::Down => units.floor(),
+        Rounding::Up => units.ceil(),
+        Rounding::HalfEven => {
+            let floor = units.floor();
+            if units - floor != 0.5 { nearest } else if floor % 2.0 == 0.0 { floor } else { floor + 1.0 }
+        }
+    }
+}
+
+/// Amount in smallest units
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Amount(pub u64);
+
+impl Amount {
+    pub const ZERO: Amount = Amount(0);
+
+    /// `social` in units of `unit` SOCIAL (negative or non-finite amounts are 0)
+    pub fn from_social(social: f64, unit: f64, rounding: Rounding) -> Amount {
+        if !(social.is_finite() && social > 0.0) { return Amount::ZERO; }
+        Amount(round_units(social / unit.max(1e-12), rounding) as u64)
+    }
+
+    pub fn to_social(self, unit: f64) -> f64 { self.0 as f64 * unit.max(1e-12) }
+
+    pub fn units(self) -> u64 { self.0 }
+
+    pub fn checked_sub(self, rhs: Amount) -> Option<Amount> { self.0.checked_sub(rhs.0).map(Amount) }
+
+    pub fn saturating_sub(self, rhs: Amount) -> Amount { Amount(self.0.saturating_sub(rhs.0)) }
+}
+
+impl Add for Amount {
+    type Output = Amount;
+    fn add(self, rhs: Amount) -> Amount { Amount(self.0 + rhs.0) }
+}
+
+impl AddAssign for Amount {
+    fn add_assign(&mut self, rhs: Amount) { self.0 += rhs.0; }
+}
+
+impl Sum for Amount {
+    fn sum<I: Iterator<Item = Amount>>(iter: I) -> Amount { iter.fold(Amount::ZERO, Add::add) }
+}
+
+/// Where the units left after flooring every part go
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(tag = "policy", rename_all = "snake_case"))]
+pub enum DustPolicy {
+    /// One unit each to the parts with the largest fractional share (ties by position); pool distributions
+    LargestRemainder,
+    /// All of it to one part (e.g. the creator of a tip, the burn of an escrow)
+    ToPart { index: usize },
+    /// None of it; the caller carries it over (`DustLedger`)
+    Carry,
+}
+
+/// Parts in input order plus the undistributed dust
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Split {
+    pub parts: Vec<Amount>,
+    pub dust: Amount,
+}
+
+/// Apportion `total` over fractional unit `shares` (summing to at most `total`): each part is floored, then the
+/// leftover units are placed per `policy`, never lifting a part above `cap`
+pub fn apportion(total: Amount, shares: &[f64], cap: Option<Amount>, policy: DustPolicy) -> Split {
+    let cap = cap.map_or(u64::MAX, |c| c.0);
+    let share = |s: f64| if s.is_finite() { s.max(0.0) } else { 0.0 };
+    let mut parts: Vec<u64> = shares.iter().map(|&s| (share(s).floor() as u64).min(cap)).collect();
+    let mut left = total.0.saturating_sub(parts.iter().sum());
+    match policy {
+        DustPolicy::LargestRemainder => {
+            let frac = |i: usize| share(shares[i]) - share(shares[i]).floor();
+            let mut order: Vec<usize> = (0..shares.len()).filter(|&i| share(shares[i]) > 0.0).collect();
+            order.sort_by(|&a, &b| frac(b).total_cmp(&frac(a)).then(a.cmp(&b)));
+            for i in order {
+                if left == 0 { break; }
+                if parts[i] < cap {
+                    parts[i] += 1;
+                    left -= 1;
+                }
+            }
+        }
+        DustPolicy::ToPart { index } => if let Some(p) = parts.get_mut(index) {
+            let add = left.min(cap - *p);
+            *p += add;
+            left -= add;
+        },
+        DustPolicy::Carry => {}
+    }
+    Split { parts: parts.into_iter().map(Amount).collect(), dust: Amount(left) }
+}
+
+/// Split `total` proportionally to `weights` (all zero: everything is dust)
+pub fn split(total: Amount, weights: &[f64], policy: DustPolicy) -> Split {
+    let w = |x: f64| if x.is_finite() { x.max(0.0) } else { 0.0 };
+    let sum: f64 = weights.iter().map(|&x| w(x)).sum();
+    let shares: Vec<f64> = weights.iter().map(|&x| if sum > 0.0 { total.0 as f64 * w(x) / sum } else { 0.0 }).collect();
+    apportion(total, &shares, None, policy)
+}
+
+/// Dust carried from one pool to the next
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DustLedger {
+    pub carried: Amount,
+}
+
+impl DustLedger {
+    /// Record a distribution's dust
+    pub fn carry(&mut self, dust: Amount) { self.carried += dust; }
+
+    /// `pool` plus everything carried so far (which is then cleared)
+    pub fn fund(&mut self, pool: Amount) -> Amount { pool + std::mem::take(&mut self.carried) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_amount_rounding_and_split() {
+        assert_eq!(Amount::from_social(0.3, 0.1, Rounding::Down), Amount(3));
+        assert_eq!(Amount::from_social(0.35, 0.1, Rounding::Down), Amount(3));
+        assert_eq!(Amount::from_social(0.31, 0.1, Rounding::Up), Amount(4));
+        assert_eq!(Amount::from_social(2.5, 1.0, Rounding::HalfEven), Amount(2));
+        assert_eq!(Amount::from_social(3.5, 1.0, Rounding::HalfEven), Amount(4));
+        assert_eq!(Amount::from_social(f64::NAN, 0.1, Rounding::Up), Amount::ZERO);
+
+        // 10 over three equal weights: 3 each and 1 leftover unit
+        let s = split(Amount(10), &[1.0, 1.0, 1.0], DustPolicy::LargestRemainder);
+        assert_eq!((s.parts, s.dust), (vec![Amount(4), Amount(3), Amount(3)], Amount::ZERO));
+        let s = split(Amount(10), &[1.0, 1.0, 1.0], DustPolicy::ToPart { index: 2 });
+        assert_eq!(s.parts[2], Amount(4));
+        let s = split(Amount(10), &[1.0, 1.0, 1.0], DustPolicy::Carry);
+        assert_eq!((s.parts.iter().copied().sum::<Amount>(), s.dust), (Amount(9), Amount(1)));
+        assert_eq!(split(Amount(10), &[0.0, 0.0], DustPolicy::LargestRemainder).dust, Amount(10));
+        // a cap leaves what cannot be placed as dust
+        assert_eq!(apportion(Amount(10), &[5.0, 5.0], Some(Amount(4)), DustPolicy::LargestRemainder).dust, Amount(2));
+
+        let mut ledger = DustLedger::default();
+        ledger.carry(s.dust);
+        assert_eq!(ledger.fund(Amount(10)), Amount(11));
+        assert_eq!(ledger.carried, Amount::ZERO);
+    }
+}
@@ -0,0 +1,73 @@
+// napi-rs native Node addon (feature `napi`)
+// - Batch functions take arrays of plain objects shaped like the serde types (same as the CLI JSON / wasm bindings)
+// - Inputs are decoded on the JS thread; scoring runs on the libuv pool and resolves to a Float64Array
+// - `params` may be `undefined`/`null` for `Params::default()`; supplied params are validated
+
+use crate::{Actor, Content, Params, QInputs, RewardInput, RiskSignals, RiskWeights};
+use napi::bindgen_prelude::{AsyncTask, Float64Array};
+use napi::{Env, Error, JsUnknown, Result, Task, ValueType};
+use napi_derive::napi;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct CostItem { actor: Actor, content: Content, base_fare: Option<f64> }
+
+/// Background job scoring one batch
+pub struct Batch(Box<dyn FnMut() -> Vec<f64> + Send>);
+
+impl Task for Batch {
+    type Output = Vec<f64>;
+    type JsValue = Float64Array;
+
+    fn compute(&mut self) -> Result<Vec<f64>> { Ok((self.0)()) }
+
+    fn resolve(&mut self, _env: Env, output: Vec<f64>) -> Result<Float64Array> { Ok(output.into()) }
+}
+
+fn from_js<T: DeserializeOwned>(env: &Env, value: JsUnknown) -> Result<T> {
+    env.from_js_value(value).map_err(|e| Error::from_reason(e.to_string()))
+}
+
+fn params_from_js(env: &Env, params: Option<JsUnknown>) -> Result<Params> {
+    let Some(params) = params else { return Ok(Params::default()) };
+    if matches!(params.get_type()?, ValueType::Undefined | ValueType::Null) { return Ok(Params::default()); }
+    let p: Params = from_js(env, params)?;
+    p.validate().map_err(|errs| {
+        let msgs: Vec<String> = errs.iter().map(|e| e.to_string()).collect();
+        Error::from_reason(format!("invalid params: {}", msgs.join("; ")))
+    })?;
+    Ok(p)
+}
+
+fn batch<T: Send + 'static>(items: Vec<T>, params: Params, f: fn(&T, &Params) -> f64) -> AsyncTask<Batch> {
+    AsyncTask::new(Batch(Box::new(move || items.iter().map(|x| f(x, &params)).collect())))
+}
+
+/// `calculateCostBatch([{actor, content, base_fare?}], params?) -> Promise<Float64Array>`
+#[napi(js_name = "calculateCostBatch")]
+pub fn calculate_cost_batch(env: Env, items: JsUnknown, params: Option<JsUnknown>) -> Result<AsyncTask<Batch>> {
+    let items: Vec<CostItem> = from_js(&env, items)?;
+    Ok(batch(items, params_from_js(&env, params)?, |x, p| crate::calculate_post_cost(&x.actor, &x.content, p, x.base_fare.unwrap_or(1.0))))
+}
+
+/// `calculateQualityBatch([{A, R, T, D, H, S}], params?) -> Promise<Float64Array>`
+#[napi(js_name = "calculateQualityBatch")]
+pub fn calculate_quality_batch(env: Env, items: JsUnknown, params: Option<JsUnknown>) -> Result<AsyncTask<Batch>> {
+    let items: Vec<QInputs> = from_js(&env, items)?;
+    Ok(batch(items, params_from_js(&env, params)?, |x, p| crate::calculate_quality(x.clone(), p)))
+}
+
+/// `calculateRiskBatch([riskSignals]) -> Promise<Float64Array>` with the default weights
+#[napi(js_name = "calculateRiskBatch")]
+pub fn calculate_risk_batch(env: Env, items: JsUnknown) -> Result<AsyncTask<Batch>> {
+    let items: Vec<Option<RiskSignals>> = from_js(&env, items)?;
+    Ok(batch(items, Params::default(), |x, _| crate::calculate_risk(x, &RiskWeights::default())))
+}
+
+/// `calculateRewardBatch([rewardInput], params?) -> Promise<Float64Array>`
+#[napi(js_name = "calculateRewardBatch")]
+pub fn calculate_reward_batch(env: Env, items: JsUnknown, params: Option<JsUnknown>) -> Result<AsyncTask<Batch>> {
+    let items: Vec<RewardInput> = from_js(&env, items)?;
+    Ok(batch(items, params_from_js(&env, params)?, crate::calculate_serve_reward))
+}
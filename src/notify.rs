@@ -0,0 +1,80 @@
+// Notification throttling (attention protection on the recipient side)
+// - priority = clamp(w_q * sender_q + w_rel * relationship_strength, 0, 1)
+// - Load pressure = clamp(recipient_load / max_load, 0, 1), recipient_load being notifications received in the
+//   current window; the delivery and digest bars rise linearly with it:
+//   deliver if priority >= deliver_base + deliver_k * pressure, else digest if priority >= digest_base + digest_k *
+//   pressure, else drop
+// - Relationships at or above `bypass_relationship` always deliver, so close contacts are never throttled
+
+use crate::{clamp, Quality};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum NotifyDecision {
+    /// Notify now
+    Deliver,
+    /// Hold for the next digest
+    Digest,
+    Drop,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct NotifyParams {
+    pub w_q: f64,
+    pub w_rel: f64,
+    /// Notifications per window at which pressure reaches 1
+    pub max_load: f64,
+    pub deliver_base: f64,
+    pub deliver_k: f64,
+    pub digest_base: f64,
+    pub digest_k: f64,
+    pub bypass_relationship: f64,
+}
+
+impl Default for NotifyParams {
+    fn default() -> Self {
+        NotifyParams {
+            w_q: 0.5, w_rel: 0.5, max_load: 50.0,
+            deliver_base: 0.3, deliver_k: 0.5, digest_base: 0.1, digest_k: 0.3,
+            bypass_relationship: 0.9,
+        }
+    }
+}
+
+/// Decide how to surface one notification
+pub fn throttle(recipient_load: f64, sender_q: impl Into<Quality>, relationship_strength: f64, params: &NotifyParams) -> NotifyDecision {
+    let rel = if relationship_strength.is_finite() { clamp(relationship_strength, 0.0, 1.0) } else { 0.0 };
+    if rel >= params.bypass_relationship { return NotifyDecision::Deliver; }
+    let priority = clamp(params.w_q * sender_q.into().get() + params.w_rel * rel, 0.0, 1.0);
+    let pressure = if params.max_load > 0.0 && recipient_load.is_finite() { clamp(recipient_load / params.max_load, 0.0, 1.0) } else { 1.0 };
+    if priority >= params.deliver_base + params.deliver_k * pressure {
+        NotifyDecision::Deliver
+    } else if priority >= params.digest_base + params.digest_k * pressure {
+        NotifyDecision::Digest
+    } else {
+        NotifyDecision::Drop
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_throttle_tightens_with_load() {
+        let p = NotifyParams::default();
+        // stranger with decent quality: delivered when quiet, digested when busy
+        assert_eq!(throttle(0.0, 0.9, 0.0, &p), NotifyDecision::Deliver);
+        assert_eq!(throttle(50.0, 0.9, 0.0, &p), NotifyDecision::Digest);
+        assert_eq!(throttle(50.0, 0.3, 0.1, &p), NotifyDecision::Drop);
+        assert_eq!(throttle(10.0, 0.3, 0.1, &p), NotifyDecision::Digest);
+        // close contacts always get through
+        assert_eq!(throttle(1e6, 0.1, 0.95, &p), NotifyDecision::Deliver);
+    }
+}
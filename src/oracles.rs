@@ -0,0 +1,321 @@
+// Price oracle trait and adapters (USD anchoring for C_min, escrows, subscriptions)
+// - Enabled by the `tiers` feature
+// - Other pricing currencies chain through `usd_per_unit`; see `currency`
+
+use crate::currency::Currency;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+pub trait PriceOracle {
+    fn usd_per_social(&self) -> Option<f64>;
+    fn usd_per_usdc(&self) -> Option<f64> { Some(1.0) }
+    /// Timestamp (secs) of the latest `usd_per_social` quote, if the feed reports one
+    fn updated_at(&self) -> Option<u64> { None }
+    /// USD per unit of `currency` (USD itself and USDC by default; fiat needs a quote, e.g. `currency::WithFx`)
+    fn usd_per_unit(&self, currency: Currency) -> Option<f64> {
+        match currency {
+            Currency::Usd => Some(1.0),
+            Currency::Usdc => self.usd_per_usdc(),
+            _ => None,
+        }
+    }
+}
+
+// --- example stub oracle (fixed price) ---
+pub struct StubOracle { pub usd_per_social_px: Option<f64> }
+impl PriceOracle for StubOracle {
+    fn usd_per_social(&self) -> Option<f64> { self.usd_per_social_px }
+}
+
+/// How `Aggregate` combines source prices
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum AggregateMode {
+    Median,
+    /// Drop `trim` (0..0.5) of the sorted prices from each end, then average
+    TrimmedMean { trim: f64 },
+}
+
+/// Combines several oracles into one robust price
+/// - Non-finite or non-positive quotes are discarded
+/// - Returns None unless at least `quorum` sources quote a price
+pub struct Aggregate {
+    pub sources: Vec<Box<dyn PriceOracle>>,
+    pub mode: AggregateMode,
+    pub quorum: usize,
+}
+
+impl Aggregate {
+    pub fn new(sources: Vec<Box<dyn PriceOracle>>, mode: AggregateMode, quorum: usize) -> Self {
+        Self { sources, mode, quorum }
+    }
+
+    fn combine(&self, quote: impl Fn(&dyn PriceOracle) -> Option<f64>) -> Option<f64> {
+        let mut px: Vec<f64> = self.sources.iter()
+            .filter_map(|o| quote(o.as_ref()))
+            .filter(|p| p.is_finite() && *p > 0.0)
+            .collect();
+        if px.is_empty() || px.len() < self.quorum { return None; }
+        px.sort_by(f64::total_cmp);
+        Some(match self.mode {
+            AggregateMode::Median => median_sorted(&px),
+            AggregateMode::TrimmedMean { trim } => {
+                let k = ((px.len() as f64) * crate::clamp(trim, 0.0, 0.5)).floor() as usize;
+                let kept = if 2 * k < px.len() { &px[k..px.len() - k] } else { &px[..] };
+                kept.iter().sum::<f64>() / kept.len() as f64
+            }
+        })
+    }
+}
+
+impl PriceOracle for Aggregate {
+    fn usd_per_social(&self) -> Option<f64> { self.combine(|o| o.usd_per_social()) }
+    fn usd_per_usdc(&self) -> Option<f64> { self.combine(|o| o.usd_per_usdc()) }
+    fn usd_per_unit(&self, currency: Currency) -> Option<f64> { self.combine(|o| o.usd_per_unit(currency)) }
+    /// Oldest timestamp among sources currently quoting a price
+    fn updated_at(&self) -> Option<u64> {
+        self.sources.iter().filter(|o| o.usd_per_social().is_some()).filter_map(|o| o.updated_at()).min()
+    }
+}
+
+/// Time-weighted average price over a sliding window
+/// - Each sample's price holds until the next sample (step function)
+/// - As a `PriceOracle`, "now" is the latest sample timestamp
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TwapOracle {
+    pub window_secs: u64,
+    /// (timestamp secs, USD per SOCIAL), strictly increasing timestamps
+    pub samples: VecDeque<(u64, f64)>,
+}
+
+impl TwapOracle {
+    pub fn new(window_secs: u64) -> Self { Self { window_secs, samples: VecDeque::new() } }
+
+    /// Record a price sample; out-of-order, non-finite and non-positive samples are ignored
+    pub fn record(&mut self, timestamp: u64, price: f64) {
+        if !(price.is_finite() && price > 0.0) { return; }
+        if self.samples.back().is_some_and(|&(t, _)| timestamp <= t) { return; }
+        self.samples.push_back((timestamp, price));
+        // keep the newest sample at or before the window start: it covers the window's beginning
+        let start = timestamp.saturating_sub(self.window_secs);
+        while self.samples.len() > 1 && self.samples[1].0 <= start {
+            self.samples.pop_front();
+        }
+    }
+
+    /// TWAP over `[now - window_secs, now]` (None before the first sample)
+    pub fn twap_at(&self, now: u64) -> Option<f64> {
+        let start = now.saturating_sub(self.window_secs);
+        let (mut acc, mut dur) = (0.0, 0u64);
+        let mut last = None;
+        for (i, &(t, p)) in self.samples.iter().enumerate() {
+            if t > now { break; }
+            last = Some(p);
+            let end = self.samples.get(i + 1).map_or(now, |&(tn, _)| tn.min(now));
+            let seg_start = t.max(start);
+            if end > seg_start {
+                acc += p * (end - seg_start) as f64;
+                dur += end - seg_start;
+            }
+        }
+        if dur == 0 { last } else { Some(acc / dur as f64) }
+    }
+}
+
+impl PriceOracle for TwapOracle {
+    fn usd_per_social(&self) -> Option<f64> {
+        self.samples.back().and_then(|&(t, _)| self.twap_at(t))
+    }
+    fn updated_at(&self) -> Option<u64> { self.samples.back().map(|&(t, _)| t) }
+}
+
+// -------- Checked conversions --------
+
+/// Acceptance rules for oracle prices
+/// - `max_age_secs`: quotes older than this (or without a timestamp) are stale
+/// - `max_deviation_pct`: quotes deviating more than this from `reference_usd_per_social` are outliers
+/// - `fallback_usd_per_social`: peg used when the quote is rejected (None => hard failure)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct OraclePolicy {
+    pub max_age_secs: Option<u64>,
+    pub max_deviation_pct: Option<f64>,
+    pub reference_usd_per_social: Option<f64>,
+    pub fallback_usd_per_social: Option<f64>,
+}
+
+impl OraclePolicy {
+    /// No freshness/deviation checks; use `px` when the oracle has no price
+    pub fn with_fallback(px: f64) -> Self {
+        Self { fallback_usd_per_social: Some(px), ..Default::default() }
+    }
+}
+
+/// Why an oracle quote was not used
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Rejection {
+    Missing,
+    Stale { age_secs: Option<u64> },
+    Outlier { deviation_pct: f64 },
+    /// No USD quote for the pricing currency (never covered by the fallback peg)
+    Unquoted { currency: Currency },
+}
+
+/// Where the conversion price came from
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum PriceSource {
+    Oracle,
+    Fallback { reason: Rejection },
+}
+
+/// Successful USD -> SOCIAL conversion
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Conversion {
+    pub social: f64,
+    pub usd_per_social: f64,
+    pub source: PriceSource,
+}
+
+/// Quote rejected and no fallback configured
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct OracleError { pub reason: Rejection }
+
+impl std::fmt::Display for OracleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "oracle price rejected without fallback: {:?}", self.reason)
+    }
+}
+
+impl std::error::Error for OracleError {}
+
+/// Oracle plus acceptance policy and the current time, bundled for pricing calls
+#[derive(Clone, Copy)]
+pub struct PriceContext<'a> {
+    pub oracle: &'a dyn PriceOracle,
+    pub policy: &'a OraclePolicy,
+    pub now: u64,
+}
+
+/// Resolve the USD-per-SOCIAL price under `policy`
+pub fn checked_usd_per_social(oracle: &dyn PriceOracle, policy: &OraclePolicy, now: u64) -> Result<(f64, PriceSource), OracleError> {
+    let reason = match oracle.usd_per_social().filter(|p| p.is_finite() && *p > 0.0) {
+        None => Rejection::Missing,
+        Some(px) => {
+            let age = oracle.updated_at().map(|t| now.saturating_sub(t));
+            let deviation = policy.reference_usd_per_social
+                .filter(|r| *r > 0.0)
+                .map(|r| (px / r - 1.0).abs() * 100.0);
+            if policy.max_age_secs.is_some_and(|max| age.is_none_or(|a| a > max)) {
+                Rejection::Stale { age_secs: age }
+            } else if let Some(d) = deviation.filter(|d| policy.max_deviation_pct.is_some_and(|max| *d > max)) {
+                Rejection::Outlier { deviation_pct: d }
+            } else {
+                return Ok((px, PriceSource::Oracle));
+            }
+        }
+    };
+    match policy.fallback_usd_per_social.filter(|p| p.is_finite() && *p > 0.0) {
+        Some(px) => Ok((px, PriceSource::Fallback { reason })),
+        None => Err(OracleError { reason }),
+    }
+}
+
+/// Convert a USD amount to SOCIAL, reporting whether the oracle or the fallback peg was used
+pub fn usd_to_social(usd: f64, oracle: &dyn PriceOracle, policy: &OraclePolicy, now: u64) -> Result<Conversion, OracleError> {
+    crate::currency::convert(usd, Currency::Usd, &PriceContext { oracle, policy, now })
+}
+
+pub(crate) fn median_sorted(px: &[f64]) -> f64 {
+    let n = px.len();
+    if n % 2 == 1 { px[n / 2] } else { (px[n / 2 - 1] + px[n / 2]) / 2.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stub(px: Option<f64>) -> Box<dyn PriceOracle> { Box::new(StubOracle { usd_per_social_px: px }) }
+
+    #[test]
+    fn test_median_ignores_bad_feed() {
+        let agg = Aggregate::new(vec![stub(Some(0.20)), stub(Some(0.21)), stub(Some(50.0))], AggregateMode::Median, 2);
+        assert_eq!(agg.usd_per_social(), Some(0.21));
+        let even = Aggregate::new(vec![stub(Some(0.2)), stub(Some(0.4)), stub(None), stub(Some(f64::NAN))], AggregateMode::Median, 2);
+        assert!((even.usd_per_social().unwrap() - 0.3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_quorum_and_trimmed_mean() {
+        let agg = Aggregate::new(vec![stub(Some(0.2)), stub(None), stub(None)], AggregateMode::Median, 2);
+        assert_eq!(agg.usd_per_social(), None);
+
+        let srcs = vec![stub(Some(0.1)), stub(Some(0.2)), stub(Some(0.2)), stub(Some(0.2)), stub(Some(9.0))];
+        let agg = Aggregate::new(srcs, AggregateMode::TrimmedMean { trim: 0.2 }, 3);
+        assert!((agg.usd_per_social().unwrap() - 0.2).abs() < 1e-12);
+        assert_eq!(agg.usd_per_usdc(), Some(1.0));
+    }
+
+    #[test]
+    fn test_twap_smooths_ticks() {
+        let mut tw = TwapOracle::new(600);
+        assert_eq!(tw.usd_per_social(), None);
+        tw.record(0, 0.20);
+        assert_eq!(tw.usd_per_social(), Some(0.20));
+        tw.record(300, 0.40);
+        tw.record(600, 0.40);
+        // 0.2 for 300s, 0.4 for 300s
+        assert!((tw.usd_per_social().unwrap() - 0.30).abs() < 1e-12);
+        tw.record(500, 9.0); // out of order: ignored
+        tw.record(1200, 0.40);
+        assert!((tw.usd_per_social().unwrap() - 0.40).abs() < 1e-12);
+        assert!(tw.samples.len() <= 3);
+    }
+
+    struct Timed { px: f64, at: u64 }
+    impl PriceOracle for Timed {
+        fn usd_per_social(&self) -> Option<f64> { Some(self.px) }
+        fn updated_at(&self) -> Option<u64> { Some(self.at) }
+    }
+
+    #[test]
+    fn test_checked_conversion() {
+        let policy = OraclePolicy {
+            max_age_secs: Some(60),
+            max_deviation_pct: Some(20.0),
+            reference_usd_per_social: Some(0.2),
+            fallback_usd_per_social: Some(0.25),
+        };
+        let fresh = usd_to_social(1.0, &Timed { px: 0.2, at: 1000 }, &policy, 1030).unwrap();
+        assert_eq!((fresh.social, fresh.source), (5.0, PriceSource::Oracle));
+
+        let stale = usd_to_social(1.0, &Timed { px: 0.2, at: 1000 }, &policy, 2000).unwrap();
+        assert_eq!(stale.source, PriceSource::Fallback { reason: Rejection::Stale { age_secs: Some(1000) } });
+        assert_eq!(stale.social, 4.0);
+
+        let outlier = usd_to_social(1.0, &Timed { px: 0.5, at: 1000 }, &policy, 1000).unwrap();
+        assert!(matches!(outlier.source, PriceSource::Fallback { reason: Rejection::Outlier { .. } }));
+
+        let strict = OraclePolicy { fallback_usd_per_social: None, ..policy };
+        let err = usd_to_social(1.0, &StubOracle { usd_per_social_px: None }, &strict, 0).unwrap_err();
+        assert_eq!(err.reason, Rejection::Missing);
+        // no timestamp while freshness is required => stale
+        let err = usd_to_social(1.0, &StubOracle { usd_per_social_px: Some(0.2) }, &strict, 0).unwrap_err();
+        assert_eq!(err.reason, Rejection::Stale { age_secs: None });
+    }
+}
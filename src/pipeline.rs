@@ -0,0 +1,311 @@
+// End-to-end evaluation of one post: raw signals -> q/EF -> risk -> base fare -> cost -> propagation -> reward
+// - Mirrors the order integrators call the individual functions in
+// - `reevaluate` is the post-publication escalation flow: new risk signals only ever raise a post's risk (per-signal
+//   max), so propagation only narrows, quarantine is final, and the author owes the cost difference as a surcharge
+
+use crate::{
+    adjust_propagation_for_content, calculate_ef, calculate_post_cost, calculate_quality, calculate_risk,
+    calculate_serve_reward, update_base_cost, Actor, Content, Params, PropagationResult, QInputs, RewardInput,
+    RiskSignals, RiskWeights,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Congestion snapshot used to derive the base fare
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LoadSnapshot { pub current_base: f64, pub current_load: f64 }
+
+/// Raw inputs for one post
+/// - `load`: if present, the base fare is `update_base_cost(current_base, current_load)`; otherwise `base_fare` (default 1.0)
+/// - `serve`: optional serve to reward for this content
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PipelineInput {
+    pub quality: QInputs,
+    pub followers_q: Vec<f64>,
+    pub rl: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub posts_1h: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub content: Content,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub base_fare: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub load: Option<LoadSnapshot>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub serve: Option<RewardInput>,
+}
+
+/// Every intermediate and final result of the pipeline
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PipelineOutput {
+    pub q: f64,
+    pub ef: f64,
+    pub risk: f64,
+    pub base_fare: f64,
+    pub cost: f64,
+    pub propagation: PropagationResult,
+    pub reward: Option<f64>,
+}
+
+/// Run the full algorithm on one post
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", target = "slimechain::pipeline", skip_all, fields(input = %crate::trace::debug_hash(input))))]
+pub fn run(input: &PipelineInput, params: &Params) -> PipelineOutput {
+    let q = calculate_quality(input.quality.clone(), params);
+    let ef = calculate_ef(&input.followers_q, params);
+    let risk = calculate_risk(&input.content.risk_signals, &RiskWeights::default());
+    let base_fare = match &input.load {
+        Some(l) => update_base_cost(l.current_base, l.current_load, params),
+        None => input.base_fare.unwrap_or(1.0),
+    };
+    let actor = Actor { rl: input.rl, q, ef, posts_1h: input.posts_1h, ..Default::default() };
+    let cost = calculate_post_cost(&actor, &input.content, params, base_fare);
+    let propagation = adjust_propagation_for_content(&input.content, params);
+    let reward = input.serve.as_ref().map(|s| calculate_serve_reward(s, params));
+    PipelineOutput { q, ef, risk, base_fare, cost, propagation, reward }
+}
+
+// -------- Parameter set comparison --------
+
+/// One record evaluated under parameter sets A and B
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RecordDiff {
+    pub index: usize,
+    pub cost_a: f64,
+    pub cost_b: f64,
+    pub cost_delta: f64,
+    pub ttl_a: u32,
+    pub ttl_b: u32,
+    pub fanout_a: u32,
+    pub fanout_b: u32,
+    pub reward_a: Option<f64>,
+    pub reward_b: Option<f64>,
+}
+
+/// Distribution of `b - a` over the corpus
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DeltaStats {
+    pub count: usize,
+    pub mean_a: f64,
+    pub mean_b: f64,
+    pub mean_delta: f64,
+    pub median_delta: f64,
+    pub max_increase: f64,
+    pub max_decrease: f64,
+    pub increased: usize,
+    pub decreased: usize,
+}
+
+/// Per-record deltas plus aggregates
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DiffReport {
+    pub records: Vec<RecordDiff>,
+    pub cost: DeltaStats,
+    pub reward: DeltaStats,
+    /// Records whose TTL or fanout differs
+    pub propagation_changed: usize,
+}
+
+/// Evaluate every input under both parameter sets
+pub fn compare(inputs: &[PipelineInput], a: &Params, b: &Params) -> DiffReport {
+    let records: Vec<RecordDiff> = inputs.iter().enumerate().map(|(index, input)| {
+        let (ra, rb) = (run(input, a), run(input, b));
+        RecordDiff {
+            index,
+            cost_a: ra.cost, cost_b: rb.cost, cost_delta: rb.cost - ra.cost,
+            ttl_a: ra.propagation.ttl, ttl_b: rb.propagation.ttl,
+            fanout_a: ra.propagation.fanout, fanout_b: rb.propagation.fanout,
+            reward_a: ra.reward, reward_b: rb.reward,
+        }
+    }).collect();
+    let cost = delta_stats(records.iter().map(|r| (r.cost_a, r.cost_b)));
+    let reward = delta_stats(records.iter().filter_map(|r| Some((r.reward_a?, r.reward_b?))));
+    let propagation_changed = records.iter().filter(|r| r.ttl_a != r.ttl_b || r.fanout_a != r.fanout_b).count();
+    DiffReport { records, cost, reward, propagation_changed }
+}
+
+fn delta_stats(pairs: impl Iterator<Item = (f64, f64)>) -> DeltaStats {
+    let pairs: Vec<(f64, f64)> = pairs.collect();
+    let n = pairs.len();
+    if n == 0 { return DeltaStats::default(); }
+    let mut deltas: Vec<f64> = pairs.iter().map(|(a, b)| b - a).collect();
+    deltas.sort_by(|x, y| x.total_cmp(y));
+    let median_delta = if n % 2 == 1 { deltas[n / 2] } else { (deltas[n / 2 - 1] + deltas[n / 2]) / 2.0 };
+    DeltaStats {
+        count: n,
+        mean_a: pairs.iter().map(|p| p.0).sum::<f64>() / n as f64,
+        mean_b: pairs.iter().map(|p| p.1).sum::<f64>() / n as f64,
+        mean_delta: deltas.iter().sum::<f64>() / n as f64,
+        median_delta,
+        max_increase: deltas[n - 1].max(0.0),
+        max_decrease: deltas[0].min(0.0),
+        increased: deltas.iter().filter(|&&d| d > 0.0).count(),
+        decreased: deltas.iter().filter(|&&d| d < 0.0).count(),
+    }
+}
+
+// -------- Post-publication escalation --------
+
+/// A published post as nodes track it
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ContentState {
+    pub actor: Actor,
+    /// Content with the risk signals seen so far
+    pub content: Content,
+    /// Base fare at publication
+    pub base_fare: f64,
+    /// Publication cost plus surcharges so far
+    pub cost_paid: f64,
+    pub propagation: PropagationResult,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Transition {
+    RiskEscalated { from: f64, to: f64 },
+    /// New (lower) ttl/fanout
+    PropagationNarrowed { ttl: u32, fanout: u32 },
+    Quarantined,
+    Surcharged { amount: f64 },
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ReevaluationOutcome {
+    /// State after escalation (`cost_paid` includes the surcharge)
+    pub state: ContentState,
+    pub risk: f64,
+    /// Additional cost owed (>= 0)
+    pub surcharge: f64,
+    /// In order: risk, propagation, quarantine, surcharge; empty when nothing changed
+    pub transitions: Vec<Transition>,
+}
+
+fn escalate(seen: &Option<RiskSignals>, new: &RiskSignals) -> RiskSignals {
+    let max = |a: Option<f64>, b: Option<f64>| match (a, b) {
+        (Some(x), Some(y)) => Some(x.max(y)),
+        (x, y) => x.or(y),
+    };
+    let s = seen.clone().unwrap_or_default();
+    RiskSignals {
+        coordination: max(s.coordination, new.coordination),
+        clustering: max(s.clustering, new.clustering),
+        burst: max(s.burst, new.burst),
+        monotonicity: max(s.monotonicity, new.monotonicity),
+        abuse_history: max(s.abuse_history, new.abuse_history),
+        duplication: max(s.duplication, new.duplication),
+    }
+}
+
+/// Fold risk signals that arrived after publication into a post's state
+pub fn reevaluate(content_state: &ContentState, new_signals: &RiskSignals, params: &Params) -> ReevaluationOutcome {
+    let before = calculate_risk(&content_state.content.risk_signals, &RiskWeights::DEFAULT);
+    let mut state = content_state.clone();
+    state.content.risk_signals = Some(escalate(&state.content.risk_signals, new_signals));
+    let risk = calculate_risk(&state.content.risk_signals, &RiskWeights::DEFAULT);
+    let mut transitions = Vec::new();
+    if risk > before { transitions.push(Transition::RiskEscalated { from: before, to: risk }); }
+
+    let old = &content_state.propagation;
+    let fresh = adjust_propagation_for_content(&state.content, params);
+    if old.quarantined || fresh.quarantined {
+        state.propagation = PropagationResult::quarantine();
+        if !old.quarantined { transitions.push(Transition::Quarantined); }
+    } else {
+        state.propagation = PropagationResult { ttl: fresh.ttl.min(old.ttl), fanout: fresh.fanout.min(old.fanout), quarantined: false };
+        if (state.propagation.ttl, state.propagation.fanout) != (old.ttl, old.fanout) {
+            transitions.push(Transition::PropagationNarrowed { ttl: state.propagation.ttl, fanout: state.propagation.fanout });
+        }
+    }
+
+    let cost = calculate_post_cost(&state.actor, &state.content, params, state.base_fare);
+    let surcharge = (cost - state.cost_paid).max(0.0);
+    if surcharge > 0.0 {
+        state.cost_paid += surcharge;
+        transitions.push(Transition::Surcharged { amount: surcharge });
+    }
+    ReevaluationOutcome { state, risk, surcharge, transitions }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> PipelineInput {
+        PipelineInput {
+            quality: QInputs { A:0.8, R:0.7, T:0.9, D:0.6, H:0.2, S:0.1 },
+            followers_q: vec![0.9, 0.8, 0.3],
+            rl: 20.0,
+            posts_1h: Some(3.0),
+            content: Content::default(),
+            base_fare: None,
+            load: Some(LoadSnapshot { current_base: 1.0, current_load: 1000.0 }),
+            serve: Some(RewardInput { ticket_budget:1.0, client_q:0.8, size_bytes:25_000, ttfb_ms:150, ..Default::default() }),
+        }
+    }
+
+    #[test]
+    fn t_pipeline_matches_individual_calls() {
+        let p = Params::default();
+        let input = sample();
+        let out = run(&input, &p);
+        assert_eq!(out.base_fare, update_base_cost(1.0, 1000.0, &p));
+        let actor = Actor { rl:20.0, q:out.q, ef:out.ef, posts_1h:Some(3.0), ..Default::default() };
+        assert_eq!(out.cost, calculate_post_cost(&actor, &input.content, &p, out.base_fare));
+        assert!(out.reward.unwrap() > 0.0);
+    }
+
+    #[test]
+    fn t_compare_reports_deltas() {
+        let a = Params::default();
+        let mut b = Params::default();
+        b.cost.a *= 2.0;
+        let report = compare(&[sample(), sample()], &a, &b);
+        assert_eq!(report.cost.count, 2);
+        assert_eq!(report.cost.increased, 2);
+        assert!(report.cost.mean_delta > 0.0);
+        assert_eq!(report.reward.mean_delta, 0.0);
+        assert_eq!(report.propagation_changed, 0);
+    }
+
+    #[test]
+    fn t_reevaluate_escalates_once() {
+        let mut p = Params::default();
+        p.propagation.quarantine_threshold = Some(0.8);
+        let input = sample();
+        let out = run(&input, &p);
+        let actor = Actor { rl: 20.0, q: out.q, ef: out.ef, posts_1h: Some(3.0), ..Default::default() };
+        let state = ContentState { actor, content: input.content, base_fare: out.base_fare, cost_paid: out.cost, propagation: out.propagation };
+
+        let mild = RiskSignals { coordination: Some(0.6), clustering: Some(0.6), ..Default::default() };
+        let o = reevaluate(&state, &mild, &p);
+        assert!(matches!(o.transitions[0], Transition::RiskEscalated { from, .. } if from == 0.0));
+        assert!(o.transitions.iter().any(|t| matches!(t, Transition::PropagationNarrowed { .. })));
+        assert!(o.surcharge > 0.0);
+        assert_eq!(o.state.cost_paid, calculate_post_cost(&o.state.actor, &o.state.content, &p, o.state.base_fare));
+        // replaying the same or weaker signals changes nothing
+        assert!(reevaluate(&o.state, &mild, &p).transitions.is_empty());
+        assert!(reevaluate(&o.state, &RiskSignals { coordination: Some(0.1), ..Default::default() }, &p).transitions.is_empty());
+
+        let severe = RiskSignals { coordination: Some(1.0), clustering: Some(1.0), burst: Some(1.0), monotonicity: Some(1.0), abuse_history: Some(1.0), duplication: Some(1.0) };
+        let q = reevaluate(&o.state, &severe, &p);
+        assert!(q.state.propagation.quarantined);
+        assert!(q.transitions.contains(&Transition::Quarantined));
+    }
+}
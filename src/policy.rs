@@ -0,0 +1,197 @@
+// Decision policy compiled to lookup tables
+// - `compile` precomputes, for relays that cannot do float math per message, integer tables indexed by integers:
+//   risk (the wire stamp's u16, risk * 65535) -> ttl/fanout, load bucket -> base fare step multiplier, and
+//   posts_1h -> rate penalty multiplier
+// - Multipliers are Q16 fixed point (`Q16_ONE` = 1.0); tables are evaluated at bucket midpoints with the same formulas as
+//   `propagation_decision` (no topic), `update_base_cost` and `calculate_post_cost`
+// - `DecisionTables::bounds` is the worst-case difference from the exact results: it follows from the bucket width and
+//   the formulas' slopes (plus Q16 rounding), not from sampling; quarantine is an exact integer threshold
+// - Out of scope: topic multipliers, per-kind rate limits, and the base_min/base_max clamp (apply it to the new fare)
+
+use crate::{propagation_at_risk, Params, PropagationResult};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// 1.0 in Q16
+pub const Q16_ONE: u32 = 1 << 16;
+
+/// Table sizes
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TableSpec {
+    /// Equal-width risk buckets over 0..=1
+    pub risk_buckets: u32,
+    /// Load buckets per `target_load`
+    pub load_buckets_per_target: u32,
+    /// Loads up to this multiple of `target_load` are tabulated; above it the last bucket applies
+    pub max_load_ratio: u32,
+    /// posts_1h tabulated exactly (raised to cover the rate limit); above it the penalty is extrapolated
+    pub max_posts: u32,
+}
+
+impl Default for TableSpec {
+    fn default() -> Self { TableSpec { risk_buckets: 256, load_buckets_per_target: 16, max_load_ratio: 4, max_posts: 64 } }
+}
+
+/// Worst-case error of a table lookup against the exact computation
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ErrorBounds {
+    /// ttl/fanout steps
+    pub ttl: u32,
+    pub fanout: u32,
+    /// Relative error of the fare step (loads up to `max_load_ratio`)
+    pub fare_step_rel: f64,
+    /// Absolute error of the penalty multiplier up to `max_posts` (beyond: plus 2^-16 and 2^-33 per extra post)
+    pub penalty_abs: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DecisionTables {
+    /// Per risk bucket (index = risk_u16 * len / 65535, last bucket includes 65535)
+    pub ttl: Vec<u32>,
+    pub fanout: Vec<u32>,
+    /// Quarantine when risk_u16 exceeds this
+    pub quarantine_above: Option<u16>,
+    pub load_buckets_per_target: u32,
+    /// Q16 base fare multiplier per load bucket (index = load * load_buckets_per_target / target_load)
+    pub fare_step: Vec<u32>,
+    /// Q16 rate penalty multiplier per posts_1h
+    pub penalty: Vec<u32>,
+    /// Penalty increase per post beyond the table, Q32
+    pub penalty_slope: u64,
+    pub bounds: ErrorBounds,
+}
+
+fn q16(x: f64) -> u32 { (x * Q16_ONE as f64).round().clamp(0.0, u32::MAX as f64) as u32 }
+
+/// `update_base_cost`'s multiplier at `load_ratio` (load / target), before the base_min/base_max clamp
+fn fare_multiplier(load_ratio: f64, params: &Params) -> f64 {
+    let c = &params.congestion;
+    let mut step = c.eta * (load_ratio - 1.0);
+    if step < 0.0 { step *= c.surge.cooldown_factor.max(0.0); }
+    let m = step.exp();
+    match c.surge.max_increase_pct {
+        Some(pct) => m.min(1.0 + pct.max(0.0) / 100.0),
+        None => m,
+    }
+}
+
+/// `calculate_post_cost`'s rate multiplier at `posts` posts in the last hour against `rate`
+fn penalty_multiplier(posts: f64, rate: f64) -> f64 {
+    if posts > rate { 1.0 + 0.5 * (posts / rate - 1.0) } else { 1.0 }
+}
+
+/// `compile_with` the default table sizes
+pub fn compile(params: &Params) -> DecisionTables { compile_with(params, &TableSpec::default()) }
+
+pub fn compile_with(params: &Params, spec: &TableSpec) -> DecisionTables {
+    let p = &params.propagation;
+    let n = spec.risk_buckets.max(1);
+    // quarantine is the exact threshold below, not a bucket property
+    let mut open = params.clone();
+    open.propagation.quarantine_threshold = None;
+    let (ttl, fanout): (Vec<u32>, Vec<u32>) = (0..n)
+        .map(|i| {
+            let r = propagation_at_risk((i as f64 + 0.5) / n as f64, None, &open);
+            (r.ttl, r.fanout)
+        })
+        .unzip();
+    let quarantine_above = p.quarantine_threshold.map(|t| (t.clamp(0.0, 1.0) * 65_535.0).floor() as u16);
+
+    let bpt = spec.load_buckets_per_target.max(1);
+    let fare_step: Vec<u32> = (0..bpt * spec.max_load_ratio.max(1))
+        .map(|i| q16(fare_multiplier((i as f64 + 0.5) / bpt as f64, params)))
+        .collect();
+
+    let rate = params.cost.rate_limit_per_hour.max(1.0);
+    let posts = spec.max_posts.max(rate.ceil() as u32 + 1);
+    let penalty: Vec<u32> = (0..=posts).map(|k| q16(penalty_multiplier(k as f64, rate))).collect();
+    let penalty_slope = (0.5 / rate * 4_294_967_296.0).round() as u64;
+
+    // half a bucket of the steepest slope, then rounding to an integer (or to Q16)
+    let half = 0.5 / n as f64;
+    let steps = |k: f64| (k.abs() * half).ceil() as u32;
+    let c = &params.congestion;
+    let fare_slope = c.eta.abs() * c.surge.cooldown_factor.max(1.0);
+    let min_fare = fare_step.iter().copied().min().unwrap_or(Q16_ONE).max(1) as f64 / Q16_ONE as f64;
+    let q16_err = 0.5 / Q16_ONE as f64;
+    let bounds = ErrorBounds {
+        ttl: steps(p.k1),
+        fanout: steps(p.k2),
+        fare_step_rel: (fare_slope * 0.5 / bpt as f64).exp_m1() + q16_err / min_fare,
+        penalty_abs: q16_err,
+    };
+    DecisionTables { ttl, fanout, quarantine_above, load_buckets_per_target: bpt, fare_step, penalty, penalty_slope, bounds }
+}
+
+impl DecisionTables {
+    /// ttl/fanout for a wire-stamp risk (risk * 65535)
+    pub fn propagation(&self, risk: u16) -> PropagationResult {
+        if self.quarantine_above.is_some_and(|t| risk > t) { return PropagationResult::quarantine(); }
+        let n = self.ttl.len();
+        let i = (risk as usize * n / 65_535).min(n - 1);
+        PropagationResult { ttl: self.ttl[i], fanout: self.fanout[i], quarantined: false }
+    }
+
+    /// Q16 base fare multiplier for a load bucket (buckets past the table use the last one)
+    pub fn fare_step(&self, load_bucket: usize) -> u32 {
+        self.fare_step[load_bucket.min(self.fare_step.len() - 1)]
+    }
+
+    /// Q16 rate penalty multiplier for `posts_1h`
+    pub fn rate_penalty(&self, posts_1h: u32) -> u32 {
+        let last = self.penalty.len() - 1;
+        match self.penalty.get(posts_1h as usize) {
+            Some(&m) => m,
+            None => {
+                let extra = (posts_1h as u64 - last as u64).saturating_mul(self.penalty_slope) >> 16;
+                (self.penalty[last] as u64).saturating_add(extra).min(u32::MAX as u64) as u32
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculate_post_cost_detailed, update_base_cost, Actor, Content};
+
+    #[test]
+    fn t_tables_within_bounds() {
+        let mut params = Params::default();
+        params.propagation.quarantine_threshold = Some(0.8);
+        params.congestion.base_min = 1e-9;
+        params.congestion.base_max = 1e9;
+        let t = compile(&params);
+
+        for r in (0..=65_535u32).step_by(7) {
+            let exact = propagation_at_risk(r as f64 / 65_535.0, None, &params);
+            let got = t.propagation(r as u16);
+            assert_eq!(got.quarantined, exact.quarantined, "risk {}", r);
+            assert!(got.ttl.abs_diff(exact.ttl) <= t.bounds.ttl && got.fanout.abs_diff(exact.fanout) <= t.bounds.fanout);
+        }
+
+        let target = params.congestion.target_load;
+        let bpt = t.load_buckets_per_target as f64;
+        for i in 0..4_000 {
+            let ratio = i as f64 / 1_000.0;
+            let exact = update_base_cost(1.0, ratio * target, &params);
+            let got = t.fare_step((ratio * bpt) as usize) as f64 / Q16_ONE as f64;
+            assert!((got / exact - 1.0).abs() <= t.bounds.fare_step_rel, "load ratio {}", ratio);
+        }
+
+        for posts in 0..500u32 {
+            let actor = Actor { posts_1h: Some(posts as f64), ..Default::default() };
+            let exact = calculate_post_cost_detailed(&actor, &Content::default(), &params, 1.0).rate_multiplier;
+            let got = t.rate_penalty(posts) as f64 / Q16_ONE as f64;
+            let beyond = posts.saturating_sub(t.penalty.len() as u32 - 1) as f64;
+            let bound = t.bounds.penalty_abs + if beyond > 0.0 { 2f64.powi(-16) + beyond * 2f64.powi(-33) } else { 0.0 };
+            assert!((got - exact).abs() <= bound, "posts {}", posts);
+        }
+    }
+}
@@ -0,0 +1,200 @@
+// Proof-of-Retrieval (PoR) challenge helpers
+// - Content is committed to by a Merkle root over fixed-size chunks (`content_root`)
+// - A challenge samples chunk indices deterministically from (seed, content_hash)
+// - A response carries the sampled chunks plus Merkle paths; verification needs only the root
+
+use crate::{calculate_serve_reward, Params, RewardInput};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Chunk size used for content commitments
+pub const CHUNK_SIZE: usize = 4096;
+/// Chunks sampled per challenge
+pub const SAMPLES: usize = 4;
+
+pub type Hash32 = [u8; 32];
+
+/// Retrieval challenge for one piece of content
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Challenge {
+    pub content_hash: Hash32,
+    pub size: u64,
+    /// Sampled chunk indices (sorted, unique)
+    pub indices: Vec<u64>,
+}
+
+/// One sampled chunk with its Merkle path (leaf to root sibling hashes)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ChunkProof {
+    pub index: u64,
+    pub data: Vec<u8>,
+    pub path: Vec<Hash32>,
+}
+
+/// Server response to a challenge
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Response { pub chunks: Vec<ChunkProof> }
+
+pub(crate) fn leaf_hash(chunk: &[u8]) -> Hash32 {
+    let mut h = Sha256::new();
+    h.update([0u8]);
+    h.update(chunk);
+    h.finalize().into()
+}
+
+fn node_hash(l: &Hash32, r: &Hash32) -> Hash32 {
+    let mut h = Sha256::new();
+    h.update([1u8]);
+    h.update(l);
+    h.update(r);
+    h.finalize().into()
+}
+
+fn chunk_count(size: u64) -> u64 { size.div_ceil(CHUNK_SIZE as u64).max(1) }
+
+// All tree levels from leaves to root; an odd last node is paired with itself
+pub(crate) fn merkle_levels(mut level: Vec<Hash32>) -> Vec<Vec<Hash32>> {
+    let mut levels = Vec::new();
+    while level.len() > 1 {
+        let next = level.chunks(2).map(|p| node_hash(&p[0], p.get(1).unwrap_or(&p[0]))).collect();
+        levels.push(level);
+        level = next;
+    }
+    levels.push(level);
+    levels
+}
+
+// Sibling hashes from leaf `pos` up to (not including) the root
+pub(crate) fn merkle_path(levels: &[Vec<Hash32>], mut pos: usize) -> Vec<Hash32> {
+    let mut path = Vec::with_capacity(levels.len() - 1);
+    for level in &levels[..levels.len() - 1] {
+        path.push(*level.get(pos ^ 1).unwrap_or(&level[pos.min(level.len() - 1)]));
+        pos /= 2;
+    }
+    path
+}
+
+// Path length of every leaf in a tree over `leaves` leaves (levels below the root)
+pub(crate) fn merkle_depth(leaves: u64) -> usize { leaves.max(1).next_power_of_two().trailing_zeros() as usize }
+
+// Root implied by a leaf hash at `pos` and its path
+pub(crate) fn merkle_fold(mut h: Hash32, mut pos: u64, path: &[Hash32]) -> Hash32 {
+    for sib in path {
+        h = if pos.is_multiple_of(2) { node_hash(&h, sib) } else { node_hash(sib, &h) };
+        pos /= 2;
+    }
+    h
+}
+
+fn tree_levels(data: &[u8]) -> Vec<Vec<Hash32>> {
+    merkle_levels(if data.is_empty() { vec![leaf_hash(&[])] } else { data.chunks(CHUNK_SIZE).map(leaf_hash).collect() })
+}
+
+/// Merkle root over `CHUNK_SIZE` chunks of the content
+pub fn content_root(data: &[u8]) -> Hash32 {
+    tree_levels(data).last().unwrap()[0]
+}
+
+/// Derive a deterministic challenge from a seed (e.g., epoch beacon) and the content commitment
+pub fn generate_challenge(seed: u64, content_hash: Hash32, size: u64) -> Challenge {
+    let n = chunk_count(size);
+    let want = (SAMPLES as u64).min(n) as usize;
+    let mut indices = Vec::with_capacity(want);
+    let mut counter = 0u64;
+    while indices.len() < want {
+        let mut h = Sha256::new();
+        h.update(seed.to_le_bytes());
+        h.update(content_hash);
+        h.update(counter.to_le_bytes());
+        let d: Hash32 = h.finalize().into();
+        let idx = u64::from_le_bytes(d[..8].try_into().unwrap()) % n;
+        if !indices.contains(&idx) { indices.push(idx); }
+        counter += 1;
+    }
+    indices.sort_unstable();
+    Challenge { content_hash, size, indices }
+}
+
+/// Build the response for a challenge from the full content (server side)
+pub fn prove(challenge: &Challenge, data: &[u8]) -> Response {
+    let levels = tree_levels(data);
+    let chunks = challenge.indices.iter().map(|&index| {
+        let start = (index as usize * CHUNK_SIZE).min(data.len());
+        let end = (start + CHUNK_SIZE).min(data.len());
+        ChunkProof { index, data: data[start..end].to_vec(), path: merkle_path(&levels, index as usize) }
+    }).collect();
+    Response { chunks }
+}
+
+/// Check that the response contains every challenged chunk with a valid Merkle path to `content_hash`
+/// - Each path must have exactly the depth of the tree over `challenge.size`, checked before any hashing
+pub fn verify_response(challenge: &Challenge, response: &Response) -> bool {
+    if response.chunks.len() != challenge.indices.len() { return false; }
+    let n = chunk_count(challenge.size);
+    let depth = merkle_depth(n);
+    for (&index, proof) in challenge.indices.iter().zip(&response.chunks) {
+        if proof.index != index || proof.path.len() != depth { return false; }
+        let expected_len = if index + 1 == n {
+            challenge.size as usize - (n as usize - 1) * CHUNK_SIZE
+        } else {
+            CHUNK_SIZE
+        };
+        if proof.data.len() != expected_len { return false; }
+        if merkle_fold(leaf_hash(&proof.data), index, &proof.path) != challenge.content_hash { return false; }
+    }
+    true
+}
+
+/// Serve reward gated on a successful retrieval proof (None if the proof fails)
+pub fn verified_serve_reward(input: &RewardInput, challenge: &Challenge, response: &Response, params: &Params) -> Option<f64> {
+    verify_response(challenge, response).then(|| calculate_serve_reward(input, params))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content(len: usize) -> Vec<u8> { (0..len).map(|i| (i * 31 % 251) as u8).collect() }
+
+    #[test]
+    fn test_challenge_roundtrip() {
+        let data = content(CHUNK_SIZE * 9 + 123);
+        let root = content_root(&data);
+        let ch = generate_challenge(42, root, data.len() as u64);
+        assert_eq!(ch.indices.len(), SAMPLES);
+        assert_eq!(ch, generate_challenge(42, root, data.len() as u64));
+        assert!(verify_response(&ch, &prove(&ch, &data)));
+
+        // a server holding corrupted content fails
+        let mut bad = data.clone();
+        for i in ch.indices.iter() { bad[*i as usize * CHUNK_SIZE] ^= 0xff; }
+        assert!(!verify_response(&ch, &prove(&ch, &bad)));
+
+        // paths must match the tree depth (10 chunks: 4 levels)
+        let mut long = prove(&ch, &data);
+        assert_eq!(long.chunks[0].path.len(), 4);
+        long.chunks[0].path.push([0; 32]);
+        assert!(!verify_response(&ch, &long));
+        let mut short = prove(&ch, &data);
+        short.chunks[0].path.pop();
+        assert!(!verify_response(&ch, &short));
+    }
+
+    #[test]
+    fn test_verified_reward() {
+        let params = Params::default();
+        let data = content(1000);
+        let ch = generate_challenge(1, content_root(&data), 1000);
+        let input = RewardInput{ ticket_budget:1.0, client_q:0.9, size_bytes:1000, ttfb_ms:50, ..Default::default() };
+        assert!(verified_serve_reward(&input, &ch, &prove(&ch, &data), &params).is_some());
+        let empty = Response { chunks: vec![] };
+        assert!(verified_serve_reward(&input, &ch, &empty, &params).is_none());
+    }
+}
@@ -0,0 +1,86 @@
+// Precomputed parameter-derived constants
+// - Core functions take `&impl AsParams`: a plain `Params` derives the constants per call, a `PreparedParams` once
+// - Results are bit-identical either way (same expressions, evaluated earlier)
+
+use crate::Params;
+use std::ops::Deref;
+
+/// Constants derived from `Params` by the hot paths
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DerivedParams {
+    /// `cost.rate_limit_per_hour.max(1.0)`
+    pub rate_limit: f64,
+    /// `ln(1 + reward.size_norm_bytes.max(1.0))`, the `w_size` denominator
+    pub ln_size_norm: f64,
+    /// `reward.latency_knee_ms.max(1e-9)`
+    pub latency_knee: f64,
+}
+
+impl DerivedParams {
+    pub fn new(params: &Params) -> Self {
+        DerivedParams {
+            rate_limit: params.cost.rate_limit_per_hour.max(1.0),
+            ln_size_norm: (1.0 + params.reward.size_norm_bytes.max(1.0)).ln(),
+            latency_knee: params.reward.latency_knee_ms.max(1e-9),
+        }
+    }
+}
+
+/// Parameter source accepted by the core functions
+pub trait AsParams {
+    fn params(&self) -> &Params;
+    fn derived(&self) -> DerivedParams { DerivedParams::new(self.params()) }
+}
+
+impl AsParams for Params {
+    fn params(&self) -> &Params { self }
+}
+
+/// `Params` with its derived constants computed once (for services scoring many items under fixed params)
+#[derive(Debug, Clone)]
+pub struct PreparedParams {
+    params: Params,
+    derived: DerivedParams,
+}
+
+impl PreparedParams {
+    pub fn new(params: Params) -> Self {
+        let derived = DerivedParams::new(&params);
+        PreparedParams { params, derived }
+    }
+
+    pub fn into_inner(self) -> Params { self.params }
+}
+
+impl AsParams for PreparedParams {
+    fn params(&self) -> &Params { &self.params }
+    fn derived(&self) -> DerivedParams { self.derived }
+}
+
+impl Deref for PreparedParams {
+    type Target = Params;
+    fn deref(&self) -> &Params { &self.params }
+}
+
+impl From<Params> for PreparedParams {
+    fn from(params: Params) -> Self { PreparedParams::new(params) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculate_post_cost, calculate_serve_reward, Actor, Content, RewardInput};
+
+    #[test]
+    fn t_prepared_matches_plain() {
+        let mut p = Params::default();
+        p.cost.rate_limit_per_hour = 0.5;
+        p.reward.size_norm_bytes = 4096.0;
+        let prepared = PreparedParams::new(p.clone());
+        let actor = Actor { rl: 120.0, q: 0.8, ef: 28.3, posts_1h: Some(90.0), ..Default::default() };
+        let content = Content { is_claim: Some(true), ..Default::default() };
+        assert_eq!(calculate_post_cost(&actor, &content, &prepared, 1.0), calculate_post_cost(&actor, &content, &p, 1.0));
+        let r = RewardInput { ticket_budget: 5.0, client_q: 0.9, size_bytes: 250_000, ttfb_ms: 120, server_cluster_risk: 0.1, ..Default::default() };
+        assert_eq!(calculate_serve_reward(&r, &prepared), calculate_serve_reward(&r, &p));
+    }
+}
@@ -0,0 +1,50 @@
+// Named parameter regimes
+// - `default`: `Params::default()`
+// - `musk-mode`: `tiers::apply_musk_mode_params` on top of the defaults (requires the `tiers` feature)
+
+use crate::Params;
+
+/// Names of the built-in presets
+pub fn names() -> Vec<&'static str> {
+    let mut v = vec!["default"];
+    if cfg!(feature = "tiers") { v.push("musk-mode"); }
+    v
+}
+
+/// One-line description of a preset
+pub fn description(name: &str) -> Option<&'static str> {
+    match name {
+        "default" => Some("Reference tuning (Params::default())"),
+        "musk-mode" => Some("Wider base reach (ttl 5, fanout 6) with steeper risk attenuation and higher risk cost weights"),
+        _ => None,
+    }
+}
+
+/// Parameters for a named preset (`None` if unknown)
+pub fn preset(name: &str) -> Option<Params> {
+    match name {
+        "default" => Some(Params::default()),
+        #[cfg(feature = "tiers")]
+        "musk-mode" => {
+            let mut p = Params::default();
+            crate::tiers::apply_musk_mode_params(&mut p);
+            Some(p)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_presets_resolve_and_validate() {
+        for name in names() {
+            let p = preset(name).unwrap();
+            assert!(p.validate().is_ok(), "{}", name);
+            assert!(description(name).is_some());
+        }
+        assert!(preset("nope").is_none());
+    }
+}
@@ -0,0 +1,99 @@
+// Cold-start defaults and shrinkage toward priors
+// - `new_actor` builds the `Actor` for an account with no history from one `PriorParams`, so every caller prices a
+//   newcomer the same way instead of picking q = 1 (perfect) or q = 0 (maximally risky) ad hoc
+// - `blend` shrinks an observed mean toward the prior: (prior.mean * prior.weight + observed * n) / (prior.weight + n),
+//   i.e. the prior counts as `weight` pseudo-observations; history takes over as observations accumulate
+// - `observe` applies `blend` to an actor's quality and EF given how many observations each rests on
+// - Non-finite observations are ignored (the prior is returned)
+
+use crate::{clamp, Actor};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Prior mean and its weight in pseudo-observations
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Prior {
+    pub mean: f64,
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PriorParams {
+    /// Quality of a newcomer (0..1); matches `ReputationParams::initial_q` by default
+    pub q: Prior,
+    /// Effective followers of a newcomer
+    pub ef: Prior,
+    /// Request load assumed before any is measured
+    pub rl: f64,
+}
+
+impl PriorParams {
+    pub const DEFAULT: Self = Self {
+        q: Prior { mean: 0.5, weight: 20.0 },
+        ef: Prior { mean: 0.0, weight: 5.0 },
+        rl: 0.0,
+    };
+}
+
+impl Default for PriorParams {
+    fn default() -> Self { Self::DEFAULT }
+}
+
+/// Actor for an account with no history: prior quality and EF, no posts yet, age 0 (the new-account surcharge
+/// applies), unverified
+pub fn new_actor(params: &PriorParams) -> Actor {
+    Actor {
+        rl: params.rl.max(0.0),
+        q: clamp(params.q.mean, 0.0, 1.0),
+        ef: params.ef.mean.max(0.0),
+        posts_1h: Some(0.0),
+        account_age_days: Some(0.0),
+        verification_level: None,
+        kind_posts_1h: None,
+    }
+}
+
+/// `observed` (a mean over `n_observations`) shrunk toward `prior`
+pub fn blend(prior: Prior, observed: f64, n_observations: u64) -> f64 {
+    let w = if prior.weight.is_finite() { prior.weight.max(0.0) } else { 0.0 };
+    let n = n_observations as f64;
+    if !observed.is_finite() || n == 0.0 { return prior.mean; }
+    (prior.mean * w + observed * n) / (w + n)
+}
+
+/// `actor` with quality and EF shrunk toward the priors, given the observations behind each
+pub fn observe(actor: &Actor, q_observations: u64, ef_observations: u64, params: &PriorParams) -> Actor {
+    Actor {
+        q: clamp(blend(params.q, actor.q, q_observations), 0.0, 1.0),
+        ef: blend(params.ef, actor.ef, ef_observations).max(0.0),
+        ..actor.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_priors_shrink_new_accounts() {
+        let p = PriorParams::default();
+        let fresh = new_actor(&p);
+        assert_eq!((fresh.q, fresh.ef, fresh.account_age_days), (0.5, 0.0, Some(0.0)));
+
+        // one perfect post barely moves the prior; a long record dominates it
+        assert!((blend(p.q, 1.0, 1) - 11.0 / 21.0).abs() < 1e-12);
+        assert!(blend(p.q, 1.0, 10_000) > 0.99);
+        assert_eq!(blend(p.q, f64::NAN, 50), 0.5);
+        assert_eq!(blend(p.q, 0.9, 0), 0.5);
+
+        // two bad posts leave a newcomer near the prior, not at q = 0
+        let shrunk = observe(&Actor { q: 0.0, ef: 40.0, ..fresh.clone() }, 2, 0, &p);
+        assert!((shrunk.q - 10.0 / 22.0).abs() < 1e-12);
+        assert_eq!(shrunk.ef, 0.0);
+        assert_eq!(observe(&fresh, 0, 0, &p).q, fresh.q);
+    }
+}
@@ -0,0 +1,171 @@
+// Propagation extensions (hop-by-hop forwarding model, total-reach budget, fanout waves)
+// - Builds on `adjust_propagation` in the crate root
+
+pub mod simulate;
+
+use crate::{clamp, Ef, Params, PropagationResult, Quality, Risk};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Compute the forwarding decision for the next hop on the path
+/// - TTL drops by one per hop, plus a risk-driven cut of `k1 * hop_risk`
+/// - Fanout is attenuated by `k2 * hop_risk` and never grows along the path
+/// - `ttl == 0` means the relay must not forward any further
+/// - Quarantine is sticky: a quarantined decision (or hop risk above the threshold) stays quarantined
+pub fn next_hop(current: &PropagationResult, hop_risk: f64, params: &Params) -> PropagationResult {
+    let risk = clamp(hop_risk, 0.0, 1.0);
+    if current.quarantined || params.propagation.quarantine_threshold.is_some_and(|t| risk > t) {
+        return PropagationResult::quarantine();
+    }
+    if current.ttl == 0 || current.fanout == 0 {
+        return PropagationResult { ttl: 0, fanout: 0, quarantined: false };
+    }
+    let ttl = (current.ttl as f64 - 1.0 - params.propagation.k1 * risk).round().max(0.0);
+    if ttl == 0.0 {
+        return PropagationResult { ttl: 0, fanout: 0, quarantined: false };
+    }
+    let fanout = clamp(current.fanout as f64 - params.propagation.k2 * risk, 1.0, current.fanout as f64);
+    PropagationResult { ttl: ttl as u32, fanout: fanout.round() as u32, quarantined: false }
+}
+
+/// Outcome of consuming impressions from a reach budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ReachGrant {
+    /// Impressions that may be delivered now
+    pub granted: u64,
+    /// Budget left after this grant
+    pub remaining: u64,
+    /// True once the budget is fully spent
+    pub exhausted: bool,
+}
+
+/// Compute the capped total impression budget for a post
+/// raw = (reach_base + reach_per_ef * max(EF,0)) * clamp(q,0,1) * (1 - risk_k * clamp(risk,0,1))
+pub fn reach_budget(actor_ef: impl Into<Ef>, quality: impl Into<Quality>, risk: impl Into<Risk>, params: &Params) -> u64 {
+    let r = &params.propagation.reach;
+    let audience = r.reach_base.max(0.0) + r.reach_per_ef.max(0.0) * actor_ef.into().get();
+    let damping = 1.0 - clamp(r.risk_k, 0.0, 1.0) * risk.into().get();
+    let raw = audience * quality.into().get() * damping;
+    clamp(raw, 0.0, r.reach_cap.max(0.0)).round() as u64
+}
+
+/// Take up to `impressions` from the remaining budget
+pub fn consume(budget: u64, impressions: u64) -> ReachGrant {
+    let granted = impressions.min(budget);
+    let remaining = budget - granted;
+    ReachGrant { granted, remaining, exhausted: remaining == 0 }
+}
+
+/// One delayed fanout wave
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FanoutWave {
+    /// Delay after publication before this wave is sent
+    pub delay_secs: u64,
+    /// Peers to forward to in this wave
+    pub peers: u32,
+}
+
+/// Split a fanout decision into delayed waves so high-risk content gets a circuit-breaker window
+/// - Low risk: a single immediate wave
+/// - Higher risk: a smaller first wave, remaining peers spread evenly over later waves
+pub fn schedule(result: &PropagationResult, risk: f64, params: &Params) -> Vec<FanoutWave> {
+    let sp = &params.propagation.schedule;
+    let risk = clamp(risk, 0.0, 1.0);
+    if result.quarantined || result.fanout == 0 { return Vec::new(); }
+    if risk <= sp.immediate_below || sp.max_waves <= 1 || result.fanout == 1 {
+        return vec![FanoutWave { delay_secs: 0, peers: result.fanout }];
+    }
+    let first = ((result.fanout as f64 * (1.0 - risk)).floor() as u32).max(1);
+    let mut waves = vec![FanoutWave { delay_secs: 0, peers: first }];
+    let rest = result.fanout - first;
+    let later = (sp.max_waves - 1).min(rest);
+    for i in 0..later {
+        // distribute the remainder so earlier waves get the extra peer
+        let peers = rest / later + u32::from(i < rest % later);
+        waves.push(FanoutWave { delay_secs: sp.wave_delay_secs * (i as u64 + 1), peers });
+    }
+    waves
+}
+
+/// Re-evaluate pending waves against an updated decision (e.g., `adjust_propagation` with new risk signals)
+/// - `already_sent` is the number of peers forwarded so far
+/// - Pending waves are trimmed in order so total fanout never exceeds the updated decision
+pub fn reevaluate_waves(pending: &[FanoutWave], already_sent: u32, updated: &PropagationResult) -> Vec<FanoutWave> {
+    if updated.quarantined { return Vec::new(); }
+    let mut budget = updated.fanout.saturating_sub(already_sent);
+    let mut out = Vec::new();
+    for w in pending {
+        if budget == 0 { break; }
+        let peers = w.peers.min(budget);
+        budget -= peers;
+        out.push(FanoutWave { delay_secs: w.delay_secs, peers });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_hop_decrements() {
+        let params = Params::default();
+        let start = PropagationResult { ttl: 4, fanout: 5, quarantined: false };
+        let clean = next_hop(&start, 0.0, &params);
+        assert_eq!((clean.ttl, clean.fanout), (3, 5));
+
+        let risky = next_hop(&start, 0.5, &params);
+        assert!(risky.ttl < clean.ttl);
+        assert!(risky.fanout < clean.fanout);
+    }
+
+    #[test]
+    fn test_next_hop_terminates() {
+        let params = Params::default();
+        let mut pr = PropagationResult { ttl: 4, fanout: 5, quarantined: false };
+        let mut hops = 0;
+        while pr.ttl > 0 {
+            pr = next_hop(&pr, 0.1, &params);
+            hops += 1;
+        }
+        assert!(hops <= 4);
+        assert_eq!(pr.fanout, 0);
+    }
+
+    #[test]
+    fn test_reach_budget() {
+        let params = Params::default();
+        let clean = reach_budget(30.0, 0.8, 0.0, &params);
+        let risky = reach_budget(30.0, 0.8, 0.9, &params);
+        assert_eq!(clean, 560);
+        assert!(risky < clean);
+        assert!(reach_budget(1e12, 1.0, 0.0, &params) <= params.propagation.reach.reach_cap as u64);
+
+        let g = consume(clean, 500);
+        assert_eq!((g.granted, g.remaining, g.exhausted), (500, 60, false));
+        let g = consume(g.remaining, 500);
+        assert_eq!((g.granted, g.remaining, g.exhausted), (60, 0, true));
+    }
+
+    #[test]
+    fn test_schedule_waves() {
+        let params = Params::default();
+        let pr = PropagationResult { ttl: 4, fanout: 5, quarantined: false };
+        assert_eq!(schedule(&pr, 0.1, &params), vec![FanoutWave { delay_secs: 0, peers: 5 }]);
+
+        let waves = schedule(&pr, 0.6, &params);
+        assert_eq!(waves[0], FanoutWave { delay_secs: 0, peers: 2 });
+        assert_eq!(waves.iter().map(|w| w.peers).sum::<u32>(), 5);
+        assert!(waves.windows(2).all(|w| w[0].delay_secs < w[1].delay_secs));
+
+        // new risk arrives after the first wave: decision tightened to fanout 3
+        let tightened = PropagationResult { ttl: 3, fanout: 3, quarantined: false };
+        let left = reevaluate_waves(&waves[1..], waves[0].peers, &tightened);
+        assert_eq!(left.iter().map(|w| w.peers).sum::<u32>(), 1);
+        assert!(reevaluate_waves(&waves[1..], 2, &PropagationResult::quarantine()).is_empty());
+    }
+}
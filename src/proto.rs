@@ -0,0 +1,562 @@
+// Protobuf messages generated by prost from proto/slimechain/v1/slimechain.proto (feature `proto`)
+// - `From<native>` for every message; `TryFrom<message>` back, failing on missing required fields or unknown enum values
+// - Absent sub-messages that have a serde default in Rust take that default, mirroring the JSON behavior
+
+use crate::pipeline;
+use std::collections::BTreeMap;
+
+include!(concat!(env!("OUT_DIR"), "/slimechain.v1.rs"));
+
+/// Message that cannot be converted to the native type
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtoError { pub path: String, pub reason: String }
+
+impl std::fmt::Display for ProtoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "{}: {}", self.path, self.reason) }
+}
+
+impl std::error::Error for ProtoError {}
+
+fn required<T>(x: Option<T>, path: &str) -> Result<T, ProtoError> {
+    x.ok_or_else(|| ProtoError { path: path.into(), reason: "missing".into() })
+}
+
+// -------- Topic --------
+
+impl From<crate::ContentTopic> for Topic {
+    fn from(t: crate::ContentTopic) -> Self {
+        match t {
+            crate::ContentTopic::General => Topic::General,
+            crate::ContentTopic::News => Topic::News,
+            crate::ContentTopic::Finance => Topic::Finance,
+            crate::ContentTopic::Health => Topic::Health,
+            crate::ContentTopic::Elections => Topic::Elections,
+        }
+    }
+}
+
+/// `TOPIC_UNSPECIFIED` maps to None
+fn topic_from(raw: i32, path: &str) -> Result<Option<crate::ContentTopic>, ProtoError> {
+    let bad = || ProtoError { path: path.into(), reason: format!("unknown topic {}", raw) };
+    Ok(match Topic::try_from(raw).map_err(|_| bad())? {
+        Topic::Unspecified => None,
+        Topic::General => Some(crate::ContentTopic::General),
+        Topic::News => Some(crate::ContentTopic::News),
+        Topic::Finance => Some(crate::ContentTopic::Finance),
+        Topic::Health => Some(crate::ContentTopic::Health),
+        Topic::Elections => Some(crate::ContentTopic::Elections),
+    })
+}
+
+// -------- Content kind --------
+
+impl From<crate::ContentKind> for ContentKind {
+    fn from(k: crate::ContentKind) -> Self {
+        match k {
+            crate::ContentKind::Text => ContentKind::Text,
+            crate::ContentKind::Link => ContentKind::Link,
+            crate::ContentKind::Media => ContentKind::Media,
+        }
+    }
+}
+
+/// `CONTENT_KIND_UNSPECIFIED` maps to None
+fn kind_from(raw: i32, path: &str) -> Result<Option<crate::ContentKind>, ProtoError> {
+    let bad = || ProtoError { path: path.into(), reason: format!("unknown content kind {}", raw) };
+    Ok(match ContentKind::try_from(raw).map_err(|_| bad())? {
+        ContentKind::Unspecified => None,
+        ContentKind::Text => Some(crate::ContentKind::Text),
+        ContentKind::Link => Some(crate::ContentKind::Link),
+        ContentKind::Media => Some(crate::ContentKind::Media),
+    })
+}
+
+// -------- Fact-check state --------
+
+impl From<crate::FactCheckState> for FactCheckState {
+    fn from(s: crate::FactCheckState) -> Self {
+        match s {
+            crate::FactCheckState::Unreviewed => FactCheckState::Unreviewed,
+            crate::FactCheckState::EvidenceProvided => FactCheckState::EvidenceProvided,
+            crate::FactCheckState::VerifiedTrue => FactCheckState::VerifiedTrue,
+            crate::FactCheckState::VerifiedFalse => FactCheckState::VerifiedFalse,
+            crate::FactCheckState::Disputed => FactCheckState::Disputed,
+        }
+    }
+}
+
+/// `FACT_CHECK_STATE_UNSPECIFIED` maps to None
+fn fact_check_from(raw: i32, path: &str) -> Result<Option<crate::FactCheckState>, ProtoError> {
+    let bad = || ProtoError { path: path.into(), reason: format!("unknown fact-check state {}", raw) };
+    Ok(match FactCheckState::try_from(raw).map_err(|_| bad())? {
+        FactCheckState::Unspecified => None,
+        FactCheckState::Unreviewed => Some(crate::FactCheckState::Unreviewed),
+        FactCheckState::EvidenceProvided => Some(crate::FactCheckState::EvidenceProvided),
+        FactCheckState::VerifiedTrue => Some(crate::FactCheckState::VerifiedTrue),
+        FactCheckState::VerifiedFalse => Some(crate::FactCheckState::VerifiedFalse),
+        FactCheckState::Disputed => Some(crate::FactCheckState::Disputed),
+    })
+}
+
+// -------- Verification level --------
+
+impl From<crate::VerificationLevel> for VerificationLevel {
+    fn from(v: crate::VerificationLevel) -> Self {
+        match v {
+            crate::VerificationLevel::Unverified => VerificationLevel::Unverified,
+            crate::VerificationLevel::Email => VerificationLevel::Email,
+            crate::VerificationLevel::Phone => VerificationLevel::Phone,
+            crate::VerificationLevel::Identity => VerificationLevel::Identity,
+        }
+    }
+}
+
+/// `VERIFICATION_LEVEL_UNSPECIFIED` maps to None
+fn verification_from(raw: i32, path: &str) -> Result<Option<crate::VerificationLevel>, ProtoError> {
+    let bad = || ProtoError { path: path.into(), reason: format!("unknown verification level {}", raw) };
+    Ok(match VerificationLevel::try_from(raw).map_err(|_| bad())? {
+        VerificationLevel::Unspecified => None,
+        VerificationLevel::Unverified => Some(crate::VerificationLevel::Unverified),
+        VerificationLevel::Email => Some(crate::VerificationLevel::Email),
+        VerificationLevel::Phone => Some(crate::VerificationLevel::Phone),
+        VerificationLevel::Identity => Some(crate::VerificationLevel::Identity),
+    })
+}
+
+// -------- Parameters --------
+
+impl From<&crate::Params> for Params {
+    fn from(p: &crate::Params) -> Self {
+        let w = &p.q_weights;
+        let c = &p.cost;
+        let pr = &p.propagation;
+        let r = &p.reward;
+        let g = &p.congestion;
+        Params {
+            q_weights: Some(QWeights {
+                w_a: w.w_a, w_r: w.w_r, w_t: w.w_t, w_d: w.w_d, w_h: w.w_h, w_s: w.w_s,
+                unverified_caps: Some(UnverifiedCaps {
+                    unverified: w.unverified_caps.unverified, email: w.unverified_caps.email,
+                    phone: w.unverified_caps.phone, identity: w.unverified_caps.identity,
+                }),
+            }),
+            q_min: p.q_min,
+            ef: Some(EfParams { gamma: p.ef.gamma, cap: p.ef.cap }),
+            cost: Some(CostParams {
+                alpha: c.alpha, beta: c.beta, a: c.a, b: c.b, lambda_actor: c.lambda_actor, lambda_content: c.lambda_content,
+                rate_limit_per_hour: c.rate_limit_per_hour, cmin: c.cmin, cmax: c.cmax,
+                new_account_surcharge: Some(c.new_account_surcharge), new_account_days: Some(c.new_account_days),
+                kind_rate_limits: c.kind_rate_limits.iter()
+                    .map(|(&k, &x)| KindRateLimit { kind: ContentKind::from(k) as i32, rate_limit_per_hour: x })
+                    .collect(),
+                fact_check: Some(FactCheckMultipliers {
+                    unreviewed: c.fact_check.unreviewed, evidence_provided: c.fact_check.evidence_provided,
+                    verified_true: c.fact_check.verified_true, verified_false: c.fact_check.verified_false, disputed: c.fact_check.disputed,
+                }),
+            }),
+            propagation: Some(PropagationParams {
+                ttl_base: pr.ttl_base, fanout_base: pr.fanout_base, k1: pr.k1, k2: pr.k2,
+                topic_multipliers: Some(TopicMultipliers {
+                    entries: pr.topic_multipliers.iter()
+                        .map(|(&t, m)| TopicMultiplier { topic: Topic::from(t) as i32, ttl: m.ttl, fanout: m.fanout })
+                        .collect(),
+                }),
+                reach: Some(ReachParams { reach_base: pr.reach.reach_base, reach_per_ef: pr.reach.reach_per_ef, risk_k: pr.reach.risk_k, reach_cap: pr.reach.reach_cap }),
+                quarantine_threshold: pr.quarantine_threshold,
+                schedule: Some(ScheduleParams { immediate_below: pr.schedule.immediate_below, wave_delay_secs: pr.schedule.wave_delay_secs, max_waves: pr.schedule.max_waves }),
+            }),
+            reward: Some(RewardParams {
+                r0: r.r0, mu: r.mu, size_norm_bytes: r.size_norm_bytes, latency_knee_ms: r.latency_knee_ms,
+                epoch: Some(EpochParams { max_server_share: r.epoch.max_server_share, unit: r.epoch.unit }),
+                repetition_half: Some(r.repetition_half),
+                availability: Some(AvailabilityParams {
+                    a0: r.availability.a0, min_uptime: r.availability.min_uptime, probes_required: r.availability.probes_required,
+                    stake_norm: r.availability.stake_norm, stake_exp: r.availability.stake_exp,
+                }),
+                latency_curve: Some(LatencyCurve { points: r.latency_curve.iter().map(|p| CurvePoint { x: p.x, y: p.y }).collect() }),
+            }),
+            congestion: Some(CongestionParams {
+                eta: g.eta, target_load: g.target_load, base_min: g.base_min, base_max: g.base_max,
+                controller: Some(match g.controller {
+                    crate::CongestionController::Exponential => congestion_params::Controller::Exponential(ExponentialController {}),
+                    crate::CongestionController::Pid { kp, ki, kd, i_limit } => congestion_params::Controller::Pid(PidController { kp, ki, kd, i_limit }),
+                }),
+                load_ema_alpha: Some(g.load_ema_alpha),
+                surge: Some(SurgeParams {
+                    max_increase_pct: g.surge.max_increase_pct, max_increases_per_window: g.surge.max_increases_per_window,
+                    window_updates: g.surge.window_updates, cooldown_factor: g.surge.cooldown_factor,
+                }),
+            }),
+        }
+    }
+}
+
+impl TryFrom<Params> for crate::Params {
+    type Error = ProtoError;
+    fn try_from(p: Params) -> Result<Self, ProtoError> {
+        let w = required(p.q_weights, "q_weights")?;
+        let ef = required(p.ef, "ef")?;
+        let c = required(p.cost, "cost")?;
+        let pr = required(p.propagation, "propagation")?;
+        let r = required(p.reward, "reward")?;
+        let g = required(p.congestion, "congestion")?;
+        let d = crate::Params::default();
+
+        let topic_multipliers = match pr.topic_multipliers {
+            None => d.propagation.topic_multipliers,
+            Some(m) => m.entries.iter().enumerate().map(|(i, e)| {
+                let path = format!("propagation.topic_multipliers[{}].topic", i);
+                let topic = topic_from(e.topic, &path)?
+                    .ok_or_else(|| ProtoError { path, reason: "topic must be set".into() })?;
+                Ok((topic, crate::TopicMultiplier { ttl: e.ttl, fanout: e.fanout }))
+            }).collect::<Result<BTreeMap<_, _>, ProtoError>>()?,
+        };
+        let kind_rate_limits = c.kind_rate_limits.iter().enumerate().map(|(i, e)| {
+            let path = format!("cost.kind_rate_limits[{}].kind", i);
+            let kind = kind_from(e.kind, &path)?.ok_or_else(|| ProtoError { path, reason: "kind must be set".into() })?;
+            Ok((kind, e.rate_limit_per_hour))
+        }).collect::<Result<BTreeMap<_, _>, ProtoError>>()?;
+        let controller = match g.controller {
+            None | Some(congestion_params::Controller::Exponential(_)) => crate::CongestionController::Exponential,
+            Some(congestion_params::Controller::Pid(c)) => crate::CongestionController::Pid { kp: c.kp, ki: c.ki, kd: c.kd, i_limit: c.i_limit },
+        };
+        Ok(crate::Params {
+            q_weights: crate::QWeights {
+                w_a: w.w_a, w_r: w.w_r, w_t: w.w_t, w_d: w.w_d, w_h: w.w_h, w_s: w.w_s,
+                unverified_caps: w.unverified_caps.map_or(d.q_weights.unverified_caps, |x| crate::UnverifiedCaps {
+                    unverified: x.unverified, email: x.email, phone: x.phone, identity: x.identity,
+                }),
+            },
+            q_min: p.q_min,
+            ef: crate::EfParams { gamma: ef.gamma, cap: ef.cap },
+            cost: crate::CostParams {
+                alpha: c.alpha, beta: c.beta, a: c.a, b: c.b, lambda_actor: c.lambda_actor, lambda_content: c.lambda_content,
+                rate_limit_per_hour: c.rate_limit_per_hour, cmin: c.cmin, cmax: c.cmax,
+                new_account_surcharge: c.new_account_surcharge.unwrap_or(d.cost.new_account_surcharge),
+                new_account_days: c.new_account_days.unwrap_or(d.cost.new_account_days),
+                kind_rate_limits,
+                fact_check: c.fact_check.map_or(d.cost.fact_check, |x| crate::FactCheckMultipliers {
+                    unreviewed: x.unreviewed, evidence_provided: x.evidence_provided,
+                    verified_true: x.verified_true, verified_false: x.verified_false, disputed: x.disputed,
+                }),
+            },
+            propagation: crate::PropagationParams {
+                ttl_base: pr.ttl_base, fanout_base: pr.fanout_base, k1: pr.k1, k2: pr.k2,
+                topic_multipliers,
+                reach: pr.reach.map_or(d.propagation.reach, |x| crate::ReachParams { reach_base: x.reach_base, reach_per_ef: x.reach_per_ef, risk_k: x.risk_k, reach_cap: x.reach_cap }),
+                quarantine_threshold: pr.quarantine_threshold,
+                schedule: pr.schedule.map_or(d.propagation.schedule, |x| crate::ScheduleParams { immediate_below: x.immediate_below, wave_delay_secs: x.wave_delay_secs, max_waves: x.max_waves }),
+            },
+            reward: crate::RewardParams {
+                r0: r.r0, mu: r.mu, size_norm_bytes: r.size_norm_bytes, latency_knee_ms: r.latency_knee_ms,
+                epoch: r.epoch.map_or(d.reward.epoch, |x| crate::EpochParams { max_server_share: x.max_server_share, unit: x.unit }),
+                repetition_half: r.repetition_half.unwrap_or(d.reward.repetition_half),
+                availability: r.availability.map_or(d.reward.availability, |x| crate::AvailabilityParams {
+                    a0: x.a0, min_uptime: x.min_uptime, probes_required: x.probes_required, stake_norm: x.stake_norm, stake_exp: x.stake_exp,
+                }),
+                latency_curve: r.latency_curve.map_or(d.reward.latency_curve, |x| x.points.iter().map(|p| crate::CurvePoint { x: p.x, y: p.y }).collect()),
+            },
+            congestion: crate::CongestionParams {
+                eta: g.eta, target_load: g.target_load, base_min: g.base_min, base_max: g.base_max,
+                controller,
+                load_ema_alpha: g.load_ema_alpha.unwrap_or(d.congestion.load_ema_alpha),
+                surge: g.surge.map_or(d.congestion.surge, |x| crate::SurgeParams {
+                    max_increase_pct: x.max_increase_pct, max_increases_per_window: x.max_increases_per_window,
+                    window_updates: x.window_updates, cooldown_factor: x.cooldown_factor,
+                }),
+            },
+        })
+    }
+}
+
+impl From<&crate::RiskWeights> for RiskWeights {
+    fn from(w: &crate::RiskWeights) -> Self {
+        RiskWeights { w_coord: w.w_coord, w_clust: w.w_clust, w_burst: w.w_burst, w_mono: w.w_mono, w_hist: w.w_hist, w_dup: w.w_dup }
+    }
+}
+
+impl From<RiskWeights> for crate::RiskWeights {
+    fn from(w: RiskWeights) -> Self {
+        crate::RiskWeights { w_coord: w.w_coord, w_clust: w.w_clust, w_burst: w.w_burst, w_mono: w.w_mono, w_hist: w.w_hist, w_dup: w.w_dup }
+    }
+}
+
+// -------- Inputs --------
+
+impl From<&crate::QInputs> for QInputs {
+    fn from(q: &crate::QInputs) -> Self { QInputs { a: q.A, r: q.R, t: q.T, d: q.D, h: q.H, s: q.S } }
+}
+
+impl From<QInputs> for crate::QInputs {
+    fn from(q: QInputs) -> Self { crate::QInputs { A: q.a, R: q.r, T: q.t, D: q.d, H: q.h, S: q.s } }
+}
+
+impl From<&crate::Actor> for Actor {
+    fn from(a: &crate::Actor) -> Self {
+        Actor {
+            rl: a.rl, q: a.q, ef: a.ef, posts_1h: a.posts_1h, account_age_days: a.account_age_days,
+            verification_level: a.verification_level.map_or(VerificationLevel::Unspecified, VerificationLevel::from) as i32,
+            kind_posts_1h: a.kind_posts_1h,
+        }
+    }
+}
+
+impl TryFrom<Actor> for crate::Actor {
+    type Error = ProtoError;
+    fn try_from(a: Actor) -> Result<Self, ProtoError> {
+        Ok(crate::Actor {
+            rl: a.rl, q: a.q, ef: a.ef, posts_1h: a.posts_1h, account_age_days: a.account_age_days,
+            verification_level: verification_from(a.verification_level, "verification_level")?,
+            kind_posts_1h: a.kind_posts_1h,
+        })
+    }
+}
+
+impl From<&crate::RiskSignals> for RiskSignals {
+    fn from(s: &crate::RiskSignals) -> Self {
+        RiskSignals {
+            coordination: s.coordination, clustering: s.clustering, burst: s.burst, monotonicity: s.monotonicity, abuse_history: s.abuse_history,
+            duplication: s.duplication,
+        }
+    }
+}
+
+impl From<RiskSignals> for crate::RiskSignals {
+    fn from(s: RiskSignals) -> Self {
+        crate::RiskSignals {
+            coordination: s.coordination, clustering: s.clustering, burst: s.burst, monotonicity: s.monotonicity, abuse_history: s.abuse_history,
+            duplication: s.duplication,
+        }
+    }
+}
+
+impl From<&crate::Content> for Content {
+    fn from(c: &crate::Content) -> Self {
+        Content {
+            is_claim: c.is_claim, has_evidence: c.has_evidence,
+            risk_signals: c.risk_signals.as_ref().map(RiskSignals::from),
+            topic: c.topic.map_or(Topic::Unspecified, Topic::from) as i32,
+            kind: c.kind.map_or(ContentKind::Unspecified, ContentKind::from) as i32,
+            fact_check: c.fact_check.map_or(FactCheckState::Unspecified, FactCheckState::from) as i32,
+        }
+    }
+}
+
+impl TryFrom<Content> for crate::Content {
+    type Error = ProtoError;
+    fn try_from(c: Content) -> Result<Self, ProtoError> {
+        Ok(crate::Content {
+            is_claim: c.is_claim, has_evidence: c.has_evidence,
+            risk_signals: c.risk_signals.map(Into::into),
+            topic: topic_from(c.topic, "topic")?,
+            kind: kind_from(c.kind, "kind")?,
+            fact_check: fact_check_from(c.fact_check, "fact_check")?,
+        })
+    }
+}
+
+impl From<&crate::RewardInput> for RewardInput {
+    fn from(r: &crate::RewardInput) -> Self {
+        RewardInput {
+            ticket_budget: r.ticket_budget, client_q: r.client_q, size_bytes: r.size_bytes, ttfb_ms: r.ttfb_ms,
+            server_cluster_risk: r.server_cluster_risk, prev_serves: r.prev_serves, ttfb_percentile: r.ttfb_percentile,
+            region: r.region.clone(),
+        }
+    }
+}
+
+impl From<RewardInput> for crate::RewardInput {
+    fn from(r: RewardInput) -> Self {
+        crate::RewardInput {
+            ticket_budget: r.ticket_budget, client_q: r.client_q, size_bytes: r.size_bytes, ttfb_ms: r.ttfb_ms,
+            server_cluster_risk: r.server_cluster_risk, prev_serves: r.prev_serves, ttfb_percentile: r.ttfb_percentile,
+            region: r.region,
+        }
+    }
+}
+
+impl From<&pipeline::PipelineInput> for PipelineInput {
+    fn from(i: &pipeline::PipelineInput) -> Self {
+        PipelineInput {
+            quality: Some((&i.quality).into()),
+            followers_q: i.followers_q.clone(),
+            rl: i.rl,
+            posts_1h: i.posts_1h,
+            content: Some((&i.content).into()),
+            base_fare: i.base_fare,
+            load: i.load.as_ref().map(|l| LoadSnapshot { current_base: l.current_base, current_load: l.current_load }),
+            serve: i.serve.as_ref().map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<PipelineInput> for pipeline::PipelineInput {
+    type Error = ProtoError;
+    fn try_from(i: PipelineInput) -> Result<Self, ProtoError> {
+        Ok(pipeline::PipelineInput {
+            quality: required(i.quality, "quality")?.into(),
+            followers_q: i.followers_q,
+            rl: i.rl,
+            posts_1h: i.posts_1h,
+            content: i.content.map(crate::Content::try_from).transpose()
+                .map_err(|e| ProtoError { path: format!("content.{}", e.path), reason: e.reason })?
+                .unwrap_or_default(),
+            base_fare: i.base_fare,
+            load: i.load.map(|l| pipeline::LoadSnapshot { current_base: l.current_base, current_load: l.current_load }),
+            serve: i.serve.map(Into::into),
+        })
+    }
+}
+
+// -------- Outputs --------
+
+impl From<crate::PropagationResult> for PropagationResult {
+    fn from(r: crate::PropagationResult) -> Self { PropagationResult { ttl: r.ttl, fanout: r.fanout, quarantined: r.quarantined } }
+}
+
+impl From<PropagationResult> for crate::PropagationResult {
+    fn from(r: PropagationResult) -> Self { crate::PropagationResult { ttl: r.ttl, fanout: r.fanout, quarantined: r.quarantined } }
+}
+
+impl From<&pipeline::PipelineOutput> for PipelineOutput {
+    fn from(o: &pipeline::PipelineOutput) -> Self {
+        PipelineOutput {
+            q: o.q, ef: o.ef, risk: o.risk, base_fare: o.base_fare, cost: o.cost,
+            propagation: Some(o.propagation.clone().into()), reward: o.reward,
+        }
+    }
+}
+
+impl TryFrom<PipelineOutput> for pipeline::PipelineOutput {
+    type Error = ProtoError;
+    fn try_from(o: PipelineOutput) -> Result<Self, ProtoError> {
+        Ok(pipeline::PipelineOutput {
+            q: o.q, ef: o.ef, risk: o.risk, base_fare: o.base_fare, cost: o.cost,
+            propagation: required(o.propagation, "propagation")?.into(), reward: o.reward,
+        })
+    }
+}
+
+impl From<&crate::QualityBreakdown> for QualityBreakdown {
+    fn from(b: &crate::QualityBreakdown) -> Self {
+        QualityBreakdown { a: b.a, r: b.r, t: b.t, d: b.d, h: b.h, s: b.s, raw: b.raw, unverified_cap_applied: b.unverified_cap_applied, q: b.q }
+    }
+}
+
+impl From<QualityBreakdown> for crate::QualityBreakdown {
+    fn from(b: QualityBreakdown) -> Self {
+        crate::QualityBreakdown { a: b.a, r: b.r, t: b.t, d: b.d, h: b.h, s: b.s, raw: b.raw, unverified_cap_applied: b.unverified_cap_applied, q: b.q }
+    }
+}
+
+impl From<&crate::RiskBreakdown> for RiskBreakdown {
+    fn from(b: &crate::RiskBreakdown) -> Self {
+        RiskBreakdown {
+            coordination: b.coordination, clustering: b.clustering, burst: b.burst, monotonicity: b.monotonicity, abuse_history: b.abuse_history,
+            duplication: b.duplication, raw: b.raw, risk: b.risk,
+        }
+    }
+}
+
+impl From<RiskBreakdown> for crate::RiskBreakdown {
+    fn from(b: RiskBreakdown) -> Self {
+        crate::RiskBreakdown {
+            coordination: b.coordination, clustering: b.clustering, burst: b.burst, monotonicity: b.monotonicity, abuse_history: b.abuse_history,
+            duplication: b.duplication, raw: b.raw, risk: b.risk,
+        }
+    }
+}
+
+impl From<&crate::CostBreakdown> for CostBreakdown {
+    fn from(b: &crate::CostBreakdown) -> Self {
+        CostBreakdown {
+            base_fare: b.base_fare, rl_cost: b.rl_cost, ef_cost: b.ef_cost, risk_actor: b.risk_actor, risk_content: b.risk_content,
+            risk_multiplier: b.risk_multiplier, claim_multiplier: b.claim_multiplier, rate_multiplier: b.rate_multiplier,
+            age_multiplier: b.age_multiplier, discount: b.discount, pre_floor: b.pre_floor, cmin_applied: b.cmin_applied,
+            cmax_applied: b.cmax_applied, cost: b.cost,
+        }
+    }
+}
+
+impl From<CostBreakdown> for crate::CostBreakdown {
+    fn from(b: CostBreakdown) -> Self {
+        crate::CostBreakdown {
+            base_fare: b.base_fare, rl_cost: b.rl_cost, ef_cost: b.ef_cost, risk_actor: b.risk_actor, risk_content: b.risk_content,
+            risk_multiplier: b.risk_multiplier, claim_multiplier: b.claim_multiplier, rate_multiplier: b.rate_multiplier,
+            age_multiplier: b.age_multiplier, discount: b.discount, pre_floor: b.pre_floor, cmin_applied: b.cmin_applied,
+            cmax_applied: b.cmax_applied, cost: b.cost,
+        }
+    }
+}
+
+impl From<&crate::RewardBreakdown> for RewardBreakdown {
+    fn from(b: &crate::RewardBreakdown) -> Self {
+        RewardBreakdown {
+            w_size: b.w_size, w_latency: b.w_latency, diversity: b.diversity, client_q: b.client_q,
+            repetition: b.repetition, uncapped: b.uncapped, reward: b.reward, budget_capped: b.budget_capped,
+        }
+    }
+}
+
+impl From<RewardBreakdown> for crate::RewardBreakdown {
+    fn from(b: RewardBreakdown) -> Self {
+        crate::RewardBreakdown {
+            w_size: b.w_size, w_latency: b.w_latency, diversity: b.diversity, client_q: b.client_q,
+            repetition: b.repetition, uncapped: b.uncapped, reward: b.reward, budget_capped: b.budget_capped,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+
+    #[test]
+    fn t_params_roundtrip_through_wire() {
+        let mut native = crate::Params::default();
+        native.congestion.controller = crate::CongestionController::Pid { kp: 0.5, ki: 0.1, kd: 0.0, i_limit: 2.0 };
+        native.propagation.topic_multipliers.clear();
+        let bytes = Params::from(&native).encode_to_vec();
+        let back = crate::Params::try_from(Params::decode(bytes.as_slice()).unwrap()).unwrap();
+        assert_eq!(Params::from(&back).encode_to_vec(), bytes);
+        #[cfg(feature = "serde")]
+        assert_eq!(serde_json::to_value(&back).unwrap(), serde_json::to_value(&native).unwrap());
+
+        // absent defaulted sub-messages fall back to the Rust defaults; required ones fail
+        let mut msg = Params::from(&crate::Params::default());
+        msg.propagation.as_mut().unwrap().topic_multipliers = None;
+        msg.reward.as_mut().unwrap().latency_curve = None;
+        let cost = msg.cost.as_mut().unwrap();
+        (cost.new_account_surcharge, cost.new_account_days) = (None, None);
+        msg.reward.as_mut().unwrap().repetition_half = None;
+        msg.congestion.as_mut().unwrap().load_ema_alpha = None;
+        let back = crate::Params::try_from(msg.clone()).unwrap();
+        assert_eq!(back.propagation.topic_multipliers.len(), 2);
+        assert_eq!(back.reward.latency_curve.len(), 4);
+        assert_eq!((back.cost.new_account_surcharge, back.cost.new_account_days), (0.5, 30.0));
+        assert_eq!((back.reward.repetition_half, back.congestion.load_ema_alpha), (10, 0.3));
+        msg.cost = None;
+        assert_eq!(crate::Params::try_from(msg).unwrap_err().path, "cost");
+    }
+
+    #[test]
+    fn t_content_topic_mapping() {
+        let c = crate::Content { topic: Some(crate::ContentTopic::Health), ..Default::default() };
+        let m = Content::from(&c);
+        assert_eq!(m.topic(), Topic::Health);
+        assert_eq!(crate::Content::try_from(Content::default()).unwrap().topic, None);
+        assert!(crate::Content::try_from(Content { topic: 42, ..Default::default() }).is_err());
+    }
+
+    #[test]
+    fn t_actor_verification_mapping() {
+        let a = crate::Actor { account_age_days: Some(3.0), verification_level: Some(crate::VerificationLevel::Phone), ..Default::default() };
+        let m = Actor::from(&a);
+        assert_eq!(m.verification_level(), VerificationLevel::Phone);
+        let back = crate::Actor::try_from(m).unwrap();
+        assert_eq!((back.account_age_days, back.verification_level), (Some(3.0), Some(crate::VerificationLevel::Phone)));
+        assert_eq!(crate::Actor::try_from(Actor::default()).unwrap().verification_level, None);
+        assert_eq!(crate::Actor::try_from(Actor { verification_level: 9, ..Default::default() }).unwrap_err().path, "verification_level");
+    }
+}
@@ -0,0 +1,258 @@
+// PyO3 bindings: the `slimechain_algo` Python module (feature `python`)
+// - Typed classes for Params, Actor, Content and RiskSignals; functions mirror the crate's core API
+// - `params=None` means `Params::default()`; params built from JSON or presets are validated
+// - Topics are the snake_case names used in JSON ("health", "elections", ...)
+
+use crate::{Actor, Content, ContentTopic, Params, QInputs, RewardInput, RiskSignals, RiskWeights};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+fn invalid(errs: Vec<crate::ParamError>) -> PyErr {
+    let msgs: Vec<String> = errs.iter().map(|e| e.to_string()).collect();
+    PyValueError::new_err(format!("invalid params: {}", msgs.join("; ")))
+}
+
+fn checked(p: Params) -> PyResult<PyParams> {
+    p.validate().map_err(invalid)?;
+    Ok(PyParams { inner: p })
+}
+
+/// Parameter bundle; `Params()` gives the defaults
+#[pyclass(name = "Params", module = "slimechain_algo")]
+#[derive(Clone)]
+pub struct PyParams {
+    pub inner: Params,
+}
+
+#[pymethods]
+impl PyParams {
+    #[new]
+    fn new() -> Self { PyParams { inner: Params::default() } }
+
+    /// Parse and validate params from a JSON document (same shape as `to_json`)
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        checked(serde_json::from_str(json).map_err(|e| PyValueError::new_err(e.to_string()))?)
+    }
+
+    /// Built-in preset by name (see `preset_names()`)
+    #[staticmethod]
+    fn preset(name: &str) -> PyResult<Self> {
+        let p = crate::presets::preset(name).ok_or_else(|| PyValueError::new_err(format!("unknown preset {:?}", name)))?;
+        checked(p)
+    }
+
+    #[staticmethod]
+    fn preset_names() -> Vec<&'static str> { crate::presets::names() }
+
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.inner).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Raise ValueError listing every out-of-range field
+    fn validate(&self) -> PyResult<()> { self.inner.validate().map_err(invalid) }
+
+    fn __repr__(&self) -> String { "Params(...)".into() }
+}
+
+#[pyclass(name = "Actor", module = "slimechain_algo", get_all, set_all)]
+#[derive(Clone)]
+pub struct PyActor {
+    pub rl: f64,
+    pub q: f64,
+    pub ef: f64,
+    pub posts_1h: Option<f64>,
+    pub account_age_days: Option<f64>,
+}
+
+#[pymethods]
+impl PyActor {
+    #[new]
+    #[pyo3(signature = (rl, q, ef, posts_1h=None, account_age_days=None))]
+    fn new(rl: f64, q: f64, ef: f64, posts_1h: Option<f64>, account_age_days: Option<f64>) -> Self { PyActor { rl, q, ef, posts_1h, account_age_days } }
+
+    fn __repr__(&self) -> String {
+        format!("Actor(rl={}, q={}, ef={}, posts_1h={:?}, account_age_days={:?})", self.rl, self.q, self.ef, self.posts_1h, self.account_age_days)
+    }
+}
+
+impl From<&PyActor> for Actor {
+    fn from(a: &PyActor) -> Self { Actor { rl: a.rl, q: a.q, ef: a.ef, posts_1h: a.posts_1h, account_age_days: a.account_age_days, ..Default::default() } }
+}
+
+/// Risk signals in [0, 1]; None = missing
+#[pyclass(name = "RiskSignals", module = "slimechain_algo", get_all, set_all)]
+#[derive(Clone, Default)]
+pub struct PyRiskSignals {
+    pub coordination: Option<f64>,
+    pub clustering: Option<f64>,
+    pub burst: Option<f64>,
+    pub monotonicity: Option<f64>,
+    pub abuse_history: Option<f64>,
+    pub duplication: Option<f64>,
+}
+
+#[pymethods]
+impl PyRiskSignals {
+    #[new]
+    #[pyo3(signature = (coordination=None, clustering=None, burst=None, monotonicity=None, abuse_history=None, duplication=None))]
+    fn new(coordination: Option<f64>, clustering: Option<f64>, burst: Option<f64>, monotonicity: Option<f64>, abuse_history: Option<f64>, duplication: Option<f64>) -> Self {
+        PyRiskSignals { coordination, clustering, burst, monotonicity, abuse_history, duplication }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("RiskSignals(coordination={:?}, clustering={:?}, burst={:?}, monotonicity={:?}, abuse_history={:?}, duplication={:?})",
+            self.coordination, self.clustering, self.burst, self.monotonicity, self.abuse_history, self.duplication)
+    }
+}
+
+impl From<&PyRiskSignals> for RiskSignals {
+    fn from(s: &PyRiskSignals) -> Self {
+        RiskSignals {
+            coordination: s.coordination, clustering: s.clustering, burst: s.burst, monotonicity: s.monotonicity, abuse_history: s.abuse_history,
+            duplication: s.duplication,
+        }
+    }
+}
+
+#[pyclass(name = "Content", module = "slimechain_algo", get_all, set_all)]
+#[derive(Clone, Default)]
+pub struct PyContent {
+    pub is_claim: Option<bool>,
+    pub has_evidence: Option<bool>,
+    pub risk_signals: Option<PyRiskSignals>,
+    /// "general", "news", "finance", "health" or "elections"
+    pub topic: Option<String>,
+}
+
+#[pymethods]
+impl PyContent {
+    #[new]
+    #[pyo3(signature = (is_claim=None, has_evidence=None, risk_signals=None, topic=None))]
+    fn new(is_claim: Option<bool>, has_evidence: Option<bool>, risk_signals: Option<PyRiskSignals>, topic: Option<String>) -> PyResult<Self> {
+        let c = PyContent { is_claim, has_evidence, risk_signals, topic };
+        c.to_content()?;
+        Ok(c)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Content(is_claim={:?}, has_evidence={:?}, topic={:?})", self.is_claim, self.has_evidence, self.topic)
+    }
+}
+
+impl PyContent {
+    fn to_content(&self) -> PyResult<Content> {
+        let topic = self.topic.as_ref()
+            .map(|t| serde_json::from_value::<ContentTopic>(serde_json::Value::String(t.clone()))
+                .map_err(|_| PyValueError::new_err(format!("unknown topic {:?}", t))))
+            .transpose()?;
+        Ok(Content {
+            is_claim: self.is_claim, has_evidence: self.has_evidence,
+            risk_signals: self.risk_signals.as_ref().map(RiskSignals::from), topic, kind: None, fact_check: None,
+        })
+    }
+}
+
+/// Run `f` with the given params or the defaults
+fn with_params<T>(params: Option<PyRef<'_, PyParams>>, f: impl FnOnce(&Params) -> T) -> T {
+    match params {
+        Some(p) => f(&p.inner),
+        None => f(&Params::default()),
+    }
+}
+
+// -------- Functions --------
+
+#[pyfunction]
+#[pyo3(signature = (a, r, t, d, h, s, params=None))]
+#[allow(clippy::too_many_arguments)]
+fn calculate_quality(a: f64, r: f64, t: f64, d: f64, h: f64, s: f64, params: Option<PyRef<'_, PyParams>>) -> f64 {
+    with_params(params, |p| crate::calculate_quality(QInputs { A: a, R: r, T: t, D: d, H: h, S: s }, p))
+}
+
+#[pyfunction]
+#[pyo3(signature = (followers_q, params=None))]
+fn calculate_ef(followers_q: Vec<f64>, params: Option<PyRef<'_, PyParams>>) -> f64 {
+    with_params(params, |p| crate::calculate_ef(&followers_q, p))
+}
+
+/// Risk with the default weights
+#[pyfunction]
+#[pyo3(signature = (signals=None))]
+fn calculate_risk(signals: Option<PyRiskSignals>) -> f64 {
+    crate::calculate_risk(&signals.as_ref().map(RiskSignals::from), &RiskWeights::default())
+}
+
+#[pyfunction]
+#[pyo3(signature = (actor, content, base_fare=1.0, params=None))]
+fn calculate_post_cost(actor: PyActor, content: PyContent, base_fare: f64, params: Option<PyRef<'_, PyParams>>) -> PyResult<f64> {
+    let content = content.to_content()?;
+    Ok(with_params(params, |p| crate::calculate_post_cost(&(&actor).into(), &content, p, base_fare)))
+}
+
+/// `{"ttl", "fanout", "quarantined"}` for content (None = no signals, no topic)
+#[pyfunction]
+#[pyo3(signature = (content=None, params=None))]
+fn adjust_propagation<'py>(py: Python<'py>, content: Option<PyContent>, params: Option<PyRef<'py, PyParams>>) -> PyResult<Bound<'py, PyDict>> {
+    let content = content.map(|c| c.to_content()).transpose()?.unwrap_or_default();
+    let r = with_params(params, |p| crate::adjust_propagation_for_content(&content, p));
+    let d = PyDict::new(py);
+    d.set_item("ttl", r.ttl)?;
+    d.set_item("fanout", r.fanout)?;
+    d.set_item("quarantined", r.quarantined)?;
+    Ok(d)
+}
+
+#[pyfunction]
+#[pyo3(signature = (ticket_budget, client_q, size_bytes, ttfb_ms, server_cluster_risk, prev_serves=None, ttfb_percentile=None, params=None))]
+#[allow(clippy::too_many_arguments)]
+fn calculate_serve_reward(
+    ticket_budget: f64, client_q: f64, size_bytes: u64, ttfb_ms: u32, server_cluster_risk: f64,
+    prev_serves: Option<u32>, ttfb_percentile: Option<f64>, params: Option<PyRef<'_, PyParams>>,
+) -> f64 {
+    let input = RewardInput { ticket_budget, client_q, size_bytes, ttfb_ms, server_cluster_risk, prev_serves, ttfb_percentile, region: None };
+    with_params(params, |p| crate::calculate_serve_reward(&input, p))
+}
+
+#[pyfunction]
+#[pyo3(signature = (current_base, current_load, params=None))]
+fn update_base_cost(current_base: f64, current_load: f64, params: Option<PyRef<'_, PyParams>>) -> f64 {
+    with_params(params, |p| crate::update_base_cost(current_base, current_load, p))
+}
+
+#[pymodule]
+fn slimechain_algo(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    m.add_class::<PyParams>()?;
+    m.add_class::<PyActor>()?;
+    m.add_class::<PyRiskSignals>()?;
+    m.add_class::<PyContent>()?;
+    m.add_function(wrap_pyfunction!(calculate_quality, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_ef, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_risk, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_post_cost, m)?)?;
+    m.add_function(wrap_pyfunction!(adjust_propagation, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_serve_reward, m)?)?;
+    m.add_function(wrap_pyfunction!(update_base_cost, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_classes_convert_like_serde() {
+        let content = PyContent {
+            is_claim: Some(true), has_evidence: Some(false), topic: Some("health".into()),
+            risk_signals: Some(PyRiskSignals { coordination: Some(0.5), ..Default::default() }),
+        };
+        let actor = PyActor { rl: 120.0, q: 0.8, ef: 28.3, posts_1h: Some(12.0), account_age_days: None };
+        let native: Content = serde_json::from_str(r#"{"is_claim":true,"has_evidence":false,"topic":"health","risk_signals":{"coordination":0.5}}"#).unwrap();
+        let p = Params::default();
+        let want = crate::calculate_post_cost(&Actor::from(&actor), &native, &p, 1.0);
+        assert_eq!(calculate_post_cost(actor, content, 1.0, None).unwrap(), want);
+        assert!(PyContent { topic: Some("sports".into()), ..Default::default() }.to_content().is_err());
+    }
+}
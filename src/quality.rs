@@ -0,0 +1,133 @@
+// Standard derivation of `QInputs` from raw content and author signals
+// - A: signed/provenance-verified content scores `a_signed`, other content `a_unsigned`
+// - R: author's share of posts without an upheld report, shrunk toward `r_prior` (weight `r_prior_posts`)
+// - T: linear decay from 1 at publication to 0 at the end of the freshness window (per-topic override)
+// - D: log-scaled text length blended with media richness
+// - S: spam classifier score above its noise floor, rescaled to 0..1
+// - H: 1 for human-verified authors, 0 otherwise
+// - Every component is clamped to 0..1; non-finite raw values count as 0
+
+use crate::{clamp, ContentTopic, QInputs};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Raw signals a client has about one post and its author
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RawContentSignals {
+    /// Content carries a valid author signature / provenance proof
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub signed: bool,
+    /// Author's prior posts and how many of them had a report upheld
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub author_posts: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub author_upheld_reports: u64,
+    /// Seconds since publication
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub age_secs: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub topic: Option<ContentTopic>,
+    /// Text length in characters
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub text_len: u32,
+    /// Images, videos and other attachments
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub media_items: u32,
+    /// Spam classifier probability (0..1)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub spam_score: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub human_verified: bool,
+}
+
+/// Mapping constants from raw signals to `QInputs`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DerivationParams {
+    pub a_signed: f64,
+    pub a_unsigned: f64,
+    pub r_prior: f64,
+    /// Pseudo-posts backing `r_prior` (new authors start at the prior)
+    pub r_prior_posts: f64,
+    /// Seconds until T reaches 0
+    pub freshness_window_secs: f64,
+    /// Per-topic windows replacing `freshness_window_secs` (e.g. shorter for news)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub topic_windows_secs: BTreeMap<ContentTopic, f64>,
+    /// Characters at which the length term saturates
+    pub length_norm: f64,
+    /// Attachments at which the media term saturates
+    pub media_norm: f64,
+    /// Weight of the length term in D (the media term gets the rest)
+    pub length_share: f64,
+    /// Classifier scores at or below this count as no spam signal
+    pub spam_floor: f64,
+}
+
+impl Default for DerivationParams {
+    fn default() -> Self {
+        DerivationParams {
+            a_signed: 1.0, a_unsigned: 0.5,
+            r_prior: 0.5, r_prior_posts: 10.0,
+            freshness_window_secs: 7.0 * 86_400.0,
+            topic_windows_secs: BTreeMap::from([(ContentTopic::News, 86_400.0), (ContentTopic::Elections, 2.0 * 86_400.0)]),
+            length_norm: 2_000.0, media_norm: 3.0, length_share: 0.7,
+            spam_floor: 0.1,
+        }
+    }
+}
+
+fn unit(x: f64) -> f64 { if x.is_finite() { clamp(x, 0.0, 1.0) } else { 0.0 } }
+
+impl QInputs {
+    /// Compute the quality inputs from raw signals
+    pub fn from_raw(raw: RawContentSignals, p: &DerivationParams) -> QInputs {
+        let a = if raw.signed { p.a_signed } else { p.a_unsigned };
+
+        let posts = raw.author_posts as f64;
+        let clean = posts - (raw.author_upheld_reports as f64).min(posts);
+        let prior_posts = p.r_prior_posts.max(0.0);
+        let r = if posts + prior_posts > 0.0 { (clean + unit(p.r_prior) * prior_posts) / (posts + prior_posts) } else { unit(p.r_prior) };
+
+        let window = raw.topic.and_then(|t| p.topic_windows_secs.get(&t).copied()).unwrap_or(p.freshness_window_secs);
+        let t = if window > 0.0 { 1.0 - raw.age_secs as f64 / window } else { 0.0 };
+
+        let length = (raw.text_len as f64).ln_1p() / p.length_norm.max(1.0).ln_1p();
+        let media = if p.media_norm > 0.0 { raw.media_items as f64 / p.media_norm } else { 0.0 };
+        let share = unit(p.length_share);
+        let d = share * unit(length) + (1.0 - share) * unit(media);
+
+        let floor = clamp(p.spam_floor, 0.0, 1.0);
+        let s = if floor < 1.0 { (unit(raw.spam_score) - floor) / (1.0 - floor) } else { 0.0 };
+
+        QInputs { A: unit(a), R: unit(r), T: unit(t), D: unit(d), H: if raw.human_verified { 1.0 } else { 0.0 }, S: unit(s) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_from_raw_components() {
+        let p = DerivationParams::default();
+        let raw = RawContentSignals {
+            signed: true, author_posts: 90, author_upheld_reports: 10, age_secs: 43_200, topic: Some(ContentTopic::News),
+            text_len: 2_000, media_items: 3, spam_score: 0.55, human_verified: true,
+        };
+        let q = QInputs::from_raw(raw.clone(), &p);
+        assert_eq!((q.A, q.H, q.D), (1.0, 1.0, 1.0));
+        assert_eq!(q.R, (80.0 + 5.0) / 100.0);
+        assert_eq!(q.T, 0.5);
+        assert!((q.S - 0.5).abs() < 1e-12);
+
+        // general content uses the default window; a new unsigned author sits at the prior
+        let fresh = QInputs::from_raw(RawContentSignals { topic: None, author_posts: 0, author_upheld_reports: 0, signed: false, spam_score: f64::NAN, ..raw }, &p);
+        assert_eq!((fresh.A, fresh.R, fresh.S), (0.5, 0.5, 0.0));
+        assert!(fresh.T > 0.9);
+    }
+}
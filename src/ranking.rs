@@ -0,0 +1,74 @@
+// Feed ranking score
+// - score = q^q_exp * (1 + w_ef * ln(1 + EF) / ln(1 + ef_norm)) * 0.5^(recency_secs / half_life_secs)
+//   * (1 - risk_k * risk) * (1 + w_affinity * affinity)
+// - Monotonicity (for non-negative params with risk_k <= 1): non-decreasing in q, EF and affinity, non-increasing in
+//   recency_secs and risk; strictly so in each wherever the other factors are positive. Quality and risk enter as in
+//   pricing and propagation, so content that is cheap to post and travels far also ranks high
+// - EF enters logarithmically: follower count breaks ties between similar posts but cannot carry low-quality content
+// - Always >= 0; q = 0 or risk_k * risk = 1 ranks last
+
+use crate::{clamp, Ef, Quality, Risk};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RankParams {
+    pub q_exp: f64,
+    pub w_ef: f64,
+    /// EF at which the EF term adds `w_ef`
+    pub ef_norm: f64,
+    /// Age at which the recency factor halves
+    pub half_life_secs: f64,
+    pub risk_k: f64,
+    pub w_affinity: f64,
+}
+
+impl Default for RankParams {
+    fn default() -> Self {
+        RankParams { q_exp: 1.0, w_ef: 0.5, ef_norm: 100.0, half_life_secs: 6.0 * 3_600.0, risk_k: 0.8, w_affinity: 1.0 }
+    }
+}
+
+/// Ranking score of one post for one viewer (`affinity` in 0..1: viewer's interaction strength with the author)
+pub fn feed_score(
+    q: impl Into<Quality>,
+    ef: impl Into<Ef>,
+    recency_secs: f64,
+    risk: impl Into<Risk>,
+    affinity: f64,
+    params: &RankParams,
+) -> f64 {
+    let q = q.into().get().powf(params.q_exp.max(0.0));
+    let ef = if params.ef_norm > 0.0 { 1.0 + params.w_ef.max(0.0) * ef.into().get().ln_1p() / params.ef_norm.ln_1p() } else { 1.0 };
+    let age = if recency_secs.is_finite() { recency_secs.max(0.0) } else { f64::INFINITY };
+    let recency = if params.half_life_secs > 0.0 { crate::fastmath::powf_sel(0.5, age / params.half_life_secs) } else { 1.0 };
+    let risk = 1.0 - clamp(params.risk_k, 0.0, 1.0) * risk.into().get();
+    let affinity = 1.0 + params.w_affinity.max(0.0) * if affinity.is_finite() { clamp(affinity, 0.0, 1.0) } else { 0.0 };
+    q * ef * recency * risk * affinity
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_feed_score_monotonicity() {
+        let p = RankParams::default();
+        let s = |q: f64, ef: f64, age: f64, risk: f64, aff: f64| feed_score(q, ef, age, risk, aff, &p);
+        let base = s(0.7, 50.0, 3_600.0, 0.2, 0.3);
+        assert!(s(0.8, 50.0, 3_600.0, 0.2, 0.3) > base);
+        assert!(s(0.7, 80.0, 3_600.0, 0.2, 0.3) > base);
+        assert!(s(0.7, 50.0, 7_200.0, 0.2, 0.3) < base);
+        assert!(s(0.7, 50.0, 3_600.0, 0.4, 0.3) < base);
+        assert!(s(0.7, 50.0, 3_600.0, 0.2, 0.6) > base);
+
+        assert_eq!(s(0.8, 100.0, 0.0, 0.0, 0.0), 0.8 * 1.5);
+        assert!((s(1.0, 0.0, 6.0 * 3_600.0, 0.0, 0.0) - 0.5).abs() < 1e-12);
+        // a farmed follower count does not lift low-quality content past a good post from a small account
+        assert!(s(0.2, 1e6, 0.0, 0.0, 0.0) < s(0.8, 10.0, 0.0, 0.0, 0.0));
+        assert_eq!(s(0.0, 50.0, 0.0, 0.0, 1.0), 0.0);
+        assert_eq!(s(0.7, 50.0, f64::NAN, 0.0, 0.0), 0.0);
+    }
+}
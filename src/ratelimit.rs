@@ -0,0 +1,207 @@
+// Sliding-window post counters (the `posts_1h` input of `calculate_post_cost`) and token-bucket posting allowances
+// - A window of `w` seconds at time `now` counts posts with `now - w < t <= now` (unix seconds)
+// - Timestamps are kept sorted, so out-of-order arrival gives the same counts on every node
+// - Posts older than the horizon (the longest window, 24 h by default) are pruned as newer ones arrive
+// - A `TokenBucket` refills continuously at `refill_per_hour * quality factor * tier multiplier`, capped at `capacity`
+
+use crate::{calculate_post_cost, Actor, Content, Params, Quality};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+pub const TEN_MINUTES: u64 = 600;
+pub const ONE_HOUR: u64 = 3_600;
+pub const ONE_DAY: u64 = 86_400;
+
+/// Post counts over the standard windows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WindowCounts {
+    pub posts_10m: u32,
+    pub posts_1h: u32,
+    pub posts_24h: u32,
+}
+
+/// Sorted post timestamps covering `horizon_secs`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SlidingWindow {
+    pub horizon_secs: u64,
+    timestamps: VecDeque<u64>,
+}
+
+impl Default for SlidingWindow {
+    fn default() -> Self { SlidingWindow::new(ONE_DAY) }
+}
+
+impl SlidingWindow {
+    /// Window able to answer counts for any window up to `horizon_secs`
+    pub fn new(horizon_secs: u64) -> Self { SlidingWindow { horizon_secs, timestamps: VecDeque::new() } }
+
+    /// Record one post at `ts`
+    pub fn record(&mut self, ts: u64) {
+        let i = self.timestamps.partition_point(|&t| t <= ts);
+        self.timestamps.insert(i, ts);
+        if let Some(&latest) = self.timestamps.back() { self.prune(latest); }
+    }
+
+    /// Drop posts that fall outside the horizon at `now`
+    pub fn prune(&mut self, now: u64) {
+        let Some(cutoff) = now.checked_sub(self.horizon_secs) else { return };
+        while self.timestamps.front().is_some_and(|&t| t <= cutoff) {
+            self.timestamps.pop_front();
+        }
+    }
+
+    /// Posts in `(now - window_secs, now]`; windows longer than the horizon are capped to it
+    pub fn count(&self, now: u64, window_secs: u64) -> u32 {
+        let window = window_secs.min(self.horizon_secs);
+        let end = self.timestamps.partition_point(|&t| t <= now);
+        let start = match now.checked_sub(window) {
+            Some(cutoff) => self.timestamps.partition_point(|&t| t <= cutoff),
+            None => 0,
+        };
+        (end - start.min(end)) as u32
+    }
+
+    /// `Actor::posts_1h` at `now`
+    pub fn posts_1h(&self, now: u64) -> f64 { self.count(now, ONE_HOUR) as f64 }
+
+    /// 10 min / 1 h / 24 h counts at `now`
+    pub fn counts(&self, now: u64) -> WindowCounts {
+        WindowCounts { posts_10m: self.count(now, TEN_MINUTES), posts_1h: self.count(now, ONE_HOUR), posts_24h: self.count(now, ONE_DAY) }
+    }
+
+    /// Posts currently retained
+    pub fn len(&self) -> usize { self.timestamps.len() }
+
+    pub fn is_empty(&self) -> bool { self.timestamps.is_empty() }
+}
+
+// -------- Token bucket --------
+
+/// Free-tier allowance shape
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AllowanceParams {
+    /// Maximum stored allowance (in cost units)
+    pub capacity: f64,
+    /// Refill per hour at q = 1 and tier multiplier 1
+    pub refill_per_hour: f64,
+    /// Refill factor at q = 0 (rises linearly to 1 at q = 1)
+    pub min_quality_factor: f64,
+}
+
+impl Default for AllowanceParams {
+    fn default() -> Self { AllowanceParams { capacity: 20.0, refill_per_hour: 10.0, min_quality_factor: 0.25 } }
+}
+
+impl AllowanceParams {
+    /// Refill per second for an actor of quality `q` on a tier with `tier_multiplier`
+    pub fn refill_per_sec(&self, q: impl Into<Quality>, tier_multiplier: f64) -> f64 {
+        let floor = self.min_quality_factor.clamp(0.0, 1.0);
+        let quality = floor + (1.0 - floor) * q.into().get();
+        (self.refill_per_hour * quality * tier_multiplier).max(0.0) / ONE_HOUR as f64
+    }
+}
+
+/// Rejected consumption
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AllowanceDenied {
+    pub needed: f64,
+    pub available: f64,
+    /// Seconds until enough has refilled (`None` when `needed` exceeds the capacity or nothing refills)
+    pub retry_after_secs: Option<u64>,
+}
+
+/// Per-actor allowance state (serialize it to carry the bucket across nodes)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TokenBucket {
+    pub tokens: f64,
+    /// Unix seconds of the last refill
+    pub updated_at: u64,
+}
+
+impl TokenBucket {
+    /// Bucket filled to capacity at `now`
+    pub fn full(params: &AllowanceParams, now: u64) -> Self { TokenBucket { tokens: params.capacity.max(0.0), updated_at: now } }
+
+    /// Add the allowance accrued since `updated_at` (a `now` in the past leaves the bucket unchanged)
+    pub fn refill(&mut self, now: u64, q: impl Into<Quality>, tier_multiplier: f64, params: &AllowanceParams) {
+        let Some(elapsed) = now.checked_sub(self.updated_at) else { return };
+        let rate = params.refill_per_sec(q, tier_multiplier);
+        self.tokens = (self.tokens + elapsed as f64 * rate).min(params.capacity.max(0.0));
+        self.updated_at = now;
+    }
+
+    /// Refill, then take `cost` if the bucket covers it; returns the remaining allowance
+    pub fn try_consume(&mut self, cost: f64, now: u64, q: impl Into<Quality>, tier_multiplier: f64, params: &AllowanceParams) -> Result<f64, AllowanceDenied> {
+        let q = q.into();
+        self.refill(now, q, tier_multiplier, params);
+        if cost <= self.tokens {
+            self.tokens -= cost.max(0.0);
+            return Ok(self.tokens);
+        }
+        let rate = params.refill_per_sec(q, tier_multiplier);
+        let retry_after_secs = (cost <= params.capacity && rate > 0.0).then(|| ((cost - self.tokens) / rate).ceil() as u64);
+        Err(AllowanceDenied { needed: cost, available: self.tokens, retry_after_secs })
+    }
+
+    /// Price a post with `calculate_post_cost` and pay it from the allowance (refill uses `actor.q`)
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_consume_post(
+        &mut self,
+        actor: &Actor,
+        content: &Content,
+        params: &Params,
+        base_fare: f64,
+        now: u64,
+        tier_multiplier: f64,
+        allowance: &AllowanceParams,
+    ) -> Result<f64, AllowanceDenied> {
+        let cost = calculate_post_cost(actor, content, params, base_fare);
+        self.try_consume(cost, now, actor.q, tier_multiplier, allowance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_windows_and_order_independence() {
+        let t0 = 1_700_000_000;
+        let posts = [t0, t0 + 100, t0 + 700, t0 + 3_599, t0 + 3_600, t0 + 50_000];
+        let (mut a, mut b) = (SlidingWindow::default(), SlidingWindow::default());
+        for &t in &posts { a.record(t); }
+        for &t in posts.iter().rev() { b.record(t); }
+        assert_eq!(a, b);
+
+        let now = t0 + 3_600;
+        assert_eq!(a.counts(now), WindowCounts { posts_10m: 2, posts_1h: 4, posts_24h: 5 });
+        assert_eq!(a.posts_1h(t0 + 50_000), 1.0);
+        a.record(t0 + ONE_DAY + 50);
+        assert_eq!(a.len(), 6);
+    }
+
+    #[test]
+    fn t_bucket_refill_and_deny() {
+        let ap = AllowanceParams::default();
+        let mut b = TokenBucket::full(&ap, 1_000);
+        assert_eq!(b.try_consume(15.0, 1_000, 1.0, 1.0, &ap), Ok(5.0));
+        let denied = b.try_consume(8.0, 1_000, 1.0, 1.0, &ap).unwrap_err();
+        assert_eq!(denied.retry_after_secs, Some(1_080));
+        // q = 0 refills at the quality floor; a 2x tier doubles it
+        assert_eq!(b.try_consume(8.0, 2_080, 0.0, 2.0, &ap).unwrap_err().available, 5.0 + 0.5 * 10.0 * 1_080.0 / 3_600.0);
+        assert!(b.try_consume(25.0, 100_000, 1.0, 1.0, &ap).unwrap_err().retry_after_secs.is_none());
+        #[cfg(feature = "serde")]
+        assert_eq!(serde_json::from_str::<TokenBucket>(&serde_json::to_string(&b).unwrap()).unwrap(), b);
+    }
+}
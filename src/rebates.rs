@@ -0,0 +1,72 @@
+// Posting cost rebates for high-quality content that spread on its own
+// - Settled after the fact from the post's final reach: only organic impressions (relayed by readers, not boosted)
+//   count, and any post-hoc risk event (a report upheld, a risk re-evaluation, a quarantine) voids the rebate
+// - rebate = original_cost * max_share * (q - min_q) / (1 - min_q) * reach_factor, where reach_factor grows with
+//   log(organic reach) from 0 at `min_reach` to 1 at `full_reach`
+// - Never more than `max_share` of what was paid, so posting is never free in expectation
+
+use crate::{clamp, Quality};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Final reach of a post once it stopped spreading
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ReachStats {
+    pub impressions: u64,
+    /// Share of impressions that came from organic relays (0..1)
+    pub organic_share: f64,
+    /// Risk events recorded after publication
+    pub risk_events: u32,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RebateParams {
+    pub min_q: f64,
+    /// Organic impressions below which no rebate is paid
+    pub min_reach: f64,
+    /// Organic impressions at which the reach factor reaches 1
+    pub full_reach: f64,
+    /// Largest share of the original cost refunded
+    pub max_share: f64,
+}
+
+impl Default for RebateParams {
+    fn default() -> Self { RebateParams { min_q: 0.7, min_reach: 1_000.0, full_reach: 100_000.0, max_share: 0.5 } }
+}
+
+/// Partial refund of `original_cost` (SOCIAL, 0..=max_share * original_cost)
+pub fn quality_rebate(final_reach_stats: &ReachStats, original_cost: f64, q: impl Into<Quality>, params: &RebateParams) -> f64 {
+    let s = final_reach_stats;
+    let q = q.into().get();
+    let min_q = clamp(params.min_q, 0.0, 1.0);
+    let organic = s.impressions as f64 * if s.organic_share.is_finite() { clamp(s.organic_share, 0.0, 1.0) } else { 0.0 };
+    let paid = if original_cost.is_finite() { original_cost.max(0.0) } else { 0.0 };
+    if s.risk_events > 0 || q <= min_q || organic < params.min_reach.max(1.0) { return 0.0; }
+    let quality = if min_q < 1.0 { (q - min_q) / (1.0 - min_q) } else { 1.0 };
+    let (lo, hi) = (params.min_reach.max(1.0), params.full_reach);
+    let reach = if hi > lo { clamp((organic / lo).ln() / (hi / lo).ln(), 0.0, 1.0) } else { 1.0 };
+    paid * clamp(params.max_share, 0.0, 1.0) * quality * reach
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_rebate_for_organic_quality_reach() {
+        let p = RebateParams::default();
+        let viral = ReachStats { impressions: 200_000, organic_share: 0.5, risk_events: 0 };
+        // 100k organic impressions: full reach factor; q 0.85 is half way from min_q
+        assert!((quality_rebate(&viral, 4.0, 0.85, &p) - 4.0 * 0.5 * 0.5).abs() < 1e-12);
+        let mid = ReachStats { impressions: 10_000, organic_share: 1.0, risk_events: 0 };
+        assert!((quality_rebate(&mid, 4.0, 1.0, &p) - 1.0).abs() < 1e-12);
+
+        assert_eq!(quality_rebate(&ReachStats { risk_events: 1, ..viral.clone() }, 4.0, 1.0, &p), 0.0);
+        assert_eq!(quality_rebate(&ReachStats { organic_share: 0.001, ..viral.clone() }, 4.0, 1.0, &p), 0.0);
+        assert_eq!(quality_rebate(&viral, 4.0, 0.6, &p), 0.0);
+    }
+}
@@ -0,0 +1,95 @@
+// Referral bonuses contingent on the invitee's early behavior
+// - Nothing is owed until the invitee has been active `min_days`, posted `min_posts` times at an average quality of at
+//   least `min_invitee_q`, and has not been slashed; an invite farm of throwaway accounts therefore earns nothing
+// - bonus = base_bonus * inviter_q^inviter_q_exp * (avg_q - min_invitee_q) / (1 - min_invitee_q) * vested, with
+//   vested = min(1, days_active / vesting_days): the bonus accrues over the invitee's first weeks, so `bonus` is the
+//   total owed so far and a payout is `bonus - already_paid`
+// - If the invitee is slashed within `clawback_days`, `clawback` reclaims `clawback_share` of what was paid
+
+use crate::{clamp, Quality};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The invitee's record since joining
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EarlyBehavior {
+    pub days_active: f64,
+    pub posts: u32,
+    /// Average quality of those posts
+    pub avg_q: f64,
+    pub slashed: bool,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ReferralParams {
+    pub base_bonus: f64,
+    pub inviter_q_exp: f64,
+    pub min_invitee_q: f64,
+    pub min_days: f64,
+    pub min_posts: u32,
+    pub vesting_days: f64,
+    pub clawback_days: f64,
+    pub clawback_share: f64,
+}
+
+impl Default for ReferralParams {
+    fn default() -> Self {
+        ReferralParams {
+            base_bonus: 10.0, inviter_q_exp: 1.0, min_invitee_q: 0.5, min_days: 7.0, min_posts: 5,
+            vesting_days: 30.0, clawback_days: 90.0, clawback_share: 1.0,
+        }
+    }
+}
+
+/// Total bonus owed to the inviter so far (SOCIAL, >= 0)
+pub fn bonus(inviter_q: impl Into<Quality>, invitee_behavior: &EarlyBehavior, params: &ReferralParams) -> f64 {
+    let b = invitee_behavior;
+    let min_q = clamp(params.min_invitee_q, 0.0, 1.0);
+    let avg_q = if b.avg_q.is_finite() { clamp(b.avg_q, 0.0, 1.0) } else { 0.0 };
+    let days = if b.days_active.is_finite() { b.days_active.max(0.0) } else { 0.0 };
+    if b.slashed || days < params.min_days || b.posts < params.min_posts || avg_q < min_q { return 0.0; }
+    let quality = if min_q < 1.0 { (avg_q - min_q) / (1.0 - min_q) } else { 1.0 };
+    let vested = if params.vesting_days > 0.0 { (days / params.vesting_days).min(1.0) } else { 1.0 };
+    params.base_bonus.max(0.0) * inviter_q.into().get().powf(params.inviter_q_exp.max(0.0)) * quality * vested
+}
+
+/// Amount to reclaim from an inviter who was paid `paid` for this invitee
+pub fn clawback(paid: f64, invitee_behavior: &EarlyBehavior, params: &ReferralParams) -> f64 {
+    let b = invitee_behavior;
+    let within = b.days_active.is_finite() && b.days_active <= params.clawback_days;
+    if !b.slashed || !within { return 0.0; }
+    paid.max(0.0) * clamp(params.clawback_share, 0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_bonus_contingent_and_clawback() {
+        let p = ReferralParams::default();
+        let good = EarlyBehavior { days_active: 15.0, posts: 20, avg_q: 0.8, slashed: false };
+        // half vested, quality factor 0.6
+        assert!((bonus(1.0, &good, &p) - 10.0 * 0.6 * 0.5).abs() < 1e-12);
+        assert!(bonus(0.5, &good, &p) < bonus(1.0, &good, &p));
+        assert!(bonus(1.0, &EarlyBehavior { days_active: 60.0, ..good.clone() }, &p) > bonus(1.0, &good, &p));
+
+        // farm accounts: too new, silent, low quality, or slashed
+        for farm in [
+            EarlyBehavior { days_active: 2.0, ..good.clone() },
+            EarlyBehavior { posts: 1, ..good.clone() },
+            EarlyBehavior { avg_q: 0.3, ..good.clone() },
+            EarlyBehavior { slashed: true, ..good.clone() },
+        ] {
+            assert_eq!(bonus(1.0, &farm, &p), 0.0);
+        }
+
+        assert_eq!(clawback(3.0, &good, &p), 0.0);
+        assert_eq!(clawback(3.0, &EarlyBehavior { slashed: true, ..good.clone() }, &p), 3.0);
+        assert_eq!(clawback(3.0, &EarlyBehavior { slashed: true, days_active: 120.0, ..good }, &p), 0.0);
+    }
+}
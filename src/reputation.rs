@@ -0,0 +1,345 @@
+// Event-sourced actor reputation ledger
+// - Per-actor state (quality, EF, abuse history, stake) changes only by applying typed events in order
+// - Actors are created on their first event with `ReputationParams::initial_q` and no stake
+// - `state_root` hashes the whole ledger canonically (actors by id, floats by bit pattern), so two nodes that applied
+//   the same events agree on the root bit for bit
+// - Events with non-finite or negative amounts are rejected without touching the state
+// - Appeals are events too, applied with `appeals::adjust_on_upheld` / `adjust_on_rejected`; an upheld appeal needs an
+//   upheld report to reverse and a rejected one an actor the ledger knows
+// - Endorsements: an established actor vouches for a newcomer (`delegate`), granting a capped cost discount that
+//   decays with a half-life; if the newcomer is slashed, `clawback` takes quality from the endorser
+
+use crate::appeals::{adjust_on_rejected, adjust_on_upheld, Appeal, AppealParams};
+use crate::por::Hash32;
+use crate::slashing::{calculate_slash, OffenseKind, SlashParams};
+use crate::{clamp, Ef, Quality, RiskSignals};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// Update rates for the event handlers
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ReputationParams {
+    /// Quality of a newly seen actor
+    pub initial_q: f64,
+    /// EMA weight of an accepted post's quality
+    pub q_alpha: f64,
+    /// Abuse history kept per accepted post (multiplicative decay)
+    pub abuse_decay: f64,
+    /// Abuse history added per upheld report at severity 1
+    pub report_weight: f64,
+    /// Fraction of q removed per upheld report at severity 1
+    pub report_q_penalty: f64,
+    /// Abuse history added per slash at severity 1
+    pub slash_weight: f64,
+    pub slashing: SlashParams,
+    /// Redress shares for appeal events (the report rates used are the ones above)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub appeals: AppealParams,
+}
+
+impl Default for ReputationParams {
+    fn default() -> Self {
+        ReputationParams {
+            initial_q: 0.5, q_alpha: 0.05, abuse_decay: 0.99, report_weight: 0.2, report_q_penalty: 0.1, slash_weight: 0.3,
+            slashing: SlashParams::default(), appeals: AppealParams::default(),
+        }
+    }
+}
+
+/// Reputation of one actor
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ActorState {
+    pub q: f64,
+    pub ef: f64,
+    /// Abuse history signal (0..1), fed to `RiskSignals::abuse_history`
+    pub abuse_history: f64,
+    pub stake: f64,
+    pub verified: bool,
+    pub posts: u64,
+    pub upheld_reports: u32,
+    /// Upheld offenses per kind: fake serve, oracle manipulation, coordinated spam
+    pub offenses: [u32; 3],
+}
+
+impl ActorState {
+    fn new(params: &ReputationParams) -> Self {
+        ActorState { q: params.initial_q, ef: 0.0, abuse_history: 0.0, stake: 0.0, verified: false, posts: 0, upheld_reports: 0, offenses: [0; 3] }
+    }
+
+    /// Risk signals carrying this actor's abuse history
+    pub fn risk_signals(&self) -> RiskSignals { RiskSignals { abuse_history: Some(self.abuse_history), ..Default::default() } }
+}
+
+fn offense_index(kind: OffenseKind) -> usize {
+    match kind {
+        OffenseKind::FakeServe => 0,
+        OffenseKind::OracleManipulation => 1,
+        OffenseKind::CoordinatedSpam => 2,
+    }
+}
+
+/// Ledger event
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum ReputationEvent {
+    /// A post passed admission; `q` is its quality score, `ef` the freshly computed EF (if any)
+    PostAccepted { actor: String, q: f64, #[cfg_attr(feature = "serde", serde(default))] ef: Option<f64> },
+    /// A report against the actor was upheld (`severity` 0..1)
+    ReportUpheld { actor: String, severity: f64 },
+    VerificationGranted { actor: String },
+    StakeDeposited { actor: String, amount: f64 },
+    /// Provable offense; the amount follows `slashing::calculate_slash` with the actor's repeat count
+    StakeSlashed { actor: String, offense: OffenseKind, severity: f64 },
+    /// An appeal against an upheld report of `severity` succeeded; `surcharge_paid` is what the report cost the actor
+    AppealUpheld { actor: String, severity: f64, #[cfg_attr(feature = "serde", serde(default))] surcharge_paid: f64 },
+    AppealRejected { actor: String },
+}
+
+impl ReputationEvent {
+    pub fn actor(&self) -> &str {
+        match self {
+            ReputationEvent::PostAccepted { actor, .. }
+            | ReputationEvent::ReportUpheld { actor, .. }
+            | ReputationEvent::VerificationGranted { actor }
+            | ReputationEvent::StakeDeposited { actor, .. }
+            | ReputationEvent::StakeSlashed { actor, .. }
+            | ReputationEvent::AppealUpheld { actor, .. }
+            | ReputationEvent::AppealRejected { actor } => actor,
+        }
+    }
+}
+
+/// Rejected event (`index` is its position in the ledger's event sequence)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ReputationError { pub index: u64, pub reason: String }
+
+impl std::fmt::Display for ReputationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "event {}: {}", self.index, self.reason) }
+}
+
+impl std::error::Error for ReputationError {}
+
+/// All actors' states plus the number of events applied
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Ledger {
+    pub params: ReputationParams,
+    pub actors: BTreeMap<String, ActorState>,
+    pub applied: u64,
+}
+
+impl Ledger {
+    pub fn new(params: ReputationParams) -> Self { Ledger { params, actors: BTreeMap::new(), applied: 0 } }
+
+    pub fn actor(&self, id: &str) -> Option<&ActorState> { self.actors.get(id) }
+
+    /// Apply one event; returns the slashed amount for `StakeSlashed`, the refund for `AppealUpheld` (0 otherwise)
+    pub fn apply(&mut self, event: &ReputationEvent) -> Result<f64, ReputationError> {
+        let err = |reason: &str| ReputationError { index: self.applied, reason: reason.into() };
+        let nonneg = |x: f64| x.is_finite() && x >= 0.0;
+        match event {
+            ReputationEvent::PostAccepted { q, ef, .. } if !q.is_finite() || ef.is_some_and(|e| !nonneg(e)) => return Err(err("q and ef must be finite (ef >= 0)")),
+            ReputationEvent::ReportUpheld { severity, .. } | ReputationEvent::StakeSlashed { severity, .. } if !severity.is_finite() => return Err(err("severity must be finite")),
+            ReputationEvent::StakeDeposited { amount, .. } if !nonneg(*amount) => return Err(err("amount must be finite and >= 0")),
+            ReputationEvent::AppealUpheld { severity, surcharge_paid, .. } if !severity.is_finite() || !nonneg(*surcharge_paid) => {
+                return Err(err("severity must be finite and surcharge_paid finite and >= 0"));
+            }
+            ReputationEvent::AppealUpheld { actor, .. } if self.actors.get(actor).map_or(0, |s| s.upheld_reports) == 0 => {
+                return Err(err("no upheld report to reverse"));
+            }
+            ReputationEvent::AppealRejected { actor } if !self.actors.contains_key(actor) => return Err(err("unknown actor")),
+            _ => {}
+        }
+
+        let p = &self.params;
+        let s = self.actors.entry(event.actor().to_string()).or_insert_with(|| ActorState::new(p));
+        let mut amount = 0.0;
+        match event {
+            ReputationEvent::PostAccepted { q, ef, .. } => {
+                s.q = clamp(s.q + p.q_alpha * (clamp(*q, 0.0, 1.0) - s.q), 0.0, 1.0);
+                if let Some(ef) = ef { s.ef = *ef; }
+                s.abuse_history *= clamp(p.abuse_decay, 0.0, 1.0);
+                s.posts += 1;
+            }
+            ReputationEvent::ReportUpheld { severity, .. } => {
+                let sev = clamp(*severity, 0.0, 1.0);
+                s.abuse_history = clamp(s.abuse_history + p.report_weight * sev, 0.0, 1.0);
+                s.q = clamp(s.q * (1.0 - p.report_q_penalty * sev), 0.0, 1.0);
+                s.upheld_reports += 1;
+            }
+            ReputationEvent::VerificationGranted { .. } => s.verified = true,
+            ReputationEvent::StakeDeposited { amount, .. } => s.stake += amount,
+            ReputationEvent::StakeSlashed { offense, severity, .. } => {
+                let i = offense_index(*offense);
+                let slashed = calculate_slash(s.stake, *offense, *severity, s.offenses[i], &p.slashing);
+                s.stake -= slashed;
+                amount = slashed;
+                s.offenses[i] += 1;
+                s.abuse_history = clamp(s.abuse_history + p.slash_weight * clamp(*severity, 0.0, 1.0), 0.0, 1.0);
+            }
+            ReputationEvent::AppealUpheld { severity, surcharge_paid, .. } => {
+                amount = adjust_on_upheld(s, &AppealParams::for_reputation(p), &Appeal { severity: *severity, surcharge_paid: *surcharge_paid });
+            }
+            ReputationEvent::AppealRejected { .. } => adjust_on_rejected(s, &AppealParams::for_reputation(p)),
+        }
+        self.applied += 1;
+        Ok(amount)
+    }
+
+    /// Apply events in order, stopping at the first rejected one
+    pub fn apply_all<'a>(&mut self, events: impl IntoIterator<Item = &'a ReputationEvent>) -> Result<(), ReputationError> {
+        events.into_iter().try_for_each(|e| self.apply(e).map(|_| ()))
+    }
+
+    /// SHA-256 commitment over `applied` and every actor (sorted by id) with its fields in declaration order
+    pub fn state_root(&self) -> Hash32 {
+        let mut h = Sha256::new();
+        h.update(b"slimechain/reputation/v1");
+        h.update(self.applied.to_le_bytes());
+        h.update((self.actors.len() as u64).to_le_bytes());
+        for (id, s) in &self.actors {
+            h.update((id.len() as u64).to_le_bytes());
+            h.update(id.as_bytes());
+            for x in [s.q, s.ef, s.abuse_history, s.stake] { h.update(x.to_bits().to_le_bytes()); }
+            h.update([u8::from(s.verified)]);
+            h.update(s.posts.to_le_bytes());
+            h.update(s.upheld_reports.to_le_bytes());
+            for n in s.offenses { h.update(n.to_le_bytes()); }
+        }
+        h.finalize().into()
+    }
+}
+
+// -------- Endorsement --------
+
+/// Limits and decay for endorsement boosts
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EndorsementParams {
+    /// Endorsers below this quality grant nothing
+    pub min_endorser_q: f64,
+    /// Discount per unit of `endorser_q * endorser_ef * weight`
+    pub discount_per_ef: f64,
+    /// Cap on one endorsement's discount
+    pub max_discount: f64,
+    /// Cap on the summed discount of all endorsements an actor holds
+    pub max_total_discount: f64,
+    /// Days for a boost to halve (0 = no decay)
+    pub half_life_days: f64,
+    /// Fraction of the endorser's q removed per unit of weight when the endorsed actor is slashed at severity 1
+    pub clawback_q_penalty: f64,
+}
+
+impl Default for EndorsementParams {
+    fn default() -> Self {
+        EndorsementParams {
+            min_endorser_q: 0.6, discount_per_ef: 0.01, max_discount: 0.25, max_total_discount: 0.5,
+            half_life_days: 30.0, clawback_q_penalty: 0.2,
+        }
+    }
+}
+
+/// Discount granted by one endorsement at the time it was made
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EndorsementBoost {
+    /// Share of the endorser's standing put behind the endorsement (0..1)
+    pub weight: f64,
+    /// Fraction taken off the endorsed actor's posting cost (0..`max_discount`)
+    pub discount: f64,
+}
+
+impl EndorsementBoost {
+    /// Discount left `elapsed_days` after the endorsement
+    pub fn discount_at(&self, elapsed_days: f64, params: &EndorsementParams) -> f64 {
+        if params.half_life_days <= 0.0 { return self.discount; }
+        self.discount * 0.5f64.powf(elapsed_days.max(0.0) / params.half_life_days)
+    }
+}
+
+fn unit(x: f64) -> f64 { if x.is_finite() { clamp(x, 0.0, 1.0) } else { 0.0 } }
+
+/// Vouch for an actor with `weight` (0..1) of the endorser's standing
+pub fn delegate(endorser_q: impl Into<Quality>, endorser_ef: impl Into<Ef>, weight: f64, params: &EndorsementParams) -> EndorsementBoost {
+    let (q, ef, weight) = (endorser_q.into().get(), endorser_ef.into().get(), unit(weight));
+    let discount = if q < params.min_endorser_q || !ef.is_finite() { 0.0 } else {
+        (params.discount_per_ef.max(0.0) * q * ef * weight).min(unit(params.max_discount))
+    };
+    EndorsementBoost { weight, discount }
+}
+
+/// Posting cost multiplier for an actor holding `boosts` (each with its days since endorsement)
+pub fn endorsement_multiplier(boosts: &[(EndorsementBoost, f64)], params: &EndorsementParams) -> f64 {
+    let total: f64 = boosts.iter().map(|(b, days)| b.discount_at(*days, params)).sum();
+    1.0 - total.min(unit(params.max_total_discount))
+}
+
+/// Endorser's quality after the endorsed actor is slashed at `severity`; the boost should be revoked
+pub fn clawback(endorser_q: impl Into<Quality>, boost: &EndorsementBoost, severity: f64, params: &EndorsementParams) -> f64 {
+    let penalty = unit(params.clawback_q_penalty * unit(boost.weight) * unit(severity));
+    endorser_q.into().get() * (1.0 - penalty)
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    fn events() -> Vec<ReputationEvent> {
+        serde_json::from_str(r#"[
+            {"type":"stake_deposited","actor":"alice","amount":1000},
+            {"type":"post_accepted","actor":"alice","q":0.9,"ef":12.5},
+            {"type":"verification_granted","actor":"bob"},
+            {"type":"report_upheld","actor":"alice","severity":0.5},
+            {"type":"stake_slashed","actor":"alice","offense":"fake_serve","severity":1.0},
+            {"type":"stake_slashed","actor":"alice","offense":"fake_serve","severity":1.0}
+        ]"#).unwrap()
+    }
+
+    #[test]
+    fn t_replay_is_deterministic() {
+        let (mut a, mut b) = (Ledger::default(), Ledger::default());
+        a.apply_all(&events()).unwrap();
+        for e in &events() { b.apply(e).unwrap(); }
+        assert_eq!(a.state_root(), b.state_root());
+
+        let alice = a.actor("alice").unwrap();
+        assert_eq!(alice.stake, 1000.0 - 50.0 - 95.0);
+        assert_eq!(alice.offenses, [2, 0, 0]);
+        assert_eq!(alice.ef, 12.5);
+        assert!(a.actor("bob").unwrap().verified);
+
+        let before = a.state_root();
+        let bad = ReputationEvent::StakeDeposited { actor: "alice".into(), amount: f64::NAN };
+        assert_eq!(a.apply(&bad).unwrap_err().index, 6);
+        assert_eq!(a.state_root(), before);
+        a.apply(&ReputationEvent::PostAccepted { actor: "alice".into(), q: 0.9, ef: None }).unwrap();
+        assert_ne!(a.state_root(), before);
+    }
+
+    #[test]
+    fn t_endorsement_caps_decay_and_clawback() {
+        let p = EndorsementParams::default();
+        let boost = delegate(0.8, 25.0, 0.5, &p);
+        assert_eq!(boost, EndorsementBoost { weight: 0.5, discount: 0.1 });
+        assert_eq!(delegate(0.9, 1e6, 1.0, &p).discount, p.max_discount);
+        assert_eq!(delegate(0.5, 1e6, 1.0, &p).discount, 0.0);
+
+        assert_eq!(boost.discount_at(30.0, &p), 0.05);
+        let strong = delegate(0.9, 1e6, 1.0, &p);
+        assert_eq!(endorsement_multiplier(&[(strong, 0.0), (strong, 0.0), (boost, 0.0)], &p), 0.5);
+        assert_eq!(clawback(0.8, &boost, 1.0, &p), 0.8 * 0.9);
+    }
+}
@@ -0,0 +1,319 @@
+// Reward extensions (epoch pool accounting, creator payouts, batch settlement and multi-server splits on top of `calculate_serve_reward`)
+// - `BudgetGate` admits serve rewards against an epoch budget in whole units, so issuance never exceeds it
+// - `collusion_score` flags wash-serving pairs; it feeds `diversity::cluster_risk` (`w_collusion`) and so `server_cluster_risk`
+
+use crate::money::{split, Amount, DustPolicy, Rounding, Split};
+use crate::{calculate_serve_reward, Params, RewardInput};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub mod epoch;
+pub mod payouts;
+
+pub type ServerId = String;
+pub type ClientId = String;
+
+/// One serve event attributed to a server/client pair
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ServeRecord {
+    pub server_id: ServerId,
+    pub client_id: ClientId,
+    pub input: RewardInput,
+}
+
+/// Aggregate for one (server, client) pair
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PairTotal {
+    pub server_id: ServerId,
+    pub client_id: ClientId,
+    pub serves: u64,
+    /// Reward in smallest units (`params.reward.epoch.unit`)
+    pub units: u64,
+}
+
+/// Netted position of one account (an account can act as server and client)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AccountTotal {
+    pub account_id: String,
+    /// Rewards earned as a server (units)
+    pub credited: u64,
+    /// Escrow deducted as a client (units)
+    pub debited: u64,
+    /// `credited - debited` (units)
+    pub net: i128,
+}
+
+/// Batch settlement result; pairs sorted by (server, client), accounts by id
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SettlementReport {
+    pub pairs: Vec<PairTotal>,
+    pub accounts: Vec<AccountTotal>,
+    pub total_units: u64,
+    pub unit: f64,
+}
+
+/// Settle a batch of serves: aggregate per (server, client) pair and net server rewards against client escrow
+/// - Each serve's reward is floored to whole units before summing, so totals are exact and order-independent
+pub fn settle_batch(records: &[ServeRecord], params: &Params) -> SettlementReport {
+    let unit = params.reward.epoch.unit.max(1e-12);
+    let mut pairs: BTreeMap<(&str, &str), (u64, u64)> = BTreeMap::new();
+    let mut accounts: BTreeMap<&str, (u64, u64)> = BTreeMap::new();
+    for r in records {
+        let units = Amount::from_social(calculate_serve_reward(&r.input, params), unit, Rounding::Down).units();
+        let e = pairs.entry((r.server_id.as_str(), r.client_id.as_str())).or_insert((0, 0));
+        e.0 += 1;
+        e.1 += units;
+        accounts.entry(r.server_id.as_str()).or_insert((0, 0)).0 += units;
+        accounts.entry(r.client_id.as_str()).or_insert((0, 0)).1 += units;
+    }
+    let total_units = pairs.values().map(|p| p.1).sum();
+    SettlementReport {
+        pairs: pairs.into_iter().map(|((s, c), (serves, units))| PairTotal {
+            server_id: s.to_string(), client_id: c.to_string(), serves, units,
+        }).collect(),
+        accounts: accounts.into_iter().map(|(id, (credited, debited))| AccountTotal {
+            account_id: id.to_string(), credited, debited, net: credited as i128 - debited as i128,
+        }).collect(),
+        total_units,
+        unit,
+    }
+}
+
+/// Discount for serving the same content to the same client again: `0.5^(prev_serves / half)`
+/// - `half == 0` disables the discount
+pub fn repetition_factor(prev_serves: u32, half: u32) -> f64 {
+    if half == 0 { return 1.0; }
+    crate::fastmath::powf_sel(0.5, prev_serves as f64 / half as f64)
+}
+
+/// Byte range delivered by one server for a chunked serve
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ChunkContribution {
+    pub server_id: ServerId,
+    pub bytes: u64,
+    pub ttfb_ms: u32,
+}
+
+/// Split a single serve reward across the servers that delivered it
+/// - Weight per server: `bytes * 1 / (1 + ttfb_ms/latency_knee_ms)`
+/// - Parts are whole units of `params.reward.epoch.unit` in `contributions` order: the reward is floored to units and
+///   split by weight, leftover units going to the largest remainders, so parts sum exactly to the floored reward
+///   (with no positive weight all of it is `dust`)
+pub fn split_serve_reward(input: &RewardInput, contributions: &[ChunkContribution], params: &Params) -> Split {
+    let total = Amount::from_social(calculate_serve_reward(input, params), params.reward.epoch.unit, Rounding::Down);
+    let knee = params.reward.latency_knee_ms.max(1e-9);
+    let weights: Vec<f64> = contributions.iter()
+        .map(|c| c.bytes as f64 / (1.0 + c.ttfb_ms as f64 / knee))
+        .collect();
+    split(total, &weights, DustPolicy::LargestRemainder)
+}
+
+/// Pseudo-serves added to both sides of a pair in `collusion_score`, so a handful of serves cannot score high
+const COLLUSION_PRIOR_SERVES: f64 = 10.0;
+
+/// Per-server collusion score (0..1) from `(server, client, serves)` counts, sorted by server
+/// - Pair dependence `n² / (N_server * N_client)`: the share of the server's serves going to the client times the share
+///   of the client's serves coming from the server, i.e. the pair's term of the mean-square contingency `χ²/N + 1`
+/// - Both totals get `COLLUSION_PRIOR_SERVES` added; the score is the server's most dependent pair
+/// - A wash pair (server and client mostly serving each other) approaches 1; a server with a broad audience, or a
+///   client that fetches from many servers, stays near 0
+pub fn collusion_score(server_client_pairs: &[(ServerId, ClientId, u64)]) -> Vec<(ServerId, f64)> {
+    let mut servers: BTreeMap<&str, BTreeMap<&str, u64>> = BTreeMap::new();
+    let mut clients: BTreeMap<&str, u64> = BTreeMap::new();
+    for (s, c, n) in server_client_pairs {
+        *servers.entry(s.as_str()).or_default().entry(c.as_str()).or_insert(0) += n;
+        *clients.entry(c.as_str()).or_insert(0) += n;
+    }
+    servers.into_iter().map(|(s, own)| {
+        let n_server = own.values().sum::<u64>() as f64 + COLLUSION_PRIOR_SERVES;
+        let score = own.iter().map(|(c, &n)| {
+            let n = n as f64;
+            n * n / (n_server * (clients[c] as f64 + COLLUSION_PRIOR_SERVES))
+        }).fold(0.0, f64::max);
+        (s.to_string(), score)
+    }).collect()
+}
+
+/// How `BudgetGate` rations the epoch budget
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(tag = "policy", rename_all = "snake_case"))]
+pub enum BudgetPolicy {
+    /// Full rewards until the budget runs out; the serve that crosses it gets the remainder, later ones nothing
+    FirstCome,
+    /// Every reward scaled by `min(1, budget / expected_demand)` (e.g. last epoch's total), still clipped to what is
+    /// left, so the budget stretches over the whole epoch
+    ProRata { expected_demand: f64 },
+}
+
+/// Outcome of one `BudgetGate::admit`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Admission {
+    pub requested_units: u64,
+    /// Units actually paid (<= requested)
+    pub units: u64,
+    /// `units * unit`
+    pub amount: f64,
+}
+
+impl Admission {
+    pub fn rejected(&self) -> bool { self.units == 0 && self.requested_units > 0 }
+}
+
+/// Running epoch payout total; `admit` never pays past `budget_units` (a gate loaded already over budget has none left)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BudgetGate {
+    pub policy: BudgetPolicy,
+    pub unit: f64,
+    pub budget_units: u64,
+    pub paid_units: u64,
+}
+
+impl BudgetGate {
+    /// Gate for an epoch budget in SOCIAL, counted in `params.reward.epoch.unit`
+    pub fn new(budget: f64, policy: BudgetPolicy, params: &Params) -> Self {
+        let unit = params.reward.epoch.unit.max(1e-12);
+        BudgetGate { policy, unit, budget_units: Amount::from_social(budget, unit, Rounding::Down).units(), paid_units: 0 }
+    }
+
+    pub fn remaining_units(&self) -> u64 { self.budget_units.saturating_sub(self.paid_units) }
+
+    /// Admit one serve reward (SOCIAL), charging what is paid against the budget
+    pub fn admit(&mut self, reward: f64) -> Admission {
+        let requested_units = Amount::from_social(reward, self.unit, Rounding::Down).units();
+        let scaled = match self.policy {
+            BudgetPolicy::FirstCome => requested_units,
+            BudgetPolicy::ProRata { expected_demand } => {
+                let scale = if expected_demand > 0.0 { (self.budget_units as f64 * self.unit / expected_demand).min(1.0) } else { 1.0 };
+                (requested_units as f64 * scale).floor() as u64
+            }
+        };
+        let units = scaled.min(self.remaining_units());
+        self.paid_units = self.paid_units.saturating_add(units);
+        Admission { requested_units, units, amount: units as f64 * self.unit }
+    }
+
+    /// `calculate_serve_reward` for `input`, admitted
+    pub fn admit_serve(&mut self, input: &RewardInput, params: &Params) -> Admission {
+        self.admit(calculate_serve_reward(input, params))
+    }
+
+    /// Start the next epoch with `budget` (unpaid units are not carried over)
+    pub fn next_epoch(&mut self, budget: f64) {
+        self.budget_units = Amount::from_social(budget, self.unit, Rounding::Down).units();
+        self.paid_units = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repetition_factor() {
+        assert_eq!(repetition_factor(0, 10), 1.0);
+        assert_eq!(repetition_factor(10, 10), 0.5);
+        assert_eq!(repetition_factor(20, 10), 0.25);
+        assert_eq!(repetition_factor(500, 0), 1.0);
+    }
+
+    #[test]
+    fn test_settle_batch_nets_accounts() {
+        let params = Params::default();
+        let input = RewardInput{ ticket_budget:1.0, client_q:0.8, size_bytes:50_000, ttfb_ms:100, ..Default::default() };
+        let rec = |s: &str, c: &str| ServeRecord{ server_id:s.into(), client_id:c.into(), input:input.clone() };
+        let records = vec![rec("s1", "c1"), rec("s1", "c1"), rec("s2", "s1"), rec("s1", "c2")];
+        let report = settle_batch(&records, &params);
+        let mut reversed = records.clone();
+        reversed.reverse();
+        let again = settle_batch(&reversed, &params);
+        assert_eq!(report.total_units, again.total_units);
+
+        let per = report.total_units / 4;
+        assert_eq!(report.pairs.len(), 3);
+        assert_eq!((report.pairs[0].server_id.as_str(), report.pairs[0].client_id.as_str(), report.pairs[0].serves), ("s1", "c1", 2));
+        let s1 = report.accounts.iter().find(|a| a.account_id == "s1").unwrap();
+        assert_eq!((s1.credited, s1.debited, s1.net), (3 * per, per, 2 * per as i128));
+        assert_eq!(report.accounts.iter().map(|a| a.net).sum::<i128>(), 0);
+    }
+
+    #[test]
+    fn test_split_serve_reward() {
+        let params = Params::default();
+        let input = RewardInput{ ticket_budget:1.5, client_q:0.8, size_bytes:300_000, ttfb_ms:120, server_cluster_risk:0.2, ..Default::default() };
+        let parts = vec![
+            ChunkContribution{ server_id:"a".into(), bytes:100_000, ttfb_ms:100 },
+            ChunkContribution{ server_id:"b".into(), bytes:100_000, ttfb_ms:900 },
+            ChunkContribution{ server_id:"c".into(), bytes:100_000, ttfb_ms:100 },
+        ];
+        let total = Amount::from_social(calculate_serve_reward(&input, &params), params.reward.epoch.unit, Rounding::Down);
+        let shares = split_serve_reward(&input, &parts, &params);
+        assert_eq!((shares.parts.iter().copied().sum::<Amount>(), shares.dust), (total, Amount::ZERO));
+        assert!(shares.parts[0] > shares.parts[1]);
+        assert!(shares.parts[0].units().abs_diff(shares.parts[2].units()) <= 1);
+
+        let none = vec![ChunkContribution{ server_id:"a".into(), bytes:0, ttfb_ms:0 }];
+        let idle = split_serve_reward(&input, &none, &params);
+        assert_eq!((idle.parts, idle.dust), (vec![Amount::ZERO], total));
+    }
+
+    #[test]
+    fn test_collusion_score() {
+        let mut pairs: Vec<(ServerId, ClientId, u64)> = Vec::new();
+        for s in 0..10 {
+            for c in 0..50 { pairs.push((format!("s{}", s), format!("c{}", c), 4)); }
+        }
+        // a wash pair: one server hammering a client that only it serves
+        pairs.push(("wash".into(), "puppet".into(), 400));
+        pairs.push(("wash".into(), "c0".into(), 4));
+        let scores = collusion_score(&pairs);
+        assert_eq!(scores.len(), 11);
+        let score = |id: &str| scores.iter().find(|(s, _)| s == id).unwrap().1;
+        assert!(score("s0") < 0.01);
+        assert!(score("wash") > 0.9);
+        assert!(collusion_score(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_budget_gate_bounds_issuance() {
+        let params = Params::default();
+        let mut first = BudgetGate::new(10.0, BudgetPolicy::FirstCome, &params);
+        let paid: Vec<Admission> = (0..8).map(|_| first.admit(3.0)).collect();
+        assert_eq!(paid[0].units, 3_000_000);
+        assert_eq!(paid[3].units, 1_000_000);
+        assert!(paid[4].rejected());
+        assert_eq!(first.paid_units, first.budget_units);
+
+        // demand of 20 against a budget of 10: everyone gets half
+        let mut pro = BudgetGate::new(10.0, BudgetPolicy::ProRata { expected_demand: 20.0 }, &params);
+        let paid: Vec<Admission> = (0..8).map(|_| pro.admit(2.5)).collect();
+        assert!(paid.iter().all(|a| a.units == 1_250_000));
+        assert_eq!(pro.remaining_units(), 0);
+        assert!(pro.admit(2.5).rejected());
+
+        pro.next_epoch(1.0);
+        assert_eq!(pro.admit(f64::NAN).units, 0);
+        let total: u64 = (0..100).map(|i| pro.admit(i as f64 * 0.37).units).sum();
+        assert!(total <= pro.budget_units);
+        // a deserialized gate past its budget pays nothing instead of underflowing
+        let mut over = BudgetGate { paid_units: pro.budget_units + 1, ..pro.clone() };
+        assert_eq!(over.remaining_units(), 0);
+        assert!(over.admit(1.0).rejected());
+    }
+}
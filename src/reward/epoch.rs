@@ -0,0 +1,137 @@
+// Epoch reward pool distribution
+// - Pool is split proportionally to each server's summed serve rewards
+// - Per-server cap with water-filling redistribution of the excess
+// - Integer smallest-unit accounting (`money::apportion`): payouts always sum to <= budget
+// - `distribute_scheduled` takes the budget from the emission schedule (`emission::epoch_budget`)
+
+use super::{ServeRecord, ServerId};
+use crate::emission::{epoch_budget, EmissionParams};
+use crate::money::{apportion, Amount, DustPolicy, Rounding};
+use crate::{calculate_serve_reward, Params};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Payout for one server
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ServerPayout {
+    pub server_id: ServerId,
+    /// Summed per-serve reward scores
+    pub score: f64,
+    /// Payout in smallest units
+    pub units: u64,
+    /// Payout as `units * unit`
+    pub amount: f64,
+    /// True when the per-server cap bound the payout
+    pub capped: bool,
+}
+
+/// Epoch distribution result (payouts ordered by server id)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EpochDistribution {
+    pub payouts: Vec<ServerPayout>,
+    /// Budget in smallest units
+    pub budget_units: u64,
+    /// Undistributed units (caps bound or no scores); carry over to the next epoch
+    pub dust_units: u64,
+}
+
+/// Distribute an epoch budget over servers proportionally to their summed serve scores
+pub fn distribute(budget: f64, records: &[ServeRecord], params: &Params) -> EpochDistribution {
+    let ep = &params.reward.epoch;
+    let unit = ep.unit.max(1e-12);
+    let budget_units = Amount::from_social(budget, unit, Rounding::Down).units();
+    let cap_units = (budget_units as f64 * crate::clamp(ep.max_server_share, 0.0, 1.0)).floor() as u64;
+
+    let mut scores: BTreeMap<&str, f64> = BTreeMap::new();
+    for r in records {
+        *scores.entry(r.server_id.as_str()).or_insert(0.0) += calculate_serve_reward(&r.input, params);
+    }
+    let ids: Vec<&str> = scores.keys().copied().collect();
+    let score: Vec<f64> = ids.iter().map(|id| scores[id]).collect();
+
+    // water-filling: pin servers whose proportional share exceeds the cap, redistribute the rest
+    let mut capped = vec![false; ids.len()];
+    let mut shares = vec![0.0; ids.len()];
+    loop {
+        let pinned = capped.iter().filter(|&&c| c).count() as f64 * cap_units as f64;
+        let free_budget = (budget_units as f64 - pinned).max(0.0);
+        let free_score: f64 = score.iter().zip(&capped).filter(|(_, &c)| !c).map(|(s, _)| s).sum();
+        let mut changed = false;
+        for i in 0..ids.len() {
+            if capped[i] { shares[i] = cap_units as f64; continue; }
+            shares[i] = if free_score > 0.0 { free_budget * score[i] / free_score } else { 0.0 };
+            if shares[i] > cap_units as f64 {
+                capped[i] = true;
+                changed = true;
+            }
+        }
+        if !changed { break; }
+    }
+
+    // ties in the remainder go by server id
+    let split = apportion(Amount(budget_units), &shares, Some(Amount(cap_units)), DustPolicy::LargestRemainder);
+
+    let payouts = ids.iter().zip(&split.parts).enumerate().map(|(i, (id, units))| ServerPayout {
+        server_id: id.to_string(),
+        score: score[i],
+        units: units.units(),
+        amount: units.to_social(unit),
+        capped: capped[i],
+    }).collect();
+    EpochDistribution { payouts, budget_units, dust_units: split.dust.units() }
+}
+
+/// `distribute` the scheduled budget of epoch `epoch_index`
+pub fn distribute_scheduled(epoch_index: u64, emission: &EmissionParams, records: &[ServeRecord], params: &Params) -> EpochDistribution {
+    distribute(epoch_budget(epoch_index, emission), records, params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RewardInput;
+
+    fn rec(server: &str, size: u64) -> ServeRecord {
+        ServeRecord {
+            server_id: server.into(),
+            client_id: "c".into(),
+            input: RewardInput{ ticket_budget:10.0, client_q:0.9, size_bytes:size, ttfb_ms:100, server_cluster_risk:0.0, ..Default::default() },
+        }
+    }
+
+    #[test]
+    fn test_distribute_exact_sum() {
+        let mut params = Params::default();
+        params.reward.epoch.max_server_share = 1.0;
+        let records: Vec<ServeRecord> = (0..7).map(|i| rec(&format!("s{}", i % 3), 10_000 + i * 777)).collect();
+        let d = distribute(100.0, &records, &params);
+        let total: u64 = d.payouts.iter().map(|p| p.units).sum();
+        assert_eq!(total + d.dust_units, d.budget_units);
+        assert_eq!(d.dust_units, 0);
+        assert_eq!(d.payouts.len(), 3);
+        assert_eq!(d.payouts[0].server_id, "s0");
+
+        let emission = EmissionParams { initial_budget: 100.0, ..Default::default() };
+        assert_eq!(distribute_scheduled(0, &emission, &records, &params).budget_units, d.budget_units);
+    }
+
+    #[test]
+    fn test_distribute_cap_and_dust() {
+        let mut params = Params::default();
+        params.reward.epoch.max_server_share = 0.4;
+        let mut records = vec![rec("big", 900_000); 20];
+        records.push(rec("small", 1_000));
+        let d = distribute(10.0, &records, &params);
+        let big = &d.payouts[0];
+        assert!(big.capped);
+        assert_eq!(big.units, d.budget_units * 2 / 5);
+        // small is capped too after redistribution, the remainder is dust
+        assert_eq!(d.payouts[1].units, d.budget_units * 2 / 5);
+        assert_eq!(d.dust_units, d.budget_units / 5);
+    }
+}
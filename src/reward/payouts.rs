@@ -0,0 +1,136 @@
+// Creator payout pool distribution (the engagement-side mirror of `epoch::distribute`)
+// - Each engagement counts `weight * user_q^q_exp`; engagements from users below `min_user_q` count nothing
+// - Audience diversity: a user's n engagements with the same creator count `n^repeat_exp` times their mean weight,
+//   so one account (or a farm of low-quality ones) hammering a creator adds little next to many distinct users
+// - Integer smallest-unit accounting (`money::apportion`, largest remainder): payouts sum exactly to the pool whenever
+//   any creator scores; otherwise the whole pool is dust
+
+use crate::money::{apportion, Amount, DustPolicy, Rounding};
+use crate::{clamp, Params};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PayoutParams {
+    /// Engaging users below this quality are ignored
+    pub min_user_q: f64,
+    pub q_exp: f64,
+    /// Exponent on repeat engagements from one user with one creator (1 = no damping)
+    pub repeat_exp: f64,
+    /// Smallest payable amount
+    pub unit: f64,
+}
+
+impl Default for PayoutParams {
+    fn default() -> Self { PayoutParams { min_user_q: 0.3, q_exp: 1.0, repeat_exp: 0.5, unit: 1e-6 } }
+}
+
+impl PayoutParams {
+    /// Defaults with the smallest unit taken from the epoch pool params
+    pub fn for_params(params: &Params) -> Self { PayoutParams { unit: params.reward.epoch.unit, ..Default::default() } }
+}
+
+/// One engagement (like, reply, reshare, ...) by a user with a creator's content
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Engagement {
+    pub creator_id: String,
+    pub user_id: String,
+    /// Quality of the engaging user (0..1)
+    pub user_q: f64,
+    /// Weight of the engagement kind (e.g. 1 for a like, more for a reshare)
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CreatorPayout {
+    pub creator_id: String,
+    pub score: f64,
+    /// Payout in smallest units
+    pub units: u64,
+    /// Payout as `units * unit`
+    pub amount: f64,
+}
+
+/// Distribution result (payouts ordered by creator id)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PayoutDistribution {
+    pub payouts: Vec<CreatorPayout>,
+    /// Pool in smallest units
+    pub pool_units: u64,
+    /// Undistributed units (no creator scored); carry over to the next pool
+    pub dust_units: u64,
+}
+
+/// Split `pool` over creators proportionally to their quality- and diversity-weighted engagement
+pub fn distribute(pool: f64, engagements: &[Engagement], params: &PayoutParams) -> PayoutDistribution {
+    let unit = params.unit.max(1e-12);
+    let pool_units = Amount::from_social(pool, unit, Rounding::Down).units();
+
+    // per (creator, user): engagement count and summed weight
+    let mut pairs: BTreeMap<(&str, &str), (u32, f64)> = BTreeMap::new();
+    for e in engagements {
+        let q = if e.user_q.is_finite() { clamp(e.user_q, 0.0, 1.0) } else { 0.0 };
+        let w = if q >= params.min_user_q && e.weight.is_finite() { e.weight.max(0.0) * q.powf(params.q_exp) } else { 0.0 };
+        let p = pairs.entry((e.creator_id.as_str(), e.user_id.as_str())).or_insert((0, 0.0));
+        p.0 += 1;
+        p.1 += w;
+    }
+    let mut scores: BTreeMap<&str, f64> = BTreeMap::new();
+    for ((creator, _), (n, w)) in pairs {
+        *scores.entry(creator).or_insert(0.0) += w / n as f64 * (n as f64).powf(params.repeat_exp);
+    }
+    let ids: Vec<&str> = scores.keys().copied().collect();
+    let score: Vec<f64> = ids.iter().map(|id| scores[id]).collect();
+    let total: f64 = score.iter().sum();
+
+    // ties in the remainder go by creator id
+    let shares: Vec<f64> = score.iter().map(|s| if total > 0.0 { pool_units as f64 * s / total } else { 0.0 }).collect();
+    let split = apportion(Amount(pool_units), &shares, None, DustPolicy::LargestRemainder);
+
+    let payouts = ids.iter().zip(&split.parts).enumerate().map(|(i, (id, units))| CreatorPayout {
+        creator_id: id.to_string(),
+        score: score[i],
+        units: units.units(),
+        amount: units.to_social(unit),
+    }).collect();
+    PayoutDistribution { payouts, pool_units, dust_units: split.dust.units() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eng(creator: &str, user: &str, user_q: f64) -> Engagement {
+        Engagement { creator_id: creator.into(), user_id: user.into(), user_q, weight: 1.0 }
+    }
+
+    #[test]
+    fn t_distribute_weights_quality_and_diversity() {
+        let p = PayoutParams::default();
+        let mut e: Vec<Engagement> = (0..4).map(|i| eng("alice", &format!("u{}", i), 0.8)).collect();
+        // one high-quality account engaging 16 times, and a farm of low-quality accounts
+        e.extend((0..16).map(|_| eng("bob", "fan", 0.8)));
+        e.extend((0..50).map(|i| eng("carol", &format!("bot{}", i), 0.1)));
+        e.push(eng("carol", "u0", 0.8));
+
+        let d = distribute(1.0, &e, &p);
+        assert_eq!(d.payouts.iter().map(|x| x.units).sum::<u64>(), d.pool_units);
+        assert_eq!(d.dust_units, 0);
+        let (alice, bob, carol) = (&d.payouts[0], &d.payouts[1], &d.payouts[2]);
+        assert!((alice.score - 3.2).abs() < 1e-12 && (bob.score - 3.2).abs() < 1e-12);
+        assert!((carol.score - 0.8).abs() < 1e-12);
+        assert!(alice.units.abs_diff(bob.units) <= 1 && carol.units < alice.units / 3);
+
+        let idle = distribute(1.0, &[eng("dave", "bot", 0.0)], &p);
+        assert_eq!((idle.payouts[0].units, idle.dust_units), (0, idle.pool_units));
+    }
+}
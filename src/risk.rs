@@ -0,0 +1,95 @@
+// Risk signals derived from recent activity, for filling `RiskSignals` before pricing
+// - `duplication_score`: how many recent posts (typically the last hour, across authors) are near-duplicates of this
+//   one by content fingerprint; copy-paste campaigns push it to 1
+// - `campaign_score`: over a window of posts (e.g., one topic's last hour), near-duplicate share x (timing tightness +
+//   author spread) / 2, so many accounts posting the same text in quick succession score near 1 while one author
+//   repeating itself, or unrelated posts, stay low; it is meant for `RiskSignals::coordination` and `burst`
+// - Pure functions of their inputs, so nodes that saw the same recent posts agree on the signal
+
+use crate::fingerprint::Fingerprint;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Fingerprints at most this many bits apart count as near-duplicates
+pub const NEAR_DUPLICATE_BITS: u32 = 3;
+
+/// Near-duplicates at which `duplication_score` reaches 1
+pub const DUPLICATION_SATURATION: usize = 5;
+
+/// Consecutive posts at most this many seconds apart count as a burst
+pub const CAMPAIGN_BURST_SECS: u64 = 120;
+
+/// One post as seen by the campaign detector
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PostMeta {
+    pub author: String,
+    /// Unix seconds
+    pub timestamp: u64,
+    pub fingerprint: Fingerprint,
+}
+
+/// `RiskSignals::duplication` (0..1) for a post against recent fingerprints; empty texts (fingerprint 0) score 0
+pub fn duplication_score(fingerprint: Fingerprint, recent_fingerprints: &[Fingerprint]) -> f64 {
+    if fingerprint == Fingerprint::default() { return 0.0; }
+    let near = recent_fingerprints.iter().filter(|&&f| f != Fingerprint::default() && fingerprint.distance(f) <= NEAR_DUPLICATE_BITS).count();
+    (near as f64 / DUPLICATION_SATURATION as f64).min(1.0)
+}
+
+/// Campaign signal (0..1) for a window of posts; fewer than two posts score 0
+pub fn campaign_score(posts: &[PostMeta]) -> f64 {
+    let n = posts.len();
+    if n < 2 { return 0.0; }
+    let near = |a: &PostMeta, b: &PostMeta| {
+        a.fingerprint != Fingerprint::default() && a.fingerprint.distance(b.fingerprint) <= NEAR_DUPLICATE_BITS
+    };
+    let similar = (0..n).filter(|&i| (0..n).any(|j| j != i && near(&posts[i], &posts[j]))).count() as f64 / n as f64;
+
+    let mut times: Vec<u64> = posts.iter().map(|p| p.timestamp).collect();
+    times.sort_unstable();
+    let timing = times.windows(2).filter(|w| w[1] - w[0] <= CAMPAIGN_BURST_SECS).count() as f64 / (n - 1) as f64;
+
+    let authors = posts.iter().map(|p| p.author.as_str()).collect::<BTreeSet<_>>().len();
+    let spread = (authors - 1) as f64 / (n - 1) as f64;
+
+    similar * (timing + spread) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fingerprint::fingerprint;
+    use crate::{calculate_risk, RiskSignals, RiskWeights};
+
+    #[test]
+    fn t_copy_paste_campaign_raises_risk() {
+        let spam = fingerprint("Claim your free tokens now at the official airdrop page, limited time only");
+        let recent: Vec<Fingerprint> = (0..8).map(|i| fingerprint(&format!("Claim your free tokens now at the official airdrop page, limited time only {}", "!".repeat(i)))).collect();
+        assert_eq!(duplication_score(spam, &recent), 1.0);
+        assert_eq!(duplication_score(spam, &recent[..2]), 0.4);
+        assert_eq!(duplication_score(fingerprint("a quiet walk by the river"), &recent), 0.0);
+        assert_eq!(duplication_score(Fingerprint::default(), &[Fingerprint::default()]), 0.0);
+
+        let clean = calculate_risk(&Some(RiskSignals::default()), &RiskWeights::DEFAULT);
+        let dup = calculate_risk(&Some(RiskSignals { duplication: Some(duplication_score(spam, &recent)), ..Default::default() }), &RiskWeights::DEFAULT);
+        assert!(dup > clean);
+    }
+
+    #[test]
+    fn t_campaign_needs_similarity_timing_and_authors() {
+        let fp = fingerprint("Vote early: polling stations in the north close at noon tomorrow, pass it on");
+        let post = |author: usize, timestamp: u64, fingerprint: Fingerprint| PostMeta { author: format!("u{}", author), timestamp, fingerprint };
+        let ring: Vec<PostMeta> = (0..10).map(|i| post(i, 1_000 + 30 * i as u64, fp)).collect();
+        assert_eq!(campaign_score(&ring), 1.0);
+
+        let one_author: Vec<PostMeta> = (0..10).map(|i| post(0, 1_000 + 30 * i as u64, fp)).collect();
+        assert_eq!(campaign_score(&one_author), 0.5);
+        let slow: Vec<PostMeta> = (0..10).map(|i| post(i, 1_000 + 3_600 * i as u64, fp)).collect();
+        assert_eq!(campaign_score(&slow), 0.5);
+        let organic: Vec<PostMeta> = (0..10).map(|i| post(i, 1_000 + 30 * i as u64, fingerprint(&format!("post number {} about something else entirely", i)))).collect();
+        assert!(campaign_score(&organic) < 0.2);
+        assert_eq!(campaign_score(&ring[..1]), 0.0);
+    }
+}
@@ -0,0 +1,206 @@
+// Shadow-mode dual evaluation
+// - `Evaluator` runs every call under the active and a candidate `Params` and returns the active result only
+// - Differences above `tolerance` are logged as `ShadowDelta`s (bounded by `max_log`; older entries are kept, newer
+//   ones only counted) and every call feeds a per-kind `ShadowSummary`
+// - Read-only trial of a parameter change: callers switch with `promote` once the deltas look right
+
+use crate::{
+    adjust_propagation_for_content, calculate_availability_reward, calculate_ef, calculate_post_cost, calculate_quality,
+    calculate_serve_reward, update_base_cost, Actor, Content, Params, PreparedParams, PropagationResult, QInputs, RewardInput,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Which result a delta belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ShadowKind { Quality, Ef, Cost, Ttl, Fanout, Reward, AvailabilityReward, BaseFare }
+
+/// One diverging result
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ShadowDelta {
+    pub kind: ShadowKind,
+    /// Call sequence number (1-based, counted over all calls)
+    pub seq: u64,
+    pub active: f64,
+    pub candidate: f64,
+    /// `candidate - active`
+    pub delta: f64,
+}
+
+/// Per-kind aggregate
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct KindSummary {
+    pub evaluations: u64,
+    /// Evaluations whose |delta| exceeded the tolerance
+    pub diverged: u64,
+    pub sum_delta: f64,
+    pub max_abs_delta: f64,
+}
+
+/// Aggregates since construction (or the last `reset`)
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ShadowSummary {
+    pub kinds: BTreeMap<ShadowKind, KindSummary>,
+    /// Deltas not logged because the log was full
+    pub dropped: u64,
+}
+
+/// Active/candidate evaluator
+#[derive(Debug, Clone)]
+pub struct Evaluator {
+    active: PreparedParams,
+    candidate: PreparedParams,
+    /// Absolute difference treated as equal (0: any difference is logged)
+    pub tolerance: f64,
+    /// Maximum logged deltas between `drain` calls
+    pub max_log: usize,
+    seq: u64,
+    log: Vec<ShadowDelta>,
+    summary: ShadowSummary,
+}
+
+impl Evaluator {
+    pub fn new(active: Params, candidate: Params) -> Self {
+        Evaluator {
+            active: active.into(), candidate: candidate.into(), tolerance: 0.0, max_log: 10_000,
+            seq: 0, log: Vec::new(), summary: ShadowSummary::default(),
+        }
+    }
+
+    pub fn active(&self) -> &Params { &self.active }
+
+    pub fn candidate(&self) -> &Params { &self.candidate }
+
+    /// Record one pair of results and return the active one
+    fn record(&mut self, kind: ShadowKind, active: f64, candidate: f64) -> f64 {
+        let delta = candidate - active;
+        let s = self.summary.kinds.entry(kind).or_default();
+        s.evaluations += 1;
+        // NaN on either side counts as a divergence unless both are NaN
+        let diverged = if delta.is_nan() { active.is_nan() != candidate.is_nan() } else { delta.abs() > self.tolerance };
+        if diverged {
+            s.diverged += 1;
+            if delta.is_finite() {
+                s.sum_delta += delta;
+                s.max_abs_delta = s.max_abs_delta.max(delta.abs());
+            }
+            if self.log.len() < self.max_log {
+                self.log.push(ShadowDelta { kind, seq: self.seq, active, candidate, delta });
+            } else {
+                self.summary.dropped += 1;
+            }
+        }
+        active
+    }
+
+    /// Start a new call (the `seq` its deltas carry)
+    fn next(&mut self) { self.seq += 1; }
+
+    pub fn quality(&mut self, inp: QInputs) -> f64 {
+        self.next();
+        let (a, c) = (calculate_quality(inp.clone(), &self.active), calculate_quality(inp, &self.candidate));
+        self.record(ShadowKind::Quality, a, c)
+    }
+
+    pub fn ef(&mut self, followers_q: &[f64]) -> f64 {
+        self.next();
+        let (a, c) = (calculate_ef(followers_q, &self.active), calculate_ef(followers_q, &self.candidate));
+        self.record(ShadowKind::Ef, a, c)
+    }
+
+    pub fn post_cost(&mut self, actor: &Actor, content: &Content, base_fare: f64) -> f64 {
+        self.next();
+        let (a, c) = (calculate_post_cost(actor, content, &self.active, base_fare), calculate_post_cost(actor, content, &self.candidate, base_fare));
+        self.record(ShadowKind::Cost, a, c)
+    }
+
+    pub fn propagation(&mut self, content: &Content) -> PropagationResult {
+        self.next();
+        let (a, c) = (adjust_propagation_for_content(content, &self.active), adjust_propagation_for_content(content, &self.candidate));
+        self.record(ShadowKind::Ttl, a.ttl as f64, c.ttl as f64);
+        self.record(ShadowKind::Fanout, a.fanout as f64, c.fanout as f64);
+        a
+    }
+
+    pub fn serve_reward(&mut self, input: &RewardInput) -> f64 {
+        self.next();
+        let (a, c) = (calculate_serve_reward(input, &self.active), calculate_serve_reward(input, &self.candidate));
+        self.record(ShadowKind::Reward, a, c)
+    }
+
+    pub fn availability_reward(&mut self, uptime_ratio: f64, probes_passed: u32, stake: f64) -> f64 {
+        self.next();
+        let a = calculate_availability_reward(uptime_ratio, probes_passed, stake, &self.active);
+        let c = calculate_availability_reward(uptime_ratio, probes_passed, stake, &self.candidate);
+        self.record(ShadowKind::AvailabilityReward, a, c)
+    }
+
+    /// Base fare step from the same current fare (both sides share the active fare trajectory)
+    pub fn base_fare(&mut self, current_base: f64, current_load: f64) -> f64 {
+        self.next();
+        let (a, c) = (update_base_cost(current_base, current_load, &self.active), update_base_cost(current_base, current_load, &self.candidate));
+        self.record(ShadowKind::BaseFare, a, c)
+    }
+
+    /// Take the logged deltas (frees the log for new entries)
+    pub fn drain(&mut self) -> Vec<ShadowDelta> { std::mem::take(&mut self.log) }
+
+    pub fn summary(&self) -> &ShadowSummary { &self.summary }
+
+    /// Clear the log and the aggregates
+    pub fn reset(&mut self) {
+        self.log.clear();
+        self.summary = ShadowSummary::default();
+    }
+
+    /// Make the candidate active (and keep shadowing it against itself until a new candidate is set)
+    pub fn promote(&mut self) {
+        self.active = self.candidate.clone();
+        self.reset();
+    }
+
+    /// Start trialling a new candidate
+    pub fn set_candidate(&mut self, candidate: Params) {
+        self.candidate = candidate.into();
+        self.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_returns_active_and_logs_deltas() {
+        let mut candidate = Params::default();
+        candidate.cost.a = 2.0;
+        let mut ev = Evaluator::new(Params::default(), candidate.clone());
+        let actor = Actor { rl: 10.0, q: 0.8, ef: 5.0, posts_1h: None, ..Default::default() };
+        let cost = ev.post_cost(&actor, &Content::default(), 1.0);
+        assert_eq!(cost, calculate_post_cost(&actor, &Content::default(), &Params::default(), 1.0));
+        ev.propagation(&Content::default());
+
+        let s = ev.summary();
+        assert_eq!((s.kinds[&ShadowKind::Cost].diverged, s.kinds[&ShadowKind::Ttl].diverged), (1, 0));
+        let log = ev.drain();
+        assert_eq!((log.len(), log[0].seq), (1, 1));
+        assert_eq!(log[0].candidate, calculate_post_cost(&actor, &Content::default(), &candidate, 1.0));
+
+        ev.max_log = 0;
+        ev.post_cost(&actor, &Content::default(), 1.0);
+        assert_eq!(ev.summary().dropped, 1);
+        ev.promote();
+        assert_eq!(ev.post_cost(&actor, &Content::default(), 1.0), log[0].candidate);
+        assert!(ev.drain().is_empty());
+    }
+}
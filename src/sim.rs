@@ -0,0 +1,192 @@
+// Multi-epoch scenario simulation
+// - Deterministic: a scenario is a fixed population of actor cohorts plus a load curve
+// - Base fares follow `congestion::simulate`; every post of a cohort pays the same cost within an epoch
+// - `demand` generates seeded load series (Poisson arrivals, diurnal cycle, bursts); a scenario's `demand` replaces
+//   its hand-written `load_curve`
+// - `agents` is the agent-based variant (sampled population, demand response, propagation and serving)
+// - `attacks` prices canned adversarial scenarios (sybil farm, burst spam, amplification ring, wash serving)
+// - `scenario` is the versioned, validated file format wrapping either variant plus an attack mix and a params
+//   reference (feature `serde`)
+// - `sweep` scores grids or Latin-hypercube samples of parameter space on the agent-based variant (feature `serde`)
+
+use crate::congestion;
+use crate::{calculate_post_cost, calculate_serve_reward, Actor, Content, Params, RewardInput};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub mod agents;
+pub mod attacks;
+pub mod demand;
+#[cfg(feature = "serde")]
+pub mod scenario;
+#[cfg(feature = "serde")]
+pub mod sweep;
+
+#[cfg(feature = "serde")]
+pub use sweep::sweep;
+
+/// Group of identical actors
+/// - `posts_per_epoch`: posts by each member per epoch (rounded to whole posts per cohort)
+/// - `serves_per_post`/`serve`: retrievals rewarded per post (no reward when `serve` is None)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Cohort {
+    pub name: String,
+    pub count: u32,
+    pub actor: Actor,
+    pub posts_per_epoch: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub content: Content,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub serves_per_post: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub serve: Option<RewardInput>,
+}
+
+/// Simulation scenario; `load_curve` is cycled when shorter than `epochs`, and ignored when `demand` is set
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Scenario {
+    pub cohorts: Vec<Cohort>,
+    pub load_curve: Vec<f64>,
+    pub epochs: u32,
+    #[cfg_attr(feature = "serde", serde(default = "default_initial_base"))]
+    pub initial_base: f64,
+    /// Generated load, one step per epoch
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub demand: Option<demand::PoissonBurstModel>,
+}
+
+#[cfg(feature = "serde")]
+fn default_initial_base() -> f64 { 1.0 }
+
+/// Post-weighted cost distribution
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CostStats { pub min: f64, pub mean: f64, pub median: f64, pub p90: f64, pub max: f64 }
+
+/// Results of one epoch
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EpochReport {
+    pub epoch: u32,
+    pub load: f64,
+    pub base_fare: f64,
+    pub posts: u64,
+    pub cost: CostStats,
+    pub total_cost: f64,
+    pub reward_total: f64,
+}
+
+/// Per-epoch `(load, base fare)` of a scenario under `params`
+pub(crate) fn loads_and_fares(scenario: &Scenario, params: &Params) -> (Vec<f64>, Vec<f64>) {
+    let n = scenario.epochs as usize;
+    let loads: Vec<f64> = if let Some(d) = &scenario.demand { d.posts(n) }
+        else if scenario.load_curve.is_empty() { vec![0.0; n] }
+        else { scenario.load_curve.iter().copied().cycle().take(n).collect() };
+    let bases = congestion::simulate(&loads, scenario.initial_base, params);
+    (loads, bases)
+}
+
+/// Run `scenario.epochs` epochs under `params`
+pub fn run(scenario: &Scenario, params: &Params) -> Vec<EpochReport> {
+    let (loads, bases) = loads_and_fares(scenario, params);
+
+    let rewards: Vec<f64> = scenario.cohorts.iter()
+        .map(|c| c.serve.as_ref().map_or(0.0, |s| calculate_serve_reward(s, params)))
+        .collect();
+
+    loads.iter().zip(&bases).enumerate().map(|(e, (&load, &base_fare))| {
+        // (cost per post, posts)
+        let mut samples: Vec<(f64, u64)> = Vec::with_capacity(scenario.cohorts.len());
+        let mut reward_total = 0.0;
+        for (c, r) in scenario.cohorts.iter().zip(&rewards) {
+            let posts = (c.count as f64 * c.posts_per_epoch.max(0.0)).round() as u64;
+            if posts == 0 { continue; }
+            samples.push((calculate_post_cost(&c.actor, &c.content, params, base_fare), posts));
+            // f64 and saturating sums: post counts are only bounded by u64 (`as` saturates huge rates)
+            reward_total += r * posts as f64 * c.serves_per_post as f64;
+        }
+        let posts = samples.iter().fold(0u64, |n, s| n.saturating_add(s.1));
+        let total_cost = samples.iter().map(|&(cost, k)| cost * k as f64).sum();
+        EpochReport { epoch: e as u32, load, base_fare, posts, cost: cost_stats(&mut samples), total_cost, reward_total }
+    }).collect()
+}
+
+/// Weighted summary statistics over `(value, weight)` samples
+fn cost_stats(samples: &mut [(f64, u64)]) -> CostStats {
+    let total = samples.iter().fold(0u64, |n, s| n.saturating_add(s.1));
+    if total == 0 { return CostStats::default(); }
+    samples.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let quantile = |p: f64| {
+        let rank = ((p * total as f64).ceil() as u64).max(1);
+        let mut seen = 0u64;
+        for &(v, k) in samples.iter() {
+            seen = seen.saturating_add(k);
+            if seen >= rank { return v; }
+        }
+        samples[samples.len() - 1].0
+    };
+    CostStats {
+        min: samples[0].0,
+        mean: samples.iter().map(|&(v, k)| v * k as f64).sum::<f64>() / total as f64,
+        median: quantile(0.5),
+        p90: quantile(0.9),
+        max: samples[samples.len() - 1].0,
+    }
+}
+
+/// Flatten epoch reports into CSV (header + one row per epoch)
+pub fn to_csv(reports: &[EpochReport]) -> String {
+    let mut out = String::from("epoch,load,base_fare,posts,cost_min,cost_mean,cost_median,cost_p90,cost_max,total_cost,reward_total\n");
+    for r in reports {
+        out.push_str(&format!("{},{},{},{},{},{},{},{},{},{},{}\n",
+            r.epoch, r.load, r.base_fare, r.posts, r.cost.min, r.cost.mean, r.cost.median, r.cost.p90, r.cost.max,
+            r.total_cost, r.reward_total));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scenario() -> Scenario {
+        let cohort = |name: &str, count, rl, posts_per_epoch| Cohort {
+            name: name.into(), count, posts_per_epoch,
+            actor: Actor { rl, q: 0.8, ef: 5.0, posts_1h: None, ..Default::default() },
+            content: Content::default(),
+            serves_per_post: 2,
+            serve: Some(RewardInput { ticket_budget: 1.0, client_q: 0.8, size_bytes: 10_000, ttfb_ms: 100, ..Default::default() }),
+        };
+        Scenario { cohorts: vec![cohort("casual", 90, 1.0, 1.0), cohort("power", 10, 50.0, 5.0)], load_curve: vec![200.0, 1500.0], epochs: 4, initial_base: 1.0, demand: None }
+    }
+
+    #[test]
+    fn t_epochs_follow_load_curve() {
+        let p = Params::default();
+        let reports = run(&scenario(), &p);
+        assert_eq!(reports.len(), 4);
+        assert_eq!(reports[3].load, 1500.0);
+        assert_eq!(reports[0].posts, 140);
+        assert!(reports[1].base_fare > reports[0].base_fare);
+        let c = &reports[0].cost;
+        assert!(c.min <= c.median && c.median <= c.p90 && c.p90 <= c.max);
+        assert!(reports[0].reward_total > 0.0);
+        assert_eq!(to_csv(&reports).lines().count(), 5);
+    }
+
+    #[test]
+    fn t_huge_post_rates_saturate() {
+        let mut s = scenario();
+        for c in &mut s.cohorts { c.posts_per_epoch = 1e30; }
+        let r = &run(&s, &Params::default())[0];
+        assert_eq!(r.posts, u64::MAX);
+        assert!(r.reward_total.is_finite() && r.reward_total > 0.0);
+        assert!(r.cost.min <= r.cost.median && r.cost.p90 <= r.cost.max);
+    }
+}
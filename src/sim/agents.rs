@@ -0,0 +1,293 @@
+// Agent-based network simulation
+// - A synthetic population is drawn once from `Population` distributions (quality, EF, posting rate, risk, budget)
+// - Each epoch every agent posts (stochastically rounded rate, at most `MAX_POSTS_PER_EPOCH`), pays `calculate_post_cost` at the epoch's base fare or
+//   is priced out, propagates with `adjust_propagation`/`reach_budget`, and its impressions are served and rewarded
+// - Post volume feeds the load, and the load rolls the base fare over through `epoch::Epoch::advance`
+// - Deterministic: all draws come from `det_rand::DetRng` keyed by `seed` (same scenario + params => same report)
+
+use crate::det_rand::DetRng;
+use crate::epoch::{Epoch, EpochInputs};
+use crate::por::Hash32;
+use crate::propagation::reach_budget;
+use crate::stats::{StatsReport, StreamStats};
+use crate::{adjust_propagation, calculate_post_cost, calculate_risk, calculate_serve_reward, Actor, Content, Params, RewardInput, RiskSignals, RiskWeights};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Cap on one agent's posts in one epoch (heavy-tailed or unbounded rates would otherwise overflow the counters)
+pub const MAX_POSTS_PER_EPOCH: f64 = 1e9;
+
+/// Sampling distribution for one agent attribute
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Dist {
+    Const { value: f64 },
+    Uniform { lo: f64, hi: f64 },
+    /// `median * e^(sigma * z)`, z standard normal
+    LogNormal { median: f64, sigma: f64 },
+}
+
+impl Dist {
+    fn sample(&self, rng: &mut DetRng) -> f64 {
+        match *self {
+            Dist::Const { value } => value,
+            Dist::Uniform { lo, hi } => lo + (hi - lo) * rng.next_f64(),
+            Dist::LogNormal { median, sigma } => {
+                // Box-Muller; 1 - u keeps the log argument in (0, 1]
+                let (u1, u2) = (1.0 - rng.next_f64(), rng.next_f64());
+                let z = (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos();
+                median * (sigma * z).exp()
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn const_dist(value: f64) -> Dist { Dist::Const { value } }
+#[cfg(feature = "serde")]
+fn default_rl() -> Dist { const_dist(1.0) }
+#[cfg(feature = "serde")]
+fn default_risk() -> Dist { const_dist(0.0) }
+
+/// Population shape
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Population {
+    pub size: u32,
+    /// Actor quality (clamped to 0..1)
+    pub quality: Dist,
+    pub ef: Dist,
+    /// Expected posts per agent per epoch
+    pub posts_per_epoch: Dist,
+    #[cfg_attr(feature = "serde", serde(default = "default_rl"))]
+    pub rl: Dist,
+    /// Coordination signal of the agent's posts (0..1): raises their cost and risk, damps their propagation
+    #[cfg_attr(feature = "serde", serde(default = "default_risk"))]
+    pub risk: Dist,
+    /// Highest cost an agent pays for one post (`None`: always pays)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub willingness_to_pay: Option<Dist>,
+}
+
+/// One synthetic actor
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Agent {
+    pub actor: Actor,
+    pub risk: f64,
+    pub posts_per_epoch: f64,
+    pub willingness_to_pay: Option<f64>,
+}
+
+#[cfg(feature = "serde")]
+fn default_initial_base() -> f64 { 1.0 }
+
+/// Agent-based scenario
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AgentScenario {
+    pub population: Population,
+    pub epochs: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub seed: u64,
+    #[cfg_attr(feature = "serde", serde(default = "default_initial_base"))]
+    pub initial_base: f64,
+    /// Load units added per accepted post
+    pub load_per_post: f64,
+    /// Load not caused by the population (cycled; empty = 0)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub background_load: Vec<f64>,
+    /// Content template (each agent's `coordination` signal overrides its risk signals)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub content: Content,
+    /// Serve template (`client_q` is set to the poster's quality); no rewards when None
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub serve: Option<RewardInput>,
+    /// Expected rewarded serves per impression
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub serves_per_impression: f64,
+}
+
+/// Aggregate metrics of one epoch
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AgentEpochReport {
+    pub epoch: u32,
+    /// Fare the epoch's posts were priced at
+    pub base_fare: f64,
+    /// Load observed during the epoch (drives the next fare)
+    pub load: f64,
+    pub posts_attempted: u64,
+    pub posts: u64,
+    /// Attempts above the agent's willingness to pay
+    pub priced_out: u64,
+    /// Distribution of paid post costs
+    pub cost: StatsReport,
+    pub quarantined_posts: u64,
+    pub impressions: u64,
+    pub serves: u64,
+    pub reward_total: f64,
+}
+
+fn key(seed: u64) -> Hash32 {
+    let mut k = [0u8; 32];
+    k[..8].copy_from_slice(&seed.to_le_bytes());
+    k
+}
+
+/// Draw the scenario's population
+pub fn population(scenario: &AgentScenario) -> Vec<Agent> {
+    let p = &scenario.population;
+    let mut rng = DetRng::new("sim/population", 0, &key(scenario.seed));
+    (0..p.size).map(|_| Agent {
+        actor: Actor {
+            rl: p.rl.sample(&mut rng).max(0.0),
+            q: p.quality.sample(&mut rng).clamp(0.0, 1.0),
+            ef: p.ef.sample(&mut rng).max(0.0),
+            posts_1h: None,
+            ..Default::default()
+        },
+        risk: p.risk.sample(&mut rng).clamp(0.0, 1.0),
+        posts_per_epoch: p.posts_per_epoch.sample(&mut rng).max(0.0),
+        willingness_to_pay: p.willingness_to_pay.as_ref().map(|d| d.sample(&mut rng)),
+    }).collect()
+}
+
+/// Per-agent totals over a whole run (same order as `population`)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AgentTotals {
+    pub posts: u64,
+    pub cost_paid: f64,
+    pub reward: f64,
+}
+
+/// Epoch reports plus per-agent totals
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AgentRun {
+    pub agents: Vec<Agent>,
+    pub totals: Vec<AgentTotals>,
+    pub epochs: Vec<AgentEpochReport>,
+}
+
+/// Run `scenario.epochs` epochs under `params`
+pub fn run(scenario: &AgentScenario, params: &Params) -> Vec<AgentEpochReport> { run_detailed(scenario, params).epochs }
+
+/// `run` keeping the population and what each agent paid and earned
+pub fn run_detailed(scenario: &AgentScenario, params: &Params) -> AgentRun {
+    let agents = population(scenario);
+    let mut totals = vec![AgentTotals::default(); agents.len()];
+    let weights = RiskWeights::DEFAULT;
+    // per-agent constants: content (scenario content with the agent's coordination signal), propagation decision,
+    // risk and per-serve reward
+    let static_: Vec<_> = agents.iter().map(|a| {
+        let signals = Some(RiskSignals { coordination: Some(a.risk), ..scenario.content.risk_signals.clone().unwrap_or_default() });
+        let decision = adjust_propagation(&signals, params);
+        let risk = calculate_risk(&signals, &weights);
+        let reward = scenario.serve.as_ref().map_or(0.0, |s| calculate_serve_reward(&RewardInput { client_q: a.actor.q, ..s.clone() }, params));
+        (Content { risk_signals: signals, ..scenario.content.clone() }, decision, risk, reward)
+    }).collect();
+
+    let mut epoch = Epoch::genesis(params.clone(), scenario.initial_base);
+    let epochs = (0..scenario.epochs).map(|e| {
+        let mut rng = DetRng::new("sim/epoch", e as u64, &key(scenario.seed));
+        let base_fare = epoch.base_fare();
+        let mut costs = StreamStats::default();
+        let (mut attempted, mut posts, mut priced_out, mut quarantined, mut impressions, mut serves) = (0, 0, 0, 0, 0, 0);
+        let mut reward_total = 0.0;
+        for ((a, (content, decision, risk, reward)), t) in agents.iter().zip(&static_).zip(&mut totals) {
+            let k = rng.stochastic_round(a.posts_per_epoch.min(MAX_POSTS_PER_EPOCH));
+            if k == 0 { continue; }
+            attempted += k;
+            let actor = Actor { posts_1h: Some(k as f64), ..a.actor.clone() };
+            let cost = calculate_post_cost(&actor, content, params, base_fare);
+            if a.willingness_to_pay.is_some_and(|w| cost > w) {
+                priced_out += k;
+                continue;
+            }
+            posts += k;
+            costs.push_n(cost, k);
+            t.posts += k;
+            t.cost_paid += cost * k as f64;
+            if decision.quarantined {
+                quarantined += k;
+                continue;
+            }
+            let reach = k.saturating_mul(reach_budget(a.actor.ef, a.actor.q, *risk, params));
+            let s = rng.stochastic_round(reach as f64 * scenario.serves_per_impression.max(0.0));
+            impressions += reach;
+            serves += s;
+            reward_total += reward * s as f64;
+            t.reward += reward * s as f64;
+        }
+        let background = match scenario.background_load.len() {
+            0 => 0.0,
+            n => scenario.background_load[e as usize % n],
+        };
+        let load = background + posts as f64 * scenario.load_per_post;
+        epoch = epoch.advance(EpochInputs { load_samples: vec![load], posts, rewards_paid: reward_total, next_params: None });
+        AgentEpochReport {
+            epoch: e, base_fare, load, posts_attempted: attempted, posts, priced_out, cost: costs.report(),
+            quarantined_posts: quarantined, impressions, serves, reward_total,
+        }
+    }).collect();
+    AgentRun { agents, totals, epochs }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scenario() -> AgentScenario {
+        AgentScenario {
+            population: Population {
+                size: 500,
+                quality: Dist::Uniform { lo: 0.2, hi: 1.0 },
+                ef: Dist::LogNormal { median: 5.0, sigma: 1.0 },
+                posts_per_epoch: Dist::LogNormal { median: 2.0, sigma: 0.8 },
+                rl: Dist::Const { value: 1.0 },
+                risk: Dist::Uniform { lo: 0.0, hi: 0.6 },
+                willingness_to_pay: Some(Dist::LogNormal { median: 3.0, sigma: 0.5 }),
+            },
+            epochs: 12, seed: 42, initial_base: 1.0, load_per_post: 1.5, background_load: vec![200.0],
+            content: Content::default(),
+            serve: Some(RewardInput { ticket_budget: 1.0, size_bytes: 10_000, ttfb_ms: 100, ..Default::default() }),
+            serves_per_impression: 0.1,
+        }
+    }
+
+    #[test]
+    fn t_deterministic_and_demand_responds_to_fare() {
+        let p = Params::default();
+        let s = scenario();
+        let a = run(&s, &p);
+        #[cfg(feature = "serde")]
+        assert_eq!(serde_json::to_string(&a).unwrap(), serde_json::to_string(&run(&s, &p)).unwrap());
+        assert_eq!(a.len(), 12);
+        assert!(a.iter().all(|r| r.posts + r.priced_out == r.posts_attempted && r.cost.count == r.posts));
+        assert!(a[0].serves > 0 && a[0].reward_total > 0.0);
+        let d = run_detailed(&s, &p);
+        assert_eq!(d.totals.iter().map(|t| t.posts).sum::<u64>(), a.iter().map(|r| r.posts).sum::<u64>());
+
+        // a heavier load per post drives the fare up and prices more attempts out
+        let heavy = run(&AgentScenario { load_per_post: 5.0, ..s.clone() }, &p);
+        assert!(heavy[11].base_fare > a[11].base_fare);
+        let out = |r: &[AgentEpochReport]| r.iter().map(|e| e.priced_out).sum::<u64>();
+        assert!(out(&heavy) > out(&a));
+        assert_ne!(run(&AgentScenario { seed: 7, ..s.clone() }, &p)[0].posts_attempted, a[0].posts_attempted);
+
+        // unbounded rates are capped rather than looping or overflowing
+        let flood = Population { size: 3, posts_per_epoch: Dist::Const { value: f64::INFINITY }, willingness_to_pay: None, ..s.population.clone() };
+        let r = run(&AgentScenario { population: flood, epochs: 1, ..s }, &p);
+        assert_eq!((r[0].posts, r[0].cost.count), (3 * MAX_POSTS_PER_EPOCH as u64, 3 * MAX_POSTS_PER_EPOCH as u64));
+    }
+}
@@ -0,0 +1,190 @@
+// Canned adversarial scenarios
+// - Each attack prices what the attacker pays (post costs, tickets) and what it gets back (impressions, SOCIAL rewards)
+//   under a given `Params`, so parameter proposals can be compared against known attacks
+// - `impressions` are reach budgets (`propagation::reach_budget`) of propagated posts; quarantined posts reach nobody
+// - Single-shot and deterministic: no population or congestion dynamics (see `agents` for those)
+
+use crate::propagation::reach_budget;
+use crate::{
+    adjust_propagation, calculate_ef, calculate_post_cost, calculate_risk, calculate_serve_reward, Actor, Content, Params,
+    RewardInput, RiskSignals, RiskWeights,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Outcome of one attack
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AttackReport {
+    pub attack: String,
+    /// SOCIAL spent by the attacker
+    pub cost: f64,
+    /// SOCIAL earned by the attacker
+    pub earned: f64,
+    /// Impressions gained
+    pub impressions: u64,
+    /// `cost / impressions` (None without impressions)
+    pub cost_per_impression: Option<f64>,
+    /// `earned - cost`
+    pub net: f64,
+}
+
+impl AttackReport {
+    fn new(attack: &str, cost: f64, earned: f64, impressions: u64) -> Self {
+        AttackReport {
+            attack: attack.into(), cost, earned, impressions,
+            cost_per_impression: (impressions > 0).then(|| cost / impressions as f64),
+            net: earned - cost,
+        }
+    }
+}
+
+/// Reach of one post with `signals`, 0 when quarantined
+fn post_reach(actor: &Actor, signals: &Option<RiskSignals>, params: &Params) -> u64 {
+    if adjust_propagation(signals, params).quarantined { return 0; }
+    reach_budget(actor.ef, actor.q, calculate_risk(signals, &RiskWeights::DEFAULT), params)
+}
+
+/// Sybil accounts follow the attacker to inflate its EF; each sybil posts enough to look active
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SybilFarm {
+    pub attacker: Actor,
+    pub sybils: u32,
+    /// Quality each sybil reaches (below `q_min` it adds no EF)
+    pub sybil_q: f64,
+    /// Posts each sybil makes to keep that quality
+    pub posts_per_sybil: u32,
+    /// Attacker posts that exploit the inflated reach
+    pub attacker_posts: u32,
+    pub base_fare: f64,
+}
+
+/// Cost of running the sybils; impressions gained by the attacker's posts over its organic reach
+pub fn sybil_farm(a: &SybilFarm, params: &Params) -> AttackReport {
+    let sybil = Actor { rl: 1.0, q: a.sybil_q, ef: 0.0, posts_1h: None, ..Default::default() };
+    let cost = (a.sybils as u64 * a.posts_per_sybil as u64) as f64 * calculate_post_cost(&sybil, &Content::default(), params, a.base_fare);
+    let boosted = Actor { ef: a.attacker.ef + calculate_ef(&vec![a.sybil_q; a.sybils as usize], params), ..a.attacker.clone() };
+    let gain = post_reach(&boosted, &None, params).saturating_sub(post_reach(&a.attacker, &None, params));
+    AttackReport::new("sybil_farm", cost, 0.0, gain * a.attacker_posts as u64)
+}
+
+/// One actor floods `posts` posts within an hour
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BurstSpam {
+    pub actor: Actor,
+    pub posts: u32,
+    pub base_fare: f64,
+    /// Burst signal detectors attach to the flood (0..1)
+    pub burst: f64,
+}
+
+/// Each post is priced at its rank in the hour (`posts_1h` = posts so far, rate-limit penalty included)
+pub fn burst_spam(a: &BurstSpam, params: &Params) -> AttackReport {
+    let signals = Some(RiskSignals { burst: Some(a.burst), ..Default::default() });
+    let content = Content { risk_signals: signals.clone(), ..Default::default() };
+    let cost = (1..=a.posts)
+        .map(|i| calculate_post_cost(&Actor { posts_1h: Some(i as f64), ..a.actor.clone() }, &content, params, a.base_fare))
+        .sum();
+    AttackReport::new("burst_spam", cost, 0.0, post_reach(&a.actor, &signals, params) * a.posts as u64)
+}
+
+/// A ring of accounts reposting each other's content
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AmplificationRing {
+    pub member: Actor,
+    pub members: u32,
+    pub reposts_per_member: u32,
+    /// Coordination/clustering signal detectors attach to the ring's posts (0..1)
+    pub coordination: f64,
+    pub base_fare: f64,
+}
+
+pub fn amplification_ring(a: &AmplificationRing, params: &Params) -> AttackReport {
+    let signals = Some(RiskSignals { coordination: Some(a.coordination), clustering: Some(a.coordination), ..Default::default() });
+    let content = Content { risk_signals: signals.clone(), ..Default::default() };
+    let posts = a.members as u64 * a.reposts_per_member as u64;
+    let actor = Actor { posts_1h: Some(a.reposts_per_member as f64), ..a.member.clone() };
+    let cost = posts as f64 * calculate_post_cost(&actor, &content, params, a.base_fare);
+    AttackReport::new("amplification_ring", cost, 0.0, post_reach(&a.member, &signals, params) * posts)
+}
+
+/// A server and a colluding client replay the same content to farm serve rewards
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WashServing {
+    /// Serve template (`prev_serves` is set per replay)
+    pub serve: RewardInput,
+    pub serves: u32,
+    /// Fraction of each ticket the colluding client actually pays (1 = full price)
+    pub ticket_paid_share: f64,
+}
+
+/// Replays see the repetition discount (`prev_serves` = 0, 1, ..)
+pub fn wash_serving(a: &WashServing, params: &Params) -> AttackReport {
+    let cost = a.serves as f64 * a.serve.ticket_budget.max(0.0) * a.ticket_paid_share.clamp(0.0, 1.0);
+    let earned = (0..a.serves).map(|i| calculate_serve_reward(&RewardInput { prev_serves: Some(i), ..a.serve.clone() }, params)).sum();
+    AttackReport::new("wash_serving", cost, earned, 0)
+}
+
+/// Any canned attack (JSON: `{"kind": "burst_spam", ...}`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Attack {
+    SybilFarm(SybilFarm),
+    BurstSpam(BurstSpam),
+    AmplificationRing(AmplificationRing),
+    WashServing(WashServing),
+}
+
+pub fn run(attack: &Attack, params: &Params) -> AttackReport {
+    match attack {
+        Attack::SybilFarm(a) => sybil_farm(a, params),
+        Attack::BurstSpam(a) => burst_spam(a, params),
+        Attack::AmplificationRing(a) => amplification_ring(a, params),
+        Attack::WashServing(a) => wash_serving(a, params),
+    }
+}
+
+/// The four attacks at illustrative sizes
+pub fn library() -> Vec<Attack> {
+    let actor = Actor { rl: 1.0, q: 0.6, ef: 5.0, posts_1h: None, ..Default::default() };
+    vec![
+        Attack::SybilFarm(SybilFarm { attacker: actor.clone(), sybils: 500, sybil_q: 0.55, posts_per_sybil: 5, attacker_posts: 20, base_fare: 1.0 }),
+        Attack::BurstSpam(BurstSpam { actor: actor.clone(), posts: 200, base_fare: 1.0, burst: 0.9 }),
+        Attack::AmplificationRing(AmplificationRing { member: actor, members: 50, reposts_per_member: 10, coordination: 0.8, base_fare: 1.0 }),
+        Attack::WashServing(WashServing {
+            serve: RewardInput { ticket_budget: 1.0, client_q: 0.5, size_bytes: 100_000, ttfb_ms: 50, server_cluster_risk: 0.5, ..Default::default() },
+            serves: 100,
+            ticket_paid_share: 1.0,
+        }),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_library_runs_and_is_unprofitable_by_default() {
+        let p = Params::default();
+        let reports: Vec<_> = library().iter().map(|a| run(a, &p)).collect();
+        assert_eq!(reports.iter().map(|r| r.attack.as_str()).collect::<Vec<_>>(), ["sybil_farm", "burst_spam", "amplification_ring", "wash_serving"]);
+        assert!(reports.iter().all(|r| r.cost > 0.0 && r.net <= 0.0));
+        assert!(reports[..3].iter().all(|r| r.impressions > 0));
+
+        // the burst is priced progressively: a flood costs more than that many isolated posts
+        let Attack::BurstSpam(b) = &library()[1] else { unreachable!() };
+        let single = calculate_post_cost(&Actor { posts_1h: Some(1.0), ..b.actor.clone() }, &Content::default(), &p, 1.0);
+        assert!(reports[1].cost > single * b.posts as f64);
+    }
+}
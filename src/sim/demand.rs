@@ -0,0 +1,139 @@
+// Stochastic demand: Poisson arrivals on a diurnal cycle with bursts
+// - The rate at step t is `base_rate * (1 + diurnal_amplitude * cos(2π (t - peak_step) / period_steps))`, times
+//   `burst_multiplier` while a burst is running; a burst starts at a quiet step with probability `burst_prob` and
+//   lasts `burst_steps`
+// - Posts per step are Poisson at that rate; retrievals are Poisson at `retrievals_per_post` times it, sharing the
+//   burst schedule (a viral burst drives both)
+// - Deterministic: draws come from `det_rand::DetRng` keyed by `seed`, one stream each for bursts, posts and retrievals
+// - `posts` feeds `congestion::simulate` and `Scenario::demand`; `epoch_inputs` feeds `epoch::Epoch::advance`
+
+use crate::det_rand::DetRng;
+use crate::epoch::EpochInputs;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PoissonBurstModel {
+    pub seed: u64,
+    /// Mean posts per step before the cycle and bursts
+    pub base_rate: f64,
+    /// 0 = flat, 1 = rate touches 0 at the trough
+    pub diurnal_amplitude: f64,
+    /// Steps per cycle (0 disables the cycle)
+    pub period_steps: u32,
+    pub peak_step: u32,
+    pub burst_prob: f64,
+    pub burst_multiplier: f64,
+    pub burst_steps: u32,
+    pub retrievals_per_post: f64,
+}
+
+impl Default for PoissonBurstModel {
+    fn default() -> Self {
+        PoissonBurstModel {
+            seed: 0, base_rate: 1_000.0, diurnal_amplitude: 0.5, period_steps: 24, peak_step: 20,
+            burst_prob: 0.02, burst_multiplier: 4.0, burst_steps: 3, retrievals_per_post: 20.0,
+        }
+    }
+}
+
+/// Generated series (one entry per step)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DemandSeries {
+    pub posts: Vec<f64>,
+    pub retrievals: Vec<f64>,
+    pub bursting: Vec<bool>,
+}
+
+/// Poisson draw (Knuth below 30, normal approximation above)
+fn poisson(lambda: f64, rng: &mut DetRng) -> f64 {
+    if !(lambda.is_finite() && lambda > 0.0) { return 0.0; }
+    if lambda < 30.0 {
+        let limit = (-lambda).exp();
+        let (mut k, mut p) = (0.0, rng.next_f64());
+        while p > limit { k += 1.0; p *= rng.next_f64(); }
+        k
+    } else {
+        let (u1, u2) = (1.0 - rng.next_f64(), rng.next_f64());
+        let z = (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos();
+        (lambda + lambda.sqrt() * z).round().max(0.0)
+    }
+}
+
+impl PoissonBurstModel {
+    /// Mean rate at `step` outside bursts
+    pub fn rate(&self, step: u64) -> f64 {
+        let cycle = if self.period_steps == 0 { 1.0 } else {
+            let phase = (step as f64 - self.peak_step as f64) / self.period_steps as f64;
+            1.0 + self.diurnal_amplitude.clamp(0.0, 1.0) * (std::f64::consts::TAU * phase).cos()
+        };
+        self.base_rate.max(0.0) * cycle
+    }
+
+    /// Posts, retrievals and burst flags for `steps` steps
+    pub fn generate(&self, steps: usize) -> DemandSeries {
+        let key = [0u8; 32];
+        let mut bursts = DetRng::new("sim/demand/bursts", self.seed, &key);
+        let mut posts_rng = DetRng::new("sim/demand/posts", self.seed, &key);
+        let mut retrievals_rng = DetRng::new("sim/demand/retrievals", self.seed, &key);
+        let mut out = DemandSeries { posts: Vec::with_capacity(steps), retrievals: Vec::with_capacity(steps), bursting: Vec::with_capacity(steps) };
+        let mut remaining = 0;
+        for t in 0..steps as u64 {
+            if remaining == 0 && bursts.next_f64() < self.burst_prob { remaining = self.burst_steps; }
+            let bursting = remaining > 0;
+            remaining = remaining.saturating_sub(1);
+            let rate = self.rate(t) * if bursting { self.burst_multiplier.max(0.0) } else { 1.0 };
+            out.posts.push(poisson(rate, &mut posts_rng));
+            out.retrievals.push(poisson(rate * self.retrievals_per_post.max(0.0), &mut retrievals_rng));
+            out.bursting.push(bursting);
+        }
+        out
+    }
+
+    /// Posting load per step (a `load_curve` / `congestion::simulate` series)
+    pub fn posts(&self, steps: usize) -> Vec<f64> { self.generate(steps).posts }
+
+    /// `epochs` epoch inputs of `samples_per_epoch` posting-load samples each (`posts` = their sum)
+    pub fn epoch_inputs(&self, epochs: usize, samples_per_epoch: usize) -> Vec<EpochInputs> {
+        let per = samples_per_epoch.max(1);
+        self.posts(epochs * per).chunks(per).map(|c| EpochInputs {
+            load_samples: c.to_vec(),
+            posts: c.iter().sum::<f64>() as u64,
+            ..Default::default()
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_demand_deterministic_diurnal_bursty() {
+        let m = PoissonBurstModel { burst_prob: 0.05, ..Default::default() };
+        let s = m.generate(24 * 30);
+        assert_eq!(s, m.generate(24 * 30));
+        assert_ne!(s.posts, PoissonBurstModel { seed: 1, ..m.clone() }.posts(24 * 30));
+
+        // quiet-step posts average out to the cycle: peak hour well above the trough hour
+        let mean_at = |h: usize| {
+            let xs: Vec<f64> = (h..s.posts.len()).step_by(24).filter(|&i| !s.bursting[i]).map(|i| s.posts[i]).collect();
+            xs.iter().sum::<f64>() / xs.len() as f64
+        };
+        assert!((mean_at(20) / m.rate(20) - 1.0).abs() < 0.05);
+        assert!(mean_at(20) > 2.5 * mean_at(8));
+        // bursts happen and lift load
+        let burst_mean = s.posts.iter().zip(&s.bursting).filter(|(_, &b)| b).map(|(x, _)| x).sum::<f64>()
+            / s.bursting.iter().filter(|&&b| b).count() as f64;
+        assert!(burst_mean > 2.0 * m.base_rate);
+        assert!((s.retrievals.iter().sum::<f64>() / s.posts.iter().sum::<f64>() - 20.0).abs() < 0.5);
+
+        let inputs = m.epoch_inputs(3, 24);
+        assert_eq!(inputs.len(), 3);
+        assert_eq!(inputs[0].load_samples.len(), 24);
+    }
+}
@@ -0,0 +1,250 @@
+// Versioned scenario files (feature `serde`)
+// - A `ScenarioFile` is a shareable simulation configuration: the population (cohorts for `sim::run`, or a sampled
+//   population for `agents::run`), an attack mix priced under the same params, the load curve (or generated
+//   `demand`), the epoch count, and the params it is meant to run under (a preset and/or a pinned `audit::params_hash`)
+// - `version` is required; versions this build does not know are rejected rather than half-read
+// - `load` parses and validates, reporting every problem with a dotted path into the file; `run` resolves the params
+//   and runs the population and every attack
+// - Documents without `version` are legacy `sim::Scenario`s (`is_versioned`), still accepted by `simulate`
+
+use super::agents::{self, AgentEpochReport, AgentScenario, Population};
+use super::attacks::{self, Attack, AttackReport};
+use super::demand::PoissonBurstModel;
+use super::{Cohort, EpochReport, Scenario};
+use crate::audit::params_hash;
+use crate::por::Hash32;
+use crate::{presets, Content, ParamError, Params, RewardInput};
+use serde::{Deserialize, Serialize};
+
+/// Newest scenario format this build reads
+pub const SCENARIO_VERSION: u32 = 1;
+
+/// Params a scenario runs under: a named preset (else the caller's params), optionally pinned by hash
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ParamsRef {
+    #[serde(default)]
+    pub preset: Option<String>,
+    /// `audit::params_hash` the resolved params must match
+    #[serde(default)]
+    pub hash: Option<Hash32>,
+}
+
+/// Sampled agents with demand response (`agents::run`); the load curve is their background load
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AgentPopulation {
+    pub population: Population,
+    #[serde(default)]
+    pub seed: u64,
+    pub load_per_post: f64,
+    #[serde(default)]
+    pub content: Content,
+    #[serde(default)]
+    pub serve: Option<RewardInput>,
+    #[serde(default)]
+    pub serves_per_impression: f64,
+}
+
+/// Who posts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "model", rename_all = "snake_case")]
+pub enum PopulationSpec {
+    /// Fixed cohorts of identical actors (`sim::run`)
+    Cohorts { cohorts: Vec<Cohort> },
+    /// Sampled agents (`agents::run`)
+    Agents(Box<AgentPopulation>),
+}
+
+fn default_initial_base() -> f64 { 1.0 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ScenarioFile {
+    pub version: u32,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub params: ParamsRef,
+    pub population: PopulationSpec,
+    /// Attacks priced alongside the population
+    #[serde(default)]
+    pub attacks: Vec<Attack>,
+    /// Load per epoch (cycled; ignored when `demand` is set)
+    #[serde(default)]
+    pub load_curve: Vec<f64>,
+    #[serde(default)]
+    pub demand: Option<PoissonBurstModel>,
+    pub epochs: u32,
+    #[serde(default = "default_initial_base")]
+    pub initial_base: f64,
+}
+
+/// Per-epoch results of the population model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "model", rename_all = "snake_case")]
+pub enum PopulationRun {
+    Cohorts { epochs: Vec<EpochReport> },
+    Agents { epochs: Vec<AgentEpochReport> },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ScenarioReport {
+    pub name: String,
+    /// Hash of the params the scenario ran under
+    pub params_hash: Hash32,
+    #[serde(flatten)]
+    pub run: PopulationRun,
+    pub attacks: Vec<AttackReport>,
+}
+
+/// True when `data` is a JSON object with a `version` field (a `ScenarioFile` rather than a legacy `sim::Scenario`)
+pub fn is_versioned(data: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(data).is_ok_and(|v| v.get("version").is_some())
+}
+
+/// Parse and validate a JSON scenario file
+pub fn load(data: &str) -> Result<ScenarioFile, Vec<ParamError>> {
+    let err = |path: &str, reason: String| vec![ParamError { path: path.into(), reason }];
+    let doc: serde_json::Value = serde_json::from_str(data).map_err(|e| err("", e.to_string()))?;
+    // check the version first: a newer file may not parse as this build's format at all
+    match doc.get("version") {
+        None => return Err(err("version", "missing".into())),
+        Some(v) if v.as_u64().is_some_and(|v| (1..=SCENARIO_VERSION as u64).contains(&v)) => {}
+        Some(v) => return Err(err("version", format!("unsupported version {} (this build reads 1..={})", v, SCENARIO_VERSION))),
+    }
+    let file: ScenarioFile = serde_json::from_value(doc).map_err(|e| err("", e.to_string()))?;
+    file.validate()?;
+    Ok(file)
+}
+
+impl ScenarioFile {
+    pub fn validate(&self) -> Result<(), Vec<ParamError>> {
+        let mut errs = Vec::new();
+        let mut check = |path: String, ok: bool, reason: &str| {
+            if !ok { errs.push(ParamError { path, reason: reason.into() }); }
+        };
+        let nonneg = |x: f64| x.is_finite() && x >= 0.0;
+        check("epochs".into(), self.epochs > 0, "must be > 0");
+        check("initial_base".into(), self.initial_base.is_finite() && self.initial_base > 0.0, "must be finite and > 0");
+        for (i, &x) in self.load_curve.iter().enumerate() {
+            check(format!("load_curve.{}", i), nonneg(x), "must be finite and >= 0");
+        }
+        if let Some(name) = &self.params.preset {
+            check("params.preset".into(), presets::preset(name).is_some(), "unknown preset");
+        }
+        match &self.population {
+            PopulationSpec::Cohorts { cohorts } => {
+                check("population.cohorts".into(), !cohorts.is_empty(), "must not be empty");
+                for (i, c) in cohorts.iter().enumerate() {
+                    check(format!("population.cohorts.{}.posts_per_epoch", i), nonneg(c.posts_per_epoch), "must be finite and >= 0");
+                }
+            }
+            PopulationSpec::Agents(a) => {
+                check("population.population.size".into(), a.population.size > 0, "must be > 0");
+                check("population.load_per_post".into(), nonneg(a.load_per_post), "must be finite and >= 0");
+                check("population.serves_per_impression".into(), nonneg(a.serves_per_impression), "must be finite and >= 0");
+            }
+        }
+        if errs.is_empty() { Ok(()) } else { Err(errs) }
+    }
+
+    /// The preset's params (else `fallback`), checked against the pinned hash
+    pub fn resolve_params(&self, fallback: &Params) -> Result<Params, Vec<ParamError>> {
+        let params = match &self.params.preset {
+            Some(name) => presets::preset(name)
+                .ok_or_else(|| vec![ParamError { path: "params.preset".into(), reason: "unknown preset".into() }])?,
+            None => fallback.clone(),
+        };
+        if self.params.hash.is_some_and(|h| h != params_hash(&params)) {
+            return Err(vec![ParamError { path: "params.hash".into(), reason: "does not match the resolved params".into() }]);
+        }
+        Ok(params)
+    }
+
+    /// Run the population and price the attacks under the resolved params
+    pub fn run(&self, fallback: &Params) -> Result<ScenarioReport, Vec<ParamError>> {
+        let params = self.resolve_params(fallback)?;
+        let run = match &self.population {
+            PopulationSpec::Cohorts { cohorts } => PopulationRun::Cohorts {
+                epochs: super::run(&Scenario {
+                    cohorts: cohorts.clone(),
+                    load_curve: self.load_curve.clone(),
+                    epochs: self.epochs,
+                    initial_base: self.initial_base,
+                    demand: self.demand.clone(),
+                }, &params),
+            },
+            PopulationSpec::Agents(a) => {
+                let background_load = match &self.demand {
+                    Some(d) => d.posts(self.epochs as usize),
+                    None => self.load_curve.clone(),
+                };
+                PopulationRun::Agents {
+                    epochs: agents::run(&AgentScenario {
+                        population: a.population.clone(),
+                        epochs: self.epochs,
+                        seed: a.seed,
+                        initial_base: self.initial_base,
+                        load_per_post: a.load_per_post,
+                        background_load,
+                        content: a.content.clone(),
+                        serve: a.serve.clone(),
+                        serves_per_impression: a.serves_per_impression,
+                    }, &params),
+                }
+            }
+        };
+        let attacks = self.attacks.iter().map(|a| attacks::run(a, &params)).collect();
+        Ok(ScenarioReport { name: self.name.clone(), params_hash: params_hash(&params), run, attacks })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_load_validate_and_run() {
+        let doc = serde_json::json!({
+            "version": 1,
+            "name": "spam wave",
+            "params": { "preset": "default" },
+            "population": { "model": "cohorts", "cohorts": [
+                { "name": "casual", "count": 50, "posts_per_epoch": 1.0, "actor": { "rl": 1.0, "q": 0.8, "ef": 5.0 } },
+            ] },
+            "attacks": [{ "kind": "burst_spam", "actor": { "rl": 1.0, "q": 0.6, "ef": 5.0 }, "posts": 100, "base_fare": 1.0, "burst": 0.9 }],
+            "load_curve": [200.0, 1500.0],
+            "epochs": 3,
+        });
+        let data = doc.to_string();
+        assert!(is_versioned(&data));
+        let file = load(&data).unwrap();
+        let report = file.run(&Params::default()).unwrap();
+        assert_eq!(report.params_hash, params_hash(&Params::default()));
+        match &report.run {
+            PopulationRun::Cohorts { epochs } => assert_eq!((epochs.len(), epochs[0].posts), (3, 50)),
+            PopulationRun::Agents { .. } => panic!("expected cohorts"),
+        }
+        assert_eq!(report.attacks.len(), 1);
+        assert_eq!(serde_json::to_value(&report).unwrap()["model"], "cohorts");
+
+        // a pinned hash that no longer matches refuses to run
+        let pinned = ScenarioFile { params: ParamsRef { preset: None, hash: Some([0; 32]) }, ..file.clone() };
+        assert_eq!(pinned.run(&Params::default()).unwrap_err()[0].path, "params.hash");
+
+        let mut bad = doc.clone();
+        bad["epochs"] = 0.into();
+        bad["load_curve"] = serde_json::json!([1.0, -1.0]);
+        bad["params"]["preset"] = "nope".into();
+        let paths: Vec<String> = load(&bad.to_string()).unwrap_err().into_iter().map(|e| e.path).collect();
+        assert_eq!(paths, ["epochs", "load_curve.1", "params.preset"]);
+        let mut future = doc;
+        future["version"] = 2.into();
+        assert_eq!(load(&future.to_string()).unwrap_err()[0].path, "version");
+        assert!(!is_versioned(r#"{"cohorts": [], "load_curve": [], "epochs": 1}"#));
+    }
+}
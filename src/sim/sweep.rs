@@ -0,0 +1,241 @@
+// Parameter sweeps over the agent-based simulation
+// - `ParamRanges` names numeric `Params` fields by dotted path (e.g. `congestion.eta`) with a [lo, hi] range each
+// - Points come from a full grid (`steps` per range) or a seeded Latin hypercube (`samples` points, one per stratum
+//   in every dimension)
+// - Every point runs `agents::run_detailed` and is scored by `Objective` from spam cost ratio, honest cost and reward Gini
+// - Agents whose risk is at least `Objective::spam_risk` count as spammers, the rest as honest users
+// - Points come back in sampling order; with the `parallel` feature they are evaluated on the rayon pool (same results)
+
+use super::agents::{run_detailed, AgentRun, AgentScenario};
+use crate::det_rand::DetRng;
+use crate::{ParamError, Params};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// One swept field
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ParamRange {
+    /// Dotted field path into `Params` (numeric leaf)
+    pub path: String,
+    pub lo: f64,
+    pub hi: f64,
+    /// Grid points along this range (`lo` only when 1)
+    #[serde(default = "default_steps")]
+    pub steps: u32,
+}
+
+fn default_steps() -> u32 { 5 }
+
+/// How points are drawn from the ranges
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Sampling {
+    Grid,
+    LatinHypercube { samples: u32, #[serde(default)] seed: u64 },
+}
+
+/// Swept fields plus sampling strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ParamRanges {
+    pub ranges: Vec<ParamRange>,
+    pub sampling: Sampling,
+}
+
+/// Objective weights; `score = w_spam * spam_cost_ratio - w_honest * honest_cost - w_gini * reward_gini`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Objective {
+    /// Risk at or above which an agent counts as a spammer
+    pub spam_risk: f64,
+    pub w_spam: f64,
+    pub w_honest: f64,
+    pub w_gini: f64,
+}
+
+impl Default for Objective {
+    fn default() -> Self { Objective { spam_risk: 0.5, w_spam: 1.0, w_honest: 1.0, w_gini: 1.0 } }
+}
+
+/// Metrics of one simulated point
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SweepMetrics {
+    /// Mean cost per spam post over mean cost per honest post (0 when either group did not post)
+    pub spam_cost_ratio: f64,
+    /// Mean cost per honest post
+    pub honest_cost: f64,
+    /// Median over honest agents of their mean cost per post
+    pub honest_median_cost: f64,
+    /// Gini coefficient of per-agent rewards (0 = equal)
+    pub reward_gini: f64,
+    pub posts: u64,
+}
+
+/// One evaluated point
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SweepPoint {
+    /// Swept field values by path
+    pub values: BTreeMap<String, f64>,
+    /// `None` when the point's params fail `Params::validate` (see `errors`)
+    pub metrics: Option<SweepMetrics>,
+    pub score: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<ParamError>,
+}
+
+/// Evaluate every point of `ranges` around `base`; errors only for unknown or non-numeric paths
+pub fn sweep(ranges: &ParamRanges, base: &Params, scenario: &AgentScenario, objective: &Objective) -> Result<Vec<SweepPoint>, ParamError> {
+    let base_json = serde_json::to_value(base).map_err(|e| ParamError { path: String::new(), reason: e.to_string() })?;
+    for r in &ranges.ranges { with_values(&base_json, &[(&r.path, r.lo)])?; }
+    let points = points(ranges);
+    let eval = |values: &Vec<f64>| -> Result<SweepPoint, ParamError> {
+        let assignments: Vec<(&str, f64)> = ranges.ranges.iter().map(|r| r.path.as_str()).zip(values.iter().copied()).collect();
+        let params = with_values(&base_json, &assignments)?;
+        let values = assignments.iter().map(|&(p, v)| (p.to_string(), v)).collect();
+        Ok(match params.validate() {
+            Ok(()) => {
+                let m = metrics(&run_detailed(scenario, &params), objective.spam_risk);
+                let score = objective.w_spam * m.spam_cost_ratio - objective.w_honest * m.honest_cost - objective.w_gini * m.reward_gini;
+                SweepPoint { values, metrics: Some(m), score: Some(score), errors: Vec::new() }
+            }
+            Err(errors) => SweepPoint { values, metrics: None, score: None, errors },
+        })
+    };
+    #[cfg(feature = "parallel")]
+    let out = { use rayon::prelude::*; points.par_iter().map(eval).collect() };
+    #[cfg(not(feature = "parallel"))]
+    let out = points.iter().map(eval).collect();
+    out
+}
+
+/// Sample coordinates, one `Vec` per point in `ranges.ranges` order
+pub fn points(ranges: &ParamRanges) -> Vec<Vec<f64>> {
+    let rs = &ranges.ranges;
+    match ranges.sampling {
+        Sampling::Grid => {
+            let axis = |r: &ParamRange| -> Vec<f64> {
+                let n = r.steps.max(1);
+                (0..n).map(|i| if n == 1 { r.lo } else { r.lo + (r.hi - r.lo) * i as f64 / (n - 1) as f64 }).collect()
+            };
+            rs.iter().fold(vec![Vec::new()], |acc, r| {
+                let a = axis(r);
+                acc.iter().flat_map(|p| a.iter().map(move |&v| { let mut q = p.clone(); q.push(v); q })).collect()
+            })
+        }
+        Sampling::LatinHypercube { samples, seed } => {
+            let n = samples as usize;
+            let mut rng = DetRng::new("sim/sweep", seed, &[0u8; 32]);
+            let mut out = vec![Vec::with_capacity(rs.len()); n];
+            for r in rs {
+                // Fisher-Yates permutation of the strata, then a uniform draw inside each
+                let mut strata: Vec<usize> = (0..n).collect();
+                for i in (1..n).rev() { strata.swap(i, rng.up_to(i as u64) as usize); }
+                for (p, s) in out.iter_mut().zip(strata) {
+                    p.push(r.lo + (r.hi - r.lo) * (s as f64 + rng.next_f64()) / n as f64);
+                }
+            }
+            out
+        }
+    }
+}
+
+/// `base` with each dotted path set to its value
+pub(crate) fn with_values(base: &serde_json::Value, assignments: &[(&str, f64)]) -> Result<Params, ParamError> {
+    let mut v = base.clone();
+    for &(path, x) in assignments {
+        let err = |reason: &str| ParamError { path: path.to_string(), reason: reason.into() };
+        let pointer = format!("/{}", path.replace('.', "/"));
+        let slot = v.pointer_mut(&pointer).ok_or_else(|| err("no such field"))?;
+        if !slot.is_number() { return Err(err("not a numeric field")); }
+        if !x.is_finite() { return Err(err("value must be finite")); }
+        // integer fields (counts, delays) take the rounded value
+        *slot = if slot.is_u64() { (x.round().max(0.0) as u64).into() } else { serde_json::Number::from_f64(x).expect("finite").into() };
+    }
+    serde_json::from_value(v).map_err(|e| ParamError { path: String::new(), reason: e.to_string() })
+}
+
+pub(crate) fn metrics(run: &AgentRun, spam_risk: f64) -> SweepMetrics {
+    let (mut spam, mut honest) = ((0.0, 0u64), (0.0, 0u64));
+    let mut per_agent = Vec::new();
+    for (a, t) in run.agents.iter().zip(&run.totals) {
+        let g = if a.risk >= spam_risk { &mut spam } else { &mut honest };
+        g.0 += t.cost_paid;
+        g.1 += t.posts;
+        if a.risk < spam_risk && t.posts > 0 { per_agent.push(t.cost_paid / t.posts as f64); }
+    }
+    per_agent.sort_by(f64::total_cmp);
+    let honest_median_cost = match per_agent.len() {
+        0 => 0.0,
+        n if n % 2 == 1 => per_agent[n / 2],
+        n => 0.5 * (per_agent[n / 2 - 1] + per_agent[n / 2]),
+    };
+    let mean = |(c, n): (f64, u64)| if n == 0 { 0.0 } else { c / n as f64 };
+    let honest_cost = mean(honest);
+    let spam_cost_ratio = if honest_cost > 0.0 && spam.1 > 0 { mean(spam) / honest_cost } else { 0.0 };
+    let rewards: Vec<f64> = run.totals.iter().map(|t| t.reward).collect();
+    SweepMetrics { spam_cost_ratio, honest_cost, honest_median_cost, reward_gini: gini(&rewards), posts: spam.1 + honest.1 }
+}
+
+/// Gini coefficient of non-negative values (0 for an empty or all-zero set)
+pub fn gini(values: &[f64]) -> f64 {
+    let mut v: Vec<f64> = values.iter().map(|x| x.max(0.0)).collect();
+    let sum: f64 = v.iter().sum();
+    if v.is_empty() || sum <= 0.0 { return 0.0; }
+    v.sort_by(f64::total_cmp);
+    let n = v.len() as f64;
+    let weighted: f64 = v.iter().enumerate().map(|(i, x)| (i as f64 + 1.0) * x).sum();
+    2.0 * weighted / (n * sum) - (n + 1.0) / n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::agents::{Dist, Population};
+    use crate::{Content, RewardInput};
+
+    fn scenario() -> AgentScenario {
+        AgentScenario {
+            population: Population {
+                size: 200,
+                quality: Dist::Uniform { lo: 0.2, hi: 1.0 },
+                ef: Dist::LogNormal { median: 5.0, sigma: 1.0 },
+                posts_per_epoch: Dist::LogNormal { median: 2.0, sigma: 0.8 },
+                rl: Dist::Uniform { lo: 1.0, hi: 60.0 },
+                risk: Dist::Uniform { lo: 0.0, hi: 1.0 },
+                willingness_to_pay: None,
+            },
+            epochs: 4, seed: 1, initial_base: 1.0, load_per_post: 1.0, background_load: vec![300.0],
+            content: Content::default(),
+            serve: Some(RewardInput { ticket_budget: 1.0, size_bytes: 10_000, ttfb_ms: 100, ..Default::default() }),
+            serves_per_impression: 0.1,
+        }
+    }
+
+    #[test]
+    fn t_grid_and_lhs_points() {
+        let range = |path: &str, lo, hi, steps| ParamRange { path: path.into(), lo, hi, steps };
+        let grid = ParamRanges { ranges: vec![range("congestion.eta", 0.05, 0.2, 3), range("cost.alpha", 0.5, 1.5, 2)], sampling: Sampling::Grid };
+        let p = Params::default();
+        let out = sweep(&grid, &p, &scenario(), &Objective::default()).unwrap();
+        assert_eq!(out.len(), 6);
+        assert_eq!(out[5].values["congestion.eta"], 0.2);
+        let m = out[0].metrics.as_ref().unwrap();
+        assert!(m.posts > 0 && m.honest_cost > 0.0 && (0.0..1.0).contains(&m.reward_gini));
+
+        let lhs = ParamRanges { sampling: Sampling::LatinHypercube { samples: 8, seed: 3 }, ..grid };
+        let pts = points(&lhs);
+        assert_eq!(pts, points(&lhs));
+        let mut strata: Vec<usize> = pts.iter().map(|x| ((x[0] - 0.05) / 0.15 * 8.0) as usize).collect();
+        strata.sort();
+        assert_eq!(strata, (0..8).collect::<Vec<_>>());
+
+        let bad = ParamRanges { ranges: vec![range("cost.nope", 0.0, 1.0, 2)], sampling: Sampling::Grid };
+        assert_eq!(sweep(&bad, &p, &scenario(), &Objective::default()).unwrap_err().path, "cost.nope");
+        assert_eq!(gini(&[1.0, 1.0, 1.0]), 0.0);
+        assert!((gini(&[0.0, 0.0, 0.0, 1.0]) - 0.75).abs() < 1e-12);
+    }
+}
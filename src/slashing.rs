@@ -0,0 +1,78 @@
+// Slashing amount calculator for provable offenses
+// - Same deterministic style as the reward math: pure, clamped, no state
+
+use crate::{clamp, Social};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Provable offense categories
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum OffenseKind { FakeServe, OracleManipulation, CoordinatedSpam }
+
+/// Escalation curve for one offense kind
+/// fraction = min( max_fraction, base_fraction * severity * escalation^repeat_count )
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct OffenseParams { pub base_fraction: f64, pub escalation: f64, pub max_fraction: f64 }
+
+/// Slashing parameters per offense kind
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SlashParams {
+    pub fake_serve: OffenseParams,
+    pub oracle_manipulation: OffenseParams,
+    pub coordinated_spam: OffenseParams,
+}
+
+impl Default for SlashParams {
+    fn default() -> Self {
+        Self {
+            fake_serve: OffenseParams { base_fraction: 0.05, escalation: 2.0, max_fraction: 1.0 },
+            oracle_manipulation: OffenseParams { base_fraction: 0.2, escalation: 2.0, max_fraction: 1.0 },
+            coordinated_spam: OffenseParams { base_fraction: 0.02, escalation: 1.5, max_fraction: 0.5 },
+        }
+    }
+}
+
+impl SlashParams {
+    pub fn for_offense(&self, offense: OffenseKind) -> &OffenseParams {
+        match offense {
+            OffenseKind::FakeServe => &self.fake_serve,
+            OffenseKind::OracleManipulation => &self.oracle_manipulation,
+            OffenseKind::CoordinatedSpam => &self.coordinated_spam,
+        }
+    }
+}
+
+/// Compute the amount of stake to slash
+/// - `severity` in 0..1 (clamped); `repeat_count` = prior upheld offenses of the same kind
+/// - Result is in `[0, stake * max_fraction]`
+pub fn calculate_slash(stake: impl Into<Social>, offense: OffenseKind, severity: f64, repeat_count: u32, params: &SlashParams) -> f64 {
+    let p = params.for_offense(offense);
+    let escalation = p.escalation.max(1.0).powi(repeat_count.min(64) as i32);
+    let fraction = (p.base_fraction.max(0.0) * clamp(severity, 0.0, 1.0) * escalation).min(clamp(p.max_fraction, 0.0, 1.0));
+    stake.into().get() * fraction
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slash_escalates_and_caps() {
+        let p = SlashParams::default();
+        let first = calculate_slash(1000.0, OffenseKind::FakeServe, 1.0, 0, &p);
+        let second = calculate_slash(1000.0, OffenseKind::FakeServe, 1.0, 1, &p);
+        assert_eq!(first, 50.0);
+        assert_eq!(second, 100.0);
+        assert_eq!(calculate_slash(1000.0, OffenseKind::FakeServe, 1.0, 30, &p), 1000.0);
+        assert_eq!(calculate_slash(1000.0, OffenseKind::CoordinatedSpam, 1.0, 30, &p), 500.0);
+        assert_eq!(calculate_slash(1000.0, OffenseKind::OracleManipulation, 0.0, 3, &p), 0.0);
+        assert_eq!(calculate_slash(-5.0, OffenseKind::OracleManipulation, 1.0, 0, &p), 0.0);
+    }
+}
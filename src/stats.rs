@@ -0,0 +1,301 @@
+// Bounded-memory streaming statistics over evaluation results
+// - `CostStats`/`RewardStats` take one value (or breakdown) at a time and never buffer the stream
+// - Mean/variance use Welford's update; percentiles come from a log-bucketed sketch with relative error `alpha`
+// - The sketch holds at most `max_bins` buckets (lowest buckets merge first), so memory is fixed for any stream length
+// - Values <= 0 land in a dedicated zero bucket (costs and rewards are non-negative)
+
+use crate::{CostBreakdown, RewardBreakdown};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Default relative accuracy of reported percentiles
+pub const DEFAULT_ALPHA: f64 = 0.01;
+/// Default bucket budget (covers ~9 orders of magnitude at 1% accuracy before collapsing)
+pub const DEFAULT_MAX_BINS: usize = 2048;
+
+/// Log-bucketed quantile sketch (bucket `i` holds values in `(gamma^(i-1), gamma^i]`)
+#[derive(Debug, Clone)]
+pub struct QuantileSketch {
+    gamma: f64,
+    ln_gamma: f64,
+    max_bins: usize,
+    bins: BTreeMap<i32, u64>,
+    zeros: u64,
+    count: u64,
+}
+
+impl QuantileSketch {
+    pub fn new(alpha: f64, max_bins: usize) -> Self {
+        let alpha = alpha.clamp(1e-6, 0.5);
+        let gamma = (1.0 + alpha) / (1.0 - alpha);
+        QuantileSketch { gamma, ln_gamma: gamma.ln(), max_bins: max_bins.max(1), bins: BTreeMap::new(), zeros: 0, count: 0 }
+    }
+
+    /// Add one value (NaN is ignored)
+    pub fn push(&mut self, x: f64) {
+        if x.is_nan() { return; }
+        self.count += 1;
+        if x <= 0.0 {
+            self.zeros += 1;
+            return;
+        }
+        let key = (x.ln() / self.ln_gamma).ceil().clamp(i32::MIN as f64, i32::MAX as f64) as i32;
+        *self.bins.entry(key).or_insert(0) += 1;
+        self.collapse();
+    }
+
+    /// Add `x` with multiplicity `n`
+    pub fn push_n(&mut self, x: f64, n: u64) {
+        if x.is_nan() || n == 0 { return; }
+        self.count += n;
+        if x <= 0.0 {
+            self.zeros += n;
+            return;
+        }
+        let key = (x.ln() / self.ln_gamma).ceil().clamp(i32::MIN as f64, i32::MAX as f64) as i32;
+        *self.bins.entry(key).or_insert(0) += n;
+        self.collapse();
+    }
+
+    /// Fold another sketch with the same `alpha` into this one
+    pub fn merge(&mut self, other: &QuantileSketch) {
+        for (&k, &n) in &other.bins { *self.bins.entry(k).or_insert(0) += n; }
+        self.zeros += other.zeros;
+        self.count += other.count;
+        self.collapse();
+    }
+
+    /// Merge the lowest buckets until the budget holds (keeps the upper tail exact to `alpha`)
+    fn collapse(&mut self) {
+        while self.bins.len() > self.max_bins {
+            let (_, n) = self.bins.pop_first().expect("more bins than the budget");
+            *self.bins.first_entry().expect("budget is at least one bin").get_mut() += n;
+        }
+    }
+
+    /// Value at quantile `q` (0..1); `None` when empty
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if self.count == 0 { return None; }
+        let rank = (q.clamp(0.0, 1.0) * (self.count - 1) as f64).floor() as u64;
+        if rank < self.zeros { return Some(0.0); }
+        let mut seen = self.zeros;
+        for (&k, &n) in &self.bins {
+            seen += n;
+            if seen > rank {
+                // midpoint of the bucket in relative terms
+                return Some(2.0 * self.gamma.powi(k) / (1.0 + self.gamma));
+            }
+        }
+        None
+    }
+
+    pub fn count(&self) -> u64 { self.count }
+
+    pub fn bins(&self) -> usize { self.bins.len() + usize::from(self.zeros > 0) }
+}
+
+impl Default for QuantileSketch {
+    fn default() -> Self { QuantileSketch::new(DEFAULT_ALPHA, DEFAULT_MAX_BINS) }
+}
+
+/// Count/sum/min/max/mean/variance plus a percentile sketch
+#[derive(Debug, Clone, Default)]
+pub struct StreamStats {
+    count: u64,
+    sum: f64,
+    mean: f64,
+    m2: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+    sketch: QuantileSketch,
+}
+
+/// Point-in-time summary of a stream
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StatsReport {
+    pub count: u64,
+    pub sum: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub p50: Option<f64>,
+    pub p90: Option<f64>,
+    pub p99: Option<f64>,
+}
+
+impl StreamStats {
+    /// Stats whose percentiles are accurate to `alpha` (relative) within `max_bins` buckets
+    pub fn with_accuracy(alpha: f64, max_bins: usize) -> Self {
+        StreamStats { sketch: QuantileSketch::new(alpha, max_bins), ..Default::default() }
+    }
+
+    /// Add one value (non-finite values are ignored)
+    pub fn push(&mut self, x: f64) {
+        if !x.is_finite() { return; }
+        self.count += 1;
+        self.sum += x;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+        self.min = Some(self.min.map_or(x, |m| m.min(x)));
+        self.max = Some(self.max.map_or(x, |m| m.max(x)));
+        self.sketch.push(x);
+    }
+
+    /// Add `x` with multiplicity `n` (as `n` pushes, in O(1))
+    pub fn push_n(&mut self, x: f64, n: u64) {
+        if !x.is_finite() || n == 0 { return; }
+        let total = self.count + n;
+        let delta = x - self.mean;
+        self.m2 += delta * delta * (self.count as f64 * n as f64 / total as f64);
+        self.mean += delta * n as f64 / total as f64;
+        self.count = total;
+        self.sum += x * n as f64;
+        self.min = Some(self.min.map_or(x, |m| m.min(x)));
+        self.max = Some(self.max.map_or(x, |m| m.max(x)));
+        self.sketch.push_n(x, n);
+    }
+
+    /// Combine with stats from another stream (Chan et al. parallel variance)
+    pub fn merge(&mut self, other: &StreamStats) {
+        if other.count == 0 { return; }
+        let n = self.count + other.count;
+        let delta = other.mean - self.mean;
+        self.m2 += other.m2 + delta * delta * (self.count as f64 * other.count as f64 / n as f64);
+        self.mean += delta * other.count as f64 / n as f64;
+        self.count = n;
+        self.sum += other.sum;
+        self.min = [self.min, other.min].into_iter().flatten().reduce(f64::min);
+        self.max = [self.max, other.max].into_iter().flatten().reduce(f64::max);
+        self.sketch.merge(&other.sketch);
+    }
+
+    pub fn quantile(&self, q: f64) -> Option<f64> { self.sketch.quantile(q) }
+
+    pub fn report(&self) -> StatsReport {
+        StatsReport {
+            count: self.count,
+            sum: self.sum,
+            mean: self.mean,
+            std_dev: if self.count > 1 { (self.m2 / (self.count - 1) as f64).sqrt() } else { 0.0 },
+            min: self.min,
+            max: self.max,
+            p50: self.quantile(0.5),
+            p90: self.quantile(0.9),
+            p99: self.quantile(0.99),
+        }
+    }
+}
+
+/// Posting cost stream
+#[derive(Debug, Clone, Default)]
+pub struct CostStats {
+    pub values: StreamStats,
+    /// Breakdowns whose C_min floor bound the cost
+    pub floor_hits: u64,
+}
+
+/// `CostStats` summary
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CostReport {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub stats: StatsReport,
+    pub floor_hits: u64,
+}
+
+impl CostStats {
+    pub fn push(&mut self, cost: f64) { self.values.push(cost); }
+
+    pub fn push_breakdown(&mut self, b: &CostBreakdown) {
+        self.values.push(b.cost);
+        self.floor_hits += u64::from(b.cmin_applied);
+    }
+
+    pub fn merge(&mut self, other: &CostStats) {
+        self.values.merge(&other.values);
+        self.floor_hits += other.floor_hits;
+    }
+
+    pub fn report(&self) -> CostReport { CostReport { stats: self.values.report(), floor_hits: self.floor_hits } }
+}
+
+/// Serve reward stream
+#[derive(Debug, Clone, Default)]
+pub struct RewardStats {
+    pub values: StreamStats,
+    /// Breakdowns capped by the ticket budget
+    pub budget_capped: u64,
+    /// Sum of rewards before the budget cap (breakdowns only)
+    pub uncapped_sum: f64,
+}
+
+/// `RewardStats` summary
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RewardReport {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub stats: StatsReport,
+    pub budget_capped: u64,
+    pub uncapped_sum: f64,
+}
+
+impl RewardStats {
+    pub fn push(&mut self, reward: f64) { self.values.push(reward); }
+
+    pub fn push_breakdown(&mut self, b: &RewardBreakdown) {
+        self.values.push(b.reward);
+        self.budget_capped += u64::from(b.budget_capped);
+        if b.uncapped.is_finite() { self.uncapped_sum += b.uncapped; }
+    }
+
+    pub fn merge(&mut self, other: &RewardStats) {
+        self.values.merge(&other.values);
+        self.budget_capped += other.budget_capped;
+        self.uncapped_sum += other.uncapped_sum;
+    }
+
+    pub fn report(&self) -> RewardReport {
+        RewardReport { stats: self.values.report(), budget_capped: self.budget_capped, uncapped_sum: self.uncapped_sum }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_moments_and_percentiles() {
+        let (mut a, mut b) = (CostStats::default(), CostStats::default());
+        for i in 1..=100_000u32 {
+            let x = i as f64 / 100.0;
+            if i % 2 == 0 { a.push(x) } else { b.push(x) }
+        }
+        a.merge(&b);
+        let r = a.report();
+        assert_eq!(r.stats.count, 100_000);
+        assert!((r.stats.mean - 500.005).abs() < 1e-9);
+        assert_eq!((r.stats.min, r.stats.max), (Some(0.01), Some(1000.0)));
+        for (q, got) in [(0.5, r.stats.p50), (0.99, r.stats.p99)] {
+            let want = 1000.0 * q;
+            assert!((got.unwrap() - want).abs() <= want * 0.011, "{} -> {:?}", q, got);
+        }
+    }
+
+    #[test]
+    fn t_sketch_stays_bounded() {
+        let mut s = QuantileSketch::new(0.01, 64);
+        for i in 0..200_000 { s.push(1.0001f64.powi(i)); }
+        s.push(0.0);
+        assert!(s.bins() <= 65);
+        let top = s.quantile(1.0).unwrap();
+        assert!((top / 1.0001f64.powi(199_999) - 1.0).abs() < 0.011);
+        assert_eq!(s.quantile(0.0), Some(0.0));
+    }
+}
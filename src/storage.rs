@@ -0,0 +1,88 @@
+// Rent for long-term pinned content
+// - rent = Σ_{k < duration_epochs} rate_k * size_mib * replication, with `rate_0 = rate_per_mib_epoch`
+// - Congestion-coupled escalation: while storage utilization is above `congestion.target_load`, each prepaid epoch's
+//   rate takes the exponential step of `update_base_cost` (same eta, surge cap and base_min/base_max clamp) against
+//   the current utilization; below target the rate holds, so prepaying never bets on congestion easing
+// - Nodes refresh `rate_per_mib_epoch` and `utilization` once per epoch with `StorageParams::update_rate`
+
+use crate::{step_base_cost, CongestionParams, SurgeParams};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+const MIB: f64 = 1_048_576.0;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StorageParams {
+    /// Current rent in SOCIAL per MiB per epoch per replica
+    pub rate_per_mib_epoch: f64,
+    /// Current share of pinning capacity in use (0..1)
+    pub utilization: f64,
+    /// Rate controller; `target_load` is the target utilization, `base_min`/`base_max` bound the rate
+    pub congestion: CongestionParams,
+}
+
+impl Default for StorageParams {
+    fn default() -> Self {
+        StorageParams {
+            rate_per_mib_epoch: 0.01,
+            utilization: 0.0,
+            congestion: CongestionParams {
+                eta: 0.1, target_load: 0.8, base_min: 0.001, base_max: 1.0,
+                controller: Default::default(), load_ema_alpha: 1.0, surge: SurgeParams::default(),
+            },
+        }
+    }
+}
+
+impl StorageParams {
+    /// Fold this epoch's utilization into the rate (one `update_base_cost` step)
+    pub fn update_rate(&mut self, utilization: f64) {
+        self.utilization = utilization;
+        self.rate_per_mib_epoch = step_base_cost(self.rate_per_mib_epoch, utilization.max(0.0), &self.congestion);
+    }
+}
+
+/// Rent in SOCIAL for pinning `size_bytes` on `replication` nodes for `duration_epochs`
+pub fn rent(size_bytes: u64, duration_epochs: u64, replication: u32, params: &StorageParams) -> f64 {
+    let c = &params.congestion;
+    let per_epoch = size_bytes as f64 / MIB * replication as f64;
+    let utilization = params.utilization.max(0.0);
+    let mut rate = crate::clamp(params.rate_per_mib_epoch, c.base_min, c.base_max);
+    let mut total = 0.0;
+    for k in 0..duration_epochs {
+        let next = step_base_cost(rate, utilization, c).max(rate);
+        if next == rate {
+            // the rate stopped moving (at or below target, or at base_max): the remaining epochs cost the same
+            return total + rate * per_epoch * (duration_epochs - k) as f64;
+        }
+        total += rate * per_epoch;
+        rate = next;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_rent_escalates_with_congestion() {
+        let mut p = StorageParams::default();
+        let mib = 1_048_576;
+        assert!((rent(mib, 10, 3, &p) - 0.3).abs() < 1e-12);
+        assert_eq!(rent(mib, 0, 3, &p), 0.0);
+        assert_eq!(rent(0, 10, 3, &p), 0.0);
+
+        // above target, each later epoch costs more; the rate never passes base_max
+        p.utilization = 0.95;
+        let short = rent(mib, 10, 1, &p);
+        assert!(short > 0.1);
+        assert!(rent(mib, 10_000, 1, &p) <= 10_000.0 * p.congestion.base_max);
+        assert!(rent(mib, 20, 1, &p) > 2.0 * short);
+
+        p.update_rate(0.4);
+        assert!(p.rate_per_mib_epoch < 0.01);
+    }
+}
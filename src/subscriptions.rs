@@ -0,0 +1,91 @@
+// Flat monthly subscription pricing for a tier (feature `tiers`)
+// - Prices the posts and DMs a subscriber is expected to send in a month as if each were paid per item: posts at the
+//   tier's C_min, DMs at the DM escrow fee, with the tier discount applied to the total
+// - The discounted total is floored at posts x the tier's C_min (as `compute_final_cost_with_tier` floors a single
+//   post after its discount), so a subscription never costs less than the per-post floor it replaces
+// - The amount is in the tier policy's currency, rounded up to its minimum unit, and anchored to SOCIAL through the
+//   oracle under its acceptance policy, the same path the per-post C_min floor uses
+// - Negative or non-finite forecasts count as 0
+
+use crate::currency::{convert, Currency, Rounding};
+use crate::oracles::{OracleError, PriceContext, PriceSource};
+use crate::tiers::{TierPolicy, TierSpec};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Expected monthly usage included in a subscription
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct UsageForecast {
+    pub posts_per_month: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dms_per_month: f64,
+}
+
+/// Monthly subscription price
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PriceQuote {
+    /// Discounted price in `currency`
+    pub amount: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub currency: Currency,
+    pub social: f64,
+    pub usd_per_social: f64,
+    pub source: PriceSource,
+}
+
+fn count(x: f64) -> f64 { if x.is_finite() { x.max(0.0) } else { 0.0 } }
+
+/// Monthly price of `tier` for the forecast usage; fails only if the oracle price is rejected without a fallback
+pub fn monthly_price(tier: &TierSpec, usage: &UsageForecast, policy: &TierPolicy, px: &PriceContext) -> Result<PriceQuote, OracleError> {
+    let floor = count(usage.posts_per_month) * policy.cmin_usd_for(tier);
+    let list = floor + count(usage.dms_per_month) * policy.dm_escrow_usd;
+    let amount = policy.currency.round((list * count(tier.discount)).max(floor), Rounding::Up);
+    let c = convert(amount, policy.currency, px)?;
+    Ok(PriceQuote { amount, currency: policy.currency, social: c.social, usd_per_social: c.usd_per_social, source: c.source })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currency::WithFx;
+    use crate::oracles::{OraclePolicy, StubOracle};
+
+    #[test]
+    fn t_monthly_price() {
+        let tier = TierSpec { name: "T2".into(), discount: 0.8, risk_factor: 0.9, cmin_usd: None };
+        let policy = TierPolicy { tiers: vec![tier.clone()], dm_escrow_usd: 0.003, cmin_usd: 0.005, currency: Currency::Usd };
+        let oracle = StubOracle { usd_per_social_px: Some(0.2) };
+        let op = OraclePolicy::default();
+        let px = PriceContext { oracle: &oracle, policy: &op, now: 0 };
+        let usage = UsageForecast { posts_per_month: 300.0, dms_per_month: 1_000.0 };
+
+        let q = monthly_price(&tier, &usage, &policy, &px).unwrap();
+        assert!((q.amount - (1.5 + 3.0) * 0.8).abs() < 1e-12);
+        assert!((q.social - q.amount / 0.2).abs() < 1e-12);
+        assert_eq!(q.source, PriceSource::Oracle);
+
+        // a deep discount stops at the posts' C_min floor
+        let deep = TierSpec { discount: 0.1, ..tier.clone() };
+        assert!((monthly_price(&deep, &usage, &policy, &px).unwrap().amount - 1.5).abs() < 1e-12);
+
+        // a tier-specific C_min raises the post allowance; no usage costs nothing
+        let premium = TierSpec { cmin_usd: Some(0.01), ..tier.clone() };
+        assert!(monthly_price(&premium, &usage, &policy, &px).unwrap().amount > q.amount);
+        assert_eq!(monthly_price(&tier, &UsageForecast { posts_per_month: f64::NAN, dms_per_month: -5.0 }, &policy, &px).unwrap().amount, 0.0);
+
+        // priced in euros: rounded up to the cent, converted through the EUR quote
+        let eur = TierPolicy { currency: Currency::Eur, ..policy.clone() };
+        let fx = WithFx { oracle: &oracle, usd_per_unit: [(Currency::Eur, 1.1)].into() };
+        let e = monthly_price(&tier, &UsageForecast { posts_per_month: 1.0, ..usage }, &eur, &PriceContext { oracle: &fx, ..px }).unwrap();
+        assert_eq!((e.amount, e.currency), (2.41, Currency::Eur));
+        assert!((e.social - 2.41 * 1.1 / 0.2).abs() < 1e-12);
+        assert!(monthly_price(&tier, &usage, &eur, &px).is_err());
+
+        let dead = StubOracle { usd_per_social_px: None };
+        assert!(monthly_price(&tier, &usage, &policy, &PriceContext { oracle: &dead, ..px }).is_err());
+    }
+}
@@ -0,0 +1,194 @@
+// Per-client retrieval ticket budgets (the `ticket_budget` fed to `calculate_serve_reward`)
+// - Issuance per epoch: `base_per_epoch * q^q_exp * (1 + stake_bonus * min(1, (stake/stake_norm)^stake_exp))`,
+//   nothing below `min_q`
+// - Carry-over: at each epoch start `carry_share` of the unspent balance survives, and the balance is capped at
+//   `max_balance_epochs` times the new issuance, so idle clients cannot hoard budget for a later burst
+// - Anti-abuse: reward farming looks like high request volume with low diversity (the same content over and over, from
+//   few servers). With `pressure = min(1, max(0, requests / farm_volume - 1))` and
+//   `diversity = min(1, distinct_content / requests) * min(1, distinct_servers / target_servers)`, issuance is scaled by
+//   `max(min_factor, 1 - pressure * (1 - diversity))`; normal volume or diverse traffic keeps full issuance
+// - Spend-down: each request may draw at most `max_per_request`; the serve reward is paid from the balance
+// - Callers persist `TicketAccount` per client between epochs; every update is deterministic
+
+use crate::{calculate_serve_reward, clamp, Params, Quality, RewardInput, Social};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TicketParams {
+    /// Issuance per epoch for a q = 1 client without stake
+    pub base_per_epoch: f64,
+    /// Clients below this quality receive nothing
+    pub min_q: f64,
+    pub q_exp: f64,
+    /// Largest extra issuance from stake, as a multiple of the unstaked issuance
+    pub stake_bonus: f64,
+    pub stake_norm: f64,
+    pub stake_exp: f64,
+    /// Share of the unspent balance kept at the epoch boundary
+    pub carry_share: f64,
+    /// Balance cap in epochs of issuance
+    pub max_balance_epochs: f64,
+    /// Largest budget a single request may draw
+    pub max_per_request: f64,
+    /// Requests per epoch above which low diversity starts cutting issuance (pressure reaches 1 at twice this)
+    pub farm_volume: f64,
+    /// Distinct servers for full server diversity
+    pub target_servers: f64,
+    /// Smallest issuance factor
+    pub min_factor: f64,
+}
+
+impl Default for TicketParams {
+    fn default() -> Self {
+        TicketParams {
+            base_per_epoch: 100.0, min_q: 0.2, q_exp: 1.0,
+            stake_bonus: 1.0, stake_norm: 1_000.0, stake_exp: 0.5,
+            carry_share: 0.5, max_balance_epochs: 2.0, max_per_request: 1.5,
+            farm_volume: 1_000.0, target_servers: 5.0, min_factor: 0.1,
+        }
+    }
+}
+
+/// Tickets issued to a client for one epoch
+pub fn issue_per_epoch(client_q: impl Into<Quality>, stake: impl Into<Social>, params: &TicketParams) -> f64 {
+    let q = client_q.into().get();
+    if q < params.min_q { return 0.0; }
+    let stake_f = if params.stake_norm > 0.0 { (stake.into().get() / params.stake_norm).powf(params.stake_exp).min(1.0) } else { 0.0 };
+    params.base_per_epoch.max(0.0) * q.powf(params.q_exp) * (1.0 + params.stake_bonus.max(0.0) * stake_f)
+}
+
+/// A client's retrieval requests in the previous epoch
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RequestStats {
+    pub requests: u64,
+    /// Distinct content items requested
+    pub distinct_content: u64,
+    /// Distinct servers that served them
+    pub distinct_servers: u64,
+}
+
+/// Issuance terms (see `issuance_detailed`)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct IssuanceBreakdown {
+    /// Issuance before the anti-abuse factor (no stake bonus)
+    pub base: f64,
+    pub content_diversity: f64,
+    pub server_diversity: f64,
+    /// Excess volume (0..1)
+    pub pressure: f64,
+    /// Multiplier applied to `base`
+    pub factor: f64,
+    pub issued: f64,
+}
+
+/// Tickets issued for one epoch given last epoch's requests (stake bonus aside, see `TicketAccount::open_epoch`)
+pub fn issuance(client_q: impl Into<Quality>, requests_last_epoch: &RequestStats, params: &TicketParams) -> f64 {
+    issuance_detailed(client_q, requests_last_epoch, params).issued
+}
+
+/// Same as `issuance`, returning every term
+pub fn issuance_detailed(client_q: impl Into<Quality>, requests_last_epoch: &RequestStats, params: &TicketParams) -> IssuanceBreakdown {
+    let base = issue_per_epoch(client_q, 0.0, params);
+    let r = requests_last_epoch;
+    let requests = r.requests as f64;
+    let content_diversity = if r.requests > 0 { (r.distinct_content as f64 / requests).min(1.0) } else { 1.0 };
+    let server_diversity = if params.target_servers > 0.0 && r.requests > 0 { (r.distinct_servers as f64 / params.target_servers).min(1.0) } else { 1.0 };
+    let pressure = if params.farm_volume > 0.0 { clamp(requests / params.farm_volume - 1.0, 0.0, 1.0) } else { 0.0 };
+    let factor = (1.0 - pressure * (1.0 - content_diversity * server_diversity)).max(clamp(params.min_factor, 0.0, 1.0));
+    IssuanceBreakdown { base, content_diversity, server_diversity, pressure, factor, issued: base * factor }
+}
+
+/// One client's ticket balance (persist between requests and epochs; start from `Default`)
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TicketAccount {
+    pub balance: f64,
+    /// Issued at the start of the current epoch
+    pub issued: f64,
+    /// Spent in the current epoch
+    pub spent: f64,
+}
+
+impl TicketAccount {
+    /// Start an epoch: decay the unspent balance, add this epoch's issuance (stake bonus and anti-abuse factor
+    /// included) and apply the cap; returns the issuance
+    pub fn open_epoch(&mut self, client_q: impl Into<Quality>, stake: impl Into<Social>, requests_last_epoch: &RequestStats, params: &TicketParams) -> f64 {
+        let q = client_q.into();
+        let issued = issue_per_epoch(q, stake, params) * issuance_detailed(q, requests_last_epoch, params).factor;
+        let carried = self.balance.max(0.0) * clamp(params.carry_share, 0.0, 1.0);
+        self.balance = (carried + issued).min(issued * params.max_balance_epochs.max(1.0));
+        self.issued = issued;
+        self.spent = 0.0;
+        issued
+    }
+
+    /// Budget the next request may draw
+    pub fn ticket_budget(&self, params: &TicketParams) -> f64 { self.balance.max(0.0).min(params.max_per_request.max(0.0)) }
+
+    /// Deduct up to `amount`; returns what was deducted
+    pub fn spend(&mut self, amount: f64) -> f64 {
+        let spent = amount.max(0.0).min(self.balance.max(0.0));
+        self.balance -= spent;
+        self.spent += spent;
+        spent
+    }
+
+    /// Price a serve with this account's budget (overriding `input.ticket_budget`) and pay it from the balance
+    pub fn settle_serve(&mut self, input: &RewardInput, params: &Params, tickets: &TicketParams) -> f64 {
+        let input = RewardInput { ticket_budget: self.ticket_budget(tickets), ..input.clone() };
+        self.spend(calculate_serve_reward(&input, params))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_issue_spend_and_carry_over() {
+        let p = TicketParams::default();
+        assert_eq!(issue_per_epoch(0.8, 0.0, &p), 80.0);
+        assert_eq!(issue_per_epoch(0.8, 4_000.0, &p), 160.0);
+        assert_eq!(issue_per_epoch(0.1, 4_000.0, &p), 0.0);
+
+        let mut acc = TicketAccount::default();
+        acc.open_epoch(0.8, 0.0, &RequestStats::default(), &p);
+        assert_eq!(acc.ticket_budget(&p), 1.5);
+        let input = RewardInput { client_q: 0.8, size_bytes: 1_000_000_000, ttfb_ms: 0, ..Default::default() };
+        let paid = acc.settle_serve(&input, &Params::default(), &p);
+        assert!(paid > 0.0 && paid <= 1.5);
+        assert_eq!(acc.balance, 80.0 - paid);
+
+        // half the unspent balance carries over, capped at two epochs of issuance
+        acc.open_epoch(0.8, 0.0, &RequestStats::default(), &p);
+        assert_eq!(acc.balance, (80.0 - paid) * 0.5 + 80.0);
+        for _ in 0..5 { acc.open_epoch(0.8, 0.0, &RequestStats::default(), &p); }
+        let balance = acc.balance;
+        assert!(balance <= 160.0);
+        assert_eq!((acc.spend(1e9), acc.balance), (balance, 0.0));
+    }
+
+    #[test]
+    fn t_issuance_cuts_farming_patterns() {
+        let p = TicketParams::default();
+        let heavy_reader = RequestStats { requests: 3_000, distinct_content: 2_800, distinct_servers: 40 };
+        assert_eq!(issuance(0.8, &heavy_reader, &p), 80.0 * (1.0 - (1.0 - 2_800.0 / 3_000.0)));
+        // 1500 requests for the same 10 items from 2 servers
+        let farm = RequestStats { requests: 1_500, distinct_content: 10, distinct_servers: 2 };
+        let b = issuance_detailed(0.8, &farm, &p);
+        assert_eq!((b.pressure, b.server_diversity), (0.5, 0.4));
+        assert!((b.factor - (1.0 - 0.5 * (1.0 - 0.4 / 150.0))).abs() < 1e-12);
+        let extreme = RequestStats { requests: 100_000, distinct_content: 1, distinct_servers: 1 };
+        assert_eq!(issuance_detailed(0.8, &extreme, &p).factor, 0.1);
+        // a quiet client keeps full issuance whatever its diversity
+        assert_eq!(issuance(0.8, &RequestStats { requests: 900, distinct_content: 1, distinct_servers: 1 }, &p), 80.0);
+    }
+}
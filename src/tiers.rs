@@ -0,0 +1,220 @@
+
+// Tier and Musk-mode helpers applied at the app/chain edge
+// - Enabled by the `tiers` feature (default on)
+
+use crate::currency::{convert, Currency};
+use crate::{adjust_propagation_with_risk_factor, post_cost_breakdown, Actor, Content, CostBreakdown, Params, PropagationResult};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub use crate::oracles::{usd_to_social, Conversion, OracleError, OraclePolicy, PriceContext, PriceOracle, StubOracle};
+
+/// One tier of a deployment's tier ladder
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TierSpec {
+    pub name: String,
+    /// Multiplier on the final cost (1.0 = no discount)
+    pub discount: f64,
+    /// Multiplier on computed risk
+    pub risk_factor: f64,
+    /// Tier-specific C_min in the policy's currency (falls back to `TierPolicy::cmin_usd`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cmin_usd: Option<f64>,
+}
+
+/// Tier ladder plus deployment-wide price anchors (any number of tiers)
+/// - The `*_usd` anchors are amounts in `currency` (the names predate non-USD pricing)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TierPolicy {
+    pub tiers: Vec<TierSpec>,
+    pub dm_escrow_usd: f64,
+    pub cmin_usd: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub currency: Currency,
+}
+
+impl TierPolicy {
+    /// Tier by position in the ladder
+    pub fn tier(&self, index: usize) -> Option<&TierSpec> { self.tiers.get(index) }
+
+    /// Tier by name
+    pub fn tier_by_name(&self, name: &str) -> Option<&TierSpec> { self.tiers.iter().find(|t| t.name == name) }
+
+    /// Effective C_min for a tier, in `currency`
+    pub fn cmin_usd_for(&self, tier: &TierSpec) -> f64 { tier.cmin_usd.unwrap_or(self.cmin_usd) }
+}
+
+pub fn apply_musk_mode_params(p: &mut Params) {
+    p.q_weights.w_h = 0.25;
+    p.propagation.ttl_base = 5.0;
+    p.propagation.fanout_base = 6.0;
+    p.propagation.k1 = 3.0;
+    p.propagation.k2 = 3.0;
+    p.cost.alpha = 0.8;
+    p.cost.beta = 0.5;
+    p.cost.a = 1.4;
+    p.cost.b = 0.6;
+    p.cost.lambda_actor = 0.8;
+    p.cost.lambda_content = 0.6;
+    p.reward.mu = 0.5;
+}
+
+/// Compute final posting cost with tier discount and C_min.
+/// Actor/content risk is scaled by the tier's `risk_factor` before the risk multiplier is applied.
+/// Fails only if the oracle price is rejected and the policy has no fallback.
+pub fn compute_final_cost_with_tier(
+    actor: &Actor,
+    content: &Content,
+    params: &Params,
+    basefare: f64,
+    tier: &TierSpec,
+    policy: &TierPolicy,
+    px: &PriceContext,
+) -> Result<f64, OracleError> {
+    compute_final_cost_with_tier_detailed(actor, content, params, basefare, tier, policy, px).map(|d| d.cost)
+}
+
+/// Same as `compute_final_cost_with_tier`, returning the full cost breakdown.
+/// The discount is applied first, then the floor `max(params.cost.cmin, tier C_min in SOCIAL)`,
+/// so a large discount can never undercut C_min.
+pub fn compute_final_cost_with_tier_detailed(
+    actor: &Actor,
+    content: &Content,
+    params: &Params,
+    basefare: f64,
+    tier: &TierSpec,
+    policy: &TierPolicy,
+    px: &PriceContext,
+) -> Result<CostBreakdown, OracleError> {
+    let cmin_social = convert(policy.cmin_usd_for(tier), policy.currency, px)?.social;
+    Ok(post_cost_breakdown(actor, content, params, basefare, tier.risk_factor, tier.discount, Some(cmin_social)))
+}
+
+/// TTL/fanout for content posted by an actor in `tier` (risk scaled by the tier's `risk_factor`)
+pub fn adjust_propagation_with_tier(content: &Content, params: &Params, tier: &TierSpec) -> PropagationResult {
+    adjust_propagation_with_risk_factor(content, params, tier.risk_factor)
+}
+
+/// DM escrow fee in SOCIAL (payer-side hold). Receiver may auto-refund according to policy.
+/// The returned conversion records whether the oracle or the fallback peg priced the escrow.
+pub fn dm_escrow_social(policy: &TierPolicy, px: &PriceContext) -> Result<Conversion, OracleError> {
+    convert(policy.dm_escrow_usd, policy.currency, px)
+}
+
+// --- quick test ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(name: &str, discount: f64, risk_factor: f64) -> TierSpec {
+        TierSpec{ name:name.into(), discount, risk_factor, cmin_usd:None }
+    }
+
+    fn policy() -> TierPolicy {
+        TierPolicy{
+            tiers: vec![spec("T0", 1.0, 1.0), spec("T1", 0.95, 0.95), spec("T2", 0.85, 0.9), spec("T3", 0.7, 0.8)],
+            dm_escrow_usd: 0.003,
+            cmin_usd: 0.005,
+            currency: Currency::Usd,
+        }
+    }
+
+    #[test]
+    fn t_costs() {
+        let mut p = Params::default();
+        apply_musk_mode_params(&mut p);
+        let actor = Actor{ rl:120.0, q:0.8, ef:30.0, posts_1h:Some(12.0), ..Default::default() };
+        let content = Content{ is_claim:Some(true), has_evidence:Some(false), ..Default::default() };
+        let pol = policy();
+        let oracle = StubOracle{ usd_per_social_px: Some(0.2) }; // 1 SOCIAL = $0.2
+        let op = OraclePolicy::with_fallback(0.2);
+        let px = PriceContext{ oracle:&oracle, policy:&op, now:0 };
+        let basefare = 1.0;
+        let c0 = compute_final_cost_with_tier(&actor, &content, &p, basefare, pol.tier(0).unwrap(), &pol, &px).unwrap();
+        let c3 = compute_final_cost_with_tier(&actor, &content, &p, basefare, pol.tier_by_name("T3").unwrap(), &pol, &px).unwrap();
+        assert!(c3 < c0);
+        let dm = dm_escrow_social(&pol, &px).unwrap();
+        assert!(dm.social > 0.0);
+
+        // no oracle price and no fallback: hard failure instead of a silent peg
+        let dead = StubOracle{ usd_per_social_px: None };
+        let strict = OraclePolicy::default();
+        let px = PriceContext{ oracle:&dead, policy:&strict, now:0 };
+        assert!(dm_escrow_social(&pol, &px).is_err());
+    }
+
+    #[test]
+    fn t_tier_risk_factor_applied() {
+        let p = Params::default();
+        let actor = Actor{ rl:10.0, q:0.8, ef:5.0, posts_1h:None, ..Default::default() };
+        let risky = Content{ risk_signals:Some(crate::RiskSignals{ coordination:Some(0.9), clustering:Some(0.9), burst:Some(0.9), ..Default::default() }), ..Default::default() };
+        let mut pol = policy();
+        pol.tiers.iter_mut().for_each(|t| t.discount = 1.0);
+        let oracle = StubOracle{ usd_per_social_px: Some(0.2) };
+        let op = OraclePolicy::with_fallback(0.2);
+        let px = PriceContext{ oracle:&oracle, policy:&op, now:0 };
+        let (t0, t3) = (pol.tier(0).unwrap(), pol.tier(3).unwrap());
+        let c0 = compute_final_cost_with_tier(&actor, &risky, &p, 1.0, t0, &pol, &px).unwrap();
+        let c3 = compute_final_cost_with_tier(&actor, &risky, &p, 1.0, t3, &pol, &px).unwrap();
+        assert!(c3 < c0);
+        assert_eq!(c0, crate::calculate_post_cost(&actor, &risky, &p, 1.0));
+
+        let clean = Content::default();
+        let cc0 = compute_final_cost_with_tier(&actor, &clean, &p, 1.0, t0, &pol, &px).unwrap();
+        let cc3 = compute_final_cost_with_tier(&actor, &clean, &p, 1.0, t3, &pol, &px).unwrap();
+        assert_eq!(cc0, cc3);
+
+        let mut pp = Params::default();
+        pp.propagation.k2 = 4.0;
+        let f0 = adjust_propagation_with_tier(&risky, &pp, t0).fanout;
+        let f3 = adjust_propagation_with_tier(&risky, &pp, t3).fanout;
+        assert!(f3 > f0);
+    }
+
+    #[test]
+    fn t_discount_never_undercuts_cmin() {
+        let p = Params::default();
+        let actor = Actor{ rl:0.0, q:0.8, ef:0.0, posts_1h:None, ..Default::default() };
+        let mut pol = policy();
+        pol.cmin_usd = 0.2; // 1 SOCIAL at $0.2
+        pol.tiers[3].discount = 0.1;
+        let oracle = StubOracle{ usd_per_social_px: Some(0.2) };
+        let op = OraclePolicy::with_fallback(0.2);
+        let px = PriceContext{ oracle:&oracle, policy:&op, now:0 };
+        let d = compute_final_cost_with_tier_detailed(&actor, &Content::default(), &p, 1.5, pol.tier(3).unwrap(), &pol, &px).unwrap();
+        assert!(d.cmin_applied);
+        assert!((d.pre_floor - 0.15).abs() < 1e-12);
+        assert_eq!(d.cost, 1.0);
+    }
+
+    #[test]
+    fn t_arbitrary_tier_counts() {
+        let two = TierPolicy{ tiers: vec![spec("free", 1.0, 1.0), spec("pro", 0.5, 0.8)], dm_escrow_usd:0.0, cmin_usd:0.0, currency: Currency::Usd };
+        assert_eq!(two.tier(1).unwrap().name, "pro");
+        assert!(two.tier(2).is_none());
+        assert!(two.tier_by_name("T3").is_none());
+
+        let mut seven = policy();
+        seven.tiers = (0..7).map(|i| spec(&format!("L{}", i), 1.0 - i as f64 * 0.05, 1.0)).collect();
+        seven.tiers[6].cmin_usd = Some(1.0);
+        assert_eq!(seven.cmin_usd_for(seven.tier(6).unwrap()), 1.0);
+        assert_eq!(seven.cmin_usd_for(seven.tier(0).unwrap()), 0.005);
+    }
+
+    #[test]
+    fn t_musk_mode_params() {
+        let mut p = Params::default();
+        apply_musk_mode_params(&mut p);
+        assert_eq!(p.propagation.ttl_base, 5.0);
+        assert_eq!(p.reward.mu, 0.5);
+        #[cfg(feature = "serde")]
+        {
+            let back: TierPolicy = serde_json::from_str(&serde_json::to_string(&policy()).unwrap()).unwrap();
+            assert_eq!(back.tier_by_name("T2").unwrap().discount, 0.85);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+// Tip splitting between creator, protocol and referrer
+// - Amounts are whole multiples of `unit` (the smallest SOCIAL amount, `money::Amount`): the tip is floored to units,
+//   the protocol fee and referrer cut are floored from that, and the creator receives the rest
+//   (`DustPolicy::ToPart`), so the parts sum exactly to the charged amount and rounding dust always lands with the
+//   creator
+// - The sub-unit remainder of the requested amount is never charged
+// - Tips below `min_tip` (SOCIAL) are rejected; `TipParams::with_min_tip_usd` sets it from a USD anchor through the
+//   oracle helpers (feature `tiers`)
+
+use crate::money::{apportion, Amount, DustPolicy, Rounding};
+#[cfg(feature = "tiers")]
+use crate::oracles::{usd_to_social, OracleError, PriceContext};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TipParams {
+    /// Share of the tip kept by the protocol
+    pub protocol_share: f64,
+    /// Share of the tip paid to the referrer (0 when there is none)
+    pub referrer_share: f64,
+    /// Smallest accepted tip in SOCIAL
+    pub min_tip: f64,
+    /// Smallest payable amount
+    pub unit: f64,
+}
+
+impl Default for TipParams {
+    fn default() -> Self { TipParams { protocol_share: 0.02, referrer_share: 0.01, min_tip: 0.01, unit: 1e-6 } }
+}
+
+impl TipParams {
+    /// Same params with `min_tip` converted from `usd` at the current oracle price
+    #[cfg(feature = "tiers")]
+    pub fn with_min_tip_usd(self, usd: f64, px: &PriceContext) -> Result<Self, OracleError> {
+        Ok(TipParams { min_tip: usd_to_social(usd, px.oracle, px.policy, px.now)?.social, ..self })
+    }
+}
+
+/// Tip parts in SOCIAL
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TipSplit {
+    pub creator: f64,
+    pub protocol: f64,
+    pub referrer: f64,
+}
+
+impl TipSplit {
+    /// Amount actually charged to the tipper
+    pub fn total(&self) -> f64 { self.creator + self.protocol + self.referrer }
+}
+
+/// Tip below the minimum (or not a finite amount)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TipError { pub amount: f64, pub min_tip: f64 }
+
+impl std::fmt::Display for TipError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "tip {} below minimum {}", self.amount, self.min_tip)
+    }
+}
+
+impl std::error::Error for TipError {}
+
+fn share(x: f64) -> f64 { if x.is_finite() { crate::clamp(x, 0.0, 1.0) } else { 0.0 } }
+
+/// Split a tip of `amount` SOCIAL
+pub fn split(amount: f64, params: &TipParams) -> Result<TipSplit, TipError> {
+    let unit = params.unit.max(1e-12);
+    let min_tip = params.min_tip.max(unit);
+    if !amount.is_finite() || amount < min_tip {
+        return Err(TipError { amount, min_tip });
+    }
+    let units = Amount::from_social(amount, unit, Rounding::Down);
+    let protocol_share = share(params.protocol_share);
+    let referrer_share = share(params.referrer_share).min(1.0 - protocol_share);
+    let creator_share = (1.0 - protocol_share - referrer_share).max(0.0);
+    let n = units.units() as f64;
+    let s = apportion(units, &[n * creator_share, n * protocol_share, n * referrer_share], None, DustPolicy::ToPart { index: 0 });
+    Ok(TipSplit { creator: s.parts[0].to_social(unit), protocol: s.parts[1].to_social(unit), referrer: s.parts[2].to_social(unit) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_split_rounds_to_units() {
+        let p = TipParams { unit: 0.01, min_tip: 0.05, ..Default::default() };
+        // 12.345 charges 1234 units: protocol floor(24.68) = 24, referrer floor(12.34) = 12, creator the rest
+        let s = split(12.345, &p).unwrap();
+        assert_eq!((s.protocol, s.referrer), (24.0 * 0.01, 12.0 * 0.01));
+        assert_eq!(s.creator, 1198.0 * 0.01);
+
+        // shares of a tiny tip round to zero, so the creator keeps everything
+        let s = split(0.05, &p).unwrap();
+        assert_eq!((s.creator, s.protocol, s.referrer), (5.0 * 0.01, 0.0, 0.0));
+        assert_eq!(split(0.049, &p), Err(TipError { amount: 0.049, min_tip: 0.05 }));
+        assert!(split(f64::NAN, &p).is_err());
+
+        #[cfg(feature = "tiers")]
+        {
+            use crate::oracles::{OraclePolicy, StubOracle};
+            let oracle = StubOracle { usd_per_social_px: Some(0.5) };
+            let op = OraclePolicy::default();
+            let p = p.with_min_tip_usd(1.0, &PriceContext { oracle: &oracle, policy: &op, now: 0 }).unwrap();
+            assert_eq!(p.min_tip, 2.0);
+        }
+    }
+}
@@ -0,0 +1,31 @@
+// Tracing support (feature `tracing`) and trace export (feature `trace`)
+// - Core calculations emit `trace`-level events (target `slimechain::<stage>`) with their result, the caps that applied
+//   and an input hash; `pipeline::run` and epoch rollovers open `debug` spans
+// - Field values are only computed when a subscriber enables the callsite, so a disabled build pays a level check
+// - The CLI installs a stderr subscriber filtered by `RUST_LOG` (e.g. `RUST_LOG=slimechain::cost=trace`)
+// - `export` (feature `trace`) writes the same decisions as line-delimited JSON to an `io::Write`, for offline analysis
+//   without a subscriber
+
+#[cfg(feature = "trace")]
+pub mod export;
+
+use sha2::{Digest, Sha256};
+
+/// Short hex digest (first 8 bytes of SHA-256) for correlating events about the same input
+pub fn input_hash(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)[..8].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// `input_hash` of a value's `Debug` form (floats print round-trip exact, so equal inputs hash equal)
+pub(crate) fn debug_hash(value: &impl std::fmt::Debug) -> String { input_hash(format!("{:?}", value).as_bytes()) }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_hash_is_short_and_stable() {
+        assert_eq!(input_hash(b"abc"), "ba7816bf8f01cfea");
+        assert_eq!(debug_hash(&(1.0, 2.5)), input_hash(b"(1.0, 2.5)"));
+    }
+}
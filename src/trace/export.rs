@@ -0,0 +1,149 @@
+// Line-delimited JSON decision traces (feature `trace`)
+// - `TraceWriter` evaluates inputs like `pipeline::run` / `simulate_actor_costs` and writes one `TraceEvent` per stage
+//   as a JSON line to any `io::Write` (a file, a socket, a warehouse loader's stdin)
+// - An event carries the record index, the stage, the record's input hash (`trace::input_hash` of its `Debug` form),
+//   the stage's intermediate values and the caps/floors that bound its result
+// - Results are identical to the untraced functions; events come in input order, stage order within a record
+
+use super::debug_hash;
+use crate::pipeline::{PipelineInput, PipelineOutput};
+use crate::{
+    adjust_propagation_for_content, calculate_ef, calculate_post_cost_detailed, calculate_quality_detailed, calculate_risk,
+    calculate_serve_reward_detailed, update_base_cost, Actor, CostBreakdown, Params, PostEvent, RiskWeights,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+/// One stage of one evaluated record
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TraceEvent {
+    /// Position of the record in the traced run
+    pub record: u64,
+    /// `quality`, `ef`, `risk`, `base_fare`, `cost`, `propagation` or `reward`
+    pub stage: String,
+    pub input_hash: String,
+    pub values: BTreeMap<String, f64>,
+    /// Caps and floors that bound the stage's result (e.g. `cmin`, `unverified_cap`, `budget_cap`)
+    pub caps: Vec<String>,
+}
+
+/// Writes trace events for every record evaluated through it
+pub struct TraceWriter<W: Write> {
+    out: W,
+    next_record: u64,
+}
+
+impl<W: Write> TraceWriter<W> {
+    pub fn new(out: W) -> Self { TraceWriter { out, next_record: 0 } }
+
+    pub fn into_inner(self) -> W { self.out }
+
+    fn emit(&mut self, stage: &str, input_hash: &str, values: &[(&str, f64)], caps: &[(&str, bool)]) -> io::Result<()> {
+        let event = TraceEvent {
+            record: self.next_record,
+            stage: stage.into(),
+            input_hash: input_hash.into(),
+            values: values.iter().map(|&(k, v)| (k.to_string(), v)).collect(),
+            caps: caps.iter().filter(|c| c.1).map(|c| c.0.to_string()).collect(),
+        };
+        serde_json::to_writer(&mut self.out, &event)?;
+        self.out.write_all(b"\n")
+    }
+
+    /// `pipeline::run`, tracing every stage
+    pub fn pipeline(&mut self, input: &PipelineInput, params: &Params) -> io::Result<PipelineOutput> {
+        let hash = debug_hash(input);
+        let qb = calculate_quality_detailed(&input.quality, params);
+        self.emit("quality", &hash, &[("raw", qb.raw), ("q", qb.q)], &[("unverified_cap", qb.unverified_cap_applied)])?;
+        let ef = calculate_ef(&input.followers_q, params);
+        let excluded = input.followers_q.len() - input.followers_q.iter().filter(|&&q| q >= params.q_min).count();
+        self.emit("ef", &hash, &[("followers", input.followers_q.len() as f64), ("excluded", excluded as f64), ("ef", ef)], &[])?;
+        let risk = calculate_risk(&input.content.risk_signals, &RiskWeights::default());
+        self.emit("risk", &hash, &[("risk", risk)], &[])?;
+        let base_fare = match &input.load {
+            Some(l) => update_base_cost(l.current_base, l.current_load, params),
+            None => input.base_fare.unwrap_or(1.0),
+        };
+        self.emit("base_fare", &hash, &[("base_fare", base_fare)], &[])?;
+        let actor = Actor { rl: input.rl, q: qb.q, ef, posts_1h: input.posts_1h, ..Default::default() };
+        let cost = calculate_post_cost_detailed(&actor, &input.content, params, base_fare);
+        self.emit_cost(&hash, &cost)?;
+        let propagation = adjust_propagation_for_content(&input.content, params);
+        self.emit("propagation", &hash, &[("ttl", propagation.ttl as f64), ("fanout", propagation.fanout as f64)],
+            &[("quarantined", propagation.quarantined)])?;
+        let reward = match &input.serve {
+            Some(s) => {
+                let rb = calculate_serve_reward_detailed(s, params);
+                self.emit("reward", &hash, &[("uncapped", rb.uncapped), ("reward", rb.reward)], &[("budget_cap", rb.budget_capped)])?;
+                Some(rb.reward)
+            }
+            None => None,
+        };
+        self.next_record += 1;
+        Ok(PipelineOutput { q: qb.q, ef, risk, base_fare, cost: cost.cost, propagation, reward })
+    }
+
+    fn emit_cost(&mut self, hash: &str, b: &CostBreakdown) -> io::Result<()> {
+        self.emit("cost", hash, &[
+            ("base_fare", b.base_fare), ("rl_cost", b.rl_cost), ("ef_cost", b.ef_cost), ("risk_multiplier", b.risk_multiplier),
+            ("claim_multiplier", b.claim_multiplier), ("rate_multiplier", b.rate_multiplier), ("age_multiplier", b.age_multiplier),
+            ("pre_floor", b.pre_floor), ("cost", b.cost),
+        ], &[("cmin", b.cmin_applied), ("cmax", b.cmax_applied)])
+    }
+
+    /// `calculate_post_cost` for one event, traced as a `cost` record
+    pub fn cost(&mut self, event: &PostEvent, params: &Params) -> io::Result<f64> {
+        let b = calculate_post_cost_detailed(&event.actor, &event.content, params, event.base_fare);
+        self.emit_cost(&debug_hash(event), &b)?;
+        self.next_record += 1;
+        Ok(b.cost)
+    }
+}
+
+/// `pipeline::run` per input, writing the trace to `out`
+pub fn pipeline_traced(inputs: &[PipelineInput], params: &Params, out: impl Write) -> io::Result<Vec<PipelineOutput>> {
+    let mut w = TraceWriter::new(out);
+    inputs.iter().map(|i| w.pipeline(i, params)).collect()
+}
+
+/// `simulate_actor_costs`, writing the trace to `out`
+pub fn cost_batch_traced(events: &[PostEvent], params: &Params, out: impl Write) -> io::Result<Vec<f64>> {
+    let mut w = TraceWriter::new(out);
+    events.iter().map(|e| w.cost(e, params)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pipeline, Content, QInputs};
+
+    #[test]
+    fn t_trace_lines_match_results() {
+        let input = PipelineInput {
+            quality: QInputs { A: 0.8, R: 0.7, T: 0.6, D: 0.5, H: 0.0, S: 0.1 },
+            followers_q: vec![0.9, 0.2, 0.7],
+            rl: 3.0,
+            posts_1h: Some(2.0),
+            content: Content::default(),
+            base_fare: Some(1.0),
+            load: None,
+            serve: None,
+        };
+        let p = Params::default();
+        let mut buf = Vec::new();
+        let out = pipeline_traced(&[input.clone(), input.clone()], &p, &mut buf).unwrap();
+        let plain = pipeline::run(&input, &p);
+        assert_eq!((out[1].cost, out[1].q, out[1].ef), (plain.cost, plain.q, plain.ef));
+
+        let events: Vec<TraceEvent> = String::from_utf8(buf).unwrap().lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(events.len(), 12);
+        assert_eq!(events[6].record, 1);
+        assert_eq!(events[0].input_hash, events[6].input_hash);
+        let stages: Vec<&str> = events[..6].iter().map(|e| e.stage.as_str()).collect();
+        assert_eq!(stages, ["quality", "ef", "risk", "base_fare", "cost", "propagation"]);
+        assert_eq!(events[1].values["excluded"], 1.0);
+        assert_eq!(events[4].values["cost"], plain.cost);
+    }
+}
@@ -0,0 +1,90 @@
+// Trending score with burst discounting
+// - The series is `(unix secs, engagement)` samples; the window is the `window_secs` ending at the latest sample
+// - Engagement is bucketed into slots of `risk::CAMPAIGN_BURST_SECS` (the burst window of the campaign detector); a
+//   slot's excess over `spike_ratio` times the mean of its `neighbors` slots on either side, beyond `min_spike`,
+//   is a coordinated burst and is dropped, so organic growth (smooth slot to slot) keeps all its engagement
+// - score = organic rate in the recent half of the window (per hour) * min(max_accel, acceleration)^accel_exp
+//   * (1 - risk_k * author_risk), with acceleration = (recent + 1) / (prior + 1) over the organic halves
+
+use crate::risk::CAMPAIGN_BURST_SECS;
+use crate::{clamp, Risk};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TrendParams {
+    pub window_secs: u64,
+    /// Slots compared on each side of a slot
+    pub neighbors: usize,
+    pub spike_ratio: f64,
+    /// Engagement a slot may exceed its baseline by before counting as a burst
+    pub min_spike: f64,
+    pub accel_exp: f64,
+    pub max_accel: f64,
+    pub risk_k: f64,
+}
+
+impl Default for TrendParams {
+    fn default() -> Self {
+        TrendParams { window_secs: 3_600, neighbors: 3, spike_ratio: 3.0, min_spike: 5.0, accel_exp: 1.0, max_accel: 4.0, risk_k: 1.0 }
+    }
+}
+
+/// Engagement per slot over the window with coordinated bursts removed (oldest slot first)
+pub fn organic_slots(engagement_series: &[(u64, f64)], params: &TrendParams) -> Vec<f64> {
+    let Some(end) = engagement_series.iter().map(|s| s.0).max() else { return Vec::new(); };
+    let n = (params.window_secs / CAMPAIGN_BURST_SECS).max(2) as usize;
+    let start = end.saturating_sub(n as u64 * CAMPAIGN_BURST_SECS);
+    let mut slots = vec![0.0; n];
+    for &(t, x) in engagement_series {
+        if t < start || !x.is_finite() { continue; }
+        let i = (((t - start) / CAMPAIGN_BURST_SECS) as usize).min(n - 1);
+        slots[i] += x.max(0.0);
+    }
+    (0..n).map(|i| {
+        let around: Vec<f64> = (i.saturating_sub(params.neighbors)..(i + params.neighbors + 1).min(n)).filter(|&j| j != i).map(|j| slots[j]).collect();
+        let baseline = around.iter().sum::<f64>() / around.len().max(1) as f64;
+        slots[i].min(params.spike_ratio.max(1.0) * baseline + params.min_spike.max(0.0))
+    }).collect()
+}
+
+/// Trending score (>= 0) of a post
+pub fn score(engagement_series: &[(u64, f64)], author_risk: impl Into<Risk>, params: &TrendParams) -> f64 {
+    let slots = organic_slots(engagement_series, params);
+    if slots.is_empty() { return 0.0; }
+    let half = slots.len() / 2;
+    let prior: f64 = slots[..half].iter().sum();
+    let recent: f64 = slots[half..].iter().sum();
+    let hours = (slots.len() - half) as f64 * CAMPAIGN_BURST_SECS as f64 / 3_600.0;
+    let accel = ((recent + 1.0) / (prior + 1.0)).min(params.max_accel.max(1.0));
+    recent / hours * accel.powf(params.accel_exp) * (1.0 - clamp(params.risk_k, 0.0, 1.0) * author_risk.into().get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_bursts_are_discounted() {
+        let p = TrendParams::default();
+        // organic: engagement grows steadily each minute over the hour
+        let organic: Vec<(u64, f64)> = (0..60).map(|m| (m * 60, 1.0 + m as f64 / 10.0)).collect();
+        let total: f64 = organic.iter().map(|s| s.1).sum();
+        // coordinated: the same total, almost all of it in a single minute
+        let mut burst: Vec<(u64, f64)> = (0..60).map(|m| (m * 60, 1.0)).collect();
+        burst[45].1 += total - 60.0;
+
+        let o = score(&organic, 0.0, &p);
+        let b = score(&burst, 0.0, &p);
+        assert!(o > 2.0 * b);
+        assert!((organic_slots(&organic, &p).iter().sum::<f64>() - total).abs() < 1e-9);
+
+        // acceleration beats a steady stream of the same volume
+        let flat: Vec<(u64, f64)> = (0..60).map(|m| (m * 60, 4.0)).collect();
+        assert!(o > 2.0 * score(&flat, 0.0, &p));
+        assert!(score(&organic, 0.5, &p) < o);
+        assert_eq!(score(&[], 0.0, &p), 0.0);
+    }
+}
@@ -0,0 +1,173 @@
+// Strongly-typed scores and amounts
+// - `Quality` and `Risk` live in 0..=1, `Ef` and `Social` are finite and >= 0
+// - `new` validates; `From<f64>` is the escape hatch for untyped callers and clamps like the core functions do
+//   (NaN becomes 0), so typed and untyped call sites compute the same results
+// - Signatures that take several scores accept `impl Into<..>`: plain `f64` still works, but a typed `Ef` can no
+//   longer be passed where a `Quality` is expected
+// - JSON form (feature `serde`) is the bare number; deserialization validates (use `From<f64>` on raw values to clamp instead)
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Mul};
+
+/// Out-of-range value given to a validated constructor
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct UnitError { pub unit: String, pub value: f64, pub reason: String }
+
+impl fmt::Display for UnitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{} {}: {}", self.unit, self.value, self.reason) }
+}
+
+impl std::error::Error for UnitError {}
+
+macro_rules! unit {
+    ($(#[$doc:meta])* $name:ident, $hi:expr, $range:literal) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+        #[cfg_attr(feature = "serde", derive(Serialize))]
+        #[cfg_attr(feature = "serde", serde(into = "f64"))]
+        pub struct $name(f64);
+
+        impl $name {
+            /// Validated constructor
+            pub fn new(x: f64) -> Result<Self, UnitError> {
+                if x.is_finite() && (0.0..=$hi).contains(&x) { return Ok($name(x)); }
+                Err(UnitError { unit: stringify!($name).into(), value: x, reason: concat!("must be ", $range).into() })
+            }
+
+            /// Clamp into range (NaN becomes 0)
+            pub fn clamped(x: f64) -> Self { $name(if x.is_nan() { 0.0 } else { x.clamp(0.0, $hi) }) }
+
+            pub fn get(self) -> f64 { self.0 }
+        }
+
+        impl From<f64> for $name {
+            fn from(x: f64) -> Self { $name::clamped(x) }
+        }
+
+        impl From<$name> for f64 {
+            fn from(x: $name) -> f64 { x.0 }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                $name::new(f64::deserialize(d)?).map_err(serde::de::Error::custom)
+            }
+        }
+
+        #[cfg(feature = "schema")]
+        impl schemars::JsonSchema for $name {
+            fn inline_schema() -> bool { true }
+            fn schema_name() -> std::borrow::Cow<'static, str> { f64::schema_name() }
+            fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema { f64::json_schema(generator) }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { self.0.fmt(f) }
+        }
+    };
+}
+
+unit!(
+    /// Quality score (0..=1)
+    Quality, 1.0, "in 0..=1"
+);
+unit!(
+    /// Risk score (0..=1)
+    Risk, 1.0, "in 0..=1"
+);
+unit!(
+    /// Effective followers (>= 0)
+    Ef, f64::MAX, "finite and >= 0"
+);
+unit!(
+    /// SOCIAL amount (>= 0)
+    Social, f64::MAX, "finite and >= 0"
+);
+
+// -------- Arithmetic --------
+
+/// Product of two scores stays in range
+impl Mul for Quality {
+    type Output = Quality;
+    fn mul(self, rhs: Quality) -> Quality { Quality(self.0 * rhs.0) }
+}
+
+impl Mul for Risk {
+    type Output = Risk;
+    fn mul(self, rhs: Risk) -> Risk { Risk(self.0 * rhs.0) }
+}
+
+impl Risk {
+    /// `1 - risk` (the damping share that survives)
+    pub fn complement(self) -> f64 { 1.0 - self.0 }
+}
+
+impl Add for Ef {
+    type Output = Ef;
+    fn add(self, rhs: Ef) -> Ef { Ef::clamped(self.0 + rhs.0) }
+}
+
+impl Sum for Ef {
+    fn sum<I: Iterator<Item = Ef>>(iter: I) -> Ef { iter.fold(Ef(0.0), Add::add) }
+}
+
+impl Add for Social {
+    type Output = Social;
+    fn add(self, rhs: Social) -> Social { Social::clamped(self.0 + rhs.0) }
+}
+
+impl Sum for Social {
+    fn sum<I: Iterator<Item = Social>>(iter: I) -> Social { iter.fold(Social(0.0), Add::add) }
+}
+
+/// Scale an amount (negative factors give 0)
+impl Mul<f64> for Social {
+    type Output = Social;
+    fn mul(self, k: f64) -> Social { Social::clamped(self.0 * k) }
+}
+
+impl Social {
+    /// `self - rhs`, or None when it would go negative
+    pub fn checked_sub(self, rhs: Social) -> Option<Social> { (rhs.0 <= self.0).then_some(Social(self.0 - rhs.0)) }
+
+    /// `self - rhs`, floored at 0
+    pub fn saturating_sub(self, rhs: Social) -> Social { Social((self.0 - rhs.0).max(0.0)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_validation_and_escape_hatch() {
+        assert_eq!(Quality::new(0.4).map(f64::from), Ok(0.4));
+        assert_eq!(Quality::new(1.2).unwrap_err().to_string(), "Quality 1.2: must be in 0..=1");
+        assert!(Ef::new(f64::INFINITY).is_err() && Social::new(-1.0).is_err());
+        assert_eq!((Quality::from(1.5), Risk::from(f64::NAN), Ef::from(-3.0)), (Quality::clamped(1.0), Risk::default(), Ef::default()));
+
+        #[cfg(feature = "serde")]
+        {
+            assert_eq!(serde_json::to_string(&Quality::from(0.5)).unwrap(), "0.5");
+            assert!(serde_json::from_str::<Risk>("1.5").is_err());
+            assert_eq!(serde_json::from_str::<Social>("2.5").unwrap().get(), 2.5);
+        }
+    }
+
+    #[test]
+    fn t_arithmetic_stays_in_range() {
+        let (a, b) = (Social::from(3.0), Social::from(5.0));
+        assert_eq!((a + b).get(), 8.0);
+        assert_eq!(a.checked_sub(b), None);
+        assert_eq!(b.saturating_sub(a).get(), 2.0);
+        assert_eq!((a * -1.0).get(), 0.0);
+        assert_eq!([a, b].into_iter().sum::<Social>().get(), 8.0);
+        assert_eq!((Quality::from(0.5) * Quality::from(0.5)).get(), 0.25);
+        assert_eq!([Ef::from(1.0), Ef::from(2.0)].into_iter().sum::<Ef>().get(), 3.0);
+    }
+}
@@ -0,0 +1,288 @@
+// Golden conformance vectors: canonical input/output pairs under pinned params (feature `serde`)
+// - `CANONICAL` embeds `vectors/<crate version>.json`, generated by `slimechain-algo vectors` from the built-in cases
+// - `verify_all` re-evaluates it so upgraded nodes and reimplementations can assert bit-exact agreement at startup
+// - Outputs are compared exactly (serde_json round-trips f64 bit-for-bit); builds with `fast-math` do not conform
+// - `eval` is also the one dispatcher behind the CLI, `slime_eval_json` and JSON-RPC: `eval_command` maps their command
+//   names (`COMMANDS`) onto it and wraps single values as `{"cost": ..}`; non-finite results are errors everywhere
+
+use crate::slashing::{calculate_slash, OffenseKind, SlashParams};
+use crate::{
+    adjust_propagation_for_content, pipeline, calculate_availability_reward, calculate_ef, calculate_post_cost, calculate_quality,
+    calculate_risk, calculate_serve_reward, propagation, reward, update_base_cost, Actor, Content, Params, QInputs,
+    RewardInput, RiskSignals, RiskWeights,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Vector file of this crate version
+pub const CANONICAL: &str = include_str!(concat!("../vectors/", env!("CARGO_PKG_VERSION"), ".json"));
+
+/// One input/output pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Vector {
+    pub function: String,
+    pub input: Value,
+    pub output: Value,
+}
+
+/// Vector file: crate version, the pinned params and the pairs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VectorFile {
+    pub version: String,
+    pub params: Params,
+    pub vectors: Vec<Vector>,
+}
+
+/// A pair whose re-evaluated output differs from the recorded one
+#[derive(Debug, Clone, Serialize)]
+pub struct Mismatch {
+    pub index: usize,
+    pub function: String,
+    pub expected: Value,
+    pub actual: Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckReport {
+    pub version: String,
+    pub checked: usize,
+    pub passed: usize,
+    pub mismatches: Vec<Mismatch>,
+}
+
+/// What went wrong in a `VectorError`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorErrorKind {
+    UnknownFunction,
+    #[default]
+    InvalidInput,
+    /// NaN or infinite result
+    NotFinite,
+    /// Output differs from the recorded one (`verify_all`)
+    Mismatch,
+}
+
+/// Unknown function, malformed input, a non-finite result, or (from `verify_all`) a non-conforming output
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VectorError {
+    pub index: Option<usize>,
+    pub function: String,
+    pub reason: String,
+    #[serde(default)]
+    pub kind: VectorErrorKind,
+}
+
+impl std::fmt::Display for VectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.index {
+            Some(i) => write!(f, "vector {} ({}): {}", i, self.function, self.reason),
+            None => write!(f, "{}: {}", self.function, self.reason),
+        }
+    }
+}
+
+impl std::error::Error for VectorError {}
+
+fn one() -> f64 { 1.0 }
+
+#[derive(Deserialize)]
+struct CostArgs { actor: Actor, content: Content, #[serde(default = "one")] base_fare: f64 }
+#[derive(Deserialize)]
+struct EfArgs { followers_q: Vec<f64> }
+#[derive(Deserialize)]
+struct BaseArgs { current_base: f64, current_load: f64 }
+#[derive(Deserialize)]
+struct AvailabilityArgs { uptime_ratio: f64, probes_passed: u32, stake: f64 }
+#[derive(Deserialize)]
+struct ReachArgs { ef: f64, q: f64, risk: f64 }
+#[derive(Deserialize)]
+struct RepetitionArgs { prev_serves: u32, half: u32 }
+#[derive(Deserialize)]
+struct SlashArgs { stake: f64, offense: OffenseKind, severity: f64, repeat_count: u32 }
+
+/// Evaluate one function on a JSON input
+pub fn eval(function: &str, input: &Value, params: &Params) -> Result<Value, VectorError> {
+    let err = |kind: VectorErrorKind| move |reason: String| VectorError { index: None, function: function.into(), reason, kind };
+    let invalid = err(VectorErrorKind::InvalidInput);
+    fn args<T: DeserializeOwned>(input: &Value) -> Result<T, String> { T::deserialize(input).map_err(|e| format!("invalid input: {}", e)) }
+    let finite = |name: &str, x: f64| if x.is_finite() { Ok(json!(x)) } else { Err(err(VectorErrorKind::NotFinite)(format!("{} is not finite ({})", name, x))) };
+    let out = match function {
+        "calculate_quality" => finite("q", calculate_quality(args::<QInputs>(input).map_err(invalid)?, params))?,
+        "calculate_ef" => {
+            // `{"followers_q": [..]}` or the bare array
+            let followers_q = match input {
+                Value::Array(_) => args::<Vec<f64>>(input),
+                _ => args::<EfArgs>(input).map(|a| a.followers_q),
+            };
+            finite("ef", calculate_ef(&followers_q.map_err(invalid)?, params))?
+        }
+        "calculate_risk" => finite("risk", calculate_risk(&Some(args::<RiskSignals>(input).map_err(invalid)?), &RiskWeights::default()))?,
+        "calculate_post_cost" => {
+            let a: CostArgs = args(input).map_err(invalid)?;
+            finite("cost", calculate_post_cost(&a.actor, &a.content, params, a.base_fare))?
+        }
+        "adjust_propagation_for_content" => json!(adjust_propagation_for_content(&args::<Content>(input).map_err(invalid)?, params)),
+        "calculate_serve_reward" => finite("reward", calculate_serve_reward(&args::<RewardInput>(input).map_err(invalid)?, params))?,
+        "update_base_cost" => {
+            let a: BaseArgs = args(input).map_err(invalid)?;
+            finite("base", update_base_cost(a.current_base, a.current_load, params))?
+        }
+        "calculate_availability_reward" => {
+            let a: AvailabilityArgs = args(input).map_err(invalid)?;
+            finite("reward", calculate_availability_reward(a.uptime_ratio, a.probes_passed, a.stake, params))?
+        }
+        "propagation::reach_budget" => {
+            let a: ReachArgs = args(input).map_err(invalid)?;
+            json!(propagation::reach_budget(a.ef, a.q, a.risk, params))
+        }
+        "reward::repetition_factor" => {
+            let a: RepetitionArgs = args(input).map_err(invalid)?;
+            finite("factor", reward::repetition_factor(a.prev_serves, a.half))?
+        }
+        "slashing::calculate_slash" => {
+            let a: SlashArgs = args(input).map_err(invalid)?;
+            finite("slash", calculate_slash(a.stake, a.offense, a.severity, a.repeat_count, &SlashParams::default()))?
+        }
+        "pipeline::run" => {
+            let out = pipeline::run(&args(input).map_err(invalid)?, params);
+            for (name, x) in [("q", out.q), ("ef", out.ef), ("risk", out.risk), ("base_fare", out.base_fare), ("cost", out.cost)] {
+                finite(name, x)?;
+            }
+            if let Some(r) = out.reward { finite("reward", r)?; }
+            json!(out)
+        }
+        _ => return Err(err(VectorErrorKind::UnknownFunction)("unknown vector function".into())),
+    };
+    Ok(out)
+}
+
+/// Commands of the CLI and `slime_eval_json` (JSON-RPC maps its methods onto them): (command, `eval` function, key
+/// wrapping a single-value result)
+pub const COMMANDS: &[(&str, &str, Option<&str>)] = &[
+    ("cost", "calculate_post_cost", Some("cost")),
+    ("reward", "calculate_serve_reward", Some("reward")),
+    ("prop", "adjust_propagation_for_content", None),
+    ("base", "update_base_cost", Some("base")),
+    ("quality", "calculate_quality", Some("q")),
+    ("ef", "calculate_ef", Some("ef")),
+    ("risk", "calculate_risk", Some("risk")),
+    ("pipeline", "pipeline::run", None),
+];
+
+/// Evaluate a command on its input through `eval`; single values come back as `{key: value}`
+pub fn eval_command(command: &str, input: &Value, params: &Params) -> Result<Value, VectorError> {
+    let Some(&(_, function, key)) = COMMANDS.iter().find(|(c, ..)| *c == command) else {
+        return Err(VectorError { index: None, function: command.into(), reason: format!("unknown command {:?}", command), kind: VectorErrorKind::UnknownFunction });
+    };
+    let out = eval(function, input, params)?;
+    Ok(match key {
+        Some(key) => json!({ key: out }),
+        None => out,
+    })
+}
+
+/// Built-in inputs: typical values plus edge cases (zeros, negatives, saturation)
+fn cases() -> Vec<(&'static str, Value)> {
+    let mut v = vec![
+        ("calculate_quality", json!({ "A": 0.8, "R": 0.7, "T": 0.9, "D": 0.6, "H": 0.2, "S": 0.1 })),
+        ("calculate_quality", json!({ "A": 1.0, "R": 1.0, "T": 1.0, "D": 1.0, "H": 0.0, "S": 0.0 })),
+        ("calculate_quality", json!({ "A": 0.0, "R": 0.0, "T": 0.0, "D": 0.0, "H": 0.0, "S": 1.0 })),
+        ("calculate_ef", json!({ "followers_q": [] })),
+        ("calculate_ef", json!({ "followers_q": [0.9, 0.85, 0.7, 0.4, 0.95] })),
+        ("calculate_ef", json!({ "followers_q": [0.49, 0.5, 1.0] })),
+        ("calculate_risk", json!({})),
+        ("calculate_risk", json!({ "coordination": 0.5, "clustering": 0.4 })),
+        ("calculate_risk", json!({ "coordination": 1.0, "clustering": 1.0, "burst": 1.0, "monotonicity": 1.0, "abuse_history": 1.0 })),
+        ("adjust_propagation_for_content", json!({})),
+        ("adjust_propagation_for_content", json!({ "risk_signals": { "coordination": 0.8, "clustering": 0.7 }, "topic": "health" })),
+        ("calculate_serve_reward", json!({ "ticket_budget": 1.5, "client_q": 0.8, "size_bytes": 25000, "ttfb_ms": 150, "server_cluster_risk": 0.3, "prev_serves": 0 })),
+        ("calculate_serve_reward", json!({ "ticket_budget": 0.01, "client_q": 1.0, "size_bytes": 5000000, "ttfb_ms": 0, "server_cluster_risk": 0.0 })),
+        ("calculate_serve_reward", json!({ "ticket_budget": 1.0, "client_q": 0.5, "size_bytes": 100000, "ttfb_ms": 900, "server_cluster_risk": 1.0, "prev_serves": 25, "ttfb_percentile": 0.95 })),
+        ("update_base_cost", json!({ "current_base": 1.0, "current_load": 1000.0 })),
+        ("update_base_cost", json!({ "current_base": 1.0, "current_load": 0.0 })),
+        ("update_base_cost", json!({ "current_base": 99.0, "current_load": 1e9 })),
+        ("calculate_availability_reward", json!({ "uptime_ratio": 0.99, "probes_passed": 10, "stake": 1000.0 })),
+        ("calculate_availability_reward", json!({ "uptime_ratio": 0.5, "probes_passed": 3, "stake": 10.0 })),
+        ("propagation::reach_budget", json!({ "ef": 25.0, "q": 0.8, "risk": 0.2 })),
+        ("propagation::reach_budget", json!({ "ef": 0.0, "q": 0.0, "risk": 1.0 })),
+        ("reward::repetition_factor", json!({ "prev_serves": 10, "half": 10 })),
+        ("reward::repetition_factor", json!({ "prev_serves": 3, "half": 0 })),
+        ("slashing::calculate_slash", json!({ "stake": 1000.0, "offense": "fake_serve", "severity": 0.5, "repeat_count": 0 })),
+        ("slashing::calculate_slash", json!({ "stake": 1000.0, "offense": "coordinated_spam", "severity": 1.0, "repeat_count": 5 })),
+    ];
+    for (rl, ef, posts, claim, evidence) in [(0.0, 0.0, None, false, false), (120.0, 28.3, Some(12.0), true, false), (5.0, 3.0, Some(2.0), true, true), (-4.0, -1.0, Some(100.0), false, false)] {
+        v.push(("calculate_post_cost", json!({
+            "actor": { "rl": rl, "q": 0.8, "ef": ef, "posts_1h": posts },
+            "content": { "is_claim": claim, "has_evidence": evidence, "risk_signals": { "coordination": 0.5, "clustering": 0.4 } },
+            "base_fare": 1.0,
+        })));
+    }
+    v
+}
+
+/// Evaluate the built-in cases under `params`
+pub fn generate(params: &Params) -> Result<VectorFile, VectorError> {
+    let vectors = cases().into_iter()
+        .map(|(f, input)| Ok(Vector { function: f.into(), output: eval(f, &input, params)?, input }))
+        .collect::<Result<_, VectorError>>()?;
+    Ok(VectorFile { version: env!("CARGO_PKG_VERSION").into(), params: params.clone(), vectors })
+}
+
+/// Re-evaluate a vector file under its own pinned params
+pub fn check(file: &VectorFile) -> Result<CheckReport, VectorError> {
+    let mut mismatches = Vec::new();
+    for (index, v) in file.vectors.iter().enumerate() {
+        let actual = eval(&v.function, &v.input, &file.params).map_err(|e| VectorError { index: Some(index), ..e })?;
+        if actual != v.output {
+            mismatches.push(Mismatch { index, function: v.function.clone(), expected: v.output.clone(), actual });
+        }
+    }
+    let checked = file.vectors.len();
+    Ok(CheckReport { version: file.version.clone(), checked, passed: checked - mismatches.len(), mismatches })
+}
+
+/// The embedded vector file
+pub fn canonical() -> VectorFile { serde_json::from_str(CANONICAL).expect("embedded vectors parse") }
+
+/// Check this build against the embedded vectors; returns the number of pairs checked or the first mismatch
+pub fn verify_all() -> Result<usize, VectorError> {
+    let report = check(&canonical())?;
+    match report.mismatches.first() {
+        None => Ok(report.checked),
+        Some(m) => Err(VectorError {
+            index: Some(m.index), function: m.function.clone(),
+            reason: format!("expected {}, got {} ({} of {} differ)", m.expected, m.actual, report.mismatches.len(), report.checked),
+            kind: VectorErrorKind::Mismatch,
+        }),
+    }
+}
+
+// fast-math builds are not expected to conform
+#[cfg(all(test, not(feature = "fast-math")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_build_conforms_to_embedded_vectors() {
+        let file = canonical();
+        assert_eq!(file.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(file.vectors.len(), cases().len());
+        assert_eq!(verify_all(), Ok(cases().len()));
+
+        let mut bad = file.clone();
+        bad.vectors[0].output = json!(0.123);
+        assert_eq!(check(&bad).unwrap().mismatches[0].index, 0);
+        assert_eq!(eval("nope", &json!({}), &file.params).unwrap_err().to_string(), "nope: unknown vector function");
+
+        // the command layer: bare-array ef, default base fare, wrapped values, non-finite results rejected
+        let p = &file.params;
+        assert_eq!(eval_command("ef", &json!([0.9, 0.8]), p).unwrap(), json!({ "ef": calculate_ef(&[0.9, 0.8], p) }));
+        let cost = json!({ "actor": { "rl": 1.0, "q": 0.8, "ef": 3.0 }, "content": {} });
+        assert_eq!(eval_command("cost", &cost, p).unwrap()["cost"], eval("calculate_post_cost", &cost, p).unwrap());
+        let huge = json!({ "actor": { "rl": 1e308, "q": 0.0, "ef": 1e308, "posts_1h": 1e308 }, "content": {}, "base_fare": 1e308 });
+        assert_eq!(eval_command("cost", &huge, p).map_err(|e| e.kind), Err(VectorErrorKind::NotFinite));
+        assert_eq!(eval_command("nope", &json!({}), p).unwrap_err().kind, VectorErrorKind::UnknownFunction);
+    }
+}
@@ -0,0 +1,56 @@
+// wasm-bindgen bindings for browsers and Node (feature `wasm`)
+// - Inputs are plain JS objects shaped like the serde types (same as the CLI JSON)
+// - `params` may be `undefined`/`null` for `Params::default()`; supplied params are validated
+
+use crate::{Actor, Content, Params, QInputs, RewardInput, RiskSignals};
+use serde::de::DeserializeOwned;
+use wasm_bindgen::prelude::*;
+
+fn from_js<T: DeserializeOwned>(value: JsValue) -> Result<T, JsError> {
+    serde_wasm_bindgen::from_value(value).map_err(|e| JsError::new(&e.to_string()))
+}
+
+fn params_from_js(params: JsValue) -> Result<Params, JsError> {
+    if params.is_undefined() || params.is_null() { return Ok(Params::default()); }
+    let p: Params = from_js(params)?;
+    p.validate().map_err(|errs| {
+        let msgs: Vec<String> = errs.iter().map(|e| e.to_string()).collect();
+        JsError::new(&format!("invalid params: {}", msgs.join("; ")))
+    })?;
+    Ok(p)
+}
+
+/// `calculatePostCost(actor, content, baseFare, params?)`
+#[wasm_bindgen(js_name = calculatePostCost)]
+pub fn calculate_post_cost(actor: JsValue, content: JsValue, base_fare: f64, params: JsValue) -> Result<f64, JsError> {
+    let (actor, content): (Actor, Content) = (from_js(actor)?, from_js(content)?);
+    Ok(crate::calculate_post_cost(&actor, &content, &params_from_js(params)?, base_fare))
+}
+
+/// `calculateQuality({A, R, T, D, H, S}, params?)`
+#[wasm_bindgen(js_name = calculateQuality)]
+pub fn calculate_quality(inputs: JsValue, params: JsValue) -> Result<f64, JsError> {
+    let inputs: QInputs = from_js(inputs)?;
+    Ok(crate::calculate_quality(inputs, &params_from_js(params)?))
+}
+
+/// `calculateEf(Float64Array | number[], params?)`
+#[wasm_bindgen(js_name = calculateEf)]
+pub fn calculate_ef(followers_q: Vec<f64>, params: JsValue) -> Result<f64, JsError> {
+    Ok(crate::calculate_ef(&followers_q, &params_from_js(params)?))
+}
+
+/// `adjustPropagation(riskSignals?, params?) -> {ttl, fanout, quarantined}`
+#[wasm_bindgen(js_name = adjustPropagation)]
+pub fn adjust_propagation(risk_signals: JsValue, params: JsValue) -> Result<JsValue, JsError> {
+    let signals: Option<RiskSignals> = from_js(risk_signals)?;
+    let out = crate::adjust_propagation(&signals, &params_from_js(params)?);
+    serde_wasm_bindgen::to_value(&out).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// `calculateServeReward(input, params?)`
+#[wasm_bindgen(js_name = calculateServeReward)]
+pub fn calculate_serve_reward(input: JsValue, params: JsValue) -> Result<f64, JsError> {
+    let input: RewardInput = from_js(input)?;
+    Ok(crate::calculate_serve_reward(&input, &params_from_js(params)?))
+}
@@ -0,0 +1,156 @@
+// Fixed-size binary encoding of a propagation decision, for gossip message headers
+// - Relays stamp the decision they applied and the next hop checks it without parsing JSON
+// - Layout (`WIRE_LEN` = 44 bytes, little-endian): version u8 | flags u8 (bit 0 = quarantined) | ttl u32 | fanout u32 |
+//   risk u16 (risk * 65535, rounded) | params hash [u8; 32] (`audit::params_hash`)
+// - Decoding rejects a wrong length or version, unknown flag bits, and a quarantined stamp with nonzero ttl/fanout
+// - `revalidate` (feature `serde`) checks a stamp against the relay's current params: a decision made under other params
+//   is recomputed at the stamped risk and never widened, so a message relayed hours later cannot outlive a tightening
+
+use crate::por::Hash32;
+use crate::{PropagationResult, Risk};
+#[cfg(feature = "serde")]
+use crate::{propagation_at_risk, Params};
+
+pub const WIRE_VERSION: u8 = 1;
+pub const WIRE_LEN: usize = 44;
+const QUARANTINED: u8 = 1;
+
+/// Malformed stamp
+#[derive(Debug, Clone, PartialEq)]
+pub struct WireError(pub String);
+
+impl std::fmt::Display for WireError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "wire: {}", self.0) }
+}
+
+impl std::error::Error for WireError {}
+
+/// A decoded stamp: the decision, the risk it was made at (to 1/65535), and the params it was made under
+#[derive(Debug, Clone)]
+pub struct WireStamp {
+    pub result: PropagationResult,
+    pub risk: f64,
+    pub params_hash: Hash32,
+}
+
+/// Result of checking a stamped decision against the current params
+#[derive(Debug, Clone)]
+#[cfg(feature = "serde")]
+pub enum RevalidationOutcome {
+    /// Made under the current params; forward as stamped
+    Current,
+    /// Made under other params; forward with `decision` (`narrowed` when it is tighter than the stamp)
+    Stale { decision: PropagationResult, narrowed: bool },
+}
+
+#[cfg(feature = "serde")]
+impl RevalidationOutcome {
+    /// Decision to forward with, given the stamped one
+    pub fn decision(&self, stamped: &PropagationResult) -> PropagationResult {
+        match self {
+            RevalidationOutcome::Current => stamped.clone(),
+            RevalidationOutcome::Stale { decision, .. } => decision.clone(),
+        }
+    }
+}
+
+impl PropagationResult {
+    /// Check this decision, made at `risk` under the params hashing to `params_hash`, against `current_params`
+    /// - Stale decisions are recomputed at `risk` (without the topic, which the stamp does not carry) and capped by the
+    ///   stamped ttl/fanout, so the topic's narrowing survives and loosened params never widen a message in flight
+    /// - Quarantine is final; a current quarantine threshold below `risk` quarantines
+    #[cfg(feature = "serde")]
+    pub fn revalidate(&self, params_hash: &Hash32, risk: impl Into<Risk>, current_params: &Params) -> RevalidationOutcome {
+        if *params_hash == crate::audit::params_hash(current_params) { return RevalidationOutcome::Current; }
+        let fresh = propagation_at_risk(risk.into().get(), None, current_params);
+        let decision = if self.quarantined || fresh.quarantined { PropagationResult::quarantine() } else {
+            PropagationResult { ttl: fresh.ttl.min(self.ttl), fanout: fresh.fanout.min(self.fanout), quarantined: false }
+        };
+        let narrowed = decision.quarantined != self.quarantined || decision.ttl < self.ttl || decision.fanout < self.fanout;
+        RevalidationOutcome::Stale { decision, narrowed }
+    }
+
+    /// Header stamp of this decision made at `risk` under the params hashing to `params_hash`
+    pub fn to_wire_bytes(&self, risk: impl Into<Risk>, params_hash: &Hash32) -> [u8; WIRE_LEN] {
+        let mut out = [0u8; WIRE_LEN];
+        out[0] = WIRE_VERSION;
+        out[1] = if self.quarantined { QUARANTINED } else { 0 };
+        out[2..6].copy_from_slice(&self.ttl.to_le_bytes());
+        out[6..10].copy_from_slice(&self.fanout.to_le_bytes());
+        out[10..12].copy_from_slice(&((risk.into().get() * 65_535.0).round() as u16).to_le_bytes());
+        out[12..].copy_from_slice(params_hash);
+        out
+    }
+
+    /// Parse a stamp written by `to_wire_bytes`
+    pub fn from_wire_bytes(bytes: &[u8]) -> Result<WireStamp, WireError> {
+        let b: &[u8; WIRE_LEN] = bytes.try_into().map_err(|_| WireError(format!("expected {} bytes, got {}", WIRE_LEN, bytes.len())))?;
+        if b[0] != WIRE_VERSION { return Err(WireError(format!("unsupported version {}", b[0]))); }
+        if b[1] & !QUARANTINED != 0 { return Err(WireError(format!("unknown flags {:#04x}", b[1]))); }
+        let u32_at = |i: usize| u32::from_le_bytes([b[i], b[i + 1], b[i + 2], b[i + 3]]);
+        let result = PropagationResult { ttl: u32_at(2), fanout: u32_at(6), quarantined: b[1] & QUARANTINED != 0 };
+        if result.quarantined && (result.ttl != 0 || result.fanout != 0) {
+            return Err(WireError("quarantined with nonzero ttl/fanout".into()));
+        }
+        let mut params_hash = [0u8; 32];
+        params_hash.copy_from_slice(&b[12..]);
+        Ok(WireStamp { result, risk: u16::from_le_bytes([b[10], b[11]]) as f64 / 65_535.0, params_hash })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl WireStamp {
+    /// `PropagationResult::revalidate` with the stamp's own risk and params hash
+    pub fn revalidate(&self, current_params: &Params) -> RevalidationOutcome {
+        self.result.revalidate(&self.params_hash, self.risk, current_params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn t_wire_roundtrip_and_rejects() {
+        let hash = [7u8; 32];
+        let bytes = PropagationResult { ttl: 3, fanout: 12, quarantined: false }.to_wire_bytes(0.3, &hash);
+        assert_eq!(bytes.len(), WIRE_LEN);
+        let s = PropagationResult::from_wire_bytes(&bytes).unwrap();
+        assert_eq!((s.result.ttl, s.result.fanout, s.result.quarantined), (3, 12, false));
+        assert!((s.risk - 0.3).abs() < 1.0 / 65_535.0);
+        assert_eq!(s.params_hash, hash);
+
+        let q = PropagationResult::from_wire_bytes(&PropagationResult::quarantine().to_wire_bytes(1.0, &hash)).unwrap();
+        assert!(q.result.quarantined);
+        assert_eq!(q.risk, 1.0);
+
+        assert!(PropagationResult::from_wire_bytes(&bytes[..43]).is_err());
+        for (i, v) in [(0, 2), (1, 0x02), (1, QUARANTINED)] {
+            let mut bad = bytes;
+            bad[i] = v;
+            assert!(PropagationResult::from_wire_bytes(&bad).is_err());
+        }
+
+        #[cfg(feature = "serde")]
+        {
+            use crate::audit::params_hash;
+            let mut p = Params::default();
+            let decision = propagation_at_risk(0.5, None, &p);
+            let stamp = PropagationResult::from_wire_bytes(&decision.to_wire_bytes(0.5, &params_hash(&p))).unwrap();
+            assert!(matches!(stamp.revalidate(&p), RevalidationOutcome::Current));
+            // tightened params: recomputed at the stamped risk, narrower than the stamp
+            p.propagation.k1 += 2.0;
+            p.propagation.quarantine_threshold = Some(0.9);
+            match stamp.revalidate(&p) {
+                RevalidationOutcome::Stale { decision: d, narrowed } => assert!(narrowed && d.ttl < decision.ttl && d.fanout <= decision.fanout),
+                RevalidationOutcome::Current => panic!("params changed"),
+            }
+            p.propagation.quarantine_threshold = Some(0.3);
+            assert!(stamp.revalidate(&p).decision(&stamp.result).quarantined);
+            // loosened params never widen a message in flight
+            let mut loose = Params::default();
+            loose.propagation.ttl_base += 10.0;
+            assert!(matches!(stamp.revalidate(&loose), RevalidationOutcome::Stale { narrowed: false, .. }));
+        }
+    }
+}
@@ -0,0 +1,116 @@
+// End-to-end tests of the `slimechain-algo` binary: documented exit codes and the JSON it prints
+// - Each test runs the built binary (`CARGO_BIN_EXE_slimechain-algo`) in a scratch directory under the system temp dir
+// - `serve` and `grpc` are long-running servers and are not covered here
+#![cfg(feature = "serde")]
+
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+fn cli() -> Command { Command::new(env!("CARGO_BIN_EXE_slimechain-algo")) }
+
+fn run(args: &[&str]) -> Output { cli().args(args).output().expect("run slimechain-algo") }
+
+fn stdout_json(out: &Output) -> Value {
+    serde_json::from_slice(&out.stdout).unwrap_or_else(|e| panic!("{}: {}", e, String::from_utf8_lossy(&out.stdout)))
+}
+
+/// Fresh scratch directory for one test
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("slimechain-cli-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn t_vectors_and_conform() {
+    let dir = scratch("vectors");
+    let file = dir.join("vectors.json");
+    let path = file.to_str().unwrap();
+    assert!(run(&["vectors", "--out", path]).status.success());
+    let check = run(&["vectors", "--check", path]);
+    assert!(check.status.success());
+    let report = stdout_json(&check);
+    assert_eq!(report["passed"], report["checked"]);
+
+    // another implementation's results: identical ones conform, a drifted one fails unless tolerated
+    assert!(stdout_json(&run(&["conform", path]))["divergences"].as_array().unwrap().is_empty());
+    let mut results: Value = serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+    let i = results["vectors"].as_array().unwrap().iter().position(|v| v["function"] == "calculate_quality").unwrap();
+    let q = results["vectors"][i]["output"].as_f64().unwrap();
+    results["vectors"][i]["output"] = json!(q + 1e-12);
+    let drifted = dir.join("drifted.json");
+    std::fs::write(&drifted, results.to_string()).unwrap();
+    let drifted = drifted.to_str().unwrap();
+    let out = run(&["conform", drifted]);
+    assert_eq!(out.status.code(), Some(3));
+    assert_eq!(stdout_json(&out)["divergences"][0]["index"], i);
+    assert_eq!(run(&["vectors", "--check", drifted]).status.code(), Some(3));
+
+    let tolerances = dir.join("tolerances.json");
+    std::fs::write(&tolerances, r#"{"functions":{"calculate_quality":{"abs":1e-9}}}"#).unwrap();
+    assert!(run(&["conform", drifted, "--tolerances", tolerances.to_str().unwrap()]).status.success());
+    assert_eq!(run(&["conform"]).status.code(), Some(1));
+}
+
+#[test]
+fn t_bench_presets_and_schema() {
+    let out = run(&["bench", "--kind", "cost", "--n", "100"]);
+    assert!(out.status.success());
+    let report = stdout_json(&out);
+    assert_eq!((report["kind"].as_str(), report["n"].as_u64()), (Some("cost"), Some(100)));
+    assert!(report["ns_per_call"].as_f64().unwrap() > 0.0);
+    // allocation counts only with the counting allocator
+    assert_eq!(report["allocs_per_call"].is_null(), cfg!(not(feature = "bench-alloc")));
+    assert_eq!(run(&["bench", "--kind", "nope"]).status.code(), Some(1));
+
+    let list = stdout_json(&run(&["presets", "list"]));
+    let name = list[0]["name"].as_str().unwrap().to_string();
+    let shown = stdout_json(&run(&["presets", "show", &name]));
+    assert!(shown["cost"]["alpha"].is_number());
+    assert_eq!(run(&["presets", "show", "no-such-preset"]).status.code(), Some(1));
+
+    let schema = run(&["schema", "cost"]);
+    if cfg!(feature = "schema") {
+        let doc = stdout_json(&schema);
+        assert!(doc["input"].is_object() && doc["output"].is_object(), "{}", doc);
+    } else {
+        assert_eq!(schema.status.code(), Some(1));
+    }
+}
+
+#[test]
+fn t_watch_reruns_on_change() {
+    let dir = scratch("watch");
+    let input = dir.join("risk.json");
+    std::fs::write(&input, r#"{"coordination":0.5}"#).unwrap();
+    assert_eq!(run(&["risk", "-", "--watch"]).status.code(), Some(1));
+
+    let mut child = cli().args(["risk", input.to_str().unwrap(), "--watch", "--format", "json-compact"])
+        .stdout(Stdio::piped()).stderr(Stdio::piped()).spawn().unwrap();
+    let mut stderr = BufReader::new(child.stderr.take().unwrap());
+    let mut waits = 0;
+    let mut line = String::new();
+    while waits < 2 {
+        line.clear();
+        if stderr.read_line(&mut line).unwrap() == 0 { break; }
+        if line.starts_with("watching") {
+            waits += 1;
+            // a new modification time triggers the second evaluation
+            if waits == 1 {
+                std::thread::sleep(std::time::Duration::from_millis(50));
+                std::fs::write(&input, r#"{"coordination":1.0,"clustering":1.0}"#).unwrap();
+            }
+        }
+    }
+    child.kill().unwrap();
+    child.wait().unwrap();
+    let mut out = String::new();
+    child.stdout.take().unwrap().read_to_string(&mut out).unwrap();
+    assert_eq!(waits, 2);
+    let risks: Vec<f64> = out.lines().map(|doc| serde_json::from_str::<Value>(doc).unwrap()["risk"].as_f64().unwrap()).collect();
+    assert_eq!(risks.len(), 2);
+    assert!(risks[1] > risks[0]);
+}